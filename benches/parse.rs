@@ -0,0 +1,67 @@
+//! Parser throughput benches. Run with `cargo bench --bench parse`; compare
+//! runs with criterion's `--save-baseline` / `--baseline` to catch
+//! regressions in `parser.rs`.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use markdown_org_extract::parse_file;
+
+/// Tasks in the synthetic document; large enough that per-call setup does
+/// not dominate.
+const SYNTHETIC_TASKS: usize = 1_000;
+
+/// A document mixing the constructs the parser spends time on: planning
+/// lines, repeaters, property drawers, CLOCK entries and plain prose.
+fn synthetic_document() -> String {
+    let mut doc = String::from("# Journal\n\nSome intro text.\n\n");
+    for i in 0..SYNTHETIC_TASKS {
+        let day = i % 28 + 1;
+        let _ = write!(
+            doc,
+            "## TODO [#B] Task {i} :work:\n\
+             `SCHEDULED: <2026-03-{day:02} Thu 10:00 +1w>`\n\
+             `DEADLINE: <2026-04-{day:02} Sat -3d>`\n\n\
+             ```\n:PROPERTIES:\n:ID: task-{i}\n:END:\n```\n\n\
+             `CLOCK: [2026-03-{day:02} Thu 09:00]--[2026-03-{day:02} Thu 10:30] =>  1:30`\n\n\
+             Notes for task {i} with a [link](https://example.com) and *emphasis*.\n\n"
+        );
+    }
+    doc
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_file");
+
+    let synthetic = synthetic_document();
+    group.throughput(Throughput::Bytes(synthetic.len() as u64));
+    group.bench_function("synthetic", |b| {
+        b.iter(|| parse_file(black_box(&synthetic)))
+    });
+
+    let examples_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples");
+    let mut examples: Vec<String> = fs::read_dir(&examples_dir)
+        .expect("examples/ is readable")
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            (path.extension()? == "md").then(|| fs::read_to_string(path).ok())?
+        })
+        .collect();
+    examples.sort();
+    let bytes: usize = examples.iter().map(String::len).sum();
+    group.throughput(Throughput::Bytes(bytes as u64));
+    group.bench_function("examples", |b| {
+        b.iter(|| {
+            for doc in &examples {
+                black_box(parse_file(black_box(doc)));
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse);
+criterion_main!(benches);
@@ -1,12 +1,35 @@
-use chrono::{Datelike, NaiveDate};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Weekday};
 use chrono_tz::Tz;
+use clap::ValueEnum;
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::sync::Arc;
 
 use crate::error::AppError;
-use crate::timestamp::{parse_org_timestamp, ParsedTimestamp};
-use crate::types::{DayAgenda, Task, TaskType, TaskWithOffset};
+use crate::timestamp::{
+    closest_date, extract_repeater_normalized, is_overnight_normalized, parse_org_timestamp,
+    parse_repeater, parse_timestamp_fields_normalized, range_end_date_normalized, DatePreference,
+    ParsedTimestamp, Repeater, RepeaterType, RepeaterUnit,
+};
+use crate::types::{
+    CategoryAgenda, DayAgenda, ForecastMonth, FreeDay, HabitRow, LocalTime, LogEntry, NowMarker,
+    Occurrence, OverdueAge, Priority, PriorityScheme, QuarterMonth, RangeDay, SimulatedDay, Task,
+    TaskType, TaskWithOffset, Truncated,
+};
+
+mod forecast;
+mod free;
+mod habits;
+mod quarter;
+mod simulate;
+mod stuck;
 
 const DEADLINE_WARNING_DAYS: i64 = 14;
 
+/// Property Org sets to the time of the last completion of a repeating task.
+const LAST_REPEAT_PROPERTY: &str = "LAST_REPEAT";
+
 /// Sort key used in the `--tasks` flat list for tasks with `priority = None`.
 /// `u32::MAX` is strictly greater than every value `Priority::order()` can
 /// return (numeric `0..=64`, letters `A..Z` = `65..=90`), so no-priority
@@ -18,6 +41,8 @@ const NO_PRIORITY_ORDER: u32 = u32::MAX;
 struct PreparedTask<'a> {
     task: &'a Task,
     parsed: Option<ParsedTimestamp>,
+    /// Last day of a non-repeating `<d1>--<d2>` range spanning several days.
+    range_end: Option<NaiveDate>,
 }
 
 fn prepare_tasks(tasks: &[Task]) -> Vec<PreparedTask<'_>> {
@@ -26,50 +51,690 @@ fn prepare_tasks(tasks: &[Task]) -> Vec<PreparedTask<'_>> {
     // logic bracket-form-agnostic — every downstream bucket already
     // skips entries whose `parsed` is `None`. SCHEDULED/DEADLINE are
     // guaranteed active by the extract-layer regex (ADR-0014), CLOSED
-    // is guaranteed inactive there and was already excluded from
-    // overdue/upcoming by `handle_repeating_task`; the only case this
-    // filter actually drops is a PLAIN inline `[YYYY-MM-DD ...]`.
+    // is guaranteed inactive there. The one exception is the CLOSED
+    // timestamp of a done task, which lists the task on the day it was
+    // finished (ADR-0026); being done, it never turns overdue.
     tasks
         .iter()
-        .map(|t| PreparedTask {
-            task: t,
-            parsed: t
+        .map(|t| {
+            let parsed = t
                 .timestamp
                 .as_deref()
-                .and_then(|ts| parse_org_timestamp(ts, None))
-                .filter(|p| p.active),
+                .and_then(|ts| parse_org_timestamp(ts, None).ok())
+                .filter(|p| p.active || is_closed_entry(t))
+                .map(|mut p| {
+                    if let Some(ref repeater) = p.repeater {
+                        p.date = repeat_base(t, p.date, repeater);
+                    }
+                    p
+                });
+            let range_end = parsed
+                .as_ref()
+                .filter(|p| p.repeater.is_none())
+                .and(t.timestamp.as_deref())
+                .and_then(range_end_date_normalized);
+            PreparedTask {
+                task: t,
+                parsed,
+                range_end,
+            }
         })
         .collect()
 }
 
+/// Base date of a repeating `task` after its completions, as Org's
+/// `org-auto-repeat-maybe` moves the timestamp each time the task is marked
+/// done. Every completion on or after `base` is replayed in order: a `+`
+/// repeater moves one step, a `++` repeater to the first step after the
+/// completion day, a `.+` repeater to one step after the completion day. A
+/// task not completed since `base` keeps it.
+fn repeat_base(task: &Task, base: NaiveDate, repeater: &Repeater) -> NaiveDate {
+    // First occurrence of the grid through `from` that falls after `day`.
+    let step_after = |from: NaiveDate, day: NaiveDate| {
+        closest_date(from, day.succ_opt()?, DatePreference::Future, repeater)
+    };
+    let mut shifted = base;
+    for done in completion_dates(task)
+        .into_iter()
+        .filter(|&done| done >= base)
+    {
+        let next = match repeater.repeater_type {
+            RepeaterType::Cumulative => step_after(shifted, shifted),
+            RepeaterType::CatchUp => step_after(shifted, done.max(shifted)),
+            RepeaterType::Restart => step_after(done, done),
+        };
+        match next {
+            Some(next) => shifted = next,
+            None => break,
+        }
+    }
+    shifted
+}
+
+/// Sorted, distinct days `task` was completed on.
+fn completion_dates(task: &Task) -> Vec<NaiveDate> {
+    // Logbook entries carry the timestamp without its brackets.
+    let date_of = |timestamp: &str| {
+        let date = timestamp.trim().trim_start_matches(['[', '<']).get(..10)?;
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+    };
+    let logged = task.log.iter().flatten().filter_map(|entry| match entry {
+        LogEntry::State { to, timestamp, .. }
+            if TaskType::from_keyword(to).is_some_and(|t| t.is_done()) =>
+        {
+            date_of(timestamp)
+        }
+        _ => None,
+    });
+    let last_repeat = task
+        .properties
+        .as_ref()
+        .and_then(|props| props.get(LAST_REPEAT_PROPERTY))
+        .and_then(|raw| date_of(raw.trim()));
+    let closed = task
+        .closed
+        .as_deref()
+        .and_then(|closed| date_of(closed.trim_start_matches("CLOSED:").trim()));
+
+    let mut dates: Vec<NaiveDate> = logged.chain(last_repeat).chain(closed).collect();
+    dates.sort_unstable();
+    dates.dedup();
+    dates
+}
+
+/// Whether `task` stands for the day a done task was closed on.
+fn is_closed_entry(task: &Task) -> bool {
+    task.timestamp_type.as_deref() == Some("CLOSED")
+        && task.task_type.as_ref().is_some_and(TaskType::is_done)
+}
+
+/// Drop repeating tasks whose base date lies more than `years` before
+/// `today`, with one warning per dropped task.
+///
+/// Occurrence math is closed-form, so an ancient base date is not slow by
+/// itself; it is almost always a typo (`<1026-03-01 +1d>`) that would
+/// otherwise flood every day of the agenda. This is the single limit on how
+/// far a repeater is projected — the grid searches in `timestamp::repeater`
+/// stay within it by construction.
+fn apply_repeat_horizon(tasks: Vec<Task>, today: NaiveDate, years: u32) -> Vec<Task> {
+    let Some(cutoff) = today.checked_sub_months(chrono::Months::new(years.saturating_mul(12)))
+    else {
+        return tasks;
+    };
+    tasks
+        .into_iter()
+        .filter(|task| {
+            if task.timestamp_repeater.is_none() {
+                return true;
+            }
+            let Some(base) = task
+                .timestamp_date
+                .as_deref()
+                .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+            else {
+                return true;
+            };
+            if base >= cutoff {
+                return true;
+            }
+            tracing::warn!(
+                file = %task.file,
+                line = task.line,
+                base = %base,
+                "repeater base date is more than {years} years before {today}; occurrences not computed (raise --repeat-horizon)"
+            );
+            false
+        })
+        .collect()
+}
+
+/// One task per planning timestamp for a task with both `SCHEDULED:` and
+/// `DEADLINE:`, so the day views place and warn about each on its own, and
+/// one more for the `CLOSED:` date of a done task. The copies differ only
+/// in the `timestamp*` fields.
+fn expand_planning(tasks: Vec<Task>) -> Vec<Task> {
+    let mut expanded = Vec::with_capacity(tasks.len());
+    for task in tasks {
+        let closed = task
+            .closed
+            .as_ref()
+            .filter(|_| task.task_type.as_ref().is_some_and(TaskType::is_done));
+        for ts in task
+            .planning
+            .iter()
+            .flatten()
+            .chain(closed)
+            .filter(|ts| task.timestamp.as_ref() != Some(*ts))
+        {
+            expanded.push(with_timestamp(&task, ts));
+        }
+        expanded.push(task);
+    }
+    expanded
+}
+
+/// `task` with its timestamp replaced by `ts`, an already normalized
+/// planning timestamp, and the derived fields recomputed.
+fn with_timestamp(task: &Task, ts: &str) -> Task {
+    let (ts_type, date, time, end_time, active) = parse_timestamp_fields_normalized(ts);
+    Task {
+        timestamp: Some(ts.to_string()),
+        timestamp_type: ts_type,
+        timestamp_active: active,
+        timestamp_date: date,
+        timestamp_time: time,
+        timestamp_end_time: end_time,
+        timestamp_repeater: extract_repeater_normalized(ts),
+        timestamp_overnight: is_overnight_normalized(ts).then_some(true),
+        ..task.clone()
+    }
+}
+
+/// First date from which a recurring `task` contributes occurrences, for a
+/// window starting at `from`: `None` while it is `PAUSED`, otherwise `from`
+/// pushed forward to any `PAUSE_UNTIL` date. The repeater itself is left
+/// alone, so the grid picks up where it would have been.
+fn resume_from(task: &Task, from: NaiveDate) -> Option<NaiveDate> {
+    if task.paused == Some(true) {
+        return None;
+    }
+    let until = task
+        .paused_until
+        .as_deref()
+        .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok());
+    Some(until.map_or(from, |until| until.max(from)))
+}
+
+/// Whether a recurring `task`'s occurrence on `date` is suppressed by a
+/// pause.
+fn paused_on(task: &Task, date: NaiveDate) -> bool {
+    resume_from(task, date) != Some(date)
+}
+
+/// `--priority-aging`: raise the effective priority of open (TODO or custom
+/// open keyword) tasks by one level per full `days` days since their `CREATED` date, as of
+/// `today`. A task without a priority cookie counts as one level below `C`,
+/// so its first step gives it `C`. Tasks without `CREATED`, or not yet
+/// `days` old, keep `effective_priority` unset.
+///
+/// With a `--priorities` `scheme` a task without a cookie starts from the
+/// scheme's default instead, and no task rises above its highest priority.
+pub fn age_priorities(
+    tasks: &mut [Task],
+    today: NaiveDate,
+    days: u32,
+    scheme: Option<&PriorityScheme>,
+) {
+    if days == 0 {
+        return;
+    }
+    for task in tasks {
+        if !task.task_type.as_ref().is_some_and(TaskType::is_open) {
+            continue;
+        }
+        let Some(created) = task.created.as_deref().and_then(created_date) else {
+            continue;
+        };
+        let age = (today - created).num_days();
+        let levels = u32::try_from(age / i64::from(days)).unwrap_or(0);
+        if levels == 0 {
+            continue;
+        }
+        task.effective_priority = Some(match (&task.priority, scheme) {
+            (Some(priority), Some(scheme)) => scheme.raised(priority, levels),
+            (Some(priority), None) => priority.raised(levels),
+            (None, Some(scheme)) => scheme.raised(&scheme.default, levels),
+            (None, None) => Priority::C.raised(levels - 1),
+        });
+    }
+}
+
+/// `--priorities`: give every task without a cookie, and not already aged,
+/// the scheme's default as `effective_priority`, so it sorts among the
+/// cookies as Org's `org-priority-default` does.
+pub fn default_priorities(tasks: &mut [Task], scheme: &PriorityScheme) {
+    for task in tasks {
+        if task.priority.is_none() && task.effective_priority.is_none() {
+            task.effective_priority = Some(scheme.default.clone());
+        }
+    }
+}
+
+/// Date of a `CREATED: [YYYY-MM-DD ...]` value.
+fn created_date(created: &str) -> Option<NaiveDate> {
+    let start = created.find('[')? + 1;
+    created
+        .get(start..start + 10)
+        .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+}
+
+/// `--min-priority`: whether `task` ranks at `min` or above, by the same
+/// priority its sorting uses. A task without one never does.
+pub fn meets_min_priority(task: &Task, min: &Priority) -> bool {
+    priority_order(task) <= min.order()
+}
+
+/// Order of the flat task list (`--sort`). Tasks without the key come last
+/// either way; ties keep the priority order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum TaskSort {
+    /// Highest priority first
+    #[default]
+    Priority,
+    /// Earliest `DEADLINE` first
+    Deadline,
+    /// Earliest `SCHEDULED` first
+    Scheduled,
+    /// Oldest `CREATED` first
+    Created,
+    /// By file path, then line
+    File,
+    /// By heading text, ignoring case
+    Heading,
+}
+
+/// Sort the flat task list by `sort`, descending when `reverse` is set.
+pub fn sort_tasks(tasks: &mut [Task], sort: TaskSort, reverse: bool) {
+    tasks.sort_by_key(priority_order);
+    if sort == TaskSort::Priority && !reverse {
+        return;
+    }
+    let priority = |t: &Task| Some(priority_order(t)).filter(|&o| o != NO_PRIORITY_ORDER);
+    let created = |t: &Task| created_date(t.created.as_deref()?);
+    match sort {
+        TaskSort::Priority => {
+            tasks.sort_by(|a, b| present_first(priority(a), priority(b), reverse));
+        }
+        TaskSort::Deadline | TaskSort::Scheduled => {
+            let keyword = if sort == TaskSort::Deadline {
+                "DEADLINE"
+            } else {
+                "SCHEDULED"
+            };
+            tasks.sort_by(|a, b| {
+                present_first(
+                    planning_date(a, keyword),
+                    planning_date(b, keyword),
+                    reverse,
+                )
+            });
+        }
+        TaskSort::Created => {
+            tasks.sort_by(|a, b| present_first(created(a), created(b), reverse));
+        }
+        TaskSort::File => tasks.sort_by(|a, b| {
+            let key = |t: &Task| Some((t.file.clone(), t.line));
+            present_first(key(a), key(b), reverse)
+        }),
+        TaskSort::Heading => tasks.sort_by(|a, b| {
+            let key = |t: &Task| Some(t.heading.to_lowercase());
+            present_first(key(a), key(b), reverse)
+        }),
+    }
+}
+
+/// Compare two sort keys, descending when `reverse` is set; a missing key
+/// sorts last in both directions.
+fn present_first<T: Ord>(a: Option<T>, b: Option<T>, reverse: bool) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) if reverse => b.cmp(&a),
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// `YYYY-MM-DD` date of the task's `keyword` (`SCHEDULED` or `DEADLINE`)
+/// planning timestamp.
+fn planning_date(task: &Task, keyword: &str) -> Option<String> {
+    if task.timestamp_type.as_deref() == Some(keyword) {
+        return task.timestamp_date.clone();
+    }
+    task.planning
+        .iter()
+        .flatten()
+        .map(|ts| parse_timestamp_fields_normalized(ts))
+        .find(|(ts_type, ..)| ts_type.as_deref() == Some(keyword))
+        .and_then(|(_, date, ..)| date)
+}
+
+/// Sort key of a task's priority: the aged `effective_priority` when set,
+/// else its own; no priority sorts last.
+fn priority_order(task: &Task) -> u32 {
+    task.effective_priority
+        .as_ref()
+        .or(task.priority.as_ref())
+        .map_or(NO_PRIORITY_ORDER, |p| p.order())
+}
+
 /// Result of running [`filter_agenda`]. The variant is determined by the
 /// requested [`AgendaScope`]:
 ///
 /// - [`AgendaScope::Day`] / [`AgendaScope::Week`] / [`AgendaScope::Month`]
 ///   produce [`AgendaOutput::Days`] — one [`DayAgenda`] per day in the
 ///   window, each carrying overdue / scheduled / upcoming buckets.
+/// - [`AgendaScope::Fortnight`] also produces [`AgendaOutput::Days`], for
+///   the week of the anchor day and the one after it.
+/// - [`AgendaScope::RestOfWeek`] also produces [`AgendaOutput::Days`],
+///   from the anchor day through the last day of its week.
+/// - [`AgendaScope::Days`] also produces [`AgendaOutput::Days`], `days`
+///   days from the anchor day.
 /// - [`AgendaScope::Tasks`] produces [`AgendaOutput::Tasks`] — a single
 ///   flat list filtered to actionable items, with no date bucketing.
+/// - [`AgendaScope::Search`] also produces [`AgendaOutput::Tasks`]: every
+///   task given, whatever its state, in file order.
+/// - [`AgendaScope::Stuck`] also produces [`AgendaOutput::Tasks`]: open
+///   projects without a next action, in file order.
+/// - [`AgendaScope::Rescheduled`] also produces [`AgendaOutput::Tasks`]:
+///   open tasks with reschedule notes, most-postponed first.
+/// - [`AgendaScope::Waiting`] also produces [`AgendaOutput::Tasks`]:
+///   open tasks whose `WAITING_SINCE` is older than the threshold,
+///   longest-waiting first.
+/// - [`AgendaScope::Forecast`] produces [`AgendaOutput::Forecast`] — one
+///   [`ForecastMonth`] per calendar month with recurring-task workload.
+/// - [`AgendaScope::Quarter`] produces [`AgendaOutput::Quarter`] — one
+///   [`QuarterMonth`] per month of the (fiscal) quarter.
+/// - [`AgendaScope::Simulate`] produces [`AgendaOutput::Simulation`] — one
+///   [`SimulatedDay`] per day of the `--from`/`--to` window.
+/// - [`AgendaScope::Free`] produces [`AgendaOutput::FreeSlots`] — one
+///   [`FreeDay`] per workday of the window.
+/// - [`AgendaScope::Habits`] produces [`AgendaOutput::Habits`] — one
+///   [`HabitRow`] per `STYLE: habit` task.
+/// - `--group-by` wraps one of the above per attribute value in
+///   [`AgendaOutput::Grouped`].
+/// - `--custom-agenda` lists one of the above per block of the agenda in
+///   [`AgendaOutput::Blocks`].
+///
+/// Serializes as the bare inner value, which is the JSON each scope prints.
 ///
 /// The renderer in [`crate::render`] dispatches on this enum to choose
 /// between the per-day agenda layout and the flat list layout.
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
 pub enum AgendaOutput {
-    /// Per-day agenda for day / week / month scope.
+    /// Per-day agenda for day / week / month / rest-of-week scope.
     Days(Vec<DayAgenda>),
-    /// Flat task list for `--agenda tasks` / `--tasks` scope.
+    /// Flat task list for `--agenda tasks` / `--tasks` / `--agenda rescheduled`
+    /// / `--agenda waiting` scope.
     Tasks(Vec<Task>),
+    /// Per-month recurring workload for `--agenda forecast`.
+    Forecast(Vec<ForecastMonth>),
+    /// Per-month deadlines and recurring obligations for `--agenda quarter`.
+    Quarter(Vec<QuarterMonth>),
+    /// Per-day occurrence counts and effort for `--simulate`.
+    Simulation(Vec<SimulatedDay>),
+    /// Open slots within the working hours for `--agenda free`.
+    FreeSlots(Vec<FreeDay>),
+    /// Consistency graphs of the habits for `--agenda habits`.
+    Habits(Vec<HabitRow>),
+    /// One agenda per value of the `--group-by` attribute.
+    Grouped(Vec<AgendaGroup>),
+    /// One agenda per block of a `--custom-agenda`, in the order defined.
+    Blocks(Vec<AgendaBlock>),
+}
+
+/// Task attribute `--group-by` splits the agenda on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[clap(rename_all = "kebab-case")]
+pub enum GroupBy {
+    /// One agenda per `assignee`, then one for unassigned tasks
+    Assignee,
+    /// One agenda per source file
+    File,
+    /// One agenda per tag (a task shows under each of its tags), then one
+    /// for untagged tasks
+    Tag,
+    /// One agenda per priority, highest first, then one for tasks without
+    Priority,
+    /// One agenda per TODO keyword: open states, then done, then cancelled
+    TodoState,
+}
+
+impl GroupBy {
+    /// JSON field naming the value a group was built for.
+    pub fn field(self) -> &'static str {
+        match self {
+            GroupBy::Assignee => "assignee",
+            GroupBy::File => "file",
+            GroupBy::Tag => "tag",
+            GroupBy::Priority => "priority",
+            GroupBy::TodoState => "todo_state",
+        }
+    }
+
+    /// Title of the group of tasks without a value.
+    fn missing(self) -> &'static str {
+        match self {
+            GroupBy::Assignee => "Unassigned",
+            GroupBy::File => "No file",
+            GroupBy::Tag => "Untagged",
+            GroupBy::Priority => "No priority",
+            GroupBy::TodoState => "No state",
+        }
+    }
+}
+
+/// The agenda of one value of the `--group-by` attribute. Serializes as
+/// `{"<field>": value, "agenda": ...}`, the value omitted for the group of
+/// tasks without one.
+#[derive(Debug)]
+pub struct AgendaGroup {
+    pub by: GroupBy,
+    /// `None` collects the tasks without a value, e.g. nobody's tasks.
+    pub name: Option<String>,
+    pub agenda: AgendaOutput,
+}
+
+impl AgendaGroup {
+    /// Group value, or a label for the tasks without one.
+    pub fn title(&self) -> &str {
+        self.name.as_deref().unwrap_or(self.by.missing())
+    }
+}
+
+impl Serialize for AgendaGroup {
+    fn serialize<S: serde::Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        let mut map = ser.serialize_map(None)?;
+        if let Some(name) = &self.name {
+            map.serialize_entry(self.by.field(), name)?;
+        }
+        map.serialize_entry("agenda", &self.agenda)?;
+        map.end()
+    }
+}
+
+/// The agenda of one block of a `--custom-agenda`. Serializes as
+/// `{"title": ..., "agenda": ...}`.
+#[derive(Debug, Serialize)]
+pub struct AgendaBlock {
+    pub title: String,
+    pub agenda: AgendaOutput,
+}
+
+impl AgendaOutput {
+    /// True when the result holds no task at all: every day bucket, forecast
+    /// month or quarter month is empty.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            AgendaOutput::Days(days) => days.iter().all(DayAgenda::is_empty),
+            AgendaOutput::Tasks(tasks) => tasks.is_empty(),
+            AgendaOutput::Forecast(months) => months.iter().all(|m| m.tasks.is_empty()),
+            AgendaOutput::Quarter(months) => months
+                .iter()
+                .all(|m| m.deadlines.is_empty() && m.recurring.is_empty()),
+            AgendaOutput::Simulation(days) => days.iter().all(|d| d.tasks.is_empty()),
+            AgendaOutput::FreeSlots(days) => days.iter().all(|d| d.slots.is_empty()),
+            AgendaOutput::Habits(rows) => rows.is_empty(),
+            AgendaOutput::Grouped(groups) => groups.iter().all(|g| g.agenda.is_empty()),
+            AgendaOutput::Blocks(blocks) => blocks.iter().all(|b| b.agenda.is_empty()),
+        }
+    }
+}
+
+/// Whether `task` is assigned to `name`, ignoring case and a leading `@`.
+pub fn is_assigned_to(task: &Task, name: &str) -> bool {
+    let name = name.trim_start_matches('@');
+    task.assignee
+        .as_deref()
+        .is_some_and(|assignee| assignee.to_lowercase() == name.to_lowercase())
+}
+
+/// Whether `task` is in one of the TODO `states`; both spellings of
+/// `CANCELLED` name the same state.
+pub fn in_todo_state(task: &Task, states: &[String]) -> bool {
+    let Some(task_type) = &task.task_type else {
+        return false;
+    };
+    let keyword = task_type.to_string();
+    states.iter().any(|state| {
+        *state == keyword
+            || (matches!(task_type, TaskType::Cancelled(_))
+                && matches!(state.as_str(), "CANCELLED" | "CANCELED"))
+    })
+}
+
+/// Split `tasks` for `--group-by`: one group per value, then the tasks
+/// without one.
+///
+/// - `assignee`: names ordered case-insensitively; names that differ only
+///   in case share a group under the first spelling seen.
+/// - `file`: paths in lexical order.
+/// - `tag`: tags in lexical order; a task with several tags is in each.
+/// - `priority`: by the priority the task sorts by, highest first.
+/// - `todo-state`: open keywords, then done, then cancelled (both
+///   spellings in one group), alphabetically within each.
+pub fn group_tasks(tasks: Vec<Task>, by: GroupBy) -> Vec<(Option<String>, Vec<Task>)> {
+    match by {
+        GroupBy::Assignee => group_by_key(tasks, |task| {
+            let name = task.assignee.clone();
+            name.map(|name| ((0, name.to_lowercase()), name))
+                .into_iter()
+                .collect()
+        }),
+        GroupBy::File => group_by_key(tasks, |task| {
+            vec![((0, task.file.to_string()), task.file.to_string())]
+        }),
+        GroupBy::Tag => group_by_key(tasks, |task| {
+            let mut tags: Vec<&String> = task.tags.iter().flatten().collect();
+            tags.sort();
+            tags.dedup();
+            tags.into_iter()
+                .map(|tag| ((0, tag.clone()), tag.clone()))
+                .collect()
+        }),
+        GroupBy::Priority => group_by_key(tasks, |task| {
+            let priority = task.effective_priority.as_ref().or(task.priority.as_ref());
+            priority
+                .map(|p| ((p.order(), String::new()), p.to_string()))
+                .into_iter()
+                .collect()
+        }),
+        GroupBy::TodoState => group_by_key(tasks, |task| {
+            let Some(task_type) = &task.task_type else {
+                return Vec::new();
+            };
+            let keyword = task_type.to_string();
+            let key = match task_type {
+                TaskType::Cancelled(_) => (2, "CANCELLED".to_string()),
+                t if t.is_done() => (1, keyword.clone()),
+                _ => (0, keyword.clone()),
+            };
+            vec![(key, keyword)]
+        }),
+    }
+}
+
+/// Group `tasks` under each `(sort key, name)` `keys` returns for them,
+/// ordered by key; a group is named after the first name seen for its key.
+/// Tasks without a key form the last group.
+fn group_by_key(
+    tasks: Vec<Task>,
+    keys: impl Fn(&Task) -> Vec<((u32, String), String)>,
+) -> Vec<(Option<String>, Vec<Task>)> {
+    let mut named: BTreeMap<(u32, String), (String, Vec<Task>)> = BTreeMap::new();
+    let mut rest = Vec::new();
+    for task in tasks {
+        let keys = keys(&task);
+        if keys.is_empty() {
+            rest.push(task);
+            continue;
+        }
+        for (key, name) in keys {
+            named
+                .entry(key)
+                .or_insert_with(|| (name, Vec::new()))
+                .1
+                .push(task.clone());
+        }
+    }
+    let mut groups: Vec<_> = named
+        .into_values()
+        .map(|(name, tasks)| (Some(name), tasks))
+        .collect();
+    if !rest.is_empty() {
+        groups.push((None, rest));
+    }
+    groups
 }
 
 /// Effective agenda scope after resolving CLI flags. `Tasks` is selected via
-/// `--tasks` instead of `--agenda`; the other three correspond directly to
-/// `--agenda day|week|month`.
+/// `--tasks` and `Simulate` via `--simulate` instead of `--agenda`;
+/// `Forecast` carries the `--months` horizon, `Quarter` the
+/// `--fiscal-year-start`, `Waiting` the `--waiting-days` threshold, `Days`
+/// the `--days` span and `Free`
+/// the `--work-hours` / `--min-slot` settings; the rest correspond directly to
+/// `--agenda day|week|month|rest-of-week`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AgendaScope {
     Day,
     Week,
+    Fortnight,
     Month,
+    RestOfWeek,
+    Days {
+        days: u32,
+    },
     Tasks,
+    Rescheduled,
+    Search,
+    Stuck,
+    Waiting {
+        days: u32,
+    },
+    Forecast {
+        months: u32,
+    },
+    Quarter {
+        fiscal_year_start: FiscalYearStart,
+    },
+    Simulate,
+    Free {
+        work_hours: WorkHours,
+        min_slot: u32,
+    },
+    Habits {
+        days: u32,
+    },
+}
+
+/// First day of the fiscal year (`--fiscal-year-start MM-DD`). `day` is
+/// capped at 28 by the CLI validator so every fiscal month starts on the
+/// same day-of-month.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FiscalYearStart {
+    pub month: u32,
+    pub day: u32,
+}
+
+/// Working day window for `--agenda free` and `--show-gaps`
+/// (`--work-hours HH:MM-HH:MM`),
+/// `start` before `end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
 }
 
 /// The CLI date-window arguments for [`filter_agenda`], grouped into one
@@ -95,13 +760,131 @@ pub struct AgendaDates<'a> {
     /// deterministic testing and for rendering the agenda as it would look on
     /// a different day. Also the default for a missing `--from`/`--to` edge.
     pub current_date: Option<&'a str>,
+    /// Value of `--week-start`: the first day of a week in `Week`,
+    /// `Fortnight` and `RestOfWeek` scope. `None` means Monday (ISO 8601).
+    pub week_start: Option<Weekday>,
+    /// Value of `--repeat-horizon`: how many years past its base date a
+    /// repeater is still projected. `None` means
+    /// [`DEFAULT_REPEAT_HORIZON_YEARS`].
+    pub repeat_horizon: Option<u32>,
+    /// Value of `--scheduled-warning-days`: also list open SCHEDULED tasks
+    /// starting within this many days in today's upcoming bucket. `None`
+    /// keeps upcoming to deadlines only.
+    pub scheduled_warning_days: Option<u32>,
 }
 
-fn parse_date_arg(label: &str, value: &str) -> Result<NaiveDate, AppError> {
-    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+/// Default `--repeat-horizon`. Two centuries keeps a `+1y` birthday from the
+/// 1920s on the agenda while a `+1d` typo'd into year 1026 is dropped.
+pub const DEFAULT_REPEAT_HORIZON_YEARS: u32 = 200;
+
+fn parse_date_arg(label: &str, value: &str, today: NaiveDate) -> Result<NaiveDate, AppError> {
+    parse_date_expr(value, today)
         .map_err(|e| AppError::InvalidDate(format!("{label} '{value}': {e}")))
 }
 
+/// Parse a date argument: `YYYY-MM-DD`, `today`, `tomorrow`, `yesterday`
+/// or a weekday name (`mon`, `friday`: the nearest such day from today on),
+/// optionally followed by a signed offset in days, weeks, months or years
+/// (`today-1d`, `2026-05-01+2w`, `mon+1w`). A bare offset (`+3d`) counts
+/// from today. Month and year steps clamp to the end of a shorter month.
+/// Shared by the clap validators and the agenda so that a value the CLI
+/// accepts always resolves.
+pub fn parse_date_expr(value: &str, today: NaiveDate) -> Result<NaiveDate, String> {
+    let word_end = value
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(value.len());
+    let (base, offset) = match value.split_at(word_end) {
+        ("", offset) if offset.starts_with(['+', '-']) => (today, offset),
+        ("", _) => {
+            // `YYYY-MM-DD` itself contains `-`; an offset is only present
+            // when the value ends in a unit letter.
+            let split = if value.ends_with(|c: char| c.is_ascii_alphabetic()) {
+                value.rfind(['+', '-']).unwrap_or(value.len())
+            } else {
+                value.len()
+            };
+            let (date, offset) = value.split_at(split);
+            let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|e| format!("{e}; {DATE_EXPR_HINT}"))?;
+            (date, offset)
+        }
+        (word, offset) => (relative_day(word, today)?, offset),
+    };
+    if offset.is_empty() {
+        return Ok(base);
+    }
+
+    let invalid = || format!("invalid offset '{offset}'; use e.g. -1d, +2w, +1m or -1y");
+    let (negative, rest) = match offset.as_bytes()[0] {
+        b'+' => (false, &offset[1..]),
+        b'-' => (true, &offset[1..]),
+        _ => return Err(invalid()),
+    };
+    let unit = rest.chars().last().ok_or_else(invalid)?;
+    let count: u32 = rest[..rest.len() - unit.len_utf8()]
+        .parse()
+        .map_err(|_| invalid())?;
+    let shifted = match unit {
+        'd' | 'w' => {
+            let days = chrono::Duration::days(i64::from(count) * if unit == 'w' { 7 } else { 1 });
+            if negative {
+                base.checked_sub_signed(days)
+            } else {
+                base.checked_add_signed(days)
+            }
+        }
+        'm' | 'y' => {
+            let months =
+                chrono::Months::new(count.saturating_mul(if unit == 'y' { 12 } else { 1 }));
+            if negative {
+                base.checked_sub_months(months)
+            } else {
+                base.checked_add_months(months)
+            }
+        }
+        _ => return Err(invalid()),
+    };
+    shifted.ok_or_else(|| format!("offset '{offset}' is out of range"))
+}
+
+const DATE_EXPR_HINT: &str =
+    "use YYYY-MM-DD format, today, tomorrow, yesterday or a weekday, optionally +-N(d|w|m|y)";
+
+/// Day named by a word of a date argument, relative to `today`. A weekday
+/// is today when today is that day, as in Org's date prompt.
+fn relative_day(word: &str, today: NaiveDate) -> Result<NaiveDate, String> {
+    let day = match word.to_ascii_lowercase().as_str() {
+        "today" => Some(today),
+        "tomorrow" => today.succ_opt(),
+        "yesterday" => today.pred_opt(),
+        _ => {
+            let weekday: Weekday = word
+                .parse()
+                .map_err(|_| format!("unknown date '{word}'; {DATE_EXPR_HINT}"))?;
+            let ahead =
+                (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+            today.checked_add_days(chrono::Days::new(u64::from(ahead)))
+        }
+    };
+    day.ok_or_else(|| format!("'{word}' is out of range"))
+}
+
+/// Today's date in the IANA zone `tz`, the base for a `today` date argument.
+pub fn today_in_tz(tz: &str) -> Result<NaiveDate, AppError> {
+    let tz: Tz = tz
+        .parse()
+        .map_err(|_| AppError::InvalidTimezone(tz.to_string()))?;
+    Ok(compute_today_in_tz(chrono::Utc::now(), tz))
+}
+
+/// The current date and time of day in the IANA timezone `tz`.
+pub fn now_in_tz(tz: &str) -> Result<NaiveDateTime, AppError> {
+    let tz: Tz = tz
+        .parse()
+        .map_err(|_| AppError::InvalidTimezone(tz.to_string()))?;
+    Ok(chrono::Utc::now().with_timezone(&tz).naive_local())
+}
+
 /// Convert a UTC instant into the calendar date as seen in `tz`. Factored out
 /// from `filter_agenda` so it can be unit-tested with an explicit "now":
 /// dropping `.with_timezone(&tz)` would silently produce UTC-relative dates,
@@ -130,8 +913,12 @@ fn parse_range(
     to: Option<&str>,
     current_date: NaiveDate,
 ) -> Result<Option<(NaiveDate, NaiveDate)>, AppError> {
-    let from_date = from.map(|s| parse_date_arg("from", s)).transpose()?;
-    let to_date = to.map(|s| parse_date_arg("to", s)).transpose()?;
+    let from_date = from
+        .map(|s| parse_date_arg("from", s, current_date))
+        .transpose()?;
+    let to_date = to
+        .map(|s| parse_date_arg("to", s, current_date))
+        .transpose()?;
     let (start, end) = match (from_date, to_date) {
         (None, None) => return Ok(None),
         (Some(f), Some(t)) => (f, t),
@@ -161,8 +948,8 @@ fn parse_range(
 ///   `dates.current_date` is `None`.
 /// - `include_done` — value of `--tasks-include-done`. Only affects
 ///   [`AgendaScope::Tasks`]: when `true` the flat list additionally
-///   surfaces `DONE` tasks (otherwise it is TODO-only, the documented
-///   default). A no-op for day / week / month scope, which keep their
+///   surfaces `DONE` tasks (otherwise it holds open tasks, the
+///   documented default). A no-op for day / week / month scope, which keep their
 ///   Org-faithful `DONE` handling (shown on the occurrence day, hidden
 ///   from overdue / upcoming).
 /// - `include_cancelled` — value of `--tasks-include-cancelled`. Only
@@ -172,7 +959,7 @@ fn parse_range(
 ///
 /// Errors:
 /// - `AppError::InvalidDate` — any of `date`/`from`/`to`/`current-date`
-///   failed `YYYY-MM-DD` parse, or `Tasks` scope was combined with
+///   failed [`parse_date_expr`], or `Tasks` scope was combined with
 ///   date arguments.
 /// - `AppError::InvalidTimezone` — `tz` was not recognised by chrono-tz.
 /// - `AppError::DateRange` — `from > to` after edge filling.
@@ -189,15 +976,23 @@ pub fn filter_agenda(
         from,
         to,
         current_date: current_date_override,
+        week_start,
+        repeat_horizon,
+        scheduled_warning_days,
     } = dates;
+    let week_start = week_start.unwrap_or(Weekday::Mon);
+    let repeat_horizon = repeat_horizon.unwrap_or(DEFAULT_REPEAT_HORIZON_YEARS);
 
     let tz: Tz = tz
         .parse()
         .map_err(|_| AppError::InvalidTimezone(tz.to_string()))?;
 
+    // `--current-date today-1d` is relative to the real today; every other
+    // date argument's `today` is the (possibly overridden) current date.
+    let now = compute_today_in_tz(chrono::Utc::now(), tz);
     let today = match current_date_override {
-        Some(date_str) => parse_date_arg("current-date", date_str)?,
-        None => compute_today_in_tz(chrono::Utc::now(), tz),
+        Some(date_str) => parse_date_arg("current-date", date_str, now)?,
+        None => now,
     };
 
     tracing::debug!(
@@ -214,16 +1009,99 @@ pub fn filter_agenda(
     // Tasks scope is task-based, not date-centric -- reject any date argument
     // up-front so a stray `--date 2026-01-01 --agenda tasks` is loud, not
     // silently ignored. See ADR-0009 for the model.
-    if scope == AgendaScope::Tasks
-        && (date.is_some() || from.is_some() || to.is_some() || current_date_override.is_some())
+    if matches!(
+        scope,
+        AgendaScope::Tasks | AgendaScope::Rescheduled | AgendaScope::Search | AgendaScope::Stuck
+    ) && (date.is_some() || from.is_some() || to.is_some() || current_date_override.is_some())
+    {
+        let mode = match scope {
+            AgendaScope::Tasks => "tasks",
+            AgendaScope::Search => "search",
+            AgendaScope::Stuck => "stuck",
+            _ => "rescheduled",
+        };
+        return Err(AppError::DateRange(format!(
+            "{mode} mode does not accept date arguments (--date, --from, --to, --current-date)"
+        )));
+    }
+    // Forecast buckets whole calendar months from an anchor day; an explicit
+    // `--from`/`--to` range has no meaning there and is rejected rather than
+    // silently ignored.
+    if matches!(scope, AgendaScope::Forecast { .. }) && (from.is_some() || to.is_some()) {
+        return Err(AppError::DateRange(
+            "forecast mode does not accept --from/--to; use --date and --months".to_string(),
+        ));
+    }
+    if matches!(scope, AgendaScope::Quarter { .. }) && (from.is_some() || to.is_some()) {
+        return Err(AppError::DateRange(
+            "quarter mode does not accept --from/--to; use --date".to_string(),
+        ));
+    }
+    // The waiting report only needs "today", which `--current-date` sets.
+    if matches!(scope, AgendaScope::Waiting { .. })
+        && (date.is_some() || from.is_some() || to.is_some())
     {
         return Err(AppError::DateRange(
-            "tasks mode does not accept date arguments (--date, --from, --to, --current-date)"
+            "waiting mode does not accept --date/--from/--to; use --current-date".to_string(),
+        ));
+    }
+    if scope == AgendaScope::Simulate && date.is_some() {
+        return Err(AppError::DateRange(
+            "simulation does not accept --date; use --from and --to".to_string(),
+        ));
+    }
+    // Rest-of-week derives both edges from the anchor day; an explicit range
+    // is just `--agenda week --from/--to`.
+    if scope == AgendaScope::RestOfWeek && (from.is_some() || to.is_some()) {
+        return Err(AppError::DateRange(
+            "rest-of-week mode does not accept --from/--to; use --date or --agenda week"
                 .to_string(),
         ));
     }
+    if scope == AgendaScope::Fortnight && (from.is_some() || to.is_some()) {
+        return Err(AppError::DateRange(
+            "fortnight mode does not accept --from/--to; use --date or --agenda week".to_string(),
+        ));
+    }
+    if matches!(scope, AgendaScope::Days { .. }) && (from.is_some() || to.is_some()) {
+        return Err(AppError::DateRange(
+            "days mode does not accept --from/--to; use --date and --days".to_string(),
+        ));
+    }
+
+    // Only the date-centric views project repeaters; the flat lists show a
+    // repeating task once, whatever its base date.
+    let tasks = if matches!(
+        scope,
+        AgendaScope::Tasks
+            | AgendaScope::Rescheduled
+            | AgendaScope::Search
+            | AgendaScope::Stuck
+            | AgendaScope::Waiting { .. }
+            | AgendaScope::Habits { .. }
+    ) {
+        tasks
+    } else if matches!(
+        scope,
+        AgendaScope::Day
+            | AgendaScope::Week
+            | AgendaScope::Fortnight
+            | AgendaScope::Month
+            | AgendaScope::RestOfWeek
+            | AgendaScope::Days { .. }
+            | AgendaScope::Free { .. }
+    ) {
+        // Forecast, quarter and simulation count a task's work once, so
+        // only the day views see one entry per planning line.
+        apply_repeat_horizon(expand_planning(tasks), today, repeat_horizon)
+    } else {
+        apply_repeat_horizon(tasks, today, repeat_horizon)
+    };
+    let scheduled_upcoming = scheduled_warning_days
+        .map(|days| scheduled_warnings(&tasks, today, days))
+        .unwrap_or_default();
 
-    match scope {
+    let output: Result<AgendaOutput, AppError> = match scope {
         AgendaScope::Day => {
             // --from/--to: range of day-agendas. Single edge falls back to
             // `today` (current_date or --current-date).
@@ -233,7 +1111,7 @@ pub fn filter_agenda(
                 )))
             } else {
                 let target_date = match date {
-                    Some(date_str) => parse_date_arg("date", date_str)?,
+                    Some(date_str) => parse_date_arg("date", date_str, today)?,
                     None => today,
                 };
                 Ok(AgendaOutput::Days(vec![build_day_agenda(
@@ -247,20 +1125,32 @@ pub fn filter_agenda(
             let (start_date, end_date) = if let Some(range) = parse_range(from, to, today)? {
                 range
             } else if let Some(date_str) = date {
-                get_week_for_date(parse_date_arg("date", date_str)?)
+                get_week_for_date(parse_date_arg("date", date_str, today)?, week_start)
             } else {
-                get_week_for_date(today)
+                get_week_for_date(today, week_start)
             };
 
             Ok(AgendaOutput::Days(build_week_agenda(
                 &tasks, start_date, end_date, today,
             )))
         }
+        AgendaScope::Fortnight => {
+            let anchor = match date {
+                Some(date_str) => parse_date_arg("date", date_str, today)?,
+                None => today,
+            };
+            let (start_date, week_end) = get_week_for_date(anchor, week_start);
+            let end_date = week_end + chrono::Duration::days(7);
+
+            Ok(AgendaOutput::Days(build_week_agenda(
+                &tasks, start_date, end_date, today,
+            )))
+        }
         AgendaScope::Month => {
             let (start_date, end_date) = if let Some(range) = parse_range(from, to, today)? {
                 range
             } else if let Some(date_str) = date {
-                get_month_for_date(parse_date_arg("date", date_str)?)
+                get_month_for_date(parse_date_arg("date", date_str, today)?)
             } else {
                 get_month_for_date(today)
             };
@@ -269,8 +1159,33 @@ pub fn filter_agenda(
                 &tasks, start_date, end_date, today,
             )))
         }
+        AgendaScope::RestOfWeek => {
+            let start_date = match date {
+                Some(date_str) => parse_date_arg("date", date_str, today)?,
+                None => today,
+            };
+            let (_, end_date) = get_week_for_date(start_date, week_start);
+
+            Ok(AgendaOutput::Days(build_week_agenda(
+                &tasks, start_date, end_date, today,
+            )))
+        }
+        AgendaScope::Days { days } => {
+            let start_date = match date {
+                Some(date_str) => parse_date_arg("date", date_str, today)?,
+                None => today,
+            };
+            let end_date = start_date
+                .checked_add_days(chrono::Days::new(u64::from(days.max(1) - 1)))
+                .ok_or_else(|| AppError::DateRange(format!("{days} days from {start_date}")))?;
+
+            Ok(AgendaOutput::Days(build_week_agenda(
+                &tasks, start_date, end_date, today,
+            )))
+        }
         AgendaScope::Tasks => {
-            // Default: TODO only — the documented contract, pinned by the JSON
+            // Default: open tasks only (TODO and custom open keywords) — the documented
+            // contract, pinned by the JSON
             // wire-contract snapshot tests and grepped for by existing
             // pipelines. The opt-in `--tasks-include-done` (`include_done`)
             // additionally surfaces `DONE` tasks so a consumer can act on
@@ -281,50 +1196,230 @@ pub fn filter_agenda(
             let mut filtered: Vec<Task> = tasks
                 .into_iter()
                 .filter(|t| {
-                    matches!(t.task_type, Some(TaskType::Todo))
-                        || (include_done && matches!(t.task_type, Some(TaskType::Done)))
+                    t.task_type.as_ref().is_some_and(TaskType::is_open)
+                        || (include_done && t.task_type.as_ref().is_some_and(TaskType::is_done))
                         || (include_cancelled
                             && matches!(t.task_type, Some(TaskType::Cancelled(_))))
                 })
                 .collect();
-            filtered.sort_by_key(|t| {
-                t.priority
-                    .as_ref()
-                    .map(|p| p.order())
-                    .unwrap_or(NO_PRIORITY_ORDER)
-            });
+            filtered.sort_by_key(priority_order);
             Ok(AgendaOutput::Tasks(filtered))
         }
+        AgendaScope::Rescheduled => {
+            // Procrastination report: open tasks whose SCHEDULED date moved at
+            // least once, most-moved first; ties keep file order.
+            let mut postponed: Vec<Task> = tasks
+                .into_iter()
+                .filter(|t| {
+                    t.task_type.as_ref().is_some_and(TaskType::is_open)
+                        && t.rescheduled_count.is_some()
+                })
+                .collect();
+            postponed.sort_by(|a, b| {
+                b.rescheduled_count
+                    .cmp(&a.rescheduled_count)
+                    .then_with(|| a.file.cmp(&b.file))
+                    .then_with(|| a.line.cmp(&b.line))
+            });
+            Ok(AgendaOutput::Tasks(postponed))
+        }
+        AgendaScope::Search => {
+            // Search view: the caller has kept the tasks matching `--query`;
+            // every state is listed, in file order.
+            let mut found = tasks;
+            found.sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.line.cmp(&b.line)));
+            Ok(AgendaOutput::Tasks(found))
+        }
+        AgendaScope::Stuck => Ok(AgendaOutput::Tasks(stuck::build_stuck(tasks))),
+        AgendaScope::Waiting { days } => {
+            // Follow-up nag: open tasks handed off more than `days` days ago,
+            // longest-waiting first. Org has no built-in WAITING state, so
+            // the `WAITING_SINCE` date marks a delegated task whatever its
+            // keyword (`TODO`, or `WAITING` from `--todo-keywords`); an open
+            // task without it is not waiting on anyone.
+            let mut stalled: Vec<(NaiveDate, Task)> = tasks
+                .into_iter()
+                .filter(|t| t.task_type.as_ref().is_some_and(TaskType::is_open))
+                .filter_map(|t| {
+                    let since =
+                        NaiveDate::parse_from_str(t.waiting_since.as_deref()?, "%Y-%m-%d").ok()?;
+                    ((today - since).num_days() > i64::from(days)).then_some((since, t))
+                })
+                .collect();
+            stalled.sort_by(|(a_since, a), (b_since, b)| {
+                a_since
+                    .cmp(b_since)
+                    .then_with(|| a.file.cmp(&b.file))
+                    .then_with(|| a.line.cmp(&b.line))
+            });
+            Ok(AgendaOutput::Tasks(
+                stalled.into_iter().map(|(_, t)| t).collect(),
+            ))
+        }
+        AgendaScope::Forecast { months } => {
+            let start = match date {
+                Some(date_str) => parse_date_arg("date", date_str, today)?,
+                None => today,
+            };
+            Ok(AgendaOutput::Forecast(forecast::build_forecast(
+                &tasks, start, months,
+            )))
+        }
+        AgendaScope::Quarter { fiscal_year_start } => {
+            let anchor = match date {
+                Some(date_str) => parse_date_arg("date", date_str, today)?,
+                None => today,
+            };
+            let months = quarter::quarter_months(anchor, fiscal_year_start).ok_or_else(|| {
+                AppError::DateRange(format!("no fiscal quarter contains {anchor}"))
+            })?;
+            Ok(AgendaOutput::Quarter(quarter::build_quarter(
+                &tasks, &months,
+            )))
+        }
+        AgendaScope::Free {
+            work_hours,
+            min_slot,
+        } => {
+            let (start_date, end_date) = match parse_range(from, to, today)? {
+                Some(range) => range,
+                None => {
+                    let day = match date {
+                        Some(date_str) => parse_date_arg("date", date_str, today)?,
+                        None => today,
+                    };
+                    (day, day)
+                }
+            };
+            let days = build_week_agenda(&tasks, start_date, end_date, today);
+            Ok(AgendaOutput::FreeSlots(free::build_free_slots(
+                &days, work_hours, min_slot,
+            )))
+        }
+        AgendaScope::Habits { days } => {
+            let (start_date, end_date) = match parse_range(from, to, today)? {
+                Some(range) => range,
+                None => {
+                    let end = match date {
+                        Some(date_str) => parse_date_arg("date", date_str, today)?,
+                        None => today,
+                    };
+                    let start = end
+                        .checked_sub_days(chrono::Days::new(u64::from(days.saturating_sub(1))))
+                        .unwrap_or(NaiveDate::MIN);
+                    (start, end)
+                }
+            };
+            Ok(AgendaOutput::Habits(habits::build_habits(
+                &tasks, start_date, end_date,
+            )))
+        }
+        AgendaScope::Simulate => {
+            let (start, end) = parse_range(from, to, today)?.ok_or_else(|| {
+                AppError::DateRange("simulation needs --from and --to".to_string())
+            })?;
+            Ok(AgendaOutput::Simulation(simulate::build_simulation(
+                &tasks, start, end,
+            )))
+        }
+    };
+
+    let mut output = output?;
+    if let AgendaOutput::Days(ref mut days) = output {
+        if !scheduled_upcoming.is_empty() {
+            let today = today.format("%Y-%m-%d").to_string();
+            if let Some(day) = days.iter_mut().find(|day| day.date == today) {
+                day.upcoming.extend(scheduled_upcoming);
+                day.upcoming.sort_by_key(|t| t.days_offset);
+            }
+        }
     }
+    Ok(output)
+}
+
+/// Open SCHEDULED tasks starting 1..=`days` days after `today`, as upcoming
+/// entries for today's agenda. Their `timestamp_type` tells them apart from
+/// deadline warnings. As with a repeating DEADLINE, a repeating task warns
+/// only before its first occurrence; a multi-day range never does.
+fn scheduled_warnings(tasks: &[Task], today: NaiveDate, days: u32) -> Vec<TaskWithOffset> {
+    prepare_tasks(tasks)
+        .into_iter()
+        .filter(|entry| {
+            entry.task.timestamp_type.as_deref() == Some("SCHEDULED")
+                && entry.range_end.is_none()
+                && !entry.task.task_type.as_ref().is_some_and(TaskType::is_done)
+        })
+        .filter_map(|entry| {
+            let date = entry.parsed?.date;
+            let days_diff = (date - today).num_days();
+            (days_diff > 0 && days_diff <= i64::from(days) && !paused_on(entry.task, date))
+                .then(|| create_task_without_time(&Arc::new(entry.task.clone()), Some(days_diff)))
+        })
+        .collect()
 }
 
 fn build_day_agenda(tasks: &[Task], day_date: NaiveDate, current_date: NaiveDate) -> DayAgenda {
     let prepared = prepare_tasks(tasks);
-    build_day_agenda_prepared(&prepared, day_date, current_date)
+    build_day_agenda_prepared(&prepared, &share_tasks(tasks), day_date, current_date)
 }
 
+/// One `Arc` per task for the day views: every day a task appears on holds
+/// a reference to it plus the few fields of that occurrence, not a copy.
+fn share_tasks(tasks: &[Task]) -> Vec<Arc<Task>> {
+    tasks.iter().cloned().map(Arc::new).collect()
+}
+
+/// `shared` holds the same tasks as `prepared`, in the same order.
 fn build_day_agenda_prepared(
     prepared: &[PreparedTask<'_>],
+    shared: &[Arc<Task>],
     day_date: NaiveDate,
     current_date: NaiveDate,
 ) -> DayAgenda {
     let mut agenda = DayAgenda::new(day_date);
 
-    for entry in prepared {
-        let task = entry.task;
+    for (entry, task) in prepared.iter().zip(shared) {
         if let Some(ref parsed) = entry.parsed {
             if let Some(ref repeater) = parsed.repeater {
                 handle_repeating_task(task, parsed, repeater, day_date, current_date, &mut agenda);
+            } else if let Some(end) = entry.range_end {
+                push_range_day(task, parsed.date, end, day_date, &mut agenda);
             } else {
                 handle_non_repeating_task(task, parsed, day_date, current_date, &mut agenda);
             }
+            if task.timestamp_overnight == Some(true) {
+                push_overnight_continuation(task, parsed, day_date, &mut agenda);
+            }
         }
     }
 
     agenda.overdue.sort_by_key(|t| t.days_offset);
+    for entry in &mut agenda.overdue {
+        entry.overdue_age = entry
+            .days_offset
+            .map(|offset| OverdueAge::from_days(-offset));
+    }
+    for entry in agenda
+        .scheduled_timed
+        .iter_mut()
+        .chain(&mut agenda.scheduled_no_time)
+        .chain(&mut agenda.upcoming)
+    {
+        entry.anniversary_years = anniversary_years(entry);
+    }
     agenda
         .scheduled_timed
-        .sort_by(|a, b| a.task.timestamp_time.cmp(&b.task.timestamp_time));
+        .sort_by(|a, b| a.timestamp_time().cmp(&b.timestamp_time()));
+    mark_conflicts(&mut agenda.scheduled_timed);
+    for entry in agenda
+        .overdue
+        .iter_mut()
+        .chain(&mut agenda.scheduled_timed)
+        .chain(&mut agenda.scheduled_no_time)
+        .chain(&mut agenda.upcoming)
+    {
+        entry.next_occurrence = next_occurrence(entry, day_date);
+    }
     agenda.upcoming.sort_by_key(|t| t.days_offset);
     // scheduled_no_time has no time-of-day to order by, so it is sorted by
     // priority (high first, mirroring upstream org-agenda's `urgency-down`),
@@ -334,19 +1429,8 @@ fn build_day_agenda_prepared(
     // 2026-05-25 logic review). No-priority tasks sort last, consistent with
     // the `--tasks` flat list.
     agenda.scheduled_no_time.sort_by(|a, b| {
-        let pa = a
-            .task
-            .priority
-            .as_ref()
-            .map(|p| p.order())
-            .unwrap_or(NO_PRIORITY_ORDER);
-        let pb = b
-            .task
-            .priority
-            .as_ref()
-            .map(|p| p.order())
-            .unwrap_or(NO_PRIORITY_ORDER);
-        pa.cmp(&pb)
+        priority_order(&a.task)
+            .cmp(&priority_order(&b.task))
             .then_with(|| a.task.file.cmp(&b.task.file))
             .then_with(|| a.task.line.cmp(&b.task.line))
     });
@@ -354,8 +1438,89 @@ fn build_day_agenda_prepared(
     agenda
 }
 
+/// Flag the timed entries whose times overlap another's: a double
+/// booking. An entry without an end time takes up its start minute only,
+/// and one ending before it starts runs to midnight. Deadlines and closing
+/// times mark a moment rather than time spent and never conflict, nor do
+/// cancelled tasks.
+fn mark_conflicts(timed: &mut [TaskWithOffset]) {
+    let minute = |time: &str| {
+        NaiveTime::parse_from_str(time, "%H:%M")
+            .ok()
+            .map(|t| t.hour() * 60 + t.minute())
+    };
+    let spans: Vec<Option<(u32, u32)>> = timed
+        .iter()
+        .map(|entry| {
+            if matches!(entry.task.task_type, Some(TaskType::Cancelled(_)))
+                || matches!(
+                    entry.task.timestamp_type.as_deref(),
+                    Some("DEADLINE" | "CLOSED")
+                )
+            {
+                return None;
+            }
+            let start = minute(entry.timestamp_time()?)?;
+            let end = match entry.timestamp_end_time().and_then(minute) {
+                Some(end) if end >= start => end,
+                Some(_) => 24 * 60,
+                None => start,
+            };
+            Some((start, end))
+        })
+        .collect();
+    for (i, a) in spans.iter().enumerate() {
+        let Some((start, end)) = *a else { continue };
+        timed[i].conflict = spans.iter().enumerate().any(|(j, b)| {
+            b.is_some_and(|(other_start, other_end)| {
+                i != j && (start == other_start || (start < other_end && other_start < end))
+            })
+        });
+    }
+}
+
+/// First date after both the entry's own date and `day` on which a
+/// repeating entry occurs again, as `YYYY-MM-DD`. `None` for a one-off
+/// task and for a repeat step too long to have a next occurrence.
+fn next_occurrence(entry: &TaskWithOffset, day: NaiveDate) -> Option<String> {
+    let task = &entry.task;
+    let parsed = parse_org_timestamp(task.timestamp.as_deref()?, None).ok()?;
+    let repeater = parsed.repeater.as_ref()?;
+    let base = repeat_base(task, parsed.date, repeater);
+    let shown = entry
+        .timestamp_date()
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+        .unwrap_or(day);
+    let from = shown.max(day).succ_opt()?;
+    closest_date(base, from, DatePreference::Future, repeater)
+        .map(|date| date.format("%Y-%m-%d").to_string())
+}
+
+/// Tag that turns a yearly repeating task into an anniversary.
+const ANNIVERSARY_TAG: &str = "anniversary";
+
+/// Years between an anniversary's own date and the date this entry falls
+/// on, like Org's `diary-anniversary`. `None` for any other entry and for
+/// the original date itself.
+fn anniversary_years(entry: &TaskWithOffset) -> Option<u32> {
+    let task = &entry.task;
+    if !task.tags.iter().flatten().any(|t| t == ANNIVERSARY_TAG) {
+        return None;
+    }
+    let repeater = task
+        .timestamp_repeater
+        .as_deref()
+        .and_then(parse_repeater)?;
+    if repeater.unit != RepeaterUnit::Year {
+        return None;
+    }
+    let year = |date: &str| date.get(..4)?.parse::<i32>().ok();
+    let years = year(entry.timestamp_date()?)? - year(task.timestamp_date.as_deref()?)?;
+    u32::try_from(years).ok().filter(|&years| years > 0)
+}
+
 fn handle_non_repeating_task(
-    task: &Task,
+    task: &Arc<Task>,
     parsed: &crate::timestamp::ParsedTimestamp,
     day_date: NaiveDate,
     current_date: NaiveDate,
@@ -363,7 +1528,7 @@ fn handle_non_repeating_task(
 ) {
     let task_date = parsed.date;
     let days_diff = (task_date - day_date).num_days();
-    let is_done = matches!(task.task_type, Some(TaskType::Done));
+    let is_done = task.task_type.as_ref().is_some_and(TaskType::is_done);
     let is_today = day_date == current_date;
 
     let days_offset = if days_diff != 0 {
@@ -374,16 +1539,14 @@ fn handle_non_repeating_task(
 
     // Show task on its scheduled date
     if task_date == day_date {
-        let task_with_offset = TaskWithOffset {
-            task: task.clone(),
-            days_offset,
-        };
-        if task_with_offset.task.timestamp_time.is_some() {
+        let task_with_offset =
+            TaskWithOffset::new(Arc::clone(task), Occurrence::default(), days_offset);
+        if task_with_offset.timestamp_time().is_some() {
             agenda.scheduled_timed.push(task_with_offset);
         } else {
             agenda.scheduled_no_time.push(task_with_offset);
         }
-    } else if days_diff < 0 && is_today && !is_done {
+    } else if days_diff < 0 && is_today && !is_done && !is_appointment(task) {
         // Overdue only in today agenda
         agenda
             .overdue
@@ -403,14 +1566,87 @@ fn handle_non_repeating_task(
     }
 }
 
-fn create_task_without_time(task: &Task, days_offset: Option<i64>) -> TaskWithOffset {
-    let mut task_copy = task.clone();
-    task_copy.timestamp_time = None;
-    task_copy.timestamp_end_time = None;
-    TaskWithOffset {
-        task: task_copy,
-        days_offset,
+/// List a date range `<start>--<end>` on `day_date` when the day falls
+/// within it, marked with its position like an org-agenda block entry.
+/// The first day keeps the start time; the later days are untimed. A
+/// range is never overdue or upcoming.
+fn push_range_day(
+    task: &Arc<Task>,
+    start: NaiveDate,
+    end: NaiveDate,
+    day_date: NaiveDate,
+    agenda: &mut DayAgenda,
+) {
+    if day_date < start || day_date > end {
+        return;
+    }
+    let occurrence = Occurrence {
+        untimed: day_date != start,
+        ..Occurrence::default()
+    };
+    let mut entry = TaskWithOffset::new(Arc::clone(task), occurrence, None);
+    entry.range_day = Some(RangeDay {
+        day: (day_date - start).num_days() as u32 + 1,
+        days: (end - start).num_days() as u32 + 1,
+    });
+    if entry.timestamp_time().is_some() {
+        agenda.scheduled_timed.push(entry);
+    } else {
+        agenda.scheduled_no_time.push(entry);
+    }
+}
+
+/// Whether the task's timestamp is a keyword-less active one: an
+/// appointment, shown on its date but never overdue once the date has
+/// passed (upstream `org-agenda-get-timestamps` only lists the day itself).
+fn is_appointment(task: &Task) -> bool {
+    task.timestamp_type.as_deref() == Some("PLAIN")
+}
+
+/// Second half of a timed entry that crosses midnight (`22:00-01:30`): when
+/// the entry occurs on the day before `day_date`, list it on `day_date` too,
+/// as a `00:00`..end slot with `days_offset = -1` so it sorts first among the
+/// timed entries and reads as "started yesterday".
+fn push_overnight_continuation(
+    task: &Arc<Task>,
+    parsed: &crate::timestamp::ParsedTimestamp,
+    day_date: NaiveDate,
+    agenda: &mut DayAgenda,
+) {
+    let Some(start_day) = day_date.pred_opt() else {
+        return;
+    };
+    let occurs = match parsed.repeater {
+        Some(ref repeater) => {
+            closest_date(parsed.date, start_day, DatePreference::Past, repeater) == Some(start_day)
+        }
+        None => parsed.date == start_day,
+    };
+    if !occurs {
+        return;
+    }
+
+    let mut occurrence = Occurrence {
+        timestamp_time: Some("00:00".to_string()),
+        ..Occurrence::default()
+    };
+    if let Some(ref repeater) = parsed.repeater {
+        occurrence.timestamp_date = Some(start_day.format("%Y-%m-%d").to_string());
+        occurrence.timestamp = task.timestamp_type.as_deref().map(|ts_type| {
+            format_repeating_timestamp(ts_type, start_day, task.timestamp_time.as_deref(), repeater)
+        });
     }
+    agenda
+        .scheduled_timed
+        .push(TaskWithOffset::new(Arc::clone(task), occurrence, Some(-1)));
+}
+
+fn create_task_without_time(task: &Arc<Task>, days_offset: Option<i64>) -> TaskWithOffset {
+    let occurrence = Occurrence {
+        untimed: true,
+        ..Occurrence::default()
+    };
+    TaskWithOffset::new(Arc::clone(task), occurrence, days_offset)
 }
 
 /// Format an org-mode timestamp string with the original repeater type preserved
@@ -438,29 +1674,22 @@ fn format_repeating_timestamp(
 }
 
 fn push_scheduled_occurrence(
-    task: &Task,
+    task: &Arc<Task>,
     repeater: &crate::timestamp::Repeater,
     day_date: NaiveDate,
     agenda: &mut DayAgenda,
 ) {
-    let mut task_copy = task.clone();
-    task_copy.timestamp_date = Some(day_date.format("%Y-%m-%d").to_string());
-
-    if let Some(ref ts_type) = task.timestamp_type {
-        task_copy.timestamp = Some(format_repeating_timestamp(
-            ts_type,
-            day_date,
-            task.timestamp_time.as_deref(),
-            repeater,
-        ));
-    }
-
-    let task_with_offset = TaskWithOffset {
-        task: task_copy,
-        days_offset: None,
+    let occurrence = Occurrence {
+        timestamp_date: Some(day_date.format("%Y-%m-%d").to_string()),
+        timestamp: task.timestamp_type.as_deref().map(|ts_type| {
+            format_repeating_timestamp(ts_type, day_date, task.timestamp_time.as_deref(), repeater)
+        }),
+        ..Occurrence::default()
     };
 
-    if task_with_offset.task.timestamp_time.is_some() {
+    let task_with_offset = TaskWithOffset::new(Arc::clone(task), occurrence, None);
+
+    if task_with_offset.timestamp_time().is_some() {
         agenda.scheduled_timed.push(task_with_offset);
     } else {
         agenda.scheduled_no_time.push(task_with_offset);
@@ -468,43 +1697,38 @@ fn push_scheduled_occurrence(
 }
 
 fn push_overdue_occurrence(
-    task: &Task,
+    task: &Arc<Task>,
     repeater: &crate::timestamp::Repeater,
     deadline_date: NaiveDate,
     current_date: NaiveDate,
     agenda: &mut DayAgenda,
 ) {
     let days_diff = (deadline_date - current_date).num_days();
-    let mut task_copy = task.clone();
-    task_copy.timestamp_time = None;
-    task_copy.timestamp_end_time = None;
-    task_copy.timestamp_date = Some(deadline_date.format("%Y-%m-%d").to_string());
-
-    if let Some(ref ts_type) = task.timestamp_type {
-        task_copy.timestamp = Some(format_repeating_timestamp(
-            ts_type,
-            deadline_date,
-            None,
-            repeater,
-        ));
-    }
+    let occurrence = Occurrence {
+        untimed: true,
+        timestamp_date: Some(deadline_date.format("%Y-%m-%d").to_string()),
+        timestamp: task
+            .timestamp_type
+            .as_deref()
+            .map(|ts_type| format_repeating_timestamp(ts_type, deadline_date, None, repeater)),
+        ..Occurrence::default()
+    };
 
-    agenda.overdue.push(TaskWithOffset {
-        task: task_copy,
-        days_offset: Some(days_diff),
-    });
+    agenda.overdue.push(TaskWithOffset::new(
+        Arc::clone(task),
+        occurrence,
+        Some(days_diff),
+    ));
 }
 
 fn handle_repeating_task(
-    task: &Task,
+    task: &Arc<Task>,
     parsed: &crate::timestamp::ParsedTimestamp,
     repeater: &crate::timestamp::Repeater,
     day_date: NaiveDate,
     current_date: NaiveDate,
     agenda: &mut DayAgenda,
 ) {
-    use crate::timestamp::{closest_date, DatePreference};
-
     let base_date = parsed.date;
     let is_today = day_date == current_date;
 
@@ -520,14 +1744,21 @@ fn handle_repeating_task(
 
     // Show task on its occurrence day. If base_date is in the future,
     // deadline may be None; in that case use base_date as the first occurrence.
+    // A paused task keeps its repeater but shows no occurrence until it
+    // resumes, neither on the day nor as overdue or upcoming.
     let mut shown_on_day = false;
     if let Some(repeat_date) = repeat {
-        if day_date == repeat_date {
+        if day_date == repeat_date && !paused_on(task, day_date) {
             push_scheduled_occurrence(task, repeater, day_date, agenda);
             shown_on_day = true;
         }
     }
-    if !shown_on_day && deadline.is_none() && current_date < base_date && day_date == base_date {
+    if !shown_on_day
+        && deadline.is_none()
+        && current_date < base_date
+        && day_date == base_date
+        && !paused_on(task, day_date)
+    {
         push_scheduled_occurrence(task, repeater, day_date, agenda);
     }
 
@@ -537,13 +1768,13 @@ fn handle_repeating_task(
     // CLOSED). Occurrence-day scheduling above is unaffected; that matches
     // the default of `org-agenda-skip-deadline-if-done` (nil), which still
     // shows the DONE task on its actual deadline date.
-    let is_done = matches!(task.task_type, Some(TaskType::Done));
+    let is_done = task.task_type.as_ref().is_some_and(TaskType::is_done);
     let is_closed_ts = matches!(task.timestamp_type.as_deref(), Some("CLOSED"));
 
-    if is_today && !is_done && !is_closed_ts {
+    if is_today && !is_done && !is_closed_ts && !is_appointment(task) {
         // Overdue: requires a past occurrence
         if let Some(deadline_date) = deadline {
-            if deadline_date < current_date {
+            if deadline_date < current_date && !paused_on(task, deadline_date) {
                 let should_show_overdue =
                     if repeater.unit == crate::timestamp::RepeaterUnit::Workday {
                         use crate::holidays::HolidayCalendar;
@@ -568,7 +1799,10 @@ fn handle_repeating_task(
         // base date itself is still ahead of `current_date`.
         if let Some(ref ts_type) = task.timestamp_type {
             if ts_type == "DEADLINE" {
-                let next_due = if repeat.is_none() && current_date < base_date {
+                let next_due = if repeat.is_none()
+                    && current_date < base_date
+                    && !paused_on(task, base_date)
+                {
                     Some(base_date)
                 } else {
                     None
@@ -577,13 +1811,9 @@ fn handle_repeating_task(
                     let days_diff = (next_date - current_date).num_days();
                     let window = parsed.warning_days.unwrap_or(DEADLINE_WARNING_DAYS);
                     if days_diff > 0 && days_diff <= window {
-                        let mut task_copy = task.clone();
-                        task_copy.timestamp_time = None;
-                        task_copy.timestamp_end_time = None;
-                        agenda.upcoming.push(TaskWithOffset {
-                            task: task_copy,
-                            days_offset: Some(days_diff),
-                        });
+                        agenda
+                            .upcoming
+                            .push(create_task_without_time(task, Some(days_diff)));
                     }
                 }
             }
@@ -600,24 +1830,335 @@ fn build_week_agenda(
     current_date: NaiveDate,
 ) -> Vec<DayAgenda> {
     let prepared = prepare_tasks(tasks);
+    let shared = share_tasks(tasks);
     let mut result = Vec::new();
     let mut current = start_date;
 
     while current <= end_date {
-        result.push(build_day_agenda_prepared(&prepared, current, current_date));
+        result.push(build_day_agenda_prepared(
+            &prepared,
+            &shared,
+            current,
+            current_date,
+        ));
         current += chrono::Duration::days(1);
     }
 
     result
 }
 
-/// Get week boundaries (Monday to Sunday) for a specific date
-fn get_week_for_date(date: NaiveDate) -> (NaiveDate, NaiveDate) {
-    let weekday = date.weekday();
-    let days_from_monday = weekday.num_days_from_monday();
-    let monday = date - chrono::Duration::days(days_from_monday as i64);
-    let sunday = monday + chrono::Duration::days(6);
-    (monday, sunday)
+/// Move the entries of each day into one group per category, in category
+/// order, like the category column of org-agenda. A task without a
+/// `CATEGORY` falls under its file name without the extension, as in Org.
+/// Entries keep their order within each bucket.
+pub fn group_by_category(days: &mut [DayAgenda]) {
+    fn group<'a>(
+        groups: &'a mut BTreeMap<String, CategoryAgenda>,
+        task: &Task,
+    ) -> &'a mut CategoryAgenda {
+        let category = task.category.clone().unwrap_or_else(|| {
+            std::path::Path::new(&*task.file).file_stem().map_or_else(
+                || task.file.to_string(),
+                |s| s.to_string_lossy().into_owned(),
+            )
+        });
+        groups
+            .entry(category)
+            .or_insert_with_key(|category| CategoryAgenda::new(category.clone()))
+    }
+
+    for day in days {
+        let mut groups = BTreeMap::new();
+        for entry in std::mem::take(&mut day.overdue) {
+            group(&mut groups, &entry.task).overdue.push(entry);
+        }
+        for entry in std::mem::take(&mut day.scheduled_timed) {
+            group(&mut groups, &entry.task).scheduled_timed.push(entry);
+        }
+        for entry in std::mem::take(&mut day.scheduled_no_time) {
+            group(&mut groups, &entry.task)
+                .scheduled_no_time
+                .push(entry);
+        }
+        for entry in std::mem::take(&mut day.upcoming) {
+            group(&mut groups, &entry.task).upcoming.push(entry);
+        }
+        day.categories = groups.into_values().collect();
+    }
+}
+
+/// Keep at most `max` entries in each section of every day (and of every
+/// category group), counting the rest in `truncated`. The scheduled
+/// section keeps its timed entries first. Run after the `summary` is set,
+/// so it still counts every entry.
+pub fn cap_entries(days: &mut [DayAgenda], max: usize) {
+    fn cap(
+        overdue: &mut Vec<TaskWithOffset>,
+        timed: &mut Vec<TaskWithOffset>,
+        no_time: &mut Vec<TaskWithOffset>,
+        upcoming: &mut Vec<TaskWithOffset>,
+        max: usize,
+    ) -> Truncated {
+        let cut = |bucket: &mut Vec<TaskWithOffset>, keep: usize| {
+            let dropped = bucket.len().saturating_sub(keep);
+            bucket.truncate(keep);
+            dropped
+        };
+        let scheduled = cut(timed, max) + cut(no_time, max.saturating_sub(timed.len()));
+        Truncated {
+            overdue: cut(overdue, max),
+            scheduled,
+            upcoming: cut(upcoming, max),
+        }
+    }
+
+    for day in days {
+        day.truncated = cap(
+            &mut day.overdue,
+            &mut day.scheduled_timed,
+            &mut day.scheduled_no_time,
+            &mut day.upcoming,
+            max,
+        );
+        for group in &mut day.categories {
+            group.truncated = cap(
+                &mut group.overdue,
+                &mut group.scheduled_timed,
+                &mut group.scheduled_no_time,
+                &mut group.upcoming,
+                max,
+            );
+        }
+    }
+}
+
+/// Merge the sections of each day into its `timeline`, in the order the
+/// day is lived: overdue entries, the day's timed entries by time, its
+/// untimed ones, then upcoming. On the day dated `now`, a marker goes after
+/// the timed entries starting at or before its time. Run after
+/// `--max-entries-per-day`, whose counts the timeline keeps.
+pub fn merge_timeline(days: &mut [DayAgenda], now: NaiveDateTime) {
+    let today = now.date().format("%Y-%m-%d").to_string();
+    let time = now.time();
+    for day in days {
+        let past = day.overdue.len();
+        let started = day
+            .scheduled_timed
+            .iter()
+            .take_while(|entry| {
+                entry
+                    .timestamp_time()
+                    .and_then(|t| NaiveTime::parse_from_str(t, "%H:%M").ok())
+                    .is_some_and(|start| start <= time)
+            })
+            .count();
+        if day.date == today {
+            day.now = Some(NowMarker {
+                time: time.format("%H:%M").to_string(),
+                index: past + started,
+            });
+        }
+        let mut timeline = std::mem::take(&mut day.overdue);
+        timeline.append(&mut day.scheduled_timed);
+        timeline.append(&mut day.scheduled_no_time);
+        timeline.append(&mut day.upcoming);
+        day.timeline = timeline;
+    }
+}
+
+/// List the open slots between the timed entries of each workday among
+/// `days` within `work_hours`, as `--agenda free` finds them.
+pub fn show_gaps(days: &mut [DayAgenda], work_hours: WorkHours, min_slot: u32) {
+    free::fill_gaps(days, work_hours, min_slot);
+}
+
+/// Collapse a recurring task that is scheduled on several days of `days` into
+/// its first occurrence, which then lists every occurrence date in
+/// `occurrence_days`. Only repeater instances in the scheduled buckets are
+/// merged (keyed by file and line); overdue / upcoming entries, one-off tasks
+/// and overnight continuations stay on their days. A task occurring once is
+/// left untouched.
+pub fn collapse_recurring(days: &mut [DayAgenda]) {
+    use std::collections::HashMap;
+
+    fn collapsible(entry: &TaskWithOffset) -> bool {
+        entry.task.timestamp_repeater.is_some() && entry.days_offset.is_none()
+    }
+
+    let mut dates_by_task: HashMap<(Arc<str>, u32), Vec<String>> = HashMap::new();
+    for day in days.iter() {
+        for entry in day.scheduled_timed.iter().chain(&day.scheduled_no_time) {
+            if collapsible(entry) {
+                let key = (entry.task.file.clone(), entry.task.line);
+                let dates = dates_by_task.entry(key).or_default();
+                if dates.last() != Some(&day.date) {
+                    dates.push(day.date.clone());
+                }
+            }
+        }
+    }
+    dates_by_task.retain(|_, dates| dates.len() > 1);
+
+    for day in days.iter_mut() {
+        for bucket in [&mut day.scheduled_timed, &mut day.scheduled_no_time] {
+            bucket.retain_mut(|entry| {
+                if !collapsible(entry) {
+                    return true;
+                }
+                let key = (entry.task.file.clone(), entry.task.line);
+                let Some(dates) = dates_by_task.get(&key) else {
+                    return true;
+                };
+                if dates[0] != day.date {
+                    return false;
+                }
+                entry.occurrence_days = Some(dates.clone());
+                true
+            });
+        }
+    }
+}
+
+/// Annotate every timed entry of `days` with its start / end time as seen
+/// in each of `others`, treating the vault's timestamps as local to `home`
+/// (the first `--tz` zone). The time is read from the entry's `timestamp`
+/// string, so repeater occurrences and overnight continuations convert the
+/// real start rather than the agenda slot. A start that does not exist in
+/// `home` (a DST gap) is left unannotated.
+pub fn annotate_local_times(
+    days: &mut [DayAgenda],
+    home: &str,
+    others: &[&str],
+) -> Result<(), AppError> {
+    use crate::timestamp::parse_timestamp_fields_normalized;
+    use chrono::{NaiveTime, TimeZone};
+
+    let parse_tz = |name: &str| {
+        name.parse::<Tz>()
+            .map_err(|_| AppError::InvalidTimezone(name.to_string()))
+    };
+    let home_tz = parse_tz(home)?;
+    let zones = others
+        .iter()
+        .map(|name| Ok((*name, parse_tz(name)?)))
+        .collect::<Result<Vec<_>, AppError>>()?;
+    if zones.is_empty() {
+        return Ok(());
+    }
+
+    let convert = |date: NaiveDate, time: NaiveTime, zone: Tz| {
+        home_tz
+            .from_local_datetime(&date.and_time(time))
+            .earliest()
+            .map(|dt| dt.with_timezone(&zone).naive_local())
+    };
+
+    for day in days.iter_mut() {
+        for entry in day.scheduled_timed.iter_mut() {
+            let Some(ts) = entry.timestamp() else {
+                continue;
+            };
+            let (_, date, time, end_time, _) = parse_timestamp_fields_normalized(ts);
+            let parse_time = |t: String| NaiveTime::parse_from_str(&t, "%H:%M").ok();
+            let (Some(date), Some(start)) = (
+                date.and_then(|d| NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok()),
+                time.and_then(parse_time),
+            ) else {
+                continue;
+            };
+            let end = end_time.and_then(parse_time).and_then(|end| {
+                let end_date = if end < start { date.succ_opt()? } else { date };
+                Some((end_date, end))
+            });
+
+            let local: Vec<LocalTime> = zones
+                .iter()
+                .filter_map(|(name, zone)| {
+                    let local_start = convert(date, start, *zone)?;
+                    let local_end = end.and_then(|(d, t)| convert(d, t, *zone));
+                    Some(LocalTime {
+                        tz: (*name).to_string(),
+                        date: local_start.format("%Y-%m-%d").to_string(),
+                        time: local_start.format("%H:%M").to_string(),
+                        end_time: local_end.map(|e| e.format("%H:%M").to_string()),
+                    })
+                })
+                .collect();
+            if !local.is_empty() {
+                entry.local_times = Some(local);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Set `effective_date` on every DEADLINE task whose `timestamp_date` is not
+/// a workday under the bundled holiday calendar, rolled to the next workday
+/// (`forward`) or the previous one. Runs on the agenda output, so each
+/// repeater occurrence is rolled from its own date.
+pub fn roll_deadlines<'a>(tasks: impl IntoIterator<Item = &'a mut Task>, forward: bool) {
+    for task in tasks {
+        let rolled = rolled_deadline(
+            task.timestamp_type.as_deref(),
+            task.timestamp_date.as_deref(),
+            forward,
+        );
+        if let Some(rolled) = rolled {
+            task.effective_date = rolled;
+        }
+    }
+}
+
+/// [`roll_deadlines`] for day views: each entry is rolled from the date of
+/// its own occurrence, recorded on the occurrence rather than the shared task.
+pub fn roll_agenda_deadlines(days: &mut [DayAgenda], forward: bool) {
+    for day in days {
+        let entries = day
+            .overdue
+            .iter_mut()
+            .chain(&mut day.scheduled_timed)
+            .chain(&mut day.scheduled_no_time)
+            .chain(&mut day.upcoming);
+        for entry in entries {
+            let rolled = rolled_deadline(
+                entry.task.timestamp_type.as_deref(),
+                entry.timestamp_date(),
+                forward,
+            );
+            if let Some(rolled) = rolled {
+                entry.occurrence.effective_date = rolled;
+            }
+        }
+    }
+}
+
+/// `Some(effective_date)` for a DEADLINE with a valid date — `None` inside
+/// when the date is already a workday — and `None` for anything else.
+fn rolled_deadline(
+    ts_type: Option<&str>,
+    date: Option<&str>,
+    forward: bool,
+) -> Option<Option<String>> {
+    if ts_type != Some("DEADLINE") {
+        return None;
+    }
+    let date = NaiveDate::parse_from_str(date?, "%Y-%m-%d").ok()?;
+    Some(
+        crate::holidays::HolidayCalendar::global()
+            .roll_to_workday(date, forward)
+            .filter(|rolled| *rolled != date)
+            .map(|rolled| rolled.format("%Y-%m-%d").to_string()),
+    )
+}
+
+/// Get week boundaries for a specific date, for a week that begins on
+/// `week_start` (Monday to Sunday for the ISO default)
+fn get_week_for_date(date: NaiveDate, week_start: Weekday) -> (NaiveDate, NaiveDate) {
+    let days_into_week =
+        (date.weekday().num_days_from_monday() + 7 - week_start.num_days_from_monday()) % 7;
+    let first = date - chrono::Duration::days(days_into_week as i64);
+    let last = first + chrono::Duration::days(6);
+    (first, last)
 }
 
 /// Get month boundaries (first to last day) for a specific date
@@ -703,13 +2244,14 @@ mod tests {
         };
 
         Task {
-            file: "test.md".to_string(),
+            file: "test.md".into(),
             line: 1,
             heading: "Test task".to_string(),
             content: String::new(),
             task_type: Some(task_type),
             priority: None,
             created: None,
+            closed: None,
             timestamp: Some(timestamp.clone()),
             timestamp_type: Some(ts_type.to_string()),
             timestamp_active: Some(true),
@@ -719,7 +2261,36 @@ mod tests {
             timestamp_repeater: None,
             clocks: None,
             total_clock_time: None,
+            log: None,
+            effort_minutes: None,
             properties: None,
+            timestamp_overnight: None,
+            planning: None,
+            inactive_timestamps: None,
+            rescheduled_count: None,
+            effective_date: None,
+            attendees: None,
+            location: None,
+            url: None,
+            links: None,
+            subtasks: None,
+            subtasks_percent: None,
+            subtasks_progress: None,
+            progress_cookie: None,
+            progress: None,
+            children_todo: None,
+            children_done: None,
+            level: None,
+            waiting_since: None,
+            assignee: None,
+            paused: None,
+            paused_until: None,
+            effective_priority: None,
+            category: None,
+            tags: None,
+            outline_path: None,
+            inherited_priority: None,
+            inherited_tags: None,
         }
     }
 
@@ -734,13 +2305,14 @@ mod tests {
     fn create_test_plain_task(timestamp: &str, date_str: &str) -> Task {
         let active = timestamp.starts_with('<');
         Task {
-            file: "test.md".to_string(),
+            file: "test.md".into(),
             line: 1,
             heading: "Plain timestamp task".to_string(),
             content: String::new(),
             task_type: Some(TaskType::Todo),
             priority: None,
             created: None,
+            closed: None,
             timestamp: Some(timestamp.to_string()),
             timestamp_type: Some("PLAIN".to_string()),
             timestamp_active: Some(active),
@@ -750,7 +2322,36 @@ mod tests {
             timestamp_repeater: None,
             clocks: None,
             total_clock_time: None,
+            log: None,
+            effort_minutes: None,
             properties: None,
+            timestamp_overnight: None,
+            planning: None,
+            inactive_timestamps: None,
+            rescheduled_count: None,
+            effective_date: None,
+            attendees: None,
+            location: None,
+            url: None,
+            links: None,
+            subtasks: None,
+            subtasks_percent: None,
+            subtasks_progress: None,
+            progress_cookie: None,
+            progress: None,
+            children_todo: None,
+            children_done: None,
+            level: None,
+            waiting_since: None,
+            assignee: None,
+            paused: None,
+            paused_until: None,
+            effective_priority: None,
+            category: None,
+            tags: None,
+            outline_path: None,
+            inherited_priority: None,
+            inherited_tags: None,
         }
     }
 
@@ -785,6 +2386,57 @@ mod tests {
         assert_eq!(agenda.scheduled_no_time.len(), 1);
     }
 
+    #[test]
+    fn plain_active_timestamp_is_never_overdue() {
+        // A keyword-less active timestamp is an appointment: once its day
+        // has passed it is history, not a missed obligation. A weekly one
+        // still shows up on its occurrence days.
+        let tasks = vec![
+            create_test_plain_task("<2024-12-02 Mon 10:00>", "2024-12-02"),
+            create_test_plain_task("<2024-11-28 Thu +1w>", "2024-11-28"),
+        ];
+        let today = NaiveDate::from_ymd_opt(2024, 12, 4).unwrap();
+        let agenda = build_day_agenda(&tasks, today, today);
+        assert!(agenda.overdue.is_empty());
+
+        let thursday = NaiveDate::from_ymd_opt(2024, 12, 5).unwrap();
+        let agenda = build_day_agenda(&tasks, thursday, today);
+        assert_eq!(agenda.scheduled_no_time.len(), 1);
+    }
+
+    #[test]
+    fn date_range_is_listed_on_every_day_it_covers() {
+        let tasks = vec![create_test_plain_task(
+            "<2024-12-02 Mon>--<2024-12-04 Wed>",
+            "2024-12-02",
+        )];
+        let start = NaiveDate::from_ymd_opt(2024, 12, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 12, 7).unwrap();
+        let week = build_week_agenda(&tasks, start, end, end);
+        let marks: Vec<_> = week
+            .iter()
+            .map(|day| {
+                let timed = day.scheduled_timed.iter().map(|e| (e.range_day, true));
+                let untimed = day.scheduled_no_time.iter().map(|e| (e.range_day, false));
+                timed.chain(untimed).collect::<Vec<_>>()
+            })
+            .collect();
+        let day = |day, timed| vec![(Some(RangeDay { day, days: 3 }), timed)];
+        assert_eq!(
+            marks,
+            [
+                vec![],
+                day(1, false),
+                day(2, false),
+                day(3, false),
+                vec![],
+                vec![],
+                vec![]
+            ]
+        );
+        assert!(week.iter().all(|day| day.overdue.is_empty()));
+    }
+
     #[test]
     fn scheduled_no_time_sorts_by_priority_then_file_line() {
         use crate::types::Priority;
@@ -799,13 +2451,14 @@ mod tests {
         // flat list.
         let day = NaiveDate::from_ymd_opt(2024, 12, 5).unwrap();
         let make = |heading: &str, prio: Option<Priority>, file: &str, line: u32| Task {
-            file: file.to_string(),
+            file: file.into(),
             line,
             heading: heading.to_string(),
             content: String::new(),
             task_type: Some(TaskType::Todo),
             priority: prio,
             created: None,
+            closed: None,
             timestamp: Some("SCHEDULED: <2024-12-05 Thu>".to_string()),
             timestamp_type: Some("SCHEDULED".to_string()),
             timestamp_active: Some(true),
@@ -815,7 +2468,36 @@ mod tests {
             timestamp_repeater: None,
             clocks: None,
             total_clock_time: None,
+            log: None,
+            effort_minutes: None,
             properties: None,
+            timestamp_overnight: None,
+            planning: None,
+            inactive_timestamps: None,
+            rescheduled_count: None,
+            effective_date: None,
+            attendees: None,
+            location: None,
+            url: None,
+            links: None,
+            subtasks: None,
+            subtasks_percent: None,
+            subtasks_progress: None,
+            progress_cookie: None,
+            progress: None,
+            children_todo: None,
+            children_done: None,
+            level: None,
+            waiting_since: None,
+            assignee: None,
+            paused: None,
+            paused_until: None,
+            effective_priority: None,
+            category: None,
+            tags: None,
+            outline_path: None,
+            inherited_priority: None,
+            inherited_tags: None,
         };
 
         // Deliberately scrambled input order: highest priority arrives second,
@@ -1027,14 +2709,8 @@ mod tests {
         assert_eq!(agenda.overdue.len(), 0);
 
         // Check time sorting
-        assert_eq!(
-            agenda.scheduled_timed[0].task.timestamp_time,
-            Some("10:00".to_string())
-        );
-        assert_eq!(
-            agenda.scheduled_timed[1].task.timestamp_time,
-            Some("14:00".to_string())
-        );
+        assert_eq!(agenda.scheduled_timed[0].timestamp_time(), Some("10:00"));
+        assert_eq!(agenda.scheduled_timed[1].timestamp_time(), Some("14:00"));
     }
 
     #[test]
@@ -1073,13 +2749,14 @@ mod tests {
         };
 
         Task {
-            file: "test.md".to_string(),
+            file: "test.md".into(),
             line: 1,
             heading: "Test task".to_string(),
             content: String::new(),
             task_type: Some(task_type),
             priority: None,
             created: None,
+            closed: None,
             timestamp: Some(timestamp.clone()),
             timestamp_type: Some("SCHEDULED".to_string()),
             timestamp_active: Some(true),
@@ -1089,7 +2766,36 @@ mod tests {
             timestamp_repeater: None,
             clocks: None,
             total_clock_time: None,
+            log: None,
+            effort_minutes: None,
             properties: None,
+            timestamp_overnight: None,
+            planning: None,
+            inactive_timestamps: None,
+            rescheduled_count: None,
+            effective_date: None,
+            attendees: None,
+            location: None,
+            url: None,
+            links: None,
+            subtasks: None,
+            subtasks_percent: None,
+            subtasks_progress: None,
+            progress_cookie: None,
+            progress: None,
+            children_todo: None,
+            children_done: None,
+            level: None,
+            waiting_since: None,
+            assignee: None,
+            paused: None,
+            paused_until: None,
+            effective_priority: None,
+            category: None,
+            tags: None,
+            outline_path: None,
+            inherited_priority: None,
+            inherited_tags: None,
         }
     }
 
@@ -1106,13 +2812,14 @@ mod tests {
         };
 
         Task {
-            file: "test.md".to_string(),
+            file: "test.md".into(),
             line: 1,
             heading: "Test task".to_string(),
             content: String::new(),
             task_type: Some(task_type),
             priority: None,
             created: None,
+            closed: None,
             timestamp: Some(timestamp.clone()),
             timestamp_type: Some("DEADLINE".to_string()),
             timestamp_active: Some(true),
@@ -1122,7 +2829,36 @@ mod tests {
             timestamp_repeater: None,
             clocks: None,
             total_clock_time: None,
+            log: None,
+            effort_minutes: None,
             properties: None,
+            timestamp_overnight: None,
+            planning: None,
+            inactive_timestamps: None,
+            rescheduled_count: None,
+            effective_date: None,
+            attendees: None,
+            location: None,
+            url: None,
+            links: None,
+            subtasks: None,
+            subtasks_percent: None,
+            subtasks_progress: None,
+            progress_cookie: None,
+            progress: None,
+            children_todo: None,
+            children_done: None,
+            level: None,
+            waiting_since: None,
+            assignee: None,
+            paused: None,
+            paused_until: None,
+            effective_priority: None,
+            category: None,
+            tags: None,
+            outline_path: None,
+            inherited_priority: None,
+            inherited_tags: None,
         }
     }
 
@@ -1140,10 +2876,7 @@ mod tests {
         let agenda = build_day_agenda(&tasks, day_date, current_date);
 
         assert_eq!(agenda.scheduled_timed.len(), 1);
-        assert_eq!(
-            agenda.scheduled_timed[0].task.timestamp_time,
-            Some("10:00".to_string())
-        );
+        assert_eq!(agenda.scheduled_timed[0].timestamp_time(), Some("10:00"));
     }
 
     #[test]
@@ -1243,6 +2976,90 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_date_expr_today_and_offsets() {
+        let today = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+        let parse = |s| parse_date_expr(s, today);
+        assert_eq!(parse("today"), Ok(today));
+        assert_eq!(
+            parse("today-1d"),
+            Ok(NaiveDate::from_ymd_opt(2024, 1, 30).unwrap())
+        );
+        assert_eq!(
+            parse("today+2w"),
+            Ok(NaiveDate::from_ymd_opt(2024, 2, 14).unwrap())
+        );
+        // Month steps clamp to the end of a shorter month.
+        assert_eq!(
+            parse("today+1m"),
+            Ok(NaiveDate::from_ymd_opt(2024, 2, 29).unwrap())
+        );
+        assert_eq!(
+            parse("2024-03-05-1y"),
+            Ok(NaiveDate::from_ymd_opt(2023, 3, 5).unwrap())
+        );
+        assert_eq!(
+            parse("2024-03-05"),
+            Ok(NaiveDate::from_ymd_opt(2024, 3, 5).unwrap())
+        );
+        for bad in [
+            "someday",
+            "today-1",
+            "today*1d",
+            "today-xd",
+            "today+1q",
+            "2024-13-01",
+        ] {
+            assert!(parse(bad).is_err(), "{bad} must be rejected");
+        }
+    }
+
+    #[test]
+    fn test_parse_date_expr_relative_words_and_bare_offsets() {
+        // A Wednesday.
+        let today = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+        let parse = |s| parse_date_expr(s, today);
+        let day = |d| Ok(NaiveDate::from_ymd_opt(2024, 2, d).unwrap());
+        assert_eq!(parse("tomorrow"), day(1));
+        assert_eq!(parse("yesterday"), parse("today-1d"));
+        assert_eq!(parse("+3d"), day(3));
+        assert_eq!(parse("-1w"), parse("today-1w"));
+        // The nearest weekday from today on, in any spelling.
+        assert_eq!(parse("wed"), Ok(today));
+        assert_eq!(parse("mon"), day(5));
+        assert_eq!(parse("Friday"), day(2));
+        assert_eq!(parse("mon+1w"), day(12));
+        assert!(parse("someday").unwrap_err().contains("YYYY-MM-DD"));
+    }
+
+    #[test]
+    fn test_week_agenda_repeater_shares_task_and_serializes_occurrence() {
+        let tasks = vec![create_test_task_with_repeater(
+            "2024-12-02 Mon",
+            None,
+            "+1d",
+            TaskType::Todo,
+        )];
+        let start_date = NaiveDate::from_ymd_opt(2024, 12, 2).unwrap();
+        let end_date = NaiveDate::from_ymd_opt(2024, 12, 4).unwrap();
+        let current_date = NaiveDate::from_ymd_opt(2024, 12, 2).unwrap();
+
+        let week = build_week_agenda(&tasks, start_date, end_date, current_date);
+
+        let first = &week[0].scheduled_no_time[0];
+        let last = &week[2].scheduled_no_time[0];
+        assert!(Arc::ptr_eq(&first.task, &last.task));
+        assert_eq!(last.timestamp_date(), Some("2024-12-04"));
+
+        let json = serde_json::to_value(last).unwrap();
+        assert_eq!(json["timestamp_date"], "2024-12-04");
+        assert_eq!(json["heading"], first.task.heading.as_str());
+        let resolved = serde_json::to_value(&*last.resolved()).unwrap();
+        for (key, value) in resolved.as_object().unwrap() {
+            assert_eq!(&json[key], value, "field {key}");
+        }
+    }
+
     #[test]
     fn test_overdue_repeating_task_on_non_occurrence_day() {
         let tasks = vec![create_test_task_with_repeater(
@@ -1266,7 +3083,7 @@ mod tests {
             agenda.scheduled_timed.len(),
             agenda.scheduled_no_time.len()
         );
-        assert_eq!(agenda.overdue[0].task.timestamp_time, None);
+        assert_eq!(agenda.overdue[0].timestamp_time(), None);
     }
 
     #[test]
@@ -1283,7 +3100,7 @@ mod tests {
         let agenda = build_day_agenda(&tasks, day_date, current_date);
 
         assert_eq!(agenda.upcoming.len(), 1);
-        assert_eq!(agenda.upcoming[0].task.timestamp_time, None);
+        assert_eq!(agenda.upcoming[0].timestamp_time(), None);
         assert_eq!(agenda.upcoming[0].days_offset, Some(5));
     }
 
@@ -1361,18 +3178,9 @@ mod tests {
         let agenda = build_day_agenda(&tasks, day_date, current_date);
 
         assert_eq!(agenda.scheduled_timed.len(), 3);
-        assert_eq!(
-            agenda.scheduled_timed[0].task.timestamp_time,
-            Some("09:00".to_string())
-        );
-        assert_eq!(
-            agenda.scheduled_timed[1].task.timestamp_time,
-            Some("11:00".to_string())
-        );
-        assert_eq!(
-            agenda.scheduled_timed[2].task.timestamp_time,
-            Some("14:00".to_string())
-        );
+        assert_eq!(agenda.scheduled_timed[0].timestamp_time(), Some("09:00"));
+        assert_eq!(agenda.scheduled_timed[1].timestamp_time(), Some("11:00"));
+        assert_eq!(agenda.scheduled_timed[2].timestamp_time(), Some("14:00"));
     }
 
     #[test]
@@ -1387,8 +3195,8 @@ mod tests {
         let agenda = build_day_agenda(&tasks, day_date, current_date);
 
         assert_eq!(agenda.overdue.len(), 2);
-        assert_eq!(agenda.overdue[0].task.timestamp_time, None);
-        assert_eq!(agenda.overdue[1].task.timestamp_time, None);
+        assert_eq!(agenda.overdue[0].timestamp_time(), None);
+        assert_eq!(agenda.overdue[1].timestamp_time(), None);
     }
 
     #[test]
@@ -1403,8 +3211,8 @@ mod tests {
         let agenda = build_day_agenda(&tasks, day_date, current_date);
 
         assert_eq!(agenda.upcoming.len(), 2);
-        assert_eq!(agenda.upcoming[0].task.timestamp_time, None);
-        assert_eq!(agenda.upcoming[1].task.timestamp_time, None);
+        assert_eq!(agenda.upcoming[0].timestamp_time(), None);
+        assert_eq!(agenda.upcoming[1].timestamp_time(), None);
     }
 
     #[test]
@@ -1422,10 +3230,7 @@ mod tests {
 
         // Should appear in scheduled (it's an occurrence day)
         assert_eq!(agenda.scheduled_timed.len(), 1);
-        assert_eq!(
-            agenda.scheduled_timed[0].task.timestamp_time,
-            Some("10:00".to_string())
-        );
+        assert_eq!(agenda.scheduled_timed[0].timestamp_time(), Some("10:00"));
         assert_eq!(agenda.scheduled_timed[0].days_offset, None);
 
         // Should NOT appear in overdue (to avoid duplicate)
@@ -1681,6 +3486,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn scheduled_and_deadline_of_one_task_are_evaluated_independently() {
+        let tasks = crate::parse_file(
+            "## TODO Report\n`SCHEDULED: <2024-12-05 Thu 10:00> DEADLINE: <2024-12-12 Thu>`\n",
+        );
+        assert_eq!(tasks[0].planning.as_ref().map(Vec::len), Some(2));
+        let dates = AgendaDates {
+            date: Some("2024-12-05"),
+            current_date: Some("2024-12-05"),
+            ..AgendaDates::default()
+        };
+        let days = match filter_agenda(tasks, AgendaScope::Day, dates, "UTC", false, false) {
+            Ok(AgendaOutput::Days(days)) => days,
+            other => panic!("expected AgendaOutput::Days, got {other:?}"),
+        };
+        assert_eq!(days[0].scheduled_timed.len(), 1);
+        assert_eq!(days[0].scheduled_timed[0].timestamp_time(), Some("10:00"));
+        assert_eq!(days[0].upcoming.len(), 1);
+        assert_eq!(
+            days[0].upcoming[0].task.timestamp_type.as_deref(),
+            Some("DEADLINE")
+        );
+        assert_eq!(days[0].upcoming[0].days_offset, Some(7));
+    }
+
+    #[test]
+    fn done_task_is_listed_on_its_closed_day() {
+        let tasks = crate::parse_file(
+            "\
+## DONE Report
+`CLOSED: [2024-12-07 Sat 17:30] SCHEDULED: <2024-12-05 Thu>`
+
+## TODO Reopened
+`CLOSED: [2024-12-06 Fri]`
+",
+        );
+        assert_eq!(
+            tasks[0].timestamp.as_deref(),
+            Some("SCHEDULED: <2024-12-05 Thu>")
+        );
+        let dates = AgendaDates {
+            from: Some("2024-12-05"),
+            to: Some("2024-12-07"),
+            current_date: Some("2024-12-07"),
+            ..AgendaDates::default()
+        };
+        let days = match filter_agenda(tasks, AgendaScope::Day, dates, "UTC", false, false) {
+            Ok(AgendaOutput::Days(days)) => days,
+            other => panic!("expected AgendaOutput::Days, got {other:?}"),
+        };
+        assert_eq!(days[0].scheduled_no_time.len(), 1);
+        assert!(days[1].scheduled_no_time.is_empty(), "open task: no entry");
+        let closed = &days[2].scheduled_timed;
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0].task.timestamp_type.as_deref(), Some("CLOSED"));
+        assert_eq!(closed[0].timestamp_time(), Some("17:30"));
+        assert!(days[2].overdue.is_empty());
+    }
+
     #[test]
     fn tasks_scope_excludes_done_by_default() {
         // The flat `--tasks` list is TODO-only by default — the documented
@@ -2018,13 +3882,11 @@ mod tests {
             "Future occurrence day should show task"
         );
         assert_eq!(
-            agenda_future.scheduled_no_time[0].task.timestamp_date,
-            Some("2026-12-05".to_string())
+            agenda_future.scheduled_no_time[0].timestamp_date(),
+            Some("2026-12-05")
         );
         assert!(agenda_future.scheduled_no_time[0]
-            .task
-            .timestamp
-            .as_ref()
+            .timestamp()
             .unwrap()
             .contains("2026-12-05"));
     }
@@ -2053,14 +3915,9 @@ mod tests {
         );
 
         // Check that timestamp shows last occurrence date (2025-12-05)
-        assert_eq!(
-            agenda.overdue[0].task.timestamp_date,
-            Some("2025-12-05".to_string())
-        );
+        assert_eq!(agenda.overdue[0].timestamp_date(), Some("2025-12-05"));
         assert!(agenda.overdue[0]
-            .task
-            .timestamp
-            .as_ref()
+            .timestamp()
             .unwrap()
             .contains("2025-12-05"));
     }
@@ -2076,13 +3933,14 @@ mod tests {
     ) -> Task {
         let timestamp = format!("{ts_type}: <{date_str} {repeater}>");
         Task {
-            file: "test.md".to_string(),
+            file: "test.md".into(),
             line: 1,
             heading: "Test task".to_string(),
             content: String::new(),
             task_type: Some(task_type),
             priority: None,
             created: None,
+            closed: None,
             timestamp: Some(timestamp),
             timestamp_type: Some(ts_type.to_string()),
             timestamp_active: Some(true),
@@ -2092,7 +3950,36 @@ mod tests {
             timestamp_repeater: None,
             clocks: None,
             total_clock_time: None,
+            log: None,
+            effort_minutes: None,
             properties: None,
+            timestamp_overnight: None,
+            planning: None,
+            inactive_timestamps: None,
+            rescheduled_count: None,
+            effective_date: None,
+            attendees: None,
+            location: None,
+            url: None,
+            links: None,
+            subtasks: None,
+            subtasks_percent: None,
+            subtasks_progress: None,
+            progress_cookie: None,
+            progress: None,
+            children_todo: None,
+            children_done: None,
+            level: None,
+            waiting_since: None,
+            assignee: None,
+            paused: None,
+            paused_until: None,
+            effective_priority: None,
+            category: None,
+            tags: None,
+            outline_path: None,
+            inherited_priority: None,
+            inherited_tags: None,
         }
     }
 
@@ -2338,4 +4225,798 @@ mod tests {
             "CLOSED-typed timestamps must never enter the upcoming bucket"
         );
     }
+
+    #[test]
+    fn overnight_entry_continues_on_next_day() {
+        let mut task =
+            create_test_task("2024-12-05 Thu 22:00-01:30", Some("22:00"), TaskType::Todo);
+        task.timestamp_end_time = Some("01:30".to_string());
+        task.timestamp_overnight = Some(true);
+        let tasks = vec![task];
+        let start = NaiveDate::from_ymd_opt(2024, 12, 5).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 12, 7).unwrap();
+        let days = build_week_agenda(&tasks, start, end, start);
+
+        assert_eq!(days[0].scheduled_timed.len(), 1);
+        assert_eq!(days[0].scheduled_timed[0].timestamp_time(), Some("22:00"));
+
+        let next = &days[1].scheduled_timed;
+        assert_eq!(next.len(), 1, "second half must be listed on Dec 6");
+        assert_eq!(next[0].timestamp_time(), Some("00:00"));
+        assert_eq!(next[0].timestamp_end_time(), Some("01:30"));
+        assert_eq!(next[0].days_offset, Some(-1));
+
+        assert!(days[2].scheduled_timed.is_empty());
+    }
+
+    #[test]
+    fn overnight_continuation_sorts_before_same_day_entries() {
+        let mut overnight =
+            create_test_task("2024-12-05 Thu 23:00-02:00", Some("23:00"), TaskType::Todo);
+        overnight.timestamp_end_time = Some("02:00".to_string());
+        overnight.timestamp_overnight = Some(true);
+        let morning = create_test_task("2024-12-06 Fri 09:00", Some("09:00"), TaskType::Todo);
+        let tasks = vec![morning, overnight];
+        let day = NaiveDate::from_ymd_opt(2024, 12, 6).unwrap();
+        let agenda = build_day_agenda(&tasks, day, day);
+        let times: Vec<_> = agenda
+            .scheduled_timed
+            .iter()
+            .map(|t| t.timestamp_time().unwrap())
+            .collect();
+        assert_eq!(times, ["00:00", "09:00"]);
+    }
+
+    #[test]
+    fn overnight_repeating_entry_continues_after_each_occurrence() {
+        let mut task = create_test_task_with_repeater(
+            "2024-12-02 Mon",
+            Some("22:00-01:00"),
+            "+1w",
+            TaskType::Todo,
+        );
+        task.timestamp_time = Some("22:00".to_string());
+        task.timestamp_end_time = Some("01:00".to_string());
+        task.timestamp_overnight = Some(true);
+        let tasks = vec![task];
+        let start = NaiveDate::from_ymd_opt(2024, 12, 9).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 12, 11).unwrap();
+        let days = build_week_agenda(&tasks, start, end, start);
+
+        assert_eq!(days[0].scheduled_timed.len(), 1, "occurrence on Mon Dec 9");
+        assert_eq!(
+            days[1].scheduled_timed.len(),
+            1,
+            "continuation on Tue Dec 10"
+        );
+        let cont = &days[1].scheduled_timed[0];
+        assert_eq!(cont.timestamp_date(), Some("2024-12-09"));
+        assert_eq!(cont.days_offset, Some(-1));
+        assert!(days[2].scheduled_timed.is_empty());
+    }
+
+    #[test]
+    fn week_for_date_honours_week_start() {
+        // 2024-12-04 is a Wednesday.
+        let wed = NaiveDate::from_ymd_opt(2024, 12, 4).unwrap();
+        let (first, last) = get_week_for_date(wed, Weekday::Mon);
+        assert_eq!(first, NaiveDate::from_ymd_opt(2024, 12, 2).unwrap());
+        assert_eq!(last, NaiveDate::from_ymd_opt(2024, 12, 8).unwrap());
+        let (first, last) = get_week_for_date(wed, Weekday::Sun);
+        assert_eq!(first, NaiveDate::from_ymd_opt(2024, 12, 1).unwrap());
+        assert_eq!(last, NaiveDate::from_ymd_opt(2024, 12, 7).unwrap());
+        // A Sunday opens its own week under a Sunday start.
+        let sun = NaiveDate::from_ymd_opt(2024, 12, 8).unwrap();
+        assert_eq!(get_week_for_date(sun, Weekday::Sun).0, sun);
+    }
+
+    fn rest_of_week_days(date: &str, week_start: Option<Weekday>) -> Vec<String> {
+        let dates = AgendaDates {
+            current_date: Some(date),
+            week_start,
+            ..AgendaDates::default()
+        };
+        let Ok(AgendaOutput::Days(days)) = filter_agenda(
+            Vec::new(),
+            AgendaScope::RestOfWeek,
+            dates,
+            "UTC",
+            false,
+            false,
+        ) else {
+            panic!("rest-of-week must produce per-day output");
+        };
+        days.into_iter().map(|d| d.date).collect()
+    }
+
+    #[test]
+    fn rest_of_week_runs_from_today_through_week_end() {
+        assert_eq!(
+            rest_of_week_days("2024-12-04", None),
+            [
+                "2024-12-04",
+                "2024-12-05",
+                "2024-12-06",
+                "2024-12-07",
+                "2024-12-08"
+            ]
+        );
+        assert_eq!(
+            rest_of_week_days("2024-12-04", Some(Weekday::Sun)),
+            ["2024-12-04", "2024-12-05", "2024-12-06", "2024-12-07"]
+        );
+        // The last day of the week is a one-day window.
+        assert_eq!(rest_of_week_days("2024-12-08", None), ["2024-12-08"]);
+    }
+
+    #[test]
+    fn fortnight_covers_the_anchor_week_and_the_next() {
+        let dates = |week_start| AgendaDates {
+            date: Some("2024-12-04"),
+            week_start,
+            ..AgendaDates::default()
+        };
+        for (week_start, first, last) in [
+            (None, "2024-12-02", "2024-12-15"),
+            (Some(Weekday::Sun), "2024-12-01", "2024-12-14"),
+        ] {
+            let Ok(AgendaOutput::Days(days)) = filter_agenda(
+                Vec::new(),
+                AgendaScope::Fortnight,
+                dates(week_start),
+                "UTC",
+                false,
+                false,
+            ) else {
+                panic!("fortnight mode must produce per-day output");
+            };
+            assert_eq!(days.len(), 14);
+            assert_eq!(days[0].date, first);
+            assert_eq!(days[13].date, last);
+        }
+    }
+
+    #[test]
+    fn days_scope_covers_n_days_from_the_anchor() {
+        let task = create_test_task("2024-12-10 Tue", None, TaskType::Todo);
+        let dates = AgendaDates {
+            date: Some("2024-12-08"),
+            current_date: Some("2024-12-01"),
+            ..AgendaDates::default()
+        };
+        let Ok(AgendaOutput::Days(days)) = filter_agenda(
+            vec![task],
+            AgendaScope::Days { days: 3 },
+            dates,
+            "UTC",
+            false,
+            false,
+        ) else {
+            panic!("days mode must produce per-day output");
+        };
+        let dates: Vec<_> = days.iter().map(|d| d.date.as_str()).collect();
+        assert_eq!(dates, ["2024-12-08", "2024-12-09", "2024-12-10"]);
+        assert_eq!(days[2].scheduled_no_time.len(), 1);
+
+        let range = AgendaDates {
+            to: Some("2024-12-20"),
+            ..AgendaDates::default()
+        };
+        let err = filter_agenda(
+            Vec::new(),
+            AgendaScope::Days { days: 3 },
+            range,
+            "UTC",
+            false,
+            false,
+        )
+        .unwrap_err();
+        assert!(matches!(err, AppError::DateRange(_)));
+    }
+
+    #[test]
+    fn rest_of_week_rejects_explicit_range() {
+        let dates = AgendaDates {
+            from: Some("2024-12-02"),
+            ..AgendaDates::default()
+        };
+        let err = filter_agenda(
+            Vec::new(),
+            AgendaScope::RestOfWeek,
+            dates,
+            "UTC",
+            false,
+            false,
+        )
+        .unwrap_err();
+        assert!(matches!(err, AppError::DateRange(_)));
+    }
+
+    #[test]
+    fn repeat_horizon_drops_ancient_repeaters_from_day_views() {
+        let task = |date: &str| {
+            let mut t = create_test_task_with_repeater(date, None, "+1d", TaskType::Todo);
+            t.timestamp_repeater = Some("+1d".to_string());
+            t
+        };
+        let day = |repeat_horizon: Option<u32>| {
+            let dates = AgendaDates {
+                current_date: Some("2026-03-02"),
+                repeat_horizon,
+                ..AgendaDates::default()
+            };
+            let tasks = vec![task("1826-03-01 Wed"), task("1900-01-01 Mon")];
+            let Ok(AgendaOutput::Days(days)) =
+                filter_agenda(tasks, AgendaScope::Day, dates, "UTC", false, false)
+            else {
+                panic!("day scope must produce per-day output");
+            };
+            days[0].scheduled_no_time.len()
+        };
+        // 1826 is 200 years and a day back: past the default horizon.
+        assert_eq!(day(None), 1);
+        assert_eq!(day(Some(100)), 0);
+        assert_eq!(day(Some(1000)), 2);
+    }
+
+    #[test]
+    fn catch_up_and_restart_repeaters_continue_after_the_last_completion() {
+        let day = |repeater: &str, date: &str| {
+            let tasks = crate::parse_file(&format!(
+                "## TODO Review\n`SCHEDULED: <2026-01-05 Mon {repeater}>`\n\
+                 ```org-properties\nLAST_REPEAT: [2026-01-21 Wed 09:00]\n```\n"
+            ));
+            let dates = AgendaDates {
+                date: Some(date),
+                current_date: Some("2026-01-22"),
+                ..AgendaDates::default()
+            };
+            let Ok(AgendaOutput::Days(days)) =
+                filter_agenda(tasks, AgendaScope::Day, dates, "UTC", false, false)
+            else {
+                panic!("day scope must produce per-day output");
+            };
+            let day = &days[0];
+            (day.overdue.len(), day.scheduled_no_time.len())
+        };
+        // `+1w` moves one step per completion and keeps its grid: the
+        // 12th is done, the 19th stays overdue, the 26th is next.
+        assert_eq!(day("+1w", "2026-01-22"), (1, 0));
+        assert_eq!(day("+1w", "2026-01-26"), (0, 1));
+        // `++1w` catches up to the first Monday after the completion.
+        assert_eq!(day("++1w", "2026-01-22"), (0, 0));
+        assert_eq!(day("++1w", "2026-01-26"), (0, 1));
+        // `.+1w` restarts a week after the completion.
+        assert_eq!(day(".+1w", "2026-01-22"), (0, 0));
+        assert_eq!(day(".+1w", "2026-01-26"), (0, 0));
+        assert_eq!(day(".+1w", "2026-01-28"), (0, 1));
+    }
+
+    #[test]
+    fn cumulative_repeater_moves_one_step_per_completion() {
+        let tasks = crate::parse_file(
+            "## TODO Review\n`SCHEDULED: <2026-01-05 Mon +1w>`\n\
+             ```org\n:LOGBOOK:\n\
+             - State \"DONE\"       from \"TODO\"       [2026-01-06 Tue 09:00]\n\
+             - State \"DONE\"       from \"TODO\"       [2026-01-07 Wed 09:00]\n\
+             :END:\n```\n",
+        );
+        let prepared = prepare_tasks(&tasks);
+        let base = prepared[0].parsed.as_ref().map(|p| p.date);
+        // Two completions, two steps, however early they were.
+        assert_eq!(base, NaiveDate::from_ymd_opt(2026, 1, 19));
+
+        // One completed before the timestamp does not count.
+        let mut early = tasks[0].clone();
+        early.timestamp = Some("SCHEDULED: <2026-01-07 Wed +1w>".to_string());
+        let prepared = prepare_tasks(std::slice::from_ref(&early));
+        let base = prepared[0].parsed.as_ref().map(|p| p.date);
+        assert_eq!(base, NaiveDate::from_ymd_opt(2026, 1, 14));
+    }
+
+    #[test]
+    fn overlapping_timed_entries_are_flagged_as_conflicts() {
+        let tasks = crate::parse_file(
+            "## Standup\n`<2026-03-02 Mon 09:00-09:30>`\n\n\
+             ## Review\n`<2026-03-02 Mon 09:15-10:00>`\n\n\
+             ## Lunch\n`<2026-03-02 Mon 12:00-13:00>`\n\n\
+             ## Call\n`<2026-03-02 Mon 14:00>`\n\n\
+             ## Dentist\n`<2026-03-02 Mon 14:00>`\n\n\
+             ## TODO Report\n`DEADLINE: <2026-03-02 Mon 12:30>`\n",
+        );
+        let day = build_day_agenda(
+            &tasks,
+            NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(),
+        );
+        let flags: Vec<_> = day
+            .scheduled_timed
+            .iter()
+            .map(|e| (e.task.heading.as_str(), e.conflict))
+            .collect();
+        // Back-to-back Lunch and a deadline inside it do not conflict.
+        assert_eq!(
+            flags,
+            [
+                ("Standup", true),
+                ("Review", true),
+                ("Lunch", false),
+                ("Report", false),
+                ("Call", true),
+                ("Dentist", true)
+            ]
+        );
+    }
+
+    #[test]
+    fn group_by_category_splits_each_day_in_category_order() {
+        let mut work = create_test_task("2026-03-02 Mon", Some("09:00"), TaskType::Todo);
+        work.category = Some("work".to_string());
+        let mut late = create_test_task("2026-02-20 Fri", None, TaskType::Todo);
+        late.category = Some("work".to_string());
+        let mut errand = create_test_task("2026-03-02 Mon", None, TaskType::Todo);
+        errand.file = "notes/home.md".into();
+        let tasks = vec![work, late, errand];
+        let today = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let mut days = vec![build_day_agenda(&tasks, today, today)];
+        let before = days[0].totals();
+
+        group_by_category(&mut days);
+        let day = &days[0];
+        assert!(day.overdue.is_empty() && day.scheduled_timed.is_empty());
+        let groups: Vec<_> = day
+            .categories
+            .iter()
+            .map(|c| {
+                let counts = (c.overdue.len(), c.scheduled_timed.len());
+                (c.category.as_str(), counts, c.scheduled_no_time.len())
+            })
+            .collect();
+        // A task without a CATEGORY falls under its file name.
+        assert_eq!(groups, [("home", (0, 0), 1), ("work", (1, 1), 0)]);
+        assert_eq!(day.totals(), before);
+    }
+
+    #[test]
+    fn cap_entries_keeps_timed_entries_first_and_counts_the_rest() {
+        let tasks = vec![
+            create_test_task("2026-03-02 Mon", None, TaskType::Todo),
+            create_test_task("2026-03-02 Mon", Some("09:00"), TaskType::Todo),
+            create_test_task("2026-03-02 Mon", None, TaskType::Todo),
+            create_test_task("2026-02-20 Fri", None, TaskType::Todo),
+        ];
+        let today = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let mut days = vec![build_day_agenda(&tasks, today, today)];
+
+        cap_entries(&mut days, 2);
+        let day = &days[0];
+        assert_eq!(
+            (day.scheduled_timed.len(), day.scheduled_no_time.len()),
+            (1, 1)
+        );
+        assert_eq!(day.overdue.len(), 1);
+        let expected = Truncated {
+            scheduled: 1,
+            ..Truncated::default()
+        };
+        assert_eq!(day.truncated, expected);
+
+        cap_entries(&mut days, 5);
+        assert!(days[0].truncated.is_empty());
+    }
+
+    #[test]
+    fn merge_timeline_orders_the_day_and_marks_now_on_today_only() {
+        let tasks = vec![
+            create_test_task("2026-03-02 Mon", None, TaskType::Todo),
+            create_test_task("2026-03-02 Mon", Some("14:00"), TaskType::Todo),
+            create_test_task("2026-03-02 Mon", Some("09:00"), TaskType::Todo),
+            create_test_task("2026-02-20 Fri", None, TaskType::Todo),
+        ];
+        let today = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let tomorrow = today.succ_opt().unwrap();
+        let mut days = vec![
+            build_day_agenda(&tasks, today, today),
+            build_day_agenda(&tasks, tomorrow, today),
+        ];
+
+        merge_timeline(&mut days, today.and_hms_opt(9, 30, 0).unwrap());
+        let day = &days[0];
+        let dates: Vec<_> = day
+            .timeline
+            .iter()
+            .map(|e| (e.timestamp_date(), e.timestamp_time()))
+            .collect();
+        assert_eq!(
+            dates,
+            [
+                (Some("2026-02-20"), None),
+                (Some("2026-03-02"), Some("09:00")),
+                (Some("2026-03-02"), Some("14:00")),
+                (Some("2026-03-02"), None),
+            ]
+        );
+        assert!(day.scheduled_timed.is_empty() && day.overdue.is_empty());
+        let now = NowMarker {
+            time: "09:30".to_string(),
+            index: 2,
+        };
+        assert_eq!(day.now, Some(now));
+        assert!(days[1].now.is_none());
+    }
+
+    #[test]
+    fn repeating_entries_carry_their_next_occurrence() {
+        let tasks = vec![
+            create_test_task_with_repeater("2025-03-31 Mon", None, "+1w", TaskType::Todo),
+            create_test_task_with_repeater("2025-03-03 Mon", None, "+1w", TaskType::Todo),
+            create_test_task("2025-03-31 Mon", None, TaskType::Todo),
+        ];
+        let today = NaiveDate::from_ymd_opt(2025, 3, 31).unwrap();
+        let day = build_day_agenda(&tasks, today, today);
+        let next: Vec<_> = day
+            .scheduled_no_time
+            .iter()
+            .map(|e| e.next_occurrence.as_deref())
+            .collect();
+        // Both weekly tasks fall on today; the one-off task has none.
+        assert_eq!(next, [Some("2025-04-07"), Some("2025-04-07"), None]);
+    }
+
+    #[test]
+    fn anniversaries_count_years_from_their_base_date() {
+        let tasks = crate::parse_file(
+            "## Ann's birthday :anniversary:\n`<1990-05-10 Thu +1y>`\n\n\
+             ## Rent :anniversary:\n`<2020-05-10 Sun +1m>`\n\n\
+             ## Wedding :anniversary:\n`<2026-05-10 Sun +1y>`\n",
+        );
+        let dates = AgendaDates {
+            date: Some("2026-05-10"),
+            current_date: Some("2026-05-10"),
+            ..AgendaDates::default()
+        };
+        let Ok(AgendaOutput::Days(days)) =
+            filter_agenda(tasks, AgendaScope::Day, dates, "UTC", false, false)
+        else {
+            panic!("day scope must produce per-day output");
+        };
+        let years: Vec<_> = days[0]
+            .scheduled_no_time
+            .iter()
+            .map(|e| (e.task.heading.as_str(), e.anniversary_years))
+            .collect();
+        // Only a yearly repeater counts, and not on its own first date.
+        assert_eq!(
+            years,
+            [
+                ("Ann's birthday", Some(36)),
+                ("Rent", None),
+                ("Wedding", None)
+            ]
+        );
+    }
+
+    #[test]
+    fn overdue_entries_carry_their_age_bucket() {
+        let tasks: Vec<Task> = [
+            "2024-11-01 Fri",
+            "2024-11-20 Wed",
+            "2024-11-29 Fri",
+            "2024-12-04 Wed",
+        ]
+        .iter()
+        .map(|date| create_test_task(date, None, TaskType::Todo))
+        .collect();
+        let day = NaiveDate::from_ymd_opt(2024, 12, 5).unwrap();
+        let agenda = build_day_agenda(&tasks, day, day);
+        let ages: Vec<_> = agenda.overdue.iter().map(|t| t.overdue_age).collect();
+        assert_eq!(
+            ages,
+            [
+                Some(OverdueAge::OverMonth),
+                Some(OverdueAge::OverWeek),
+                Some(OverdueAge::Days4To7),
+                Some(OverdueAge::Days1To3),
+            ]
+        );
+        assert_eq!(OverdueAge::from_days(3), OverdueAge::Days1To3);
+        assert_eq!(OverdueAge::from_days(7), OverdueAge::Days4To7);
+        assert_eq!(OverdueAge::from_days(30), OverdueAge::OverWeek);
+        assert_eq!(OverdueAge::from_days(31), OverdueAge::OverMonth);
+    }
+
+    #[test]
+    fn scheduled_warning_days_adds_scheduled_tasks_to_upcoming() {
+        let upcoming = |scheduled_warning_days: Option<u32>| {
+            let tasks = crate::parse_file(
+                "## TODO Prepare\n`SCHEDULED: <2024-12-08 Sun>`\n\
+                 ## TODO Submit\n`DEADLINE: <2024-12-08 Sun>`\n\
+                 ## DONE Finished\n`SCHEDULED: <2024-12-06 Fri>`\n\
+                 ## TODO Later\n`SCHEDULED: <2024-12-20 Fri>`\n",
+            );
+            let dates = AgendaDates {
+                current_date: Some("2024-12-05"),
+                scheduled_warning_days,
+                ..AgendaDates::default()
+            };
+            let Ok(AgendaOutput::Days(days)) =
+                filter_agenda(tasks, AgendaScope::Day, dates, "UTC", false, false)
+            else {
+                panic!("day scope must produce per-day output");
+            };
+            days[0]
+                .upcoming
+                .iter()
+                .map(|t| {
+                    (
+                        t.task.heading.clone(),
+                        t.task.timestamp_type.clone(),
+                        t.days_offset,
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        let deadline = ("Submit".to_string(), Some("DEADLINE".to_string()), Some(3));
+        assert_eq!(upcoming(None), vec![deadline.clone()]);
+        assert_eq!(
+            upcoming(Some(7)),
+            vec![
+                deadline,
+                (
+                    "Prepare".to_string(),
+                    Some("SCHEDULED".to_string()),
+                    Some(3)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn collapse_recurring_keeps_first_occurrence_with_dates() {
+        let mut daily =
+            create_test_task_with_repeater("2024-12-02 Mon", None, "+2d", TaskType::Todo);
+        daily.timestamp_repeater = Some("+2d".to_string());
+        let mut one_off = create_test_task("2024-12-03 Tue", None, TaskType::Todo);
+        one_off.line = 9;
+        let tasks = vec![daily, one_off];
+        let start = NaiveDate::from_ymd_opt(2024, 12, 2).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 12, 8).unwrap();
+        let mut days = build_week_agenda(&tasks, start, end, start);
+        collapse_recurring(&mut days);
+
+        let first = &days[0].scheduled_no_time;
+        assert_eq!(first.len(), 1);
+        assert_eq!(
+            first[0].occurrence_days.as_deref(),
+            Some(
+                &[
+                    "2024-12-02".to_string(),
+                    "2024-12-04".to_string(),
+                    "2024-12-06".to_string(),
+                    "2024-12-08".to_string()
+                ][..]
+            )
+        );
+        assert!(days[2].scheduled_no_time.is_empty(), "Wed instance removed");
+        assert_eq!(days[1].scheduled_no_time.len(), 1, "one-off stays");
+        assert_eq!(days[1].scheduled_no_time[0].occurrence_days, None);
+    }
+
+    #[test]
+    fn annotate_local_times_converts_from_home_zone() {
+        let mut task = create_test_task_with_type(
+            "2024-12-05 Thu",
+            Some("18:00-19:30"),
+            TaskType::Todo,
+            "SCHEDULED",
+        );
+        task.timestamp_time = Some("18:00".to_string());
+        task.timestamp_end_time = Some("19:30".to_string());
+        let day = NaiveDate::from_ymd_opt(2024, 12, 5).unwrap();
+        let mut days = vec![build_day_agenda(&[task], day, day)];
+        annotate_local_times(
+            &mut days,
+            "Europe/Moscow",
+            &["America/New_York", "Asia/Tokyo"],
+        )
+        .unwrap();
+
+        let local = days[0].scheduled_timed[0].local_times.as_ref().unwrap();
+        // Moscow is UTC+3; New York UTC-5 in December; Tokyo UTC+9.
+        assert_eq!(local[0].tz, "America/New_York");
+        assert_eq!(local[0].date, "2024-12-05");
+        assert_eq!(local[0].time, "10:00");
+        assert_eq!(local[0].end_time.as_deref(), Some("11:30"));
+        assert_eq!(local[1].date, "2024-12-06");
+        assert_eq!(local[1].time, "00:00");
+    }
+
+    #[test]
+    fn annotate_local_times_without_extra_zones_is_noop() {
+        let task = create_test_task("2024-12-05 Thu", Some("18:00"), TaskType::Todo);
+        let day = NaiveDate::from_ymd_opt(2024, 12, 5).unwrap();
+        let mut days = vec![build_day_agenda(&[task], day, day)];
+        annotate_local_times(&mut days, "Europe/Moscow", &[]).unwrap();
+        assert!(days[0].scheduled_timed[0].local_times.is_none());
+    }
+
+    #[test]
+    fn paused_recurring_task_skips_occurrences_until_resumed() {
+        let mut task =
+            create_test_task_with_repeater("2024-12-02 Mon", None, "+1w", TaskType::Todo);
+        task.paused_until = Some("2024-12-16".to_string());
+        let start = NaiveDate::from_ymd_opt(2024, 12, 2).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 12, 23).unwrap();
+        let today = NaiveDate::from_ymd_opt(2024, 12, 19).unwrap();
+        let shown = |task: &Task| -> Vec<String> {
+            build_week_agenda(std::slice::from_ref(task), start, end, today)
+                .into_iter()
+                .filter(|d| !d.scheduled_no_time.is_empty())
+                .map(|d| d.date)
+                .collect()
+        };
+        assert_eq!(shown(&task), ["2024-12-16", "2024-12-23"]);
+
+        // Overdue only counts occurrences after the resume date.
+        let day = build_day_agenda(std::slice::from_ref(&task), today, today);
+        assert_eq!(day.overdue.len(), 1);
+        assert_eq!(day.overdue[0].days_offset, Some(-3));
+
+        task.paused = Some(true);
+        assert!(shown(&task).is_empty());
+        assert!(build_day_agenda(&[task], today, today).overdue.is_empty());
+    }
+
+    #[test]
+    fn age_priorities_raises_one_level_per_period() {
+        let created = |priority: Option<Priority>, date: &str| {
+            let mut task = create_test_task("2024-12-05 Thu", None, TaskType::Todo);
+            task.priority = priority;
+            task.created = Some(format!("CREATED: [{date}]"));
+            task
+        };
+        let mut done = created(Some(Priority::C), "2024-01-01 Mon");
+        done.task_type = Some(TaskType::Done);
+        let mut tasks = vec![
+            created(Some(Priority::C), "2024-11-01 Fri"),
+            created(Some(Priority::C), "2024-10-01 Tue"),
+            created(None, "2024-11-01 Fri"),
+            created(None, "2024-12-01 Sun"),
+            created(Some(Priority::B), "2020-01-01 Wed"),
+            done,
+        ];
+        tasks.push(create_test_task("2024-12-05 Thu", None, TaskType::Todo));
+        let today = NaiveDate::from_ymd_opt(2024, 12, 5).unwrap();
+        age_priorities(&mut tasks, today, 30, None);
+
+        let effective: Vec<_> = tasks.iter().map(|t| t.effective_priority.clone()).collect();
+        assert_eq!(
+            effective,
+            [
+                Some(Priority::B),
+                Some(Priority::A),
+                Some(Priority::C),
+                None,
+                Some(Priority::A),
+                None,
+                None
+            ]
+        );
+        // Sorting follows the effective priority.
+        assert!(priority_order(&tasks[1]) < priority_order(&tasks[0]));
+    }
+
+    #[test]
+    fn meets_min_priority_uses_the_sorting_priority() {
+        let mut task = create_test_task("2024-12-05 Thu", None, TaskType::Todo);
+        assert!(!meets_min_priority(&task, &Priority::C));
+        task.priority = Some(Priority::C);
+        assert!(!meets_min_priority(&task, &Priority::B));
+        assert!(meets_min_priority(&task, &Priority::C));
+        task.effective_priority = Some(Priority::A);
+        assert!(meets_min_priority(&task, &Priority::B));
+        task.priority = Some(Priority::Numeric(3));
+        task.effective_priority = None;
+        assert!(meets_min_priority(&task, &Priority::Numeric(3)));
+        assert!(!meets_min_priority(&task, &Priority::Numeric(2)));
+    }
+
+    #[test]
+    fn group_tasks_orders_values_and_collects_the_rest_last() {
+        let mut tasks = crate::parse_file(
+            "## TODO [#B] Draft :work:\n\n## DONE [#A] Ship :work:ops:\n\n\
+             ## TODO Reply\n\n## CANCELED Drop :ops:\n",
+        );
+        tasks[2].task_type = Some(TaskType::Other("WAITING".to_string()));
+        let groups = |by| {
+            group_tasks(tasks.clone(), by)
+                .into_iter()
+                .map(|(name, tasks)| {
+                    let headings: Vec<_> = tasks.iter().map(|t| t.heading.as_str()).collect();
+                    format!("{}={}", name.as_deref().unwrap_or("-"), headings.join("+"))
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            groups(GroupBy::Tag),
+            ["ops=Ship+Drop", "work=Draft+Ship", "-=Reply"]
+        );
+        assert_eq!(
+            groups(GroupBy::Priority),
+            ["A=Ship", "B=Draft", "-=Reply+Drop"]
+        );
+        assert_eq!(
+            groups(GroupBy::TodoState),
+            ["TODO=Draft", "WAITING=Reply", "DONE=Ship", "CANCELED=Drop"]
+        );
+        assert_eq!(groups(GroupBy::File).len(), 1);
+    }
+
+    #[test]
+    fn sort_tasks_keeps_missing_keys_last_in_both_directions() {
+        let mut tasks = crate::parse_file(
+            "## TODO [#B] beta\n`DEADLINE: <2025-03-12 Wed>`\n\n\
+             ## TODO Alpha\n`SCHEDULED: <2025-03-01 Sat>` `DEADLINE: <2025-03-05 Wed>`\n\n\
+             ## TODO [#A] Gamma\n",
+        );
+        let headings = |tasks: &[Task]| {
+            tasks
+                .iter()
+                .map(|t| t.heading.clone())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let cases = [
+            (TaskSort::Priority, false, "Gamma,beta,Alpha"),
+            (TaskSort::Priority, true, "beta,Gamma,Alpha"),
+            (TaskSort::Deadline, false, "Alpha,beta,Gamma"),
+            (TaskSort::Deadline, true, "beta,Alpha,Gamma"),
+            (TaskSort::Scheduled, false, "Alpha,Gamma,beta"),
+            (TaskSort::File, true, "Gamma,Alpha,beta"),
+            (TaskSort::Heading, false, "Alpha,beta,Gamma"),
+        ];
+        for (sort, reverse, expected) in cases {
+            sort_tasks(&mut tasks, sort, reverse);
+            assert_eq!(headings(&tasks), expected, "{sort:?} reverse={reverse}");
+        }
+    }
+
+    #[test]
+    fn age_priorities_follow_a_priority_scheme() {
+        let scheme = PriorityScheme::parse("1-9").unwrap();
+        let created = |priority: Option<Priority>| {
+            let mut task = create_test_task("2024-12-05 Thu", None, TaskType::Todo);
+            task.priority = priority;
+            task.created = Some("CREATED: [2024-10-01 Tue]".to_string());
+            task
+        };
+        let mut tasks = vec![
+            created(Some(Priority::Numeric(2))),
+            created(None),
+            create_test_task("2024-12-05 Thu", None, TaskType::Todo),
+            created(Some(Priority::A)),
+        ];
+        tasks[2].priority = Some(Priority::Numeric(7));
+        let today = NaiveDate::from_ymd_opt(2024, 12, 5).unwrap();
+        age_priorities(&mut tasks, today, 30, Some(&scheme));
+        default_priorities(&mut tasks, &scheme);
+
+        let effective: Vec<_> = tasks.iter().map(|t| t.effective_priority.clone()).collect();
+        assert_eq!(
+            effective,
+            [
+                // Clamped at the highest priority, not raised to 0.
+                Some(Priority::Numeric(1)),
+                // Two levels above the default 5.
+                Some(Priority::Numeric(3)),
+                None,
+                // A letter outside the numeric scheme ages as before.
+                Some(Priority::A),
+            ]
+        );
+        let mut plain = vec![create_test_task("2024-12-05 Thu", None, TaskType::Todo)];
+        default_priorities(&mut plain, &scheme);
+        assert_eq!(plain[0].effective_priority, Some(Priority::Numeric(5)));
+        assert!(priority_order(&plain[0]) < priority_order(&tasks[2]));
+    }
 }
@@ -0,0 +1,268 @@
+//! `--agenda forecast`: expected occurrences and effort of recurring tasks,
+//! bucketed per calendar month.
+//!
+//! Only open (`TODO`) tasks whose active timestamp carries a repeater
+//! contribute. Occurrences are enumerated on the same grid the day agenda
+//! uses ([`closest_date`]), so a `+1wd` chore skips holidays here exactly as
+//! it does in `--agenda week`. Effort comes from the task's `Effort`
+//! property (`H:MM`, the upstream Org convention), read from the
+//! `org-properties` block (ADR-0020).
+
+use chrono::NaiveDate;
+
+use super::{get_month_for_date, prepare_tasks, resume_from};
+use crate::clock::{format_duration, parse_duration};
+use crate::parser::EFFORT_PROPERTY;
+use crate::timestamp::{closest_date, DatePreference, Repeater};
+use crate::types::{ForecastEntry, ForecastMonth, Task, TaskType};
+
+/// Build `months` consecutive [`ForecastMonth`] buckets starting with the
+/// month containing `start`. The first bucket only counts occurrences on or
+/// after `start`; later buckets cover their whole month.
+pub(super) fn build_forecast(tasks: &[Task], start: NaiveDate, months: u32) -> Vec<ForecastMonth> {
+    let prepared = prepare_tasks(tasks);
+    let mut result = Vec::with_capacity(months as usize);
+    let mut month_start = start;
+
+    for _ in 0..months {
+        let (_, month_end) = get_month_for_date(month_start);
+        let mut bucket = ForecastMonth {
+            month: month_start.format("%Y-%m").to_string(),
+            occurrences: 0,
+            total_effort: None,
+            tasks: Vec::new(),
+        };
+        let mut effort_sum: Option<u32> = None;
+
+        for entry in &prepared {
+            if !entry.task.task_type.as_ref().is_some_and(TaskType::is_open) {
+                continue;
+            }
+            let Some(parsed) = entry.parsed.as_ref() else {
+                continue;
+            };
+            let Some(repeater) = parsed.repeater.as_ref() else {
+                continue;
+            };
+            let Some(from) = resume_from(entry.task, month_start) else {
+                continue;
+            };
+            let occurrences = count_occurrences(parsed.date, repeater, from, month_end);
+            if occurrences == 0 {
+                continue;
+            }
+            let (forecast_entry, total) = recurring_entry(entry.task, repeater, occurrences);
+            if let Some(t) = total {
+                effort_sum = Some(effort_sum.unwrap_or(0).saturating_add(t));
+            }
+            bucket.occurrences = bucket.occurrences.saturating_add(occurrences);
+            bucket.tasks.push(forecast_entry);
+        }
+
+        bucket.total_effort = effort_sum.map(|secs| format_duration(secs.into()));
+        bucket
+            .tasks
+            .sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.line.cmp(&b.line)));
+        result.push(bucket);
+
+        match month_end.succ_opt() {
+            Some(next) => month_start = next,
+            None => break,
+        }
+    }
+
+    result
+}
+
+/// Build the [`ForecastEntry`] for `occurrences` repetitions of `task`,
+/// returning alongside it the total effort in seconds (`None` without a
+/// valid `Effort` property or on overflow).
+pub(super) fn recurring_entry(
+    task: &Task,
+    repeater: &Repeater,
+    occurrences: u32,
+) -> (ForecastEntry, Option<u32>) {
+    let effort = task_effort_seconds(task);
+    let total = effort.and_then(|m| m.checked_mul(occurrences));
+    let entry = ForecastEntry {
+        file: task.file.to_string(),
+        line: task.line,
+        heading: task.heading.clone(),
+        repeater: repeater.canonical(),
+        occurrences,
+        effort: effort.map(|secs| format_duration(secs.into())),
+        total_effort: total.map(|secs| format_duration(secs.into())),
+    };
+    (entry, total)
+}
+
+/// Count the repeater's occurrences inside `[from, to]`.
+pub(super) fn count_occurrences(
+    base: NaiveDate,
+    repeater: &Repeater,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> u32 {
+    occurrence_dates(base, repeater, from, to).len() as u32
+}
+
+/// The repeater's occurrence dates inside `[from, to]`, in order.
+///
+/// Walks forward with `closest_date(.., Future)` from one day past the
+/// previous hit, so every unit (including `wd`) keeps its agenda semantics.
+/// The walk is bounded by the window length: each step advances at least one
+/// day, and the window is at most the `--months` cap.
+pub(super) fn occurrence_dates(
+    base: NaiveDate,
+    repeater: &Repeater,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Vec<NaiveDate> {
+    let mut dates = Vec::new();
+    let mut cursor = from;
+    while cursor <= to {
+        let Some(next) = closest_date(base, cursor, DatePreference::Future, repeater) else {
+            break;
+        };
+        if next > to || next < cursor {
+            break;
+        }
+        dates.push(next);
+        match next.succ_opt() {
+            Some(d) => cursor = d,
+            None => break,
+        }
+    }
+    dates
+}
+
+/// Per-occurrence effort in seconds from the `Effort` property, else from
+/// `effort_minutes` (an inline estimate), or `None` when neither holds a
+/// valid `H:MM[:SS]` duration.
+pub(super) fn task_effort_seconds(task: &Task) -> Option<u32> {
+    let Some(raw) = task
+        .properties
+        .as_ref()
+        .and_then(|props| props.get(EFFORT_PROPERTY))
+    else {
+        return task.effort_minutes.map(|minutes| minutes * 60);
+    };
+    let seconds = parse_duration(raw.trim());
+    if seconds.is_none() {
+        tracing::debug!(
+            file = %task.file,
+            line = task.line,
+            effort = %raw,
+            "ignoring malformed Effort property"
+        );
+    }
+    seconds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn recurring_task(timestamp: &str, task_type: TaskType, effort: Option<&str>) -> Task {
+        Task {
+            file: "chores.md".into(),
+            line: 3,
+            heading: "Water plants".to_string(),
+            task_type: Some(task_type),
+            timestamp: Some(timestamp.to_string()),
+            timestamp_type: Some("SCHEDULED".to_string()),
+            timestamp_active: Some(true),
+            properties: effort.map(|e| BTreeMap::from([("Effort".to_string(), e.to_string())])),
+            ..Task::default()
+        }
+    }
+
+    #[test]
+    fn weekly_task_counts_per_month() {
+        let tasks = vec![recurring_task(
+            "SCHEDULED: <2026-01-05 Mon +1w>",
+            TaskType::Todo,
+            Some("0:30"),
+        )];
+        let forecast = build_forecast(&tasks, date(2026, 1, 1), 2);
+        assert_eq!(forecast.len(), 2);
+        assert_eq!(forecast[0].month, "2026-01");
+        // Jan 2026 Mondays from the 5th: 5, 12, 19, 26.
+        assert_eq!(forecast[0].occurrences, 4);
+        assert_eq!(forecast[0].total_effort.as_deref(), Some("2:00"));
+        assert_eq!(forecast[0].tasks[0].repeater, "+1w");
+        assert_eq!(forecast[0].tasks[0].effort.as_deref(), Some("0:30"));
+        // Feb 2026 Mondays: 2, 9, 16, 23.
+        assert_eq!(forecast[1].month, "2026-02");
+        assert_eq!(forecast[1].occurrences, 4);
+    }
+
+    #[test]
+    fn first_month_counts_only_from_start_date() {
+        let tasks = vec![recurring_task(
+            "SCHEDULED: <2026-03-01 Sun +1d>",
+            TaskType::Todo,
+            None,
+        )];
+        let forecast = build_forecast(&tasks, date(2026, 3, 20), 1);
+        // March 20..=31.
+        assert_eq!(forecast[0].occurrences, 12);
+        assert_eq!(forecast[0].total_effort, None);
+        assert_eq!(forecast[0].tasks[0].effort, None);
+    }
+
+    #[test]
+    fn non_recurring_done_and_inactive_tasks_are_ignored() {
+        let tasks = vec![
+            recurring_task("SCHEDULED: <2026-01-05 Mon>", TaskType::Todo, None),
+            recurring_task("SCHEDULED: <2026-01-05 Mon +1w>", TaskType::Done, None),
+            recurring_task("[2026-01-05 Mon +1w]", TaskType::Todo, None),
+        ];
+        let forecast = build_forecast(&tasks, date(2026, 1, 1), 1);
+        assert_eq!(forecast[0].occurrences, 0);
+        assert!(forecast[0].tasks.is_empty());
+    }
+
+    #[test]
+    fn base_date_after_window_yields_no_occurrences() {
+        let tasks = vec![recurring_task(
+            "SCHEDULED: <2026-06-01 Mon +1m>",
+            TaskType::Todo,
+            None,
+        )];
+        let forecast = build_forecast(&tasks, date(2026, 1, 1), 5);
+        assert!(forecast.iter().all(|m| m.occurrences == 0));
+        let forecast = build_forecast(&tasks, date(2026, 1, 1), 6);
+        assert_eq!(forecast[5].month, "2026-06");
+        assert_eq!(forecast[5].occurrences, 1);
+    }
+
+    #[test]
+    fn malformed_effort_is_ignored() {
+        let tasks = vec![recurring_task(
+            "SCHEDULED: <2026-01-05 Mon +1w>",
+            TaskType::Todo,
+            Some("half an hour"),
+        )];
+        let forecast = build_forecast(&tasks, date(2026, 1, 1), 1);
+        assert_eq!(forecast[0].occurrences, 4);
+        assert_eq!(forecast[0].total_effort, None);
+    }
+
+    #[test]
+    fn count_occurrences_monthly_truncates_short_months() {
+        let rep = crate::timestamp::parse_org_timestamp("<2026-01-31 Sat +1m>", None)
+            .unwrap()
+            .repeater
+            .unwrap();
+        assert_eq!(
+            count_occurrences(date(2026, 1, 31), &rep, date(2026, 2, 1), date(2026, 2, 28)),
+            1
+        );
+    }
+}
@@ -0,0 +1,243 @@
+//! `--agenda free`: open time slots within the working hours of each
+//! workday in the window.
+//!
+//! Busy time is every timed entry of the day agenda except deadlines and
+//! closing times, which mark when something is due or was finished rather
+//! than time spent: the entry's own range,
+//! or its `Effort`, or [`DEFAULT_APPOINTMENT_MINUTES`] when it has neither.
+//! Busy intervals imported with `--busy-ics` arrive as ordinary timed
+//! entries, so markdown and calendars are subtracted alike.
+
+use chrono::{NaiveDate, NaiveTime, Timelike};
+
+use super::forecast::task_effort_seconds;
+use super::WorkHours;
+use crate::holidays::HolidayCalendar;
+use crate::types::{DayAgenda, FreeDay, FreeSlot, TaskType};
+
+/// Length assumed for a timed entry with neither an end time nor an
+/// `Effort` property.
+const DEFAULT_APPOINTMENT_MINUTES: u32 = 60;
+
+/// Minutes in a day; a busy interval running past midnight ends here.
+const DAY_MINUTES: u32 = 24 * 60;
+
+/// Free slots of at least `min_slot` minutes for every workday among
+/// `days`. Weekends and holidays of the built-in calendar are skipped.
+pub(super) fn build_free_slots(
+    days: &[DayAgenda],
+    work_hours: WorkHours,
+    min_slot: u32,
+) -> Vec<FreeDay> {
+    days.iter()
+        .filter(|day| is_workday(day))
+        .map(|day| FreeDay {
+            date: day.date.clone(),
+            slots: day_slots(day, work_hours, min_slot),
+        })
+        .collect()
+}
+
+/// `--show-gaps`: set the `gaps` of every workday among `days` to its
+/// free slots, the same ones `--agenda free` reports.
+pub(super) fn fill_gaps(days: &mut [DayAgenda], work_hours: WorkHours, min_slot: u32) {
+    for day in days.iter_mut().filter(|day| is_workday(day)) {
+        day.gaps = day_slots(day, work_hours, min_slot);
+    }
+}
+
+fn is_workday(day: &DayAgenda) -> bool {
+    NaiveDate::parse_from_str(&day.date, "%Y-%m-%d")
+        .is_ok_and(|date| HolidayCalendar::global().is_workday(date))
+}
+
+/// Open slots of at least `min_slot` minutes within `work_hours` on `day`.
+fn day_slots(day: &DayAgenda, work_hours: WorkHours, min_slot: u32) -> Vec<FreeSlot> {
+    let work_start = minute_of_day(work_hours.start);
+    let work_end = minute_of_day(work_hours.end);
+    let mut busy = busy_intervals(day);
+    busy.sort_unstable();
+
+    let mut slots = Vec::new();
+    let mut cursor = work_start;
+    for (start, end) in busy {
+        if start > cursor {
+            push_slot(&mut slots, cursor, start.min(work_end), min_slot);
+        }
+        cursor = cursor.max(end);
+        if cursor >= work_end {
+            break;
+        }
+    }
+    push_slot(&mut slots, cursor, work_end, min_slot);
+    slots
+}
+
+/// Busy `(start, end)` minutes of the day's timed entries other than
+/// deadlines and closing times.
+fn busy_intervals(day: &DayAgenda) -> Vec<(u32, u32)> {
+    day.scheduled_timed
+        .iter()
+        .filter(|entry| {
+            !matches!(entry.task.task_type, Some(TaskType::Cancelled(_)))
+                && !matches!(
+                    entry.task.timestamp_type.as_deref(),
+                    Some("DEADLINE" | "CLOSED")
+                )
+        })
+        .filter_map(|entry| {
+            let start = parse_minute(entry.timestamp_time()?)?;
+            let end = match entry.timestamp_end_time().and_then(parse_minute) {
+                // An end before the start belongs to the next day.
+                Some(end) if end > start => end,
+                Some(_) => DAY_MINUTES,
+                None => {
+                    let minutes = task_effort_seconds(&entry.task)
+                        .map_or(DEFAULT_APPOINTMENT_MINUTES, |seconds| seconds / 60);
+                    (start + minutes).min(DAY_MINUTES)
+                }
+            };
+            Some((start, end))
+        })
+        .collect()
+}
+
+fn push_slot(slots: &mut Vec<FreeSlot>, start: u32, end: u32, min_slot: u32) {
+    if end > start && end - start >= min_slot {
+        slots.push(FreeSlot {
+            start: format_minute(start),
+            end: format_minute(end),
+            minutes: end - start,
+        });
+    }
+}
+
+fn minute_of_day(time: NaiveTime) -> u32 {
+    time.hour() * 60 + time.minute()
+}
+
+fn parse_minute(time: &str) -> Option<u32> {
+    NaiveTime::parse_from_str(time, "%H:%M")
+        .ok()
+        .map(minute_of_day)
+}
+
+fn format_minute(minute: u32) -> String {
+    format!("{:02}:{:02}", minute / 60, minute % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agenda::{filter_agenda, AgendaDates, AgendaOutput, AgendaScope};
+
+    fn free(content: &str, date: &str, min_slot: u32) -> Vec<FreeDay> {
+        let work_hours = WorkHours {
+            start: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            end: NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
+        };
+        let dates = AgendaDates {
+            date: Some(date),
+            current_date: Some(date),
+            ..AgendaDates::default()
+        };
+        let scope = AgendaScope::Free {
+            work_hours,
+            min_slot,
+        };
+        match filter_agenda(
+            crate::parse_file(content),
+            scope,
+            dates,
+            "UTC",
+            false,
+            false,
+        ) {
+            Ok(AgendaOutput::FreeSlots(days)) => days,
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    fn slots(day: &FreeDay) -> Vec<(&str, &str)> {
+        day.slots
+            .iter()
+            .map(|s| (s.start.as_str(), s.end.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn subtracts_ranges_effort_and_default_length() {
+        let content = "\
+## Standup
+`<2026-01-12 Mon 09:00-09:15 +1d>`
+
+## TODO Review
+`SCHEDULED: <2026-01-12 Mon 11:00>`
+
+```org-properties
+Effort: 1:30
+```
+
+## Call
+`<2026-01-12 Mon 14:00>`
+
+## TODO Report due
+`DEADLINE: <2026-01-12 Mon 16:00>`
+
+## CANCELLED Offsite
+`<2026-01-12 Mon 16:00-18:00>`
+";
+        let days = free(content, "2026-01-12", 30);
+        assert_eq!(days.len(), 1);
+        assert_eq!(
+            slots(&days[0]),
+            [("09:15", "11:00"), ("12:30", "14:00"), ("15:00", "18:00")]
+        );
+        assert_eq!(days[0].slots[0].minutes, 105);
+    }
+
+    #[test]
+    fn short_gaps_and_weekends_are_dropped() {
+        let content = "\
+## A
+`<2026-01-12 Mon 08:00-10:00>`
+
+## B
+`<2026-01-12 Mon 10:20-17:50>`
+";
+        assert!(free(content, "2026-01-12", 30)[0].slots.is_empty());
+        assert_eq!(
+            slots(&free(content, "2026-01-12", 15)[0]),
+            [("10:00", "10:20")]
+        );
+        assert!(free(content, "2026-01-17", 30).is_empty(), "Saturday");
+    }
+
+    #[test]
+    fn fill_gaps_sets_the_slots_of_workdays() {
+        let tasks = crate::parse_file("## Sync\n`<2026-01-12 Mon 10:00-11:00 +1d>`\n");
+        let dates = AgendaDates {
+            from: Some("2026-01-12"),
+            to: Some("2026-01-17"),
+            current_date: Some("2026-01-12"),
+            ..AgendaDates::default()
+        };
+        let Ok(AgendaOutput::Days(mut days)) =
+            filter_agenda(tasks, AgendaScope::Week, dates, "UTC", false, false)
+        else {
+            panic!("week scope must produce per-day output");
+        };
+        let work_hours = WorkHours {
+            start: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            end: NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
+        };
+        fill_gaps(&mut days, work_hours, 30);
+        let gaps: Vec<_> = days[0]
+            .gaps
+            .iter()
+            .map(|s| (s.start.as_str(), s.end.as_str()))
+            .collect();
+        assert_eq!(gaps, [("09:00", "10:00"), ("11:00", "18:00")]);
+        assert!(days[5].gaps.is_empty(), "Saturday");
+    }
+}
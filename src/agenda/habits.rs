@@ -0,0 +1,177 @@
+//! `--agenda habits`: a consistency graph per habit, as Org's
+//! `org-habit` draws it.
+//!
+//! A habit is a task with the `STYLE: habit` property and a repeating
+//! `SCHEDULED` timestamp, usually `.+`. Its completions are the `DONE`
+//! state changes of its logbook, its `CLOSED` date and its `LAST_REPEAT`
+//! property. After a completion the habit is next due one repeater step
+//! later; before the first known completion it is due on its `SCHEDULED`
+//! date.
+
+use chrono::{Days, NaiveDate};
+
+use super::completion_dates;
+use crate::timestamp::{closest_date, parse_repeater, DatePreference};
+use crate::types::{HabitRow, Task, TaskType};
+
+/// Property naming the task's style, as in Org.
+const STYLE_PROPERTY: &str = "STYLE";
+
+/// Graph mark of a day with a completion.
+const DONE_MARK: char = '*';
+/// Graph mark of a day the habit was due (or overdue) and not done.
+const MISSED_MARK: char = '!';
+/// Graph mark of a day before the habit was due.
+const NOT_DUE_MARK: char = '-';
+
+/// Whether `task` is a habit: `STYLE: habit` on a task with a repeating
+/// `SCHEDULED` timestamp.
+fn is_habit(task: &Task) -> bool {
+    task.properties
+        .as_ref()
+        .and_then(|props| props.get(STYLE_PROPERTY))
+        .is_some_and(|style| style.trim().eq_ignore_ascii_case("habit"))
+        && task.timestamp_type.as_deref() == Some("SCHEDULED")
+        && task.timestamp_repeater.is_some()
+}
+
+/// One [`HabitRow`] per habit that is not cancelled, in input order, with a
+/// graph over `start..=end`.
+pub(super) fn build_habits(tasks: &[Task], start: NaiveDate, end: NaiveDate) -> Vec<HabitRow> {
+    tasks
+        .iter()
+        .filter(|task| is_habit(task) && !matches!(task.task_type, Some(TaskType::Cancelled(_))))
+        .filter_map(|task| habit_row(task, start, end))
+        .collect()
+}
+
+fn habit_row(task: &Task, start: NaiveDate, end: NaiveDate) -> Option<HabitRow> {
+    let repeater_text = task.timestamp_repeater.as_deref()?;
+    let repeater = parse_repeater(repeater_text)?;
+    let scheduled = NaiveDate::parse_from_str(task.timestamp_date.as_deref()?, "%Y-%m-%d").ok()?;
+    let completions = completion_dates(task);
+    // Next due day after a completion on `day`.
+    let due_after =
+        |day: NaiveDate| closest_date(day, day + Days::new(1), DatePreference::Future, &repeater);
+
+    let mut graph = String::new();
+    let mut done_days = 0;
+    let mut day = start;
+    while day <= end {
+        let mark = if completions.binary_search(&day).is_ok() {
+            done_days += 1;
+            DONE_MARK
+        } else {
+            let due = match completions.iter().rev().find(|&&done| done < day) {
+                Some(&last) => due_after(last),
+                None => Some(scheduled),
+            };
+            if due.is_some_and(|due| day >= due) {
+                MISSED_MARK
+            } else {
+                NOT_DUE_MARK
+            }
+        };
+        graph.push(mark);
+        day = day.succ_opt()?;
+    }
+
+    Some(HabitRow {
+        file: task.file.to_string(),
+        line: task.line,
+        heading: task.heading.clone(),
+        repeater: repeater_text.to_string(),
+        scheduled: scheduled.format("%Y-%m-%d").to_string(),
+        last_done: completions
+            .iter()
+            .rev()
+            .find(|&&done| done <= end)
+            .map(|done| done.format("%Y-%m-%d").to_string()),
+        done_days,
+        start: start.format("%Y-%m-%d").to_string(),
+        end: end.format("%Y-%m-%d").to_string(),
+        graph,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agenda::{filter_agenda, AgendaDates, AgendaOutput, AgendaScope};
+
+    fn habits(content: &str, current_date: &str, days: u32) -> Vec<HabitRow> {
+        let dates = AgendaDates {
+            current_date: Some(current_date),
+            ..AgendaDates::default()
+        };
+        match filter_agenda(
+            crate::parse_file(content),
+            AgendaScope::Habits { days },
+            dates,
+            "UTC",
+            false,
+            false,
+        ) {
+            Ok(AgendaOutput::Habits(rows)) => rows,
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn graph_marks_completions_misses_and_days_not_due() {
+        let content = "\
+## TODO Stretch
+`SCHEDULED: <2026-01-12 Mon .+2d>`
+
+```org-properties
+STYLE: habit
+LAST_REPEAT: [2026-01-10 Sat 08:00]
+```
+
+```
+:LOGBOOK:
+- State \"DONE\"       from \"TODO\"       [2026-01-10 Sat 08:00]
+- State \"DONE\"       from \"TODO\"       [2026-01-05 Mon 07:30]
+- State \"CANCELLED\"  from \"TODO\"       [2026-01-08 Thu 07:30]
+:END:
+```
+
+## TODO Not a habit
+`SCHEDULED: <2026-01-12 Mon .+1d>`
+";
+        let rows = habits(content, "2026-01-13", 10);
+        assert_eq!(rows.len(), 1);
+        let row = &rows[0];
+        assert_eq!(row.heading, "Stretch");
+        assert_eq!(
+            (row.start.as_str(), row.end.as_str()),
+            ("2026-01-04", "2026-01-13")
+        );
+        // 04: before the first known completion, not yet due.
+        // 05: done; 06: not due; 07..09: due after 05 + 2d; 10: done;
+        // 11: not due; 12..13: due again.
+        assert_eq!(row.graph, "-*-!!!*-!!");
+        assert_eq!(row.done_days, 2);
+        assert_eq!(row.last_done.as_deref(), Some("2026-01-10"));
+        assert_eq!(row.repeater, ".+2d");
+        assert_eq!(row.scheduled, "2026-01-12");
+    }
+
+    #[test]
+    fn cancelled_habits_and_plain_repeaters_are_left_out() {
+        let content = "\
+## CANCELLED Run
+`SCHEDULED: <2026-01-12 Mon .+1d>`
+```org-properties
+STYLE: habit
+```
+
+## TODO Read
+`DEADLINE: <2026-01-12 Mon .+1d>`
+```org-properties
+STYLE: habit
+```
+";
+        assert!(habits(content, "2026-01-13", 7).is_empty());
+    }
+}
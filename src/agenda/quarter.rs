@@ -0,0 +1,185 @@
+//! `--agenda quarter`: per-month summary of open deadlines and recurring
+//! obligations across one (fiscal) quarter.
+//!
+//! Quarters are counted from `--fiscal-year-start` (default `01-01`, the
+//! calendar quarters). Deadline occurrences and recurring counts use the
+//! same repeater walk as `--agenda forecast`, so both views agree on which
+//! days a `+1m` or `+1wd` task lands.
+
+use chrono::{Datelike, Months, NaiveDate};
+
+use super::forecast::{count_occurrences, occurrence_dates, recurring_entry};
+use super::{prepare_tasks, resume_from, FiscalYearStart};
+use crate::types::{QuarterDeadline, QuarterMonth, Task, TaskType};
+
+/// `[start, end]` of the three months of the fiscal quarter containing
+/// `anchor`. `None` only when the fiscal year start falls outside chrono's
+/// supported date range.
+pub(super) fn quarter_months(
+    anchor: NaiveDate,
+    fiscal: FiscalYearStart,
+) -> Option<[(NaiveDate, NaiveDate); 3]> {
+    let this_year = NaiveDate::from_ymd_opt(anchor.year(), fiscal.month, fiscal.day)?;
+    let year_start = if anchor >= this_year {
+        this_year
+    } else {
+        NaiveDate::from_ymd_opt(anchor.year() - 1, fiscal.month, fiscal.day)?
+    };
+    // `fiscal.day <= 28`, so adding months never clamps the day and every
+    // month boundary lands on the same day-of-month.
+    let month_start = |k: u32| year_start.checked_add_months(Months::new(k));
+    let mut elapsed = 0;
+    while elapsed < 11 && month_start(elapsed + 1)? <= anchor {
+        elapsed += 1;
+    }
+    let first = elapsed - elapsed % 3;
+    let mut months = [(anchor, anchor); 3];
+    for (i, slot) in months.iter_mut().enumerate() {
+        let start = month_start(first + i as u32)?;
+        let end = month_start(first + i as u32 + 1)?.pred_opt()?;
+        *slot = (start, end);
+    }
+    Some(months)
+}
+
+/// Build one [`QuarterMonth`] per month bucket. Only open (`TODO`) tasks with
+/// an active timestamp contribute: DEADLINE occurrences are listed by date,
+/// other repeating timestamps are summarised with their occurrence count.
+pub(super) fn build_quarter(
+    tasks: &[Task],
+    months: &[(NaiveDate, NaiveDate)],
+) -> Vec<QuarterMonth> {
+    let prepared = prepare_tasks(tasks);
+    let mut result = Vec::with_capacity(months.len());
+
+    for &(start, end) in months {
+        let mut bucket = QuarterMonth {
+            start: start.format("%Y-%m-%d").to_string(),
+            end: end.format("%Y-%m-%d").to_string(),
+            deadlines: Vec::new(),
+            recurring: Vec::new(),
+        };
+
+        for entry in &prepared {
+            if !entry.task.task_type.as_ref().is_some_and(TaskType::is_open) {
+                continue;
+            }
+            let Some(parsed) = entry.parsed.as_ref() else {
+                continue;
+            };
+            let is_deadline = entry.task.timestamp_type.as_deref() == Some("DEADLINE");
+
+            if is_deadline {
+                let dates = match parsed.repeater.as_ref() {
+                    Some(repeater) => resume_from(entry.task, start)
+                        .map(|from| occurrence_dates(parsed.date, repeater, from, end))
+                        .unwrap_or_default(),
+                    None if (start..=end).contains(&parsed.date) => vec![parsed.date],
+                    None => Vec::new(),
+                };
+                bucket
+                    .deadlines
+                    .extend(dates.into_iter().map(|date| QuarterDeadline {
+                        file: entry.task.file.to_string(),
+                        line: entry.task.line,
+                        heading: entry.task.heading.clone(),
+                        date: date.format("%Y-%m-%d").to_string(),
+                        priority: entry.task.priority.clone(),
+                    }));
+            } else if let Some(repeater) = parsed.repeater.as_ref() {
+                let occurrences = resume_from(entry.task, start).map_or(0, |from| {
+                    count_occurrences(parsed.date, repeater, from, end)
+                });
+                if occurrences > 0 {
+                    bucket
+                        .recurring
+                        .push(recurring_entry(entry.task, repeater, occurrences).0);
+                }
+            }
+        }
+
+        bucket.deadlines.sort_by(|a, b| {
+            a.date
+                .cmp(&b.date)
+                .then_with(|| a.file.cmp(&b.file))
+                .then_with(|| a.line.cmp(&b.line))
+        });
+        bucket
+            .recurring
+            .sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.line.cmp(&b.line)));
+        result.push(bucket);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    const CALENDAR: FiscalYearStart = FiscalYearStart { month: 1, day: 1 };
+
+    fn task(timestamp: &str, timestamp_type: &str, task_type: TaskType) -> Task {
+        Task {
+            file: "finance.md".into(),
+            line: 1,
+            heading: "File VAT return".to_string(),
+            task_type: Some(task_type),
+            timestamp: Some(timestamp.to_string()),
+            timestamp_type: Some(timestamp_type.to_string()),
+            timestamp_active: Some(true),
+            ..Task::default()
+        }
+    }
+
+    #[test]
+    fn calendar_quarter_contains_anchor() {
+        let months = quarter_months(date(2026, 5, 20), CALENDAR).unwrap();
+        assert_eq!(months[0], (date(2026, 4, 1), date(2026, 4, 30)));
+        assert_eq!(months[2], (date(2026, 6, 1), date(2026, 6, 30)));
+    }
+
+    #[test]
+    fn fiscal_quarter_wraps_calendar_year() {
+        let april = FiscalYearStart { month: 4, day: 1 };
+        // FY starting April: Q4 is January..March.
+        let months = quarter_months(date(2026, 2, 10), april).unwrap();
+        assert_eq!(months[0].0, date(2026, 1, 1));
+        assert_eq!(months[2].1, date(2026, 3, 31));
+        // Mid-month start: UK tax year, 6 April.
+        let uk = FiscalYearStart { month: 4, day: 6 };
+        let months = quarter_months(date(2026, 4, 5), uk).unwrap();
+        assert_eq!(months[0], (date(2026, 1, 6), date(2026, 2, 5)));
+        assert_eq!(months[2], (date(2026, 3, 6), date(2026, 4, 5)));
+    }
+
+    #[test]
+    fn deadlines_listed_per_occurrence_and_recurring_counted() {
+        let tasks = vec![
+            task("DEADLINE: <2026-01-20 Tue +1m>", "DEADLINE", TaskType::Todo),
+            task("DEADLINE: <2026-02-15 Sun>", "DEADLINE", TaskType::Todo),
+            task("DEADLINE: <2026-02-16 Mon>", "DEADLINE", TaskType::Done),
+            task(
+                "SCHEDULED: <2026-01-05 Mon +1w>",
+                "SCHEDULED",
+                TaskType::Todo,
+            ),
+        ];
+        let months = quarter_months(date(2026, 2, 1), CALENDAR).unwrap();
+        let quarter = build_quarter(&tasks, &months);
+        assert_eq!(quarter.len(), 3);
+        assert_eq!(quarter[0].deadlines.len(), 1);
+        let feb: Vec<&str> = quarter[1]
+            .deadlines
+            .iter()
+            .map(|d| d.date.as_str())
+            .collect();
+        assert_eq!(feb, ["2026-02-15", "2026-02-20"]);
+        assert_eq!(quarter[1].recurring.len(), 1);
+        assert_eq!(quarter[1].recurring[0].occurrences, 4);
+    }
+}
@@ -0,0 +1,144 @@
+//! `--simulate`: projected per-day workload of open tasks across a
+//! `--from`/`--to` window.
+//!
+//! Every open (`TODO`) task with an active timestamp contributes: a repeating
+//! one on each date of the repeater walk `--agenda forecast` uses, a fixed one
+//! on its own date. Effort comes from the same `Effort` property as the
+//! forecast, so both views agree on what an occurrence costs.
+
+use chrono::NaiveDate;
+
+use super::forecast::{occurrence_dates, task_effort_seconds};
+use super::{prepare_tasks, resume_from};
+use crate::clock::format_duration;
+use crate::types::{SimulatedDay, SimulatedEntry, Task, TaskType};
+
+/// Build one [`SimulatedDay`] per day of `[from, to]`, including the days
+/// nothing falls on, so overloaded days stand out against quiet ones.
+pub(super) fn build_simulation(
+    tasks: &[Task],
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Vec<SimulatedDay> {
+    let prepared = prepare_tasks(tasks);
+    let mut days: Vec<SimulatedDay> = from
+        .iter_days()
+        .take_while(|day| *day <= to)
+        .map(|day| SimulatedDay {
+            date: day.format("%Y-%m-%d").to_string(),
+            occurrences: 0,
+            total_effort: None,
+            tasks: Vec::new(),
+        })
+        .collect();
+    let mut effort_sums: Vec<Option<u32>> = vec![None; days.len()];
+
+    for entry in &prepared {
+        if !entry.task.task_type.as_ref().is_some_and(TaskType::is_open) {
+            continue;
+        }
+        let Some(parsed) = entry.parsed.as_ref() else {
+            continue;
+        };
+        let dates = match parsed.repeater.as_ref() {
+            Some(repeater) => match resume_from(entry.task, from) {
+                Some(start) => occurrence_dates(parsed.date, repeater, start, to),
+                None => continue,
+            },
+            None if (from..=to).contains(&parsed.date) => vec![parsed.date],
+            None => continue,
+        };
+        let effort = task_effort_seconds(entry.task);
+
+        for date in dates {
+            let index = (date - from).num_days() as usize;
+            let day = &mut days[index];
+            day.occurrences = day.occurrences.saturating_add(1);
+            if let Some(seconds) = effort {
+                let sum = &mut effort_sums[index];
+                *sum = Some(sum.unwrap_or(0).saturating_add(seconds));
+            }
+            day.tasks.push(SimulatedEntry {
+                file: entry.task.file.to_string(),
+                line: entry.task.line,
+                heading: entry.task.heading.clone(),
+                effort: effort.map(|secs| format_duration(secs.into())),
+            });
+        }
+    }
+
+    for (day, sum) in days.iter_mut().zip(effort_sums) {
+        day.total_effort = sum.map(|secs| format_duration(secs.into()));
+        day.tasks
+            .sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.line.cmp(&b.line)));
+    }
+    days
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn task(line: u32, timestamp: &str, task_type: TaskType, effort: Option<&str>) -> Task {
+        Task {
+            file: "work.md".into(),
+            line,
+            heading: format!("Task {line}"),
+            task_type: Some(task_type),
+            timestamp: Some(timestamp.to_string()),
+            timestamp_type: Some("SCHEDULED".to_string()),
+            timestamp_active: Some(true),
+            properties: effort.map(|e| BTreeMap::from([("Effort".to_string(), e.to_string())])),
+            ..Task::default()
+        }
+    }
+
+    #[test]
+    fn counts_recurring_and_fixed_occurrences_per_day() {
+        let tasks = vec![
+            task(
+                1,
+                "SCHEDULED: <2026-01-05 Mon +1d>",
+                TaskType::Todo,
+                Some("0:30"),
+            ),
+            task(
+                2,
+                "DEADLINE: <2026-01-07 Wed>",
+                TaskType::Todo,
+                Some("2:00"),
+            ),
+            task(3, "SCHEDULED: <2026-01-06 Tue>", TaskType::Todo, None),
+        ];
+        let days = build_simulation(&tasks, date(2026, 1, 6), date(2026, 1, 8));
+
+        assert_eq!(days.len(), 3);
+        assert_eq!(days[0].date, "2026-01-06");
+        assert_eq!(days[0].occurrences, 2);
+        assert_eq!(days[0].total_effort.as_deref(), Some("0:30"));
+        assert_eq!(days[1].occurrences, 2);
+        assert_eq!(days[1].total_effort.as_deref(), Some("2:30"));
+        let lines: Vec<u32> = days[1].tasks.iter().map(|t| t.line).collect();
+        assert_eq!(lines, [1, 2]);
+        assert_eq!(days[2].occurrences, 1);
+    }
+
+    #[test]
+    fn done_and_out_of_window_tasks_leave_days_empty() {
+        let tasks = vec![
+            task(1, "SCHEDULED: <2026-01-06 Tue +1d>", TaskType::Done, None),
+            task(2, "SCHEDULED: <2026-02-01 Sun>", TaskType::Todo, None),
+            task(3, "[2026-01-06 Tue]", TaskType::Todo, None),
+        ];
+        let days = build_simulation(&tasks, date(2026, 1, 6), date(2026, 1, 7));
+        assert_eq!(days.len(), 2);
+        assert!(days
+            .iter()
+            .all(|d| d.occurrences == 0 && d.tasks.is_empty() && d.total_effort.is_none()));
+    }
+}
@@ -0,0 +1,127 @@
+//! `--agenda stuck`: projects without a next action, as Org's
+//! `org-agenda-list-stuck-projects`.
+//!
+//! A project is an open task heading with open task headings nested below
+//! it, or one tagged [`PROJECT_TAG`]. It is stuck when no open task nested
+//! below it is scheduled, has a deadline, or is in the [`NEXT_KEYWORD`]
+//! state (a custom keyword, see `--todo-keywords`).
+
+use crate::types::{Task, TaskType};
+
+/// Tag that marks a heading as a project even before it has subtasks.
+const PROJECT_TAG: &str = "project";
+
+/// TODO keyword of a task that is the next action of its project.
+const NEXT_KEYWORD: &str = "NEXT";
+
+/// The stuck projects among `tasks`, in file order.
+pub(super) fn build_stuck(mut tasks: Vec<Task>) -> Vec<Task> {
+    tasks.sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.line.cmp(&b.line)));
+    let stuck: Vec<bool> = (0..tasks.len())
+        .map(|i| is_stuck(&tasks[i], &tasks[i + 1..]))
+        .collect();
+    tasks
+        .into_iter()
+        .zip(stuck)
+        .filter_map(|(task, stuck)| stuck.then_some(task))
+        .collect()
+}
+
+/// Whether `project` is a stuck project; `after` holds the tasks following
+/// it in file order.
+fn is_stuck(project: &Task, after: &[Task]) -> bool {
+    let (Some(level), Some(task_type)) = (project.level, &project.task_type) else {
+        return false;
+    };
+    if !task_type.is_open() {
+        return false;
+    }
+    let depth = project.outline_path.as_ref().map_or(0, Vec::len);
+    let mut open_children = after
+        .iter()
+        .take_while(|child| child.file == project.file)
+        .filter(|child| child.level.is_some())
+        .take_while(|child| child.level > Some(level))
+        .filter(|child| {
+            child
+                .outline_path
+                .as_ref()
+                .and_then(|path| path.get(depth))
+                .is_some_and(|title| *title == project.heading)
+        })
+        .filter(|child| child.task_type.as_ref().is_some_and(TaskType::is_open))
+        .peekable();
+    let tagged = project.tags.iter().flatten().any(|tag| tag == PROJECT_TAG);
+    if !tagged && open_children.peek().is_none() {
+        return false;
+    }
+    !open_children.any(is_next_action)
+}
+
+/// Whether an open task moves its project forward: scheduled, due, or
+/// marked as the next action.
+fn is_next_action(task: &Task) -> bool {
+    let planned = |ts_type: &str| ts_type == "SCHEDULED" || ts_type == "DEADLINE";
+    matches!(&task.task_type, Some(TaskType::Other(keyword)) if keyword == NEXT_KEYWORD)
+        || task.timestamp_type.as_deref().is_some_and(planned)
+        || task.planning.is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agenda::{filter_agenda, AgendaDates, AgendaOutput, AgendaScope};
+    use crate::parser::{extract_tasks_with_counter, ExtractOptions};
+    use crate::types::{TodoKeywords, DEFAULT_MAX_TASKS};
+    use std::path::Path;
+
+    fn stuck(content: &str) -> Vec<String> {
+        let keywords = TodoKeywords {
+            todo: vec![NEXT_KEYWORD.to_string()],
+            done: Vec::new(),
+        };
+        let tasks = extract_tasks_with_counter(
+            Path::new("t.md"),
+            content,
+            &[],
+            &keywords,
+            ExtractOptions::default(),
+            DEFAULT_MAX_TASKS,
+            &mut Vec::new(),
+            &mut 0,
+        );
+        match filter_agenda(
+            tasks,
+            AgendaScope::Stuck,
+            AgendaDates::default(),
+            "UTC",
+            false,
+            false,
+        ) {
+            Ok(AgendaOutput::Tasks(tasks)) => tasks.into_iter().map(|t| t.heading).collect(),
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn projects_without_a_next_action_are_stuck() {
+        let content = "\
+## TODO Move house
+### TODO Find movers
+### DONE Pack books
+`SCHEDULED: <2026-01-05 Mon>`
+
+## TODO Garden
+### TODO Buy seeds
+`SCHEDULED: <2026-03-01 Sun>`
+
+## TODO Taxes
+### NEXT Collect receipts
+
+## TODO Learn Rust :project:
+
+## TODO Single task
+";
+        assert_eq!(stuck(content), ["Move house", "Learn Rust"]);
+    }
+}
@@ -1,8 +1,14 @@
-use chrono::NaiveDate;
 use clap::{Parser, ValueEnum};
-use std::path::PathBuf;
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
 
+use crate::agenda::{AgendaScope, GroupBy, TaskSort};
+use crate::config::BlockConfig;
 use crate::format::OutputFormat;
+use crate::front_matter::FrontMatter;
+use crate::tag_match::TagMatch;
+use crate::timestamp::MAX_REPEAT_HORIZON_YEARS;
+use crate::types::{Priority, PriorityScheme, TodoKeywords};
 
 /// Color output mode for diagnostics. Mirrors the `--color auto|always|never`
 /// convention used by `cargo`, `rg`, and other Rust-ecosystem CLIs.
@@ -23,12 +29,119 @@ pub enum ColorMode {
 pub enum AgendaMode {
     /// Single-day agenda for `--date` (default: today)
     Day,
-    /// Week (Mon-Sun) containing `--date`, or `--from`..`--to` range
+    /// Week containing `--date` (see `--week-start`), or `--from`..`--to` range
     Week,
+    /// Two weeks starting with the week containing `--date` (default: today)
+    Fortnight,
     /// Whole month containing `--date`, or `--from`..`--to` range
     Month,
+    /// From `--date` (default: today) through the last day of its week
+    #[clap(name = "rest-of-week")]
+    RestOfWeek,
+    /// `--days` days starting at `--date` (default: today)
+    Days,
     /// Flat task list (no date windowing). Equivalent to the legacy `--tasks` flag.
     Tasks,
+    /// Open tasks with `Rescheduled from` notes, most-postponed first
+    Rescheduled,
+    /// Every task matching `--query`, whatever its state, in file order
+    Search,
+    /// Open projects (tasks with open subtasks, or tagged `project`) with no
+    /// scheduled, due or `NEXT` subtask
+    Stuck,
+    /// Open tasks whose `WAITING_SINCE` is more than `--waiting-days` days
+    /// ago, longest-waiting first
+    Waiting,
+    /// Per-month occurrences and effort of recurring TODO tasks for `--months`
+    /// months starting at `--date` (default: today)
+    Forecast,
+    /// Per-month deadlines and recurring obligations of the fiscal quarter
+    /// containing `--date` (see `--fiscal-year-start`)
+    Quarter,
+    /// Open slots within `--work-hours` on each workday of `--date` (default:
+    /// today) or the `--from`..`--to` range, after timed entries and
+    /// `--busy-ics` events
+    Free,
+    /// Consistency graph of every `STYLE: habit` task over the `--habit-days`
+    /// days up to `--date` (default: today) or the `--from`..`--to` range
+    Habits,
+}
+
+/// First day of the week for `--agenda week` / `fortnight` / `rest-of-week`
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+#[clap(rename_all = "lower")]
+pub enum WeekStart {
+    /// ISO 8601 week, Monday to Sunday
+    Monday,
+    /// Sunday to Saturday
+    Sunday,
+}
+
+impl WeekStart {
+    pub fn weekday(self) -> chrono::Weekday {
+        match self {
+            WeekStart::Monday => chrono::Weekday::Mon,
+            WeekStart::Sunday => chrono::Weekday::Sun,
+        }
+    }
+}
+
+/// Which copy `--dedup` keeps of a duplicated task
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+#[clap(rename_all = "lower")]
+pub enum Dedup {
+    /// Copy from the most recently modified file
+    Newest,
+    /// Copy scanned first, in `--dir` order
+    First,
+}
+
+/// Direction for `--roll-deadlines`
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+#[clap(rename_all = "lower")]
+pub enum RollDeadlines {
+    /// Next workday on or after the deadline
+    Forward,
+    /// Previous workday on or before the deadline
+    Backward,
+}
+
+/// Byte encoding of the written output, for `--output-encoding`
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum OutputEncoding {
+    /// Plain UTF-8
+    #[clap(name = "utf-8")]
+    Utf8,
+    /// UTF-8 preceded by the `EF BB BF` byte order mark
+    #[clap(name = "utf-8-bom")]
+    Utf8Bom,
+    /// Little-endian UTF-16 preceded by the `FF FE` byte order mark
+    #[clap(name = "utf-16le")]
+    Utf16Le,
+}
+
+impl OutputEncoding {
+    /// Encode `text` into the bytes written to the output. Plain UTF-8
+    /// borrows the string unchanged.
+    pub fn encode(self, text: &str) -> Cow<'_, [u8]> {
+        match self {
+            OutputEncoding::Utf8 => Cow::Borrowed(text.as_bytes()),
+            OutputEncoding::Utf8Bom => {
+                let mut bytes = Vec::with_capacity(text.len() + 3);
+                bytes.extend_from_slice(&[0xEF, 0xBB, 0xBF]);
+                bytes.extend_from_slice(text.as_bytes());
+                Cow::Owned(bytes)
+            }
+            OutputEncoding::Utf16Le => {
+                let mut bytes = Vec::with_capacity(text.len() * 2 + 2);
+                bytes.extend_from_slice(&[0xFF, 0xFE]);
+                for unit in text.encode_utf16() {
+                    bytes.extend_from_slice(&unit.to_le_bytes());
+                }
+                Cow::Owned(bytes)
+            }
+        }
+    }
 }
 
 /// `long_about` text for `--help`. Kept as a `const` so the test that pins
@@ -54,6 +167,21 @@ Examples:
   Flat task list, absolute paths, no progress noise:
     markdown-org-extract --tasks --absolute-paths --quiet
 
+  What is left of the current week:
+    markdown-org-extract --agenda rest-of-week
+
+  Recurring workload for the next six months:
+    markdown-org-extract --agenda forecast --months 6
+
+  Per-day workload of the next four weeks:
+    markdown-org-extract --simulate --from today --to today+4w
+
+  Deadlines of the current fiscal quarter, fiscal year starting in April:
+    markdown-org-extract --agenda quarter --fiscal-year-start 04-01
+
+  Today's agenda merged with the tasks exported on another machine:
+    markdown-org-extract --remote https://desktop.lan/agenda.json
+
   Public RF holidays for a year:
     markdown-org-extract --holidays 2026
 
@@ -69,14 +197,15 @@ Environment:
 
 Exit status:
   0    success (also --holidays, --completions, and a broken output pipe)
+  1    empty result with --fail-if-empty, or findings with --lint
   2    usage or input-validation error
   70   internal software error (EX_SOFTWARE: regex/serializer)
-  74   IO error (EX_IOERR: unreadable input, walker, --output write)
+  74   IO error (EX_IOERR: unreadable input, walker, --output write, --remote)
   130  aborted by SIGINT/SIGTERM (128 + signal)
 ";
 
 /// Extract org-mode tasks from a directory of markdown files
-#[derive(Parser)]
+#[derive(Parser, Clone)]
 #[command(name = "markdown-org-extract")]
 #[command(
     about = "Extract tasks from markdown files with org-mode timestamps; emits JSON by default"
@@ -85,21 +214,200 @@ Exit status:
 #[command(version)]
 pub struct Cli {
     /// Root directory to scan (recursive). `.gitignore` is respected.
+    /// Repeatable: several roots are scanned in order, each file's path
+    /// relative to its own root.
     #[arg(long, default_value = ".", help_heading = "Input")]
-    pub dir: PathBuf,
+    pub dir: Vec<PathBuf>,
+
+    /// Drop tasks that are identical apart from their file path, e.g. from
+    /// a synced copy of the same vault under a second `--dir`. `newest`
+    /// (the default when the flag has no value) keeps the copy from the most
+    /// recently modified file, `first` the one scanned first.
+    #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "newest", help_heading = "Input")]
+    pub dedup: Option<Dedup>,
+
+    /// Added to every heading level (-5..=5) to give the logical `level`
+    /// in the output, so a vault with tasks at h1 (`--heading-offset 2`)
+    /// lines up with one that keeps them at h3. Levels never drop below 1.
+    #[arg(long, default_value_t = 0, value_parser = validate_heading_offset, allow_negative_numbers = true, help_heading = "Input")]
+    pub heading_offset: i8,
+
+    /// A task without a `[#X]` cookie takes the priority of the nearest
+    /// enclosing heading that has one, task or not, so a whole project
+    /// section can be prioritised on its heading.
+    #[arg(long, help_heading = "Input")]
+    pub inherit_priority: bool,
+
+    /// Keep only a task's own heading tags: do not add the `:tag:` lists
+    /// of its enclosing headings.
+    #[arg(long, help_heading = "Input")]
+    pub no_tag_inheritance: bool,
+
+    /// Also extract list items whose text starts with a TODO keyword,
+    /// optionally after a checkbox (`- [ ] TODO Call dentist`), as tasks
+    /// of their own, for notes that do not use one heading per task.
+    #[arg(long, help_heading = "Input")]
+    pub list_items_as_tasks: bool,
+
+    /// Report every block under a task's heading — code, tables, checklists
+    /// as well — as its markdown source in `content`, instead of the text of
+    /// its paragraphs, lists and quotes. Planning lines and property blocks
+    /// are left out.
+    #[arg(long, help_heading = "Input")]
+    pub full_content: bool,
+
+    /// Priority range in use, `HIGHEST-LOWEST[:DEFAULT]`, e.g. `A-E` or
+    /// `1-9:5`, like Org's `org-priority-highest`/`-lowest`/`-default`.
+    /// Tasks without a cookie sort as the default (the middle of the range
+    /// unless given), and `--priority-aging` stops at the highest.
+    #[arg(long, value_name = "RANGE", value_parser = PriorityScheme::parse, help_heading = "Input")]
+    pub priorities: Option<PriorityScheme>,
+
+    /// Drop tasks ranked below this priority (`B` keeps `A` and `B`), by
+    /// the priority they sort by; tasks without one are dropped too unless
+    /// `--priorities` gives them a default.
+    #[arg(long, value_name = "PRIORITY", value_parser = parse_priority, help_heading = "Input")]
+    pub min_priority: Option<Priority>,
+
+    /// Raise the sorting priority of open tasks by one level for every DAYS
+    /// days since their `CREATED` date (a task without a cookie reaches `C`
+    /// first), so old items bubble up. Reported as `effective_priority`.
+    #[arg(long, value_name = "DAYS", value_parser = clap::value_parser!(u32).range(1..), help_heading = "Input")]
+    pub priority_aging: Option<u32>,
+
+    /// Keep only tasks assigned to this person (`ASSIGNEE` property or
+    /// `@name` in the heading). Case-insensitive; a leading `@` is ignored.
+    #[arg(long, help_heading = "Input")]
+    pub assignee: Option<String>,
+
+    /// Keep only tasks matching an Org tags/property match string, e.g.
+    /// `work&!someday|urgent` or `+work-meeting&TODO="WAITING"`: `|`
+    /// separates alternatives, `&` joins terms, `-`/`!` negates one.
+    #[arg(long = "match", value_name = "EXPR", value_parser = TagMatch::parse, help_heading = "Input")]
+    pub tag_match: Option<TagMatch>,
+
+    /// Keep only tasks in these TODO states, comma-separated (e.g.
+    /// `TODO,WAITING`). Listing a done or cancelled state also shows those
+    /// tasks in `--tasks` mode. `CANCELLED` and `CANCELED` are the same state.
+    #[arg(long, value_name = "LIST", value_delimiter = ',', value_parser = validate_keyword, help_heading = "Input")]
+    pub todo_state: Vec<String>,
+
+    /// Leave out done tasks (`DONE` or a custom done keyword), so the
+    /// agenda reads as a plan. Cancelled tasks are not done.
+    #[arg(long, conflicts_with = "only_done", help_heading = "Input")]
+    pub hide_done: bool,
+
+    /// Keep only done tasks, so the agenda reads as a retrospective of
+    /// what was finished. In `--tasks` mode this lists them without
+    /// `--tasks-include-done`.
+    #[arg(long, help_heading = "Input")]
+    pub only_done: bool,
+
+    /// Keep only tasks whose heading or content contains TEXT, ignoring
+    /// case. Required by `--agenda search`; narrows any other mode too.
+    #[arg(
+        long,
+        value_name = "TEXT",
+        required_if_eq("agenda", "search"),
+        help_heading = "Input"
+    )]
+    pub query: Option<String>,
+
+    /// Read `--query` as a regular expression instead of a plain substring.
+    #[arg(long, requires = "query", help_heading = "Input")]
+    pub regex: bool,
+
+    /// Keep only tasks from files under this path, relative to `--dir`: a
+    /// prefix such as `work` or `projects/acme.md`, or a glob such as
+    /// `work/**` or `**/2025-*.md`. Repeatable; a task matching any is kept.
+    #[arg(long, value_name = "PATTERN", help_heading = "Input")]
+    pub path_filter: Vec<String>,
 
     /// File matching pattern. Supported: `*.ext` and exact file names.
     #[arg(long, default_value = "*.md", help_heading = "Input")]
     pub glob: String,
 
+    /// Read `.mdextract.toml` files in the scanned directories: each one
+    /// overrides `glob`, `tags`, `category`, `todo_keywords` and
+    /// `done_keywords` for its subtree.
+    #[arg(long, help_heading = "Input")]
+    pub monorepo: bool,
+
+    /// Comma-separated extra heading keywords for open states, e.g.
+    /// `NEXT,WAITING`. They count as open tasks like `TODO`. The built-in
+    /// `TODO`, `DONE`, `CANCELLED` and `CANCELED` are always recognised;
+    /// listing one again is allowed.
+    #[arg(
+        long,
+        value_name = "LIST",
+        value_delimiter = ',',
+        value_parser = validate_keyword,
+        help_heading = "Input"
+    )]
+    pub todo_keywords: Vec<String>,
+
+    /// Comma-separated extra heading keywords for closed states, e.g.
+    /// `SHIPPED`. They count as completed tasks like `DONE`.
+    #[arg(
+        long,
+        value_name = "LIST",
+        value_delimiter = ',',
+        value_parser = validate_keyword,
+        help_heading = "Input"
+    )]
+    pub done_keywords: Vec<String>,
+
+    /// Also merge the tasks served at this `http(s)://` URL — the JSON output
+    /// of `--agenda tasks --format json` on another machine. Repeatable.
+    /// Remote `file` paths are prefixed with the host (`laptop:work.md`).
+    /// Needs a build with the `remote` cargo feature.
+    #[arg(long, value_parser = validate_remote_url, help_heading = "Input")]
+    pub remote: Vec<String>,
+
+    /// Also merge the events of this iCalendar file, e.g. a teammate's
+    /// exported calendar, as busy timed entries. Repeatable.
+    #[arg(long, value_name = "PATH", help_heading = "Input")]
+    pub busy_ics: Vec<PathBuf>,
+
     /// Output format. `md` is accepted as an alias for `markdown`.
-    #[arg(long, default_value = "json", value_enum, help_heading = "Output")]
-    pub format: OutputFormat,
+    /// Repeatable: each `--format` is paired with the `--output` at the same
+    /// position, so one scan can write e.g. JSON and HTML side by side.
+    /// Without `--format`, each `--output` extension (`.json`, `.md`,
+    /// `.html`, `.csv`) picks the format; the default is `json`.
+    #[arg(long, value_enum, help_heading = "Output")]
+    pub format: Vec<OutputFormat>,
 
     /// Write output to file instead of stdout. The path must reside in an
     /// existing directory and must not be a symlink. Use `-` for stdout.
+    /// Repeatable, one per `--format`.
     #[arg(long, help_heading = "Output")]
-    pub output: Option<PathBuf>,
+    pub output: Vec<PathBuf>,
+
+    /// Byte encoding of the `--output` file: `utf-8` (default), `utf-8-bom`,
+    /// or `utf-16le` (with BOM), for consumers such as Excel on Windows that
+    /// need a byte order mark to detect Unicode.
+    #[arg(
+        long,
+        value_enum,
+        default_value = "utf-8",
+        requires = "output",
+        help_heading = "Output"
+    )]
+    pub output_encoding: OutputEncoding,
+
+    /// Only count CLOCK entries starting on or after this date in `clocks` /
+    /// `total_clock_time`: YYYY-MM-DD or a relative date as for `--date`
+    /// (`today`, `mon`, `-7d`, `today-1w`). Open-ended when `--clocks-to` is
+    /// omitted.
+    #[arg(long, value_parser = validate_date, allow_hyphen_values = true, help_heading = "Output")]
+    pub clocks_from: Option<String>,
+
+    /// Only count CLOCK entries starting on or before this date in `clocks` /
+    /// `total_clock_time`: YYYY-MM-DD or a relative date as for `--date`
+    /// (`yesterday`, `fri`, `+3d`, `today+3d`). Open-ended when
+    /// `--clocks-from` is omitted.
+    #[arg(long, value_parser = validate_date, allow_hyphen_values = true, help_heading = "Output")]
+    pub clocks_to: Option<String>,
 
     /// Emit absolute file paths in output. Default is paths relative to `--dir`.
     /// Note: with `-v`/`-vv`/`-vvv`, diagnostic stderr also logs file paths and
@@ -108,6 +416,18 @@ pub struct Cli {
     #[arg(long, help_heading = "Output")]
     pub absolute_paths: bool,
 
+    /// When the result holds no task, write a JSON object with scan counts
+    /// and hints (e.g. "no files matched glob *.md under DIR") instead of an
+    /// empty array. Markdown/HTML keep their empty document and log the
+    /// hints on stderr.
+    #[arg(long, help_heading = "Output")]
+    pub explain_empty: bool,
+
+    /// Exit with code 1 when the result holds no task. The output is still
+    /// written.
+    #[arg(long, help_heading = "Output")]
+    pub fail_if_empty: bool,
+
     /// Comma-separated locale list for weekday name normalization (e.g. `ru,en`).
     /// Supported values: `ru`, `en`. Empty segments are tolerated
     /// (`ru,` and `,en` both parse). An unknown locale is rejected at
@@ -115,7 +435,10 @@ pub struct Cli {
     #[arg(long, default_value = "ru,en", value_parser = validate_locale, help_heading = "Agenda")]
     pub locale: String,
 
-    /// Agenda time scope: day / week / month. Mutually exclusive with `--tasks`.
+    /// Agenda view: a date window (`day`, `week`, `fortnight`, `month`,
+    /// `rest-of-week`, `days`), a flat list (`tasks`, `rescheduled`,
+    /// `search`, `stuck`, `waiting`) or a report (`forecast`, `quarter`,
+    /// `free`, `habits`). Mutually exclusive with `--tasks`.
     #[arg(
         long,
         default_value = "day",
@@ -129,6 +452,17 @@ pub struct Cli {
     #[arg(long, help_heading = "Agenda")]
     pub tasks: bool,
 
+    /// Instead of an agenda, report per-day occurrence counts and total
+    /// `Effort` of every open task (recurring and fixed) between `--from`
+    /// and `--to`, to spot overloaded days ahead.
+    #[arg(
+        long,
+        requires_all = ["from", "to"],
+        conflicts_with_all = ["tasks", "agenda", "date"],
+        help_heading = "Agenda"
+    )]
+    pub simulate: bool,
+
     /// Also include DONE tasks in the flat list (`--tasks` / `--agenda tasks`).
     /// Off by default: the flat list is TODO-only. Has no effect in
     /// `--agenda day/week/month`, which keep their Org-faithful DONE handling.
@@ -146,35 +480,169 @@ pub struct Cli {
     #[arg(long, help_heading = "Agenda")]
     pub tasks_include_cancelled: bool,
 
-    /// Window anchor for `--agenda day/week/month` (YYYY-MM-DD).
+    /// Order of the flat task list (`--tasks` / `--agenda tasks`). Tasks
+    /// without the chosen date or priority come last; ties keep the
+    /// priority order.
+    #[arg(long, value_enum, value_name = "KEY", default_value_t = TaskSort::Priority, help_heading = "Agenda")]
+    pub sort: TaskSort,
+
+    /// Reverse the `--sort` order of the flat task list.
+    #[arg(long, help_heading = "Agenda")]
+    pub reverse: bool,
+
+    /// Window anchor for `--agenda day/week/month` (YYYY-MM-DD, or
+    /// `today`, `tomorrow`, `yesterday`, a weekday such as `mon` or a bare
+    /// offset such as `+3d`, each with an optional offset like `today+1w`).
     /// In day mode the window is exactly this date; in week/month it is the
     /// week / month containing this date. Overridden by `--from`/`--to` when
     /// either is given. Not allowed in `--agenda tasks`.
-    #[arg(long, value_parser = validate_date, help_heading = "Agenda")]
+    #[arg(long, value_parser = validate_date, allow_hyphen_values = true, help_heading = "Agenda")]
     pub date: Option<String>,
 
-    /// Window start for `--agenda day/week/month` (YYYY-MM-DD). Together with
-    /// `--to` forms an explicit range that overrides `--date`. If `--to` is
-    /// omitted, the window ends at `--current-date` (or today).
-    #[arg(long, value_parser = validate_date, conflicts_with = "tasks", help_heading = "Agenda")]
+    /// Window start for `--agenda day/week/month/free/habits` and
+    /// `--simulate`: YYYY-MM-DD or a relative date as for `--date` (`today`,
+    /// `tomorrow`, `fri`, `+3d`, `today+3d`). Together with `--to` forms an
+    /// explicit range that overrides `--date`. If `--to` is omitted, the
+    /// window ends at `--current-date` (or today).
+    #[arg(long, value_parser = validate_date, allow_hyphen_values = true, conflicts_with = "tasks", help_heading = "Agenda")]
     pub from: Option<String>,
 
-    /// Window end for `--agenda day/week/month` (YYYY-MM-DD). Together with
-    /// `--from` forms an explicit range that overrides `--date`. If `--from`
-    /// is omitted, the window starts at `--current-date` (or today).
-    #[arg(long, value_parser = validate_date, conflicts_with = "tasks", help_heading = "Agenda")]
+    /// Window end for `--agenda day/week/month/free/habits` and
+    /// `--simulate`: YYYY-MM-DD or a relative date as for `--date` (`today`,
+    /// `tomorrow`, `fri`, `+3d`, `today+3d`). Together with `--from` forms an
+    /// explicit range that overrides `--date`. If `--from` is omitted, the
+    /// window starts at `--current-date` (or today).
+    #[arg(long, value_parser = validate_date, allow_hyphen_values = true, conflicts_with = "tasks", help_heading = "Agenda")]
     pub to: Option<String>,
 
-    /// IANA timezone for "today" determination (e.g. `Europe/Moscow`, `UTC`)
+    /// ISO week to show, as `YYYY-Www` (e.g. `2025-W14`): its Monday to
+    /// Sunday, the same as the matching `--from`/`--to` range. Meant for
+    /// `--agenda week`, so a script can pin an agenda by week number.
+    #[arg(long, value_name = "YYYY-Www", value_parser = validate_iso_week, conflicts_with_all = ["date", "from", "to", "tasks"], help_heading = "Agenda")]
+    pub week: Option<chrono::NaiveDate>,
+
+    /// Report an `effective_date` for DEADLINEs on weekends or holidays:
+    /// the next (`forward`) or previous (`backward`) workday under the
+    /// bundled holiday calendar. The agenda placement is unchanged.
+    #[arg(long, value_enum, help_heading = "Agenda")]
+    pub roll_deadlines: Option<RollDeadlines>,
+
+    /// Show a recurring task scheduled on several days of a day-agenda
+    /// window once, on its first day, with an `occurrence_days` list of
+    /// every date it occurs on.
+    #[arg(long, help_heading = "Agenda")]
+    pub collapse_recurring: bool,
+
+    /// Log mode for the day views: also list every CLOCK interval on the
+    /// day it started, in a `clocked` list, next to the tasks closed that
+    /// day. Has no effect in the other modes.
+    #[arg(long, help_heading = "Agenda")]
+    pub log: bool,
+
+    /// Day views: also list the open slots between the timed entries of
+    /// each workday within `--work-hours`, in a `gaps` list, to see where
+    /// a meeting still fits. Has no effect in the other modes.
+    #[arg(long, help_heading = "Agenda")]
+    pub show_gaps: bool,
+
+    /// Day views: group each day's entries by category (the `CATEGORY`
+    /// property, else the file name), in a `categories` list with
+    /// one sub-header per category in Markdown and HTML. Has no effect in
+    /// the other modes.
+    #[arg(long, help_heading = "Agenda")]
+    pub by_category: bool,
+
+    /// Day views: list at most N entries in each section of a day (1..),
+    /// followed by an "…and K more" marker. The day's `summary` still
+    /// counts every entry.
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..), help_heading = "Agenda")]
+    pub max_entries_per_day: Option<u32>,
+
+    /// Day views: list each day's overdue, scheduled and upcoming entries
+    /// as one chronological list, with a "── now ──" marker at the current
+    /// time (in the `--tz` zone) on today. Conflicts with `--by-category`.
+    #[arg(long, conflicts_with = "by_category", help_heading = "Agenda")]
+    pub timeline: bool,
+
+    /// Also warn about open SCHEDULED tasks starting within N days
+    /// (1..=366): they join today's upcoming list next to the deadline
+    /// warnings, keeping their SCHEDULED timestamp. Off by default.
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..=366), help_heading = "Agenda")]
+    pub scheduled_warning_days: Option<u32>,
+
+    /// Leave out the days with nothing on them, so a week or month view
+    /// lists only the days that hold an entry. Applies to every output
+    /// format, JSON included.
+    #[arg(long, help_heading = "Agenda")]
+    pub skip_empty_days: bool,
+
+    /// First day of the week used by `--agenda week`, `--agenda fortnight`
+    /// and `--agenda rest-of-week`.
+    #[arg(long, default_value = "monday", value_enum, help_heading = "Agenda")]
+    pub week_start: WeekStart,
+
+    /// IANA timezone for "today" determination (e.g. `Europe/Moscow`, `UTC`).
+    /// A comma-separated list (`Europe/Moscow,America/New_York`) keeps the
+    /// first zone for "today" and annotates timed day-agenda entries with
+    /// their local time in each further zone.
     #[arg(long, default_value = "Europe/Moscow", value_parser = validate_timezone, help_heading = "Agenda")]
     pub tz: String,
 
-    /// Override "today" (YYYY-MM-DD). Used as the reference point for overdue
+    /// Override "today" (YYYY-MM-DD, or relative like `today-1d` to replay
+    /// yesterday's agenda). Used as the reference point for overdue
     /// and upcoming markers, and as the default for a missing `--from`/`--to`
     /// edge. Not allowed in `--agenda tasks`.
-    #[arg(long, value_parser = validate_date, help_heading = "Agenda")]
+    #[arg(long, value_parser = validate_date, allow_hyphen_values = true, help_heading = "Agenda")]
     pub current_date: Option<String>,
 
+    /// Number of days covered by `--agenda days` (1..=366), starting with
+    /// `--date` (or today).
+    #[arg(long, default_value_t = 7, value_parser = clap::value_parser!(u32).range(1..=366), help_heading = "Agenda")]
+    pub days: u32,
+
+    /// Number of calendar months covered by `--agenda forecast` (1..=120),
+    /// starting with the month containing `--date` (or today).
+    #[arg(long, default_value_t = DEFAULT_FORECAST_MONTHS, value_parser = validate_months, help_heading = "Agenda")]
+    pub months: u32,
+
+    /// `--agenda waiting` lists open tasks whose `WAITING_SINCE` is more than
+    /// this many days before today (or `--current-date`).
+    #[arg(long, default_value_t = 7, help_heading = "Agenda")]
+    pub waiting_days: u32,
+
+    /// Working hours `--agenda free` and `--show-gaps` look for open slots
+    /// in, as `HH:MM-HH:MM`.
+    #[arg(long, default_value = "09:00-18:00", value_parser = validate_work_hours, help_heading = "Agenda")]
+    pub work_hours: crate::agenda::WorkHours,
+
+    /// Shortest open slot `--agenda free` and `--show-gaps` report, in
+    /// minutes (1..=1440).
+    #[arg(long, default_value_t = 30, value_parser = clap::value_parser!(u32).range(1..=1440), help_heading = "Agenda")]
+    pub min_slot: u32,
+
+    /// Days of history `--agenda habits` draws, up to and including
+    /// `--date` (1..=366).
+    #[arg(long, default_value_t = 21, value_parser = clap::value_parser!(u32).range(1..=366), help_heading = "Agenda")]
+    pub habit_days: u32,
+
+    /// Split the output into one agenda per value of a task attribute,
+    /// e.g. one per person with `assignee` or one per project file with
+    /// `file`. Groups left empty by the selected view are omitted.
+    #[arg(long, value_enum, help_heading = "Agenda")]
+    pub group_by: Option<GroupBy>,
+
+    /// Print the custom agenda NAME defined in the `.mdextract.toml` of the
+    /// `--dir` root: one block after another, each a view with its own
+    /// filters. Every other option applies to all blocks unless a block
+    /// sets its own value.
+    #[arg(long, value_name = "NAME", conflicts_with_all = ["agenda", "tasks"], help_heading = "Agenda")]
+    pub custom_agenda: Option<String>,
+
+    /// First day of the fiscal year for `--agenda quarter`, as `MM-DD`
+    /// (day 1..=28). The default `01-01` gives calendar quarters.
+    #[arg(long, default_value = "01-01", value_parser = validate_fiscal_year_start, help_heading = "Agenda")]
+    pub fiscal_year_start: crate::agenda::FiscalYearStart,
+
     /// Maximum number of tasks to extract before stopping (1..=10_000_000).
     /// Acts as a global cap on extracted tasks; the same value is reused as a
     /// per-file cap so a single hostile file cannot exhaust the global budget
@@ -184,6 +652,13 @@ pub struct Cli {
     #[arg(long, default_value_t = crate::types::DEFAULT_MAX_TASKS, value_parser = validate_max_tasks, help_heading = "Limits")]
     pub max_tasks: usize,
 
+    /// Years past its base date a repeater is still projected (1..=1000).
+    /// A repeating task whose base date is older than this, counted back
+    /// from today, is left out of day, forecast and quarter views with a
+    /// warning; it usually is a mistyped year.
+    #[arg(long, default_value_t = crate::agenda::DEFAULT_REPEAT_HORIZON_YEARS, value_parser = validate_repeat_horizon, help_heading = "Limits")]
+    pub repeat_horizon: u32,
+
     /// Increase logging verbosity. Repeat for more (-v = info, -vv = debug, -vvv = trace).
     /// `-vvv` is the maximum; extra `-v` are ignored and trigger a one-off
     /// saturation warning rather than unlocking a deeper level.
@@ -236,6 +711,35 @@ pub struct Cli {
         help_heading = "Actions"
     )]
     pub completions: Option<clap_complete::Shell>,
+
+    /// Check planning timestamps instead of building an agenda. Prints every
+    /// timestamp that cannot be parsed (error) or was repaired or partly
+    /// ignored (warning) as `FILE:LINE: SEVERITY: MESSAGE`, or as a JSON
+    /// array with `--format json`. Exits 1 when anything is reported.
+    #[arg(long, conflicts_with_all = ["holidays", "completions", "remote"], help_heading = "Actions")]
+    pub lint: bool,
+
+    /// Measure parser throughput on the files under DIR that match
+    /// `--glob` and print a JSON report (files, bytes, tasks, MB/s)
+    /// instead of tasks. Files are read up front, so only parsing is timed.
+    #[arg(
+        long,
+        value_name = "DIR",
+        conflicts_with_all = ["holidays", "completions", "remote", "lint"],
+        help_heading = "Actions"
+    )]
+    pub bench_corpus: Option<PathBuf>,
+
+    /// Print a JSON description of what this build supports (output
+    /// formats, agenda modes, repeaters, task keywords, locales) and exit,
+    /// so editor plugins and other front ends can feature-detect instead of
+    /// parsing `--help`.
+    #[arg(
+        long,
+        conflicts_with_all = ["holidays", "completions", "remote", "lint", "bench_corpus"],
+        help_heading = "Actions"
+    )]
+    pub capabilities: bool,
 }
 
 /// Snapshot of the color-related environment, taken once per invocation so the
@@ -359,20 +863,187 @@ impl Cli {
         )
     }
 
-    pub fn agenda_scope(&self) -> crate::agenda::AgendaScope {
-        use crate::agenda::AgendaScope;
+    /// Custom TODO keywords from `--todo-keywords` / `--done-keywords`;
+    /// fails when one keyword is in both lists.
+    pub fn keywords(&self) -> Result<TodoKeywords, String> {
+        TodoKeywords::new(&self.todo_keywords, &self.done_keywords)
+    }
+
+    /// The first `--tz` zone: decides "today" and is the zone the vault's
+    /// timestamps are read in.
+    pub fn home_tz(&self) -> &str {
+        self.tz.split(',').next().unwrap_or(&self.tz)
+    }
+
+    /// Pair each `--format` with its `--output`; `None` is stdout. Without
+    /// `--format`, every `--output` takes the format of its extension (JSON
+    /// when unknown). A single `--format` takes at most one `--output` as
+    /// before, while repeated formats need one `--output` each. Only one
+    /// output may be stdout. Clock exports skip the agenda, so they cannot
+    /// share a run with the agenda formats.
+    pub fn output_targets(&self) -> Result<Vec<(OutputFormat, Option<&Path>)>, String> {
+        fn path(p: &Path) -> Option<&Path> {
+            Some(p).filter(|p| p.as_os_str() != "-")
+        }
+        let formats = if !self.format.is_empty() {
+            self.format.clone()
+        } else if self.output.is_empty() {
+            vec![OutputFormat::Json]
+        } else {
+            self.output
+                .iter()
+                .map(|p| OutputFormat::from_extension(p).unwrap_or(OutputFormat::Json))
+                .collect()
+        };
+        if formats.len() == 1 && self.output.len() <= 1 {
+            return Ok(vec![(
+                formats[0],
+                self.output.first().and_then(|p| path(p)),
+            )]);
+        }
+        if formats.len() != self.output.len() {
+            return Err(format!(
+                "{} --format values need as many --output paths, got {}",
+                formats.len(),
+                self.output.len()
+            ));
+        }
+        let targets: Vec<_> = formats
+            .iter()
+            .zip(&self.output)
+            .map(|(&format, p)| (format, path(p)))
+            .collect();
+        if targets.iter().filter(|(_, p)| p.is_none()).count() > 1 {
+            return Err("at most one --output may be stdout (`-`)".to_string());
+        }
+        let clock_exports = targets.iter().filter(|(f, _)| f.is_clock_export()).count();
+        if clock_exports != 0 && clock_exports != targets.len() {
+            return Err(
+                "clocks-csv/clocks-json cannot be combined with agenda formats".to_string(),
+            );
+        }
+        Ok(targets)
+    }
+
+    /// Replace `--week` with the `--from`/`--to` range of its Monday and
+    /// Sunday, so the agenda only deals with explicit ranges.
+    pub fn resolve_week(&mut self) {
+        if let Some(monday) = self.week.take() {
+            let sunday = monday + chrono::Days::new(6);
+            self.from = Some(monday.format("%Y-%m-%d").to_string());
+            self.to = Some(sunday.format("%Y-%m-%d").to_string());
+        }
+    }
+
+    /// The `--tz` zones after the first, for local-time annotations.
+    pub fn extra_tzs(&self) -> Vec<&str> {
+        self.tz.split(',').skip(1).collect()
+    }
+
+    pub fn agenda_scope(&self) -> AgendaScope {
         // `--tasks` and `--agenda tasks` both produce a flat task list. The
         // legacy bool flag wins when present so existing scripts keep working;
         // otherwise the AgendaMode value decides.
         if self.tasks {
             return AgendaScope::Tasks;
         }
+        if self.simulate {
+            return AgendaScope::Simulate;
+        }
         match self.agenda {
             AgendaMode::Day => AgendaScope::Day,
             AgendaMode::Week => AgendaScope::Week,
+            AgendaMode::Fortnight => AgendaScope::Fortnight,
             AgendaMode::Month => AgendaScope::Month,
+            AgendaMode::RestOfWeek => AgendaScope::RestOfWeek,
+            AgendaMode::Days => AgendaScope::Days { days: self.days },
             AgendaMode::Tasks => AgendaScope::Tasks,
+            AgendaMode::Rescheduled => AgendaScope::Rescheduled,
+            AgendaMode::Search => AgendaScope::Search,
+            AgendaMode::Stuck => AgendaScope::Stuck,
+            AgendaMode::Waiting => AgendaScope::Waiting {
+                days: self.waiting_days,
+            },
+            AgendaMode::Forecast => AgendaScope::Forecast {
+                months: self.months,
+            },
+            AgendaMode::Quarter => AgendaScope::Quarter {
+                fiscal_year_start: self.fiscal_year_start,
+            },
+            AgendaMode::Free => AgendaScope::Free {
+                work_hours: self.work_hours,
+                min_slot: self.min_slot,
+            },
+            AgendaMode::Habits => AgendaScope::Habits {
+                days: self.habit_days,
+            },
+        }
+    }
+
+    /// These options with the keys of a custom agenda `block` in place of
+    /// their values, checked as the options are. A block switching to a
+    /// mode without dates (`tasks`, `search`, ...) drops the inherited date
+    /// arguments, which such a mode rejects.
+    pub fn with_block(&self, block: &BlockConfig) -> Result<Cli, String> {
+        fn key<T>(name: &str, value: Result<T, String>) -> Result<T, String> {
+            value.map_err(|e| format!("{name}: {e}"))
+        }
+
+        let mut cli = self.clone();
+        if let Some(ref mode) = block.agenda {
+            cli.agenda = key("agenda", AgendaMode::from_str(mode, false))?;
+            cli.tasks = false;
+            cli.simulate = false;
+            if matches!(
+                cli.agenda_scope(),
+                AgendaScope::Tasks
+                    | AgendaScope::Rescheduled
+                    | AgendaScope::Search
+                    | AgendaScope::Stuck
+            ) {
+                cli.date = None;
+                cli.from = None;
+                cli.to = None;
+                cli.current_date = None;
+            }
+        }
+        if let Some(ref date) = block.date {
+            cli.date = Some(key("date", validate_date(date))?);
+        }
+        if let Some(days) = block.days {
+            if !(1..=366).contains(&days) {
+                return Err("days: must be from 1 to 366".to_string());
+            }
+            cli.days = days;
+        }
+        if let Some(ref expr) = block.tag_match {
+            cli.tag_match = Some(key("match", TagMatch::parse(expr))?);
+        }
+        if let Some(ref states) = block.todo_state {
+            cli.todo_state = key(
+                "todo_state",
+                states.iter().map(|s| validate_keyword(s)).collect(),
+            )?;
+        }
+        if let Some(ref priority) = block.min_priority {
+            cli.min_priority = Some(key("min_priority", parse_priority(priority))?);
         }
+        if let Some(ref assignee) = block.assignee {
+            cli.assignee = Some(assignee.clone());
+        }
+        if let Some(ref query) = block.query {
+            cli.query = Some(query.clone());
+        }
+        if let Some(regex) = block.regex {
+            cli.regex = regex;
+        }
+        if let Some(ref by) = block.group_by {
+            cli.group_by = Some(key("group_by", GroupBy::from_str(by, false))?);
+        }
+        if cli.query.is_none() && (cli.agenda == AgendaMode::Search || cli.regex) {
+            return Err("query: required by agenda 'search' and by regex".to_string());
+        }
+        Ok(cli)
     }
 
     /// Initialize the global tracing subscriber from CLI flags.
@@ -408,8 +1079,9 @@ const DATE_YEAR_MAX: i32 = 2100;
 
 fn validate_date(s: &str) -> Result<String, String> {
     use chrono::Datelike;
-    let parsed = NaiveDate::parse_from_str(s, "%Y-%m-%d")
-        .map_err(|e| format!("{e}; use YYYY-MM-DD format"))?;
+    // The `--tz` zone is not known yet; UTC's today is close enough to
+    // bounds-check a `today` expression, which is re-resolved in that zone.
+    let parsed = crate::agenda::parse_date_expr(s, chrono::Utc::now().date_naive())?;
     let year = parsed.year();
     if !(DATE_YEAR_MIN..=DATE_YEAR_MAX).contains(&year) {
         return Err(format!(
@@ -431,6 +1103,93 @@ fn validate_year(s: &str) -> Result<i32, String> {
     Ok(year)
 }
 
+/// Default and upper bound for `--months`. Ten years keeps the forecast's
+/// per-task occurrence walk bounded even for daily repeaters.
+fn validate_repeat_horizon(s: &str) -> Result<u32, String> {
+    let n: u32 = s
+        .parse()
+        .map_err(|_| format!("must be a positive integer up to {MAX_REPEAT_HORIZON_YEARS}"))?;
+    if n == 0 {
+        return Err("must be at least 1".to_string());
+    }
+    if n > MAX_REPEAT_HORIZON_YEARS {
+        return Err(format!("must be at most {MAX_REPEAT_HORIZON_YEARS}"));
+    }
+    Ok(n)
+}
+
+const DEFAULT_FORECAST_MONTHS: u32 = 6;
+const MAX_FORECAST_MONTHS: u32 = 120;
+
+fn validate_months(s: &str) -> Result<u32, String> {
+    let n: u32 = s
+        .parse()
+        .map_err(|_| format!("must be a positive integer up to {MAX_FORECAST_MONTHS}"))?;
+    if n == 0 {
+        return Err("must be at least 1".to_string());
+    }
+    if n > MAX_FORECAST_MONTHS {
+        return Err(format!("must be at most {MAX_FORECAST_MONTHS}"));
+    }
+    Ok(n)
+}
+
+/// Parse `--fiscal-year-start MM-DD`. The day is capped at 28 so that
+/// every fiscal month (and quarter) boundary exists in every month.
+fn validate_fiscal_year_start(s: &str) -> Result<crate::agenda::FiscalYearStart, String> {
+    let (month, day) = s
+        .split_once('-')
+        .and_then(|(m, d)| Some((m.parse::<u32>().ok()?, d.parse::<u32>().ok()?)))
+        .ok_or_else(|| "must be MM-DD".to_string())?;
+    if !(1..=12).contains(&month) {
+        return Err("month must be between 01 and 12".to_string());
+    }
+    if !(1..=28).contains(&day) {
+        return Err("day must be between 01 and 28".to_string());
+    }
+    Ok(crate::agenda::FiscalYearStart { month, day })
+}
+
+/// Parse `--week YYYY-Www` into the Monday of that ISO week.
+fn validate_iso_week(s: &str) -> Result<chrono::NaiveDate, String> {
+    let (year, week) = s
+        .split_once(['W', 'w'])
+        .and_then(|(y, w)| {
+            Some((
+                y.strip_suffix('-')?.parse::<i32>().ok()?,
+                w.parse::<u32>().ok()?,
+            ))
+        })
+        .ok_or_else(|| "must be YYYY-Www, e.g. 2025-W14".to_string())?;
+    if !(DATE_YEAR_MIN..=DATE_YEAR_MAX).contains(&year) {
+        return Err(format!(
+            "year must be between {DATE_YEAR_MIN} and {DATE_YEAR_MAX}"
+        ));
+    }
+    chrono::NaiveDate::from_isoywd_opt(year, week, chrono::Weekday::Mon)
+        .ok_or_else(|| format!("{year} has no ISO week {week}"))
+}
+
+/// A built-in keyword or the shape of a custom one.
+fn validate_keyword(s: &str) -> Result<String, String> {
+    let keyword = s.trim();
+    TodoKeywords::validate(keyword)?;
+    Ok(keyword.to_string())
+}
+
+/// Parse `--work-hours HH:MM-HH:MM`; the end must be after the start.
+fn validate_work_hours(s: &str) -> Result<crate::agenda::WorkHours, String> {
+    let parse = |t: &str| chrono::NaiveTime::parse_from_str(t.trim(), "%H:%M").ok();
+    let (start, end) = s
+        .split_once('-')
+        .and_then(|(start, end)| Some((parse(start)?, parse(end)?)))
+        .ok_or_else(|| "must be HH:MM-HH:MM".to_string())?;
+    if end <= start {
+        return Err("end must be after start".to_string());
+    }
+    Ok(crate::agenda::WorkHours { start, end })
+}
+
 const MAX_TASKS_ALLOWED: usize = 10_000_000;
 
 /// Human-readable form of [`MAX_TASKS_ALLOWED`] for validator messages, grouped
@@ -470,9 +1229,39 @@ fn validate_timezone(s: &str) -> Result<String, String> {
     // Preserve the chrono-tz error text — it usually pinpoints the failure
     // (e.g. trailing whitespace, unknown zone name). Without it the user only
     // sees the generic IANA-hint and has to guess what went wrong.
-    s.parse::<chrono_tz::Tz>()
-        .map(|_| s.to_string())
-        .map_err(|e| format!("{e}; use IANA timezone names (e.g. 'Europe/Moscow', 'UTC')"))
+    for name in s.split(',') {
+        name.parse::<chrono_tz::Tz>()
+            .map_err(|e| format!("{e}; use IANA timezone names (e.g. 'Europe/Moscow', 'UTC')"))?;
+    }
+    Ok(s.to_string())
+}
+
+fn parse_priority(s: &str) -> Result<Priority, String> {
+    Priority::parse(s.trim())
+        .ok_or_else(|| format!("'{s}' is not a priority: use a letter A-Z or a number 0-64"))
+}
+
+fn validate_heading_offset(s: &str) -> Result<i8, String> {
+    match s.parse::<i8>() {
+        Ok(n) if (-5..=5).contains(&n) => Ok(n),
+        _ => Err("must be an integer from -5 to 5".to_string()),
+    }
+}
+
+fn validate_remote_url(s: &str) -> Result<String, String> {
+    let host = s
+        .strip_prefix("https://")
+        .or_else(|| s.strip_prefix("http://"))
+        .ok_or("must be an http:// or https:// URL")?;
+    if host.is_empty() || host.starts_with('/') {
+        return Err("URL has no host".to_string());
+    }
+    if !cfg!(feature = "remote") {
+        return Err(
+            "this build has no `remote` feature; rebuild with `--features remote`".to_string(),
+        );
+    }
+    Ok(s.to_string())
 }
 
 fn validate_locale(s: &str) -> Result<String, String> {
@@ -518,7 +1307,7 @@ pub(crate) const RU_WEEKDAY_MAPPINGS: &[(&str, &str)] = &[
 /// Locales for which `get_weekday_mappings` ships a translation table.
 /// `en` is recognised as a no-op (English weekday names need no mapping) so
 /// the default `--locale ru,en` works without warnings.
-pub(crate) const SUPPORTED_LOCALES: &[&str] = &["ru", "en"];
+pub const SUPPORTED_LOCALES: &[&str] = &["ru", "en"];
 
 /// Return the (foreign, English) weekday-name pairs for the requested
 /// `locale` string. `locale` is the comma-separated value of `--locale`
@@ -553,10 +1342,51 @@ pub fn get_weekday_mappings(locale: &str) -> Vec<(&'static str, &'static str)> {
     mappings
 }
 
+/// The part of `mappings` that applies to one document. `--locale` says
+/// which tables are available; the document says which one it is written
+/// in, so `--locale ru` does not rewrite the text of an English file.
+///
+/// The language comes from the `lang:` / `language:` key of a leading
+/// front matter block (`ru`, `ru-RU`, `en`, ...). Without one, a document
+/// holding any Cyrillic letter counts as Russian — that covers every file
+/// the Russian table could match, so detection never loses a weekday.
+pub fn mappings_for_content<'m>(
+    mappings: &'m [(&'static str, &'static str)],
+    content: &str,
+) -> &'m [(&'static str, &'static str)] {
+    let russian = match FrontMatter::parse(content).and_then(|front| front.lang) {
+        Some(lang) => lang
+            .split(['-', '_'])
+            .next()
+            .is_some_and(|primary| primary.eq_ignore_ascii_case("ru")),
+        None => content
+            .chars()
+            .any(|c| matches!(c, '\u{0400}'..='\u{04FF}')),
+    };
+    if russian {
+        mappings
+    } else {
+        &[]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_output_encoding_prefixes_bom() {
+        assert_eq!(&*OutputEncoding::Utf8.encode("ё\n"), "ё\n".as_bytes());
+        assert_eq!(
+            &*OutputEncoding::Utf8Bom.encode("a"),
+            &[0xEF, 0xBB, 0xBF, b'a']
+        );
+        assert_eq!(
+            &*OutputEncoding::Utf16Le.encode("aё"),
+            &[0xFF, 0xFE, b'a', 0x00, 0x51, 0x04]
+        );
+    }
+
     #[test]
     fn test_get_weekday_mappings_ru() {
         let mappings = get_weekday_mappings("ru");
@@ -581,12 +1411,136 @@ mod tests {
         assert_eq!(mappings.as_slice(), RU_WEEKDAY_MAPPINGS);
     }
 
+    #[test]
+    fn mappings_for_content_follows_front_matter_then_script() {
+        let ru = get_weekday_mappings("ru,en");
+        assert_eq!(mappings_for_content(&ru, "## TODO Встреча\n"), ru);
+        assert!(mappings_for_content(&ru, "## TODO Meeting <2026-01-12 Mon>\n").is_empty());
+        assert_eq!(
+            mappings_for_content(&ru, "---\nlang: ru-RU\n---\n<2026-01-12 Mon>\n"),
+            ru
+        );
+        assert!(mappings_for_content(&ru, "---\nlanguage: \"en\"\n---\nПн\n").is_empty());
+        // An unclosed block is a thematic break, not front matter.
+        assert_eq!(mappings_for_content(&ru, "---\nlang: en\nПн\n"), ru);
+        assert!(mappings_for_content(&get_weekday_mappings("en"), "Пн").is_empty());
+    }
+
     #[test]
     fn test_get_weekday_mappings_empty() {
         let mappings = get_weekday_mappings("en");
         assert!(mappings.is_empty());
     }
 
+    #[test]
+    fn validate_months_bounds() {
+        assert_eq!(validate_months("1"), Ok(1));
+        assert_eq!(validate_months("120"), Ok(120));
+        assert!(validate_months("0").unwrap_err().contains("at least 1"));
+        assert!(validate_months("121").unwrap_err().contains("at most 120"));
+        assert!(validate_months("six")
+            .unwrap_err()
+            .contains("positive integer"));
+    }
+
+    #[test]
+    fn validate_repeat_horizon_bounds() {
+        assert_eq!(validate_repeat_horizon("1"), Ok(1));
+        assert_eq!(validate_repeat_horizon("1000"), Ok(1000));
+        assert!(validate_repeat_horizon("0")
+            .unwrap_err()
+            .contains("at least 1"));
+        assert!(validate_repeat_horizon("1001")
+            .unwrap_err()
+            .contains("at most 1000"));
+    }
+
+    #[test]
+    fn validate_heading_offset_bounds() {
+        assert_eq!(validate_heading_offset("-2"), Ok(-2));
+        assert_eq!(validate_heading_offset("5"), Ok(5));
+        assert!(validate_heading_offset("6").is_err());
+        assert!(validate_heading_offset("x").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "remote")]
+    fn validate_remote_url_requires_http_host() {
+        assert!(validate_remote_url("https://desktop.lan/agenda.json").is_ok());
+        assert!(validate_remote_url("http://10.0.0.2:8080").is_ok());
+        assert!(validate_remote_url("file:///tmp/a.json")
+            .unwrap_err()
+            .contains("http"));
+        assert!(validate_remote_url("https:///a.json")
+            .unwrap_err()
+            .contains("host"));
+    }
+
+    #[test]
+    #[cfg(not(feature = "remote"))]
+    fn validate_remote_url_needs_the_remote_feature() {
+        assert!(validate_remote_url("https://desktop.lan/agenda.json")
+            .unwrap_err()
+            .contains("--features remote"));
+        assert!(validate_remote_url("file:///tmp/a.json")
+            .unwrap_err()
+            .contains("http"));
+    }
+
+    #[test]
+    fn validate_work_hours_requires_ordered_range() {
+        let hours = validate_work_hours("08:30-17:00").unwrap();
+        assert_eq!(
+            hours.start,
+            chrono::NaiveTime::from_hms_opt(8, 30, 0).unwrap()
+        );
+        assert_eq!(
+            hours.end,
+            chrono::NaiveTime::from_hms_opt(17, 0, 0).unwrap()
+        );
+        assert!(validate_work_hours("18:00-09:00")
+            .unwrap_err()
+            .contains("after start"));
+        assert!(validate_work_hours("9-17").unwrap_err().contains("HH:MM"));
+    }
+
+    #[test]
+    fn validate_iso_week_resolves_to_its_monday() {
+        let day = |y, m, d| chrono::NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(validate_iso_week("2025-W14"), Ok(day(2025, 3, 31)));
+        // ISO week 1 of 2026 starts in December 2025.
+        assert_eq!(validate_iso_week("2026-W01"), Ok(day(2025, 12, 29)));
+        assert_eq!(
+            validate_iso_week("2025-W53").unwrap_err(),
+            "2025 has no ISO week 53"
+        );
+        assert!(validate_iso_week("2026-W53").is_ok());
+        for bad in ["2025-14", "2025W14", "W14", "2025-Wxx"] {
+            assert!(
+                validate_iso_week(bad).unwrap_err().contains("YYYY-Www"),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn validate_fiscal_year_start_bounds() {
+        use crate::agenda::FiscalYearStart;
+        assert_eq!(
+            validate_fiscal_year_start("04-06"),
+            Ok(FiscalYearStart { month: 4, day: 6 })
+        );
+        assert!(validate_fiscal_year_start("13-01")
+            .unwrap_err()
+            .contains("month"));
+        assert!(validate_fiscal_year_start("02-29")
+            .unwrap_err()
+            .contains("day"));
+        assert!(validate_fiscal_year_start("April")
+            .unwrap_err()
+            .contains("MM-DD"));
+    }
+
     #[test]
     fn validate_max_tasks_accepts_valid() {
         assert_eq!(validate_max_tasks("1"), Ok(1));
@@ -663,6 +1617,13 @@ mod tests {
         assert!(validate_timezone("UTC").is_ok());
     }
 
+    #[test]
+    fn validate_timezone_accepts_comma_separated_list() {
+        assert!(validate_timezone("Europe/Moscow,America/New_York").is_ok());
+        assert!(validate_timezone("Europe/Moscow,Not/A_Zone").is_err());
+        assert!(validate_timezone("Europe/Moscow,").is_err());
+    }
+
     #[test]
     fn validate_timezone_propagates_underlying_error_and_hint() {
         // The chrono-tz Display ("failed to parse timezone") must come through
@@ -869,4 +1830,52 @@ mod tests {
         // user clearly wrote something, so honour it.
         assert!(clicolor_force_active(Some(" ")));
     }
+
+    #[test]
+    fn with_block_replaces_the_options_a_block_sets() {
+        let cli = Cli::parse_from([
+            "markdown-org-extract",
+            "--current-date",
+            "2025-03-12",
+            "--match",
+            "work",
+        ]);
+        let block = BlockConfig {
+            title: "Next".to_string(),
+            agenda: Some("tasks".to_string()),
+            todo_state: Some(vec!["WAITING".to_string()]),
+            ..BlockConfig::default()
+        };
+        let next = cli.with_block(&block).unwrap();
+        assert_eq!(next.agenda_scope(), AgendaScope::Tasks);
+        assert_eq!(next.current_date, None);
+        assert_eq!(next.todo_state, vec!["WAITING".to_string()]);
+        assert_eq!(next.tag_match.unwrap().to_string(), "work");
+
+        let today = cli
+            .with_block(&BlockConfig {
+                title: "Today".to_string(),
+                ..BlockConfig::default()
+            })
+            .unwrap();
+        assert_eq!(today.agenda_scope(), AgendaScope::Day);
+        assert_eq!(today.current_date.as_deref(), Some("2025-03-12"));
+
+        let bad = |block: BlockConfig| cli.with_block(&block).err().unwrap();
+        assert!(bad(BlockConfig {
+            agenda: Some("dya".to_string()),
+            ..BlockConfig::default()
+        })
+        .starts_with("agenda: "));
+        assert!(bad(BlockConfig {
+            agenda: Some("search".to_string()),
+            ..BlockConfig::default()
+        })
+        .starts_with("query: "));
+        assert!(bad(BlockConfig {
+            todo_state: Some(vec!["next".to_string()]),
+            ..BlockConfig::default()
+        })
+        .starts_with("todo_state: "));
+    }
 }
@@ -1,11 +1,16 @@
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use regex::Regex;
 use std::sync::LazyLock;
 
 use crate::regex_limits::{compile_bounded, CLOCK_BODY_MAX};
-use crate::types::ClockEntry;
+use crate::types::{ClockEntry, ClockRow, DayAgenda, Task};
 
 /// Regex for CLOCK entries: `CLOCK: [timestamp]--[timestamp] => duration`
 ///
+/// The duration is `H:MM` (what org-mode writes) or `H:MM:SS` (what some
+/// second-precision exporters write); timestamps may carry seconds too since
+/// their bodies are captured verbatim.
+///
 /// Supports both square brackets (org-mode inactive timestamps) and angle
 /// brackets (active timestamps), but the opening and closing bracket of each
 /// timestamp must match — `[…>` or `<…]` are rejected as malformed. Inner
@@ -13,7 +18,7 @@ use crate::types::ClockEntry;
 /// malformed input.
 static CLOCK_RE: LazyLock<Regex> = LazyLock::new(|| {
     compile_bounded(&format!(
-        r"CLOCK:\s*(?:\[([^\]<>]{{1,{CLOCK_BODY_MAX}}})\]|<([^\]<>]{{1,{CLOCK_BODY_MAX}}})>)(?:--(?:\[([^\]<>]{{1,{CLOCK_BODY_MAX}}})\]|<([^\]<>]{{1,{CLOCK_BODY_MAX}}})>))?(?:\s*=>\s*([0-9]{{1,5}}:[0-9]{{1,2}}))?"
+        r"CLOCK:\s*(?:\[([^\]<>]{{1,{CLOCK_BODY_MAX}}})\]|<([^\]<>]{{1,{CLOCK_BODY_MAX}}})>)(?:--(?:\[([^\]<>]{{1,{CLOCK_BODY_MAX}}})\]|<([^\]<>]{{1,{CLOCK_BODY_MAX}}})>))?(?:\s*=>\s*([0-9]{{1,5}}:[0-9]{{1,2}}(?::[0-9]{{1,2}})?))?"
     ))
 });
 
@@ -26,7 +31,7 @@ static CLOCK_RE: LazyLock<Regex> = LazyLock::new(|| {
 ///   not yet been closed; yields `start` only with `end = None` and
 ///   `duration = None`.
 ///
-/// The duration tail (`=> HH:MM`) is optional even on closed clocks; org-mode
+/// The duration tail (`=> HH:MM` or `=> HH:MM:SS`) is optional even on closed clocks; org-mode
 /// inserts it automatically but does not require it for the line to parse.
 pub fn extract_clocks(text: &str) -> Vec<ClockEntry> {
     let clocks: Vec<ClockEntry> = CLOCK_RE
@@ -53,24 +58,28 @@ pub fn extract_clocks(text: &str) -> Vec<ClockEntry> {
     clocks
 }
 
-/// Calculate total time from clock entries (in minutes).
+/// Calculate total time from clock entries (in seconds).
+///
+/// Each entry contributes its [`entry_seconds`]: the explicit `=> H:MM`
+/// duration when present, otherwise the span between its start and end
+/// timestamps. Hand-written clock lines often omit the `=>` total.
 ///
-/// Returns `None` when no entry has a parseable `duration` (e.g. only open
-/// clocks were present, or the input slice is empty) — there is nothing to
-/// report. Returns `Some(total)` when at least one entry contributed a
-/// duration, even if the sum is zero: a legitimate `0:00` CLOCK must be
+/// Returns `None` when no entry yields a duration (e.g. only open clocks
+/// were present, or the input slice is empty) — there is nothing to report.
+/// Returns `Some(total)` when at least one entry contributed a duration,
+/// even if the sum is zero: a legitimate `0:00` CLOCK must be
 /// distinguishable from "no duration recorded" in the output.
 ///
-/// Returns `None` on arithmetic overflow; `checked_add` prevents wrap.
-pub fn calculate_total_minutes(clocks: &[ClockEntry]) -> Option<u32> {
-    let mut total = 0u32;
+/// The total is a `u64`: entries are `u32` seconds each, so no realistic
+/// number of them can overflow it, where a `u32` sum would wrap after about
+/// 136 years of clocked time.
+pub fn calculate_total_seconds(clocks: &[ClockEntry]) -> Option<u64> {
+    let mut total = 0u64;
     let mut saw_duration = false;
     for clock in clocks {
-        if let Some(ref dur) = clock.duration {
-            if let Some(mins) = parse_duration(dur) {
-                total = total.checked_add(mins)?;
-                saw_duration = true;
-            }
+        if let Some(secs) = entry_seconds(clock) {
+            total += u64::from(secs);
+            saw_duration = true;
         }
     }
     if saw_duration {
@@ -80,9 +89,117 @@ pub fn calculate_total_minutes(clocks: &[ClockEntry]) -> Option<u32> {
     }
 }
 
-/// Format minutes as HH:MM
-pub fn format_duration(minutes: u32) -> String {
-    format!("{}:{:02}", minutes / 60, minutes % 60)
+/// Duration of one clock entry in seconds.
+///
+/// An explicit `duration` wins, even when it disagrees with the timestamps:
+/// org-mode users adjust the total by hand and the written value is what
+/// they report. Without one, a closed entry's duration is `end - start`.
+/// Open entries, unparseable timestamps, and intervals whose end precedes
+/// their start yield `None`.
+pub fn entry_seconds(clock: &ClockEntry) -> Option<u32> {
+    if let Some(ref dur) = clock.duration {
+        return parse_duration(dur);
+    }
+    let start = parse_clock_timestamp(&clock.start)?;
+    let end = parse_clock_timestamp(clock.end.as_deref()?)?;
+    let secs = (end - start).num_seconds();
+    if secs < 0 || secs > i64::from(MAX_DURATION_HOURS) * 3600 {
+        tracing::trace!(start = %clock.start, end = ?clock.end, "clock interval out of range");
+        return None;
+    }
+    u32::try_from(secs).ok()
+}
+
+/// Parse the body of a clock timestamp (`2024-12-09 Mon 10:00`, optionally
+/// with seconds) into a date-time. The weekday token is ignored, so
+/// localized weekday names do not matter. A timestamp without a time of day
+/// is not a usable clock endpoint and yields `None`.
+pub(crate) fn parse_clock_timestamp(body: &str) -> Option<NaiveDateTime> {
+    let mut tokens = body.split_whitespace();
+    let date = NaiveDate::parse_from_str(tokens.next()?, "%Y-%m-%d").ok()?;
+    let time = tokens.find_map(|t| {
+        NaiveTime::parse_from_str(t, "%H:%M:%S")
+            .or_else(|_| NaiveTime::parse_from_str(t, "%H:%M"))
+            .ok()
+    })?;
+    Some(date.and_time(time))
+}
+
+/// Format seconds as `H:MM`, or `H:MM:SS` when the value is not a whole
+/// number of minutes. Minute-precision input therefore keeps the org-mode
+/// form it was written in.
+pub fn format_duration(seconds: u64) -> String {
+    let (hours, rest) = (seconds / 3600, seconds % 3600);
+    let (mins, secs) = (rest / 60, rest % 60);
+    if secs == 0 {
+        format!("{hours}:{mins:02}")
+    } else {
+        format!("{hours}:{mins:02}:{secs:02}")
+    }
+}
+
+/// Restrict every task's clocks to entries whose start date falls inside
+/// `[from, to]` (either edge optional, both inclusive) and recompute
+/// `total_clock_time` from what remains. A task left with no clocks in the
+/// window loses both fields, exactly as if it had never been clocked.
+/// Entries whose start timestamp has no parseable date are dropped: they
+/// cannot be placed inside any window.
+pub fn apply_clock_window(tasks: &mut [Task], from: Option<NaiveDate>, to: Option<NaiveDate>) {
+    for task in tasks {
+        let Some(clocks) = task.clocks.take() else {
+            continue;
+        };
+        let kept: Vec<ClockEntry> = clocks
+            .into_iter()
+            .filter(|c| {
+                clock_start_date(&c.start)
+                    .is_some_and(|d| from.is_none_or(|f| d >= f) && to.is_none_or(|t| d <= t))
+            })
+            .collect();
+        task.total_clock_time = calculate_total_seconds(&kept).map(format_duration);
+        task.clocks = if kept.is_empty() { None } else { Some(kept) };
+    }
+}
+
+/// Flatten every task's clocks into [`ClockRow`]s, in task order then clock
+/// order as written in the file.
+pub fn clock_rows(tasks: &[Task]) -> Vec<ClockRow> {
+    tasks
+        .iter()
+        .flat_map(|task| {
+            task.clocks.iter().flatten().map(move |c| ClockRow {
+                file: task.file.to_string(),
+                line: task.line,
+                heading: task.heading.clone(),
+                start: c.start.clone(),
+                end: c.end.clone(),
+                minutes: entry_seconds(c).map(|s| (f64::from(s) / 60.0 * 100.0).round() / 100.0),
+                tags: task.tags.clone().unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+/// `--log`: list each of `rows` on the day its clock started, ordered by
+/// start time. Rows starting outside `days` are dropped.
+pub fn log_clocks(days: &mut [DayAgenda], rows: Vec<ClockRow>) {
+    for row in rows {
+        let Some(date) = clock_start_date(&row.start) else {
+            continue;
+        };
+        let date = date.format("%Y-%m-%d").to_string();
+        if let Some(day) = days.iter_mut().find(|day| day.date == date) {
+            day.clocked.push(row);
+        }
+    }
+    for day in days {
+        day.clocked.sort_by(|a, b| a.start.cmp(&b.start));
+    }
+}
+
+/// Date part of a clock timestamp body (`2024-12-09 Mon 10:00` → 2024-12-09).
+fn clock_start_date(body: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(body.split_whitespace().next()?, "%Y-%m-%d").ok()
 }
 
 /// Org-mode duration upper bound. CLOCK durations beyond this are treated as
@@ -90,16 +207,24 @@ pub fn format_duration(minutes: u32) -> String {
 /// from a parser bug or hostile input.
 const MAX_DURATION_HOURS: u32 = 10_000;
 
-/// Parse duration string like "2:05" to minutes. Returns None for malformed
-/// strings, out-of-range values, or arithmetic overflow.
-fn parse_duration(s: &str) -> Option<u32> {
-    let (h_str, m_str) = s.split_once(':')?;
-    let hours: u32 = h_str.parse().ok()?;
-    let mins: u32 = m_str.parse().ok()?;
-    if hours > MAX_DURATION_HOURS || mins >= 60 {
+/// Parse a duration string like `2:05` or `1:30:45` to seconds. Returns None
+/// for malformed strings, out-of-range values, or arithmetic overflow. Also
+/// used for the `Effort` property, which shares the syntax.
+pub(crate) fn parse_duration(s: &str) -> Option<u32> {
+    let mut parts = s.split(':');
+    let hours: u32 = parts.next()?.parse().ok()?;
+    let mins: u32 = parts.next()?.parse().ok()?;
+    let secs: u32 = match parts.next() {
+        Some(p) => p.parse().ok()?,
+        None => 0,
+    };
+    if parts.next().is_some() || hours > MAX_DURATION_HOURS || mins >= 60 || secs >= 60 {
         return None;
     }
-    hours.checked_mul(60)?.checked_add(mins)
+    hours
+        .checked_mul(3600)?
+        .checked_add(mins * 60)?
+        .checked_add(secs)
 }
 
 #[cfg(test)]
@@ -194,16 +319,18 @@ mod tests {
                 duration: Some("1:30".to_string()),
             },
         ];
-        let total = calculate_total_minutes(&clocks);
-        assert_eq!(total, Some(215)); // 125 + 90
-        assert_eq!(format_duration(215), "3:35");
+        let total = calculate_total_seconds(&clocks);
+        assert_eq!(total, Some(215 * 60)); // 125 + 90 minutes
+        assert_eq!(format_duration(215 * 60), "3:35");
     }
 
     #[test]
     fn test_parse_duration() {
-        assert_eq!(parse_duration("2:05"), Some(125));
-        assert_eq!(parse_duration("0:30"), Some(30));
-        assert_eq!(parse_duration("10:00"), Some(600));
+        assert_eq!(parse_duration("2:05"), Some(125 * 60));
+        assert_eq!(parse_duration("0:30"), Some(30 * 60));
+        assert_eq!(parse_duration("10:00"), Some(600 * 60));
+        assert_eq!(parse_duration("1:30:45"), Some(5445));
+        assert_eq!(parse_duration("0:00:07"), Some(7));
     }
 
     #[test]
@@ -213,7 +340,9 @@ mod tests {
         assert_eq!(parse_duration("1:60"), None, "minutes must be < 60");
         assert_eq!(parse_duration("1:99"), None);
         assert_eq!(parse_duration("99999:00"), None, "hours capped");
-        assert_eq!(parse_duration("1:2:3"), None, "single colon only");
+        assert_eq!(parse_duration("1:30:60"), None, "seconds must be < 60");
+        assert_eq!(parse_duration("1:2:3:4"), None, "at most H:MM:SS");
+        assert_eq!(parse_duration("1:30:"), None);
     }
 
     #[test]
@@ -241,7 +370,7 @@ mod tests {
     }
 
     #[test]
-    fn calculate_total_minutes_returns_some_zero_for_zero_duration() {
+    fn calculate_total_seconds_returns_some_zero_for_zero_duration() {
         // A closed clock that legitimately recorded 0:00 should be reported as
         // Some(0), not swallowed into None. Otherwise a CLOCK with an actual
         // duration field is indistinguishable from a missing/open clock.
@@ -250,22 +379,22 @@ mod tests {
             end: Some("2024-01-01 Mon 10:00".to_string()),
             duration: Some("0:00".to_string()),
         }];
-        assert_eq!(calculate_total_minutes(&clocks), Some(0));
+        assert_eq!(calculate_total_seconds(&clocks), Some(0));
     }
 
     #[test]
-    fn calculate_total_minutes_returns_none_for_only_open_clocks() {
+    fn calculate_total_seconds_returns_none_for_only_open_clocks() {
         // No duration fields anywhere -> nothing to sum -> None is correct.
         let clocks = vec![ClockEntry {
             start: "2024-01-01 Mon 10:00".to_string(),
             end: None,
             duration: None,
         }];
-        assert_eq!(calculate_total_minutes(&clocks), None);
+        assert_eq!(calculate_total_seconds(&clocks), None);
     }
 
     #[test]
-    fn calculate_total_minutes_mixes_open_and_zero_clocks() {
+    fn calculate_total_seconds_mixes_open_and_zero_clocks() {
         // One open clock (ignored) + one closed 0:00 -> Some(0), proving the
         // 0:00 entry is what surfaces in the result, not the open one.
         let clocks = vec![
@@ -280,27 +409,192 @@ mod tests {
                 duration: Some("0:00".to_string()),
             },
         ];
-        assert_eq!(calculate_total_minutes(&clocks), Some(0));
+        assert_eq!(calculate_total_seconds(&clocks), Some(0));
     }
 
     #[test]
     fn test_calculate_total_overflow_protected() {
-        // Even a very large but in-range duration shouldn't wrap. Two valid maxima
-        // sum below u32::MAX, but if MAX_DURATION_HOURS were larger we'd want this
-        // to return Some(_) without panic.
+        // Even a very large but in-range duration shouldn't wrap: 200
+        // maxima sum past u32::MAX.
+        let max = ClockEntry {
+            start: "x".to_string(),
+            end: Some("y".to_string()),
+            duration: Some("9999:59".to_string()),
+        };
+        let clocks = vec![max; 200];
+        let total = calculate_total_seconds(&clocks).unwrap();
+        assert!(total > u64::from(u32::MAX));
+        assert_eq!(total, (9999 * 3600 + 59 * 60) * 200);
+    }
+
+    #[test]
+    fn extract_second_precision_clock() {
+        let text = "CLOCK: [2023-02-19 Sun 21:30:15]--[2023-02-19 Sun 23:01:00] =>  1:30:45";
+        let clocks = extract_clocks(text);
+        assert_eq!(clocks.len(), 1);
+        assert_eq!(clocks[0].start, "2023-02-19 Sun 21:30:15");
+        assert_eq!(clocks[0].end.as_deref(), Some("2023-02-19 Sun 23:01:00"));
+        assert_eq!(clocks[0].duration.as_deref(), Some("1:30:45"));
+        assert_eq!(calculate_total_seconds(&clocks), Some(5445));
+    }
+
+    #[test]
+    fn format_duration_shows_seconds_only_when_present() {
+        assert_eq!(format_duration(0), "0:00");
+        assert_eq!(format_duration(90 * 60), "1:30");
+        assert_eq!(format_duration(5445), "1:30:45");
+        assert_eq!(format_duration(7), "0:00:07");
+    }
+
+    #[test]
+    fn mixed_precision_totals_keep_seconds() {
         let clocks = vec![
             ClockEntry {
-                start: "x".to_string(),
-                end: Some("y".to_string()),
-                duration: Some("9999:59".to_string()),
+                start: "a".to_string(),
+                end: Some("b".to_string()),
+                duration: Some("0:30".to_string()),
             },
             ClockEntry {
-                start: "x".to_string(),
-                end: Some("y".to_string()),
-                duration: Some("9999:59".to_string()),
+                start: "c".to_string(),
+                end: Some("d".to_string()),
+                duration: Some("0:10:30".to_string()),
             },
         ];
-        let total = calculate_total_minutes(&clocks).unwrap();
-        assert_eq!(total, (9999 * 60 + 59) * 2);
+        let total = calculate_total_seconds(&clocks).unwrap();
+        assert_eq!(format_duration(total), "0:40:30");
+    }
+
+    #[test]
+    fn closed_clock_without_duration_uses_timestamps() {
+        let clocks = extract_clocks("CLOCK: [2024-12-09 Mon 10:00]--[2024-12-09 Mon 12:30]");
+        assert_eq!(clocks[0].duration, None);
+        assert_eq!(calculate_total_seconds(&clocks), Some(150 * 60));
+    }
+
+    #[test]
+    fn computed_duration_spans_midnight_and_seconds() {
+        let clock = ClockEntry {
+            start: "2024-12-09 Mon 23:30:10".to_string(),
+            end: Some("2024-12-10 Tue 00:15:20".to_string()),
+            duration: None,
+        };
+        assert_eq!(entry_seconds(&clock), Some(45 * 60 + 10));
+    }
+
+    #[test]
+    fn explicit_duration_wins_over_timestamps() {
+        let clock = ClockEntry {
+            start: "2024-12-09 Mon 10:00".to_string(),
+            end: Some("2024-12-09 Mon 12:30".to_string()),
+            duration: Some("2:00".to_string()),
+        };
+        assert_eq!(entry_seconds(&clock), Some(120 * 60));
+    }
+
+    #[test]
+    fn computed_duration_rejects_reversed_or_unparseable_intervals() {
+        let reversed = ClockEntry {
+            start: "2024-12-09 Mon 12:00".to_string(),
+            end: Some("2024-12-09 Mon 10:00".to_string()),
+            duration: None,
+        };
+        assert_eq!(entry_seconds(&reversed), None);
+        let no_time = ClockEntry {
+            start: "2024-12-09 Mon".to_string(),
+            end: Some("2024-12-10 Tue".to_string()),
+            duration: None,
+        };
+        assert_eq!(entry_seconds(&no_time), None);
+        let open = ClockEntry {
+            start: "2024-12-09 Mon 10:00".to_string(),
+            end: None,
+            duration: None,
+        };
+        assert_eq!(entry_seconds(&open), None);
+        assert_eq!(calculate_total_seconds(&[reversed, no_time, open]), None);
+    }
+
+    fn clocked_task(starts: &[&str]) -> Task {
+        let clocks: Vec<ClockEntry> = starts
+            .iter()
+            .map(|d| ClockEntry {
+                start: format!("{d} 10:00"),
+                end: Some(format!("{d} 11:00")),
+                duration: Some("1:00".to_string()),
+            })
+            .collect();
+        Task {
+            file: "t.md".into(),
+            line: 1,
+            heading: "Long task".to_string(),
+            total_clock_time: calculate_total_seconds(&clocks).map(format_duration),
+            clocks: Some(clocks),
+            ..Task::default()
+        }
+    }
+
+    fn d(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn clock_window_keeps_only_entries_inside_range() {
+        let mut tasks = vec![clocked_task(&["2024-12-01", "2024-12-09", "2024-12-15"])];
+        apply_clock_window(&mut tasks, Some(d("2024-12-09")), Some(d("2024-12-15")));
+        assert_eq!(tasks[0].clocks.as_ref().unwrap().len(), 2);
+        assert_eq!(tasks[0].total_clock_time.as_deref(), Some("2:00"));
+    }
+
+    #[test]
+    fn clock_window_single_edge_is_open_ended() {
+        let mut tasks = vec![clocked_task(&["2024-12-01", "2024-12-09"])];
+        apply_clock_window(&mut tasks, None, Some(d("2024-12-05")));
+        assert_eq!(tasks[0].total_clock_time.as_deref(), Some("1:00"));
+    }
+
+    #[test]
+    fn clock_window_clears_fields_when_nothing_remains() {
+        let mut tasks = vec![clocked_task(&["2024-12-01"])];
+        apply_clock_window(&mut tasks, Some(d("2025-01-01")), None);
+        assert!(tasks[0].clocks.is_none());
+        assert!(tasks[0].total_clock_time.is_none());
+    }
+
+    #[test]
+    fn log_clocks_lists_rows_on_their_start_day_by_time() {
+        let mut late = clocked_task(&["2024-12-02"]);
+        late.clocks.as_mut().unwrap()[0].start = "2024-12-02 15:00".to_string();
+        let tasks = vec![late, clocked_task(&["2024-12-02", "2024-12-09"])];
+        let mut days = vec![
+            DayAgenda::new(d("2024-12-01")),
+            DayAgenda::new(d("2024-12-02")),
+        ];
+        log_clocks(&mut days, clock_rows(&tasks));
+        assert!(days[0].clocked.is_empty());
+        let starts: Vec<_> = days[1].clocked.iter().map(|r| r.start.as_str()).collect();
+        assert_eq!(starts, ["2024-12-02 10:00", "2024-12-02 15:00"]);
+    }
+
+    #[test]
+    fn clock_rows_flatten_in_task_order_with_fractional_minutes() {
+        let mut second = clocked_task(&["2024-12-10"]);
+        second.heading = "Other".to_string();
+        second.clocks.as_mut().unwrap()[0].duration = Some("0:10:30".to_string());
+        let mut open = clocked_task(&["2024-12-11"]);
+        open.clocks.as_mut().unwrap()[0].end = None;
+        open.clocks.as_mut().unwrap()[0].duration = None;
+        open.tags = Some(vec!["work".to_string(), "billing".to_string()]);
+        let tasks = vec![clocked_task(&["2024-12-01", "2024-12-02"]), second, open];
+
+        let rows = clock_rows(&tasks);
+        assert_eq!(rows.len(), 4);
+        assert!(rows[0].tags.is_empty());
+        assert_eq!(rows[3].tags, ["work", "billing"]);
+        assert_eq!(rows[0].start, "2024-12-01 10:00");
+        assert_eq!(rows[0].minutes, Some(60.0));
+        assert_eq!(rows[2].heading, "Other");
+        assert_eq!(rows[2].minutes, Some(10.5));
+        assert_eq!(rows[3].end, None);
+        assert_eq!(rows[3].minutes, None);
     }
 }
@@ -0,0 +1,363 @@
+//! `--monorepo`: per-directory `.mdextract.toml` overrides.
+//!
+//! A `.mdextract.toml` applies to its directory and everything below it, so
+//! each team of a monorepo can tune extraction for its own subtree. A key the
+//! file leaves out is inherited from the nearest ancestor's file, up to the
+//! `--dir` root, and the command line applies where no file sets it. A file
+//! that sets a key replaces the inherited value rather than adding to it.
+//!
+//! ```toml
+//! glob = "*.org.md"     # file pattern for this subtree, instead of --glob
+//! category = "backend"  # `category` of every task found here without a CATEGORY property
+//! tags = ["team-b"]     # `tags` of every task found here
+//! todo_keywords = ["NEXT"]     # in place of --todo-keywords
+//! done_keywords = ["SHIPPED"]  # in place of --done-keywords
+//! ```
+//!
+//! The file in the `--dir` root may also define custom agendas for
+//! `--custom-agenda NAME`, with or without `--monorepo`: a list of blocks,
+//! each one view with its own filters, printed one after another.
+//!
+//! ```toml
+//! [[agendas.morning]]
+//! title = "Today"
+//! agenda = "day"
+//!
+//! [[agendas.morning]]
+//! title = "Next actions"
+//! agenda = "tasks"
+//! todo_state = ["NEXT"]
+//! group_by = "tag"
+//! ```
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::Deserialize;
+
+use crate::error::AppError;
+use crate::types::{Task, TodoKeywords};
+
+/// Name of the per-directory configuration file.
+pub const CONFIG_FILE_NAME: &str = ".mdextract.toml";
+
+/// Keys of one `.mdextract.toml`. Unknown keys are rejected, so a typo does
+/// not silently fall back to the inherited value.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DirConfig {
+    /// File pattern for the subtree in place of `--glob`, matched relative
+    /// to the directory holding the file.
+    pub glob: Option<String>,
+    /// Tags given to every task of the subtree.
+    pub tags: Option<Vec<String>>,
+    /// Category given to every task of the subtree that has no `CATEGORY`
+    /// property of its own.
+    pub category: Option<String>,
+    /// Custom open keywords for the subtree in place of `--todo-keywords`.
+    pub todo_keywords: Option<Vec<String>>,
+    /// Custom done keywords for the subtree in place of `--done-keywords`.
+    pub done_keywords: Option<Vec<String>>,
+    /// Custom agendas by name, each a list of blocks. Only read from the
+    /// file in the `--dir` root.
+    #[serde(default)]
+    pub agendas: BTreeMap<String, Vec<BlockConfig>>,
+}
+
+/// One block of a custom agenda: a view and the filters that narrow it.
+/// Each key takes the value of the command-line option of the same name
+/// (`match` for `--match`) and replaces it for this block; a key left out
+/// keeps the command line's value. The values are checked as the options
+/// are, when the agenda is built.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BlockConfig {
+    /// Heading of the block in the output.
+    pub title: String,
+    /// `--agenda` mode of the block.
+    pub agenda: Option<String>,
+    pub date: Option<String>,
+    pub days: Option<u32>,
+    #[serde(rename = "match")]
+    pub tag_match: Option<String>,
+    pub todo_state: Option<Vec<String>>,
+    pub min_priority: Option<String>,
+    pub assignee: Option<String>,
+    pub query: Option<String>,
+    pub regex: Option<bool>,
+    pub group_by: Option<String>,
+}
+
+impl DirConfig {
+    /// Parse the text of a `.mdextract.toml`.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let config: Self =
+            toml::from_str(text).map_err(|e| e.to_string().trim_end().to_string())?;
+        for keyword in config
+            .todo_keywords
+            .iter()
+            .chain(&config.done_keywords)
+            .flatten()
+        {
+            TodoKeywords::validate(keyword)?;
+        }
+        TodoKeywords::new(
+            config.todo_keywords.as_deref().unwrap_or_default(),
+            config.done_keywords.as_deref().unwrap_or_default(),
+        )?;
+        Ok(config)
+    }
+}
+
+/// Settings in force for one directory: its own file layered over its
+/// parent's scope.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Scope {
+    /// Glob pattern with the directory it is relative to.
+    pub glob: Option<(String, PathBuf)>,
+    pub tags: Option<Vec<String>>,
+    pub category: Option<String>,
+    pub todo_keywords: Option<Vec<String>>,
+    pub done_keywords: Option<Vec<String>>,
+}
+
+impl Scope {
+    fn layered(&self, config: DirConfig, dir: &Path) -> Self {
+        Self {
+            glob: config
+                .glob
+                .map(|glob| (glob, dir.to_path_buf()))
+                .or_else(|| self.glob.clone()),
+            tags: config.tags.or_else(|| self.tags.clone()),
+            category: config.category.or_else(|| self.category.clone()),
+            todo_keywords: config.todo_keywords.or_else(|| self.todo_keywords.clone()),
+            done_keywords: config.done_keywords.or_else(|| self.done_keywords.clone()),
+        }
+    }
+
+    /// Keywords of the subtree: `base` (the command line) with the keys the
+    /// scope sets replaced. Fails when a file's list clashes with the one it
+    /// inherits.
+    pub fn keywords<'a>(&self, base: &'a TodoKeywords) -> Result<Cow<'a, TodoKeywords>, String> {
+        if self.todo_keywords.is_none() && self.done_keywords.is_none() {
+            return Ok(Cow::Borrowed(base));
+        }
+        TodoKeywords::new(
+            self.todo_keywords.as_ref().unwrap_or(&base.todo),
+            self.done_keywords.as_ref().unwrap_or(&base.done),
+        )
+        .map(Cow::Owned)
+    }
+
+    /// Add the scope's tags after the task's own, and give it the scope's
+    /// category unless the task sets one itself.
+    pub fn apply(&self, task: &mut Task) {
+        if task.category.is_none() {
+            task.category.clone_from(&self.category);
+        }
+        for tag in self.tags.iter().flatten() {
+            let tags = task.tags.get_or_insert_with(Vec::new);
+            if !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+    }
+}
+
+/// Scopes of the directories seen so far, each file read once per run.
+#[derive(Debug, Default)]
+pub struct ConfigTree {
+    scopes: HashMap<PathBuf, Arc<Scope>>,
+}
+
+impl ConfigTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Scope of `dir`, a directory at or below `root`. Files above `root`
+    /// are not consulted.
+    pub fn scope(&mut self, root: &Path, dir: &Path) -> Result<Arc<Scope>, AppError> {
+        if let Some(scope) = self.scopes.get(dir) {
+            return Ok(Arc::clone(scope));
+        }
+        let parent = match dir.parent() {
+            Some(parent) if dir != root && parent.starts_with(root) => self.scope(root, parent)?,
+            _ => Arc::new(Scope::default()),
+        };
+        let scope = match read_config(dir)? {
+            Some(config) => Arc::new(parent.layered(config, dir)),
+            None => parent,
+        };
+        self.scopes.insert(dir.to_path_buf(), Arc::clone(&scope));
+        Ok(scope)
+    }
+}
+
+/// Blocks of the custom agenda `name`, defined in the `.mdextract.toml` of
+/// `root`.
+pub fn custom_agenda(root: &Path, name: &str) -> Result<Vec<BlockConfig>, AppError> {
+    let path = root.join(CONFIG_FILE_NAME);
+    let blocks = read_config(root)?
+        .and_then(|mut config| config.agendas.remove(name))
+        .ok_or_else(|| {
+            AppError::InvalidConfig(format!("{}: no agenda '{name}'", path.display()))
+        })?;
+    if blocks.is_empty() {
+        return Err(AppError::InvalidConfig(format!(
+            "{}: agenda '{name}' has no blocks",
+            path.display()
+        )));
+    }
+    Ok(blocks)
+}
+
+/// The `.mdextract.toml` of `dir`, if it has one.
+fn read_config(dir: &Path) -> Result<Option<DirConfig>, AppError> {
+    let path = dir.join(CONFIG_FILE_NAME);
+    // Like the walker, do not follow a symlinked config out of the tree.
+    match std::fs::symlink_metadata(&path) {
+        Ok(meta) if meta.is_file() => {}
+        Ok(_) => return Ok(None),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(AppError::io(path.display().to_string(), e)),
+    }
+    let text =
+        std::fs::read_to_string(&path).map_err(|e| AppError::io(path.display().to_string(), e))?;
+    DirConfig::parse(&text)
+        .map(Some)
+        .map_err(|e| AppError::InvalidConfig(format!("{}: {e}", path.display())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn parse_accepts_known_keys_and_rejects_others() {
+        let config = DirConfig::parse("glob = \"*.txt\"\ntags = [\"a\", \"b\"]\n").unwrap();
+        assert_eq!(config.glob.as_deref(), Some("*.txt"));
+        assert_eq!(config.tags, Some(vec!["a".to_string(), "b".to_string()]));
+        assert_eq!(config.category, None);
+        assert!(DirConfig::parse("glbo = \"*.txt\"")
+            .unwrap_err()
+            .contains("glbo"));
+        assert!(DirConfig::parse("tags = \"a\"").is_err());
+        assert!(DirConfig::parse("todo_keywords = [\"next\"]")
+            .unwrap_err()
+            .contains("next"));
+        assert!(DirConfig::parse("todo_keywords = [\"NEXT\", \"WAITING\"]").is_ok());
+        assert!(
+            DirConfig::parse("todo_keywords = [\"HOLD\"]\ndone_keywords = [\"HOLD\"]")
+                .unwrap_err()
+                .contains("HOLD")
+        );
+    }
+
+    #[test]
+    fn nearest_file_overrides_each_key_and_inherits_the_rest() {
+        let root = tempfile::tempdir().unwrap();
+        let team = root.path().join("team");
+        let deep = team.join("notes/deep");
+        fs::create_dir_all(&deep).unwrap();
+        fs::write(
+            root.path().join(CONFIG_FILE_NAME),
+            "category = \"all\"\ntags = [\"org\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            team.join(CONFIG_FILE_NAME),
+            "glob = \"*.txt\"\ntags = [\"team\"]\n",
+        )
+        .unwrap();
+
+        let mut tree = ConfigTree::new();
+        let top = tree.scope(root.path(), root.path()).unwrap();
+        assert_eq!(top.glob, None);
+        assert_eq!(top.category.as_deref(), Some("all"));
+
+        let scope = tree.scope(root.path(), &deep).unwrap();
+        assert_eq!(scope.glob, Some(("*.txt".to_string(), team.clone())));
+        assert_eq!(scope.tags, Some(vec!["team".to_string()]));
+        assert_eq!(scope.category.as_deref(), Some("all"));
+
+        // Files above the root are not consulted.
+        let scope = ConfigTree::new().scope(&team.join("notes"), &deep).unwrap();
+        assert_eq!(*scope, Scope::default());
+    }
+
+    #[test]
+    fn apply_keeps_a_category_property() {
+        let scope = Scope {
+            category: Some("backend".to_string()),
+            ..Scope::default()
+        };
+        let mut tasks = crate::parse_file(
+            "### TODO Own\n```org-properties\nCATEGORY: infra\n```\n\n### TODO Plain\n",
+        );
+        tasks.iter_mut().for_each(|task| scope.apply(task));
+        assert_eq!(tasks[0].category.as_deref(), Some("infra"));
+        assert_eq!(tasks[1].category.as_deref(), Some("backend"));
+    }
+
+    #[test]
+    fn apply_adds_scope_tags_after_heading_tags() {
+        let scope = Scope {
+            tags: Some(vec!["team".to_string(), "work".to_string()]),
+            ..Scope::default()
+        };
+        let mut tasks = crate::parse_file("### TODO Own :work:\n\n### TODO Plain\n");
+        tasks.iter_mut().for_each(|task| scope.apply(task));
+        assert_eq!(
+            tasks[0].tags,
+            Some(vec!["work".to_string(), "team".to_string()])
+        );
+        assert_eq!(
+            tasks[1].tags,
+            Some(vec!["team".to_string(), "work".to_string()])
+        );
+    }
+
+    #[test]
+    fn custom_agenda_reads_blocks_from_the_root_file() {
+        let root = tempfile::tempdir().unwrap();
+        fs::write(
+            root.path().join(CONFIG_FILE_NAME),
+            "[[agendas.review]]\ntitle = \"Today\"\n\n\
+             [[agendas.review]]\ntitle = \"Next\"\nagenda = \"tasks\"\n\
+             match = \"work\"\ntodo_state = [\"NEXT\"]\n",
+        )
+        .unwrap();
+        let blocks = custom_agenda(root.path(), "review").unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].title, "Today");
+        assert_eq!(blocks[0].agenda, None);
+        assert_eq!(blocks[1].agenda.as_deref(), Some("tasks"));
+        assert_eq!(blocks[1].tag_match.as_deref(), Some("work"));
+        assert_eq!(blocks[1].todo_state, Some(vec!["NEXT".to_string()]));
+
+        let err = custom_agenda(root.path(), "weekly").unwrap_err();
+        assert!(
+            matches!(err, AppError::InvalidConfig(ref msg) if msg.contains("no agenda 'weekly'"))
+        );
+        assert!(
+            DirConfig::parse("[[agendas.x]]\ntitle = \"A\"\nsort = \"file\"\n")
+                .unwrap_err()
+                .contains("sort")
+        );
+    }
+
+    #[test]
+    fn malformed_file_is_a_config_error() {
+        let root = tempfile::tempdir().unwrap();
+        fs::write(root.path().join(CONFIG_FILE_NAME), "glob = ").unwrap();
+        let err = ConfigTree::new()
+            .scope(root.path(), root.path())
+            .unwrap_err();
+        assert!(matches!(err, AppError::InvalidConfig(ref msg) if msg.contains(CONFIG_FILE_NAME)));
+        assert_eq!(err.exit_code(), 2);
+    }
+}
@@ -0,0 +1,158 @@
+//! `--dedup`: drop tasks duplicated across scanned roots.
+//!
+//! Two tasks are duplicates when they come from the same relative path under
+//! two different roots (`--dir` or `--remote` sources) and every output
+//! field except `file` is equal, which is what a synced copy of the same
+//! vault (Syncthing, a second checkout) produces. Tasks that differ in any
+//! field — one copy already marked DONE, a rescheduled date — are a real
+//! conflict and both are kept, as are identical tasks in two files of one
+//! root.
+
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+use crate::types::Task;
+
+/// Where a task was read from.
+#[derive(Debug, Clone, Default)]
+pub struct Origin {
+    /// Index of the scanned root: a `--dir`, then each `--remote` source.
+    pub root: usize,
+    /// Path of the file relative to that root.
+    pub path: String,
+    /// Modification time of the file, when known.
+    pub mtime: Option<SystemTime>,
+}
+
+/// Every serialized field of `task` except `file`, compared byte for byte.
+fn content(task: &mut Task) -> Vec<u8> {
+    let file = std::mem::take(&mut task.file);
+    // Serializing a `Task` into a `Vec` cannot fail.
+    let bytes = serde_json::to_vec(task).unwrap_or_default();
+    task.file = file;
+    bytes
+}
+
+/// Collapse duplicates to one task each, at the position of the first copy.
+/// `origins[i]` says where `tasks[i]` came from; a task without an origin
+/// (an imported calendar event) is always kept. With `prefer_newest` the
+/// copy from the most recently modified file wins, ties going to the
+/// earlier one; otherwise the first copy is kept. Returns the kept tasks and
+/// the number dropped.
+pub fn dedup_tasks(
+    tasks: Vec<Task>,
+    origins: &[Origin],
+    prefer_newest: bool,
+) -> (Vec<Task>, usize) {
+    // Each kept task with its origin, and the roots its dropped copies came from.
+    let mut kept: Vec<(Task, Option<&Origin>, Vec<usize>)> = Vec::with_capacity(tasks.len());
+    let mut seen: HashMap<(&str, Vec<u8>), usize> = HashMap::new();
+    let mut dropped = 0;
+
+    for (i, mut task) in tasks.into_iter().enumerate() {
+        let Some(origin) = origins.get(i) else {
+            kept.push((task, None, Vec::new()));
+            continue;
+        };
+        let key = (origin.path.as_str(), content(&mut task));
+        match seen.get(&key) {
+            Some(&slot) if !kept[slot].2.contains(&origin.root) => {
+                dropped += 1;
+                let (first, first_origin, roots) = &mut kept[slot];
+                roots.push(origin.root);
+                if prefer_newest && origin.mtime > first_origin.and_then(|o| o.mtime) {
+                    *first = task;
+                    *first_origin = Some(origin);
+                }
+            }
+            _ => {
+                seen.insert(key, kept.len());
+                kept.push((task, Some(origin), vec![origin.root]));
+            }
+        }
+    }
+
+    (kept.into_iter().map(|(task, ..)| task).collect(), dropped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::TaskType;
+    use std::time::Duration;
+
+    fn task(file: &str, heading: &str) -> Task {
+        Task {
+            file: file.into(),
+            line: 1,
+            heading: heading.to_string(),
+            task_type: Some(TaskType::Todo),
+            ..Task::default()
+        }
+    }
+
+    fn at(root: usize, path: &str, secs: Option<u64>) -> Origin {
+        Origin {
+            root,
+            path: path.to_string(),
+            mtime: secs.map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs)),
+        }
+    }
+
+    #[test]
+    fn newest_copy_wins_at_first_position() {
+        let tasks = vec![
+            task("a/notes.md", "Pay rent"),
+            task("a/notes.md", "Call bank"),
+            task("b/notes.md", "Pay rent"),
+        ];
+        let origins = [
+            at(0, "notes.md", Some(10)),
+            at(0, "notes.md", Some(10)),
+            at(1, "notes.md", Some(20)),
+        ];
+        let (kept, dropped) = dedup_tasks(tasks, &origins, true);
+        assert_eq!(dropped, 1);
+        assert_eq!(kept.len(), 2);
+        assert_eq!(kept[0].heading, "Pay rent");
+        assert_eq!(&*kept[0].file, "b/notes.md");
+        assert_eq!(kept[1].heading, "Call bank");
+    }
+
+    #[test]
+    fn first_policy_and_unknown_mtime_keep_first_copy() {
+        let tasks = || vec![task("a.md", "Pay rent"), task("b.md", "Pay rent")];
+        let origins = [at(0, "t.md", Some(10)), at(1, "t.md", Some(20))];
+        let (kept, _) = dedup_tasks(tasks(), &origins, false);
+        assert_eq!(&*kept[0].file, "a.md");
+        let origins = [at(0, "t.md", Some(10)), at(1, "t.md", None)];
+        let (kept, _) = dedup_tasks(tasks(), &origins, true);
+        assert_eq!(&*kept[0].file, "a.md");
+    }
+
+    #[test]
+    fn differing_tasks_are_both_kept() {
+        let mut done = task("b.md", "Pay rent");
+        done.task_type = Some(TaskType::Done);
+        let origins = [at(0, "t.md", None), at(1, "t.md", None)];
+        let (kept, dropped) = dedup_tasks(vec![task("a.md", "Pay rent"), done], &origins, true);
+        assert_eq!(dropped, 0);
+        assert_eq!(kept.len(), 2);
+    }
+
+    #[test]
+    fn same_task_in_two_files_or_one_root_is_kept() {
+        let tasks = || vec![task("a.md", "Review inbox"), task("b.md", "Review inbox")];
+        // Two daily notes of one root.
+        let origins = [at(0, "2025-03-04.md", None), at(0, "2025-03-05.md", None)];
+        assert_eq!(dedup_tasks(tasks(), &origins, true).1, 0);
+        // The same relative path, but a single root.
+        let origins = [at(0, "inbox.md", None), at(0, "inbox.md", None)];
+        assert_eq!(dedup_tasks(tasks(), &origins, true).1, 0);
+        // Different files under two roots.
+        let origins = [at(0, "2025-03-04.md", None), at(1, "2025-03-05.md", None)];
+        assert_eq!(dedup_tasks(tasks(), &origins, true).1, 0);
+        // Tasks without an origin are never merged.
+        assert_eq!(dedup_tasks(tasks(), &[], true).1, 0);
+    }
+}
@@ -25,7 +25,8 @@ pub enum AppError {
     InvalidDate(String),
     /// `--tz` is not a valid IANA timezone
     InvalidTimezone(String),
-    /// `--output` path is unsafe (missing parent, symlink, etc.)
+    /// `--output` path is unsafe (missing parent, symlink, etc.) or does not
+    /// pair up with the `--format` values
     InvalidOutput(String),
     /// `--from` and `--to` form an invalid range
     DateRange(String),
@@ -33,6 +34,15 @@ pub enum AppError {
     Serialization(String),
     /// Regex compilation failed
     Regex(String),
+    /// `--remote` source could not be fetched or decoded
+    Remote(String),
+    /// A `.mdextract.toml` read under `--monorepo` is malformed
+    InvalidConfig(String),
+    /// `--query` is not a valid regular expression under `--regex`
+    InvalidQuery(String),
+    /// A keyword is declared both open and done by `--todo-keywords` /
+    /// `--done-keywords` or a `.mdextract.toml`
+    InvalidKeywords(String),
 }
 
 impl fmt::Display for AppError {
@@ -51,6 +61,10 @@ impl fmt::Display for AppError {
             AppError::DateRange(msg) => write!(f, "{msg}"),
             AppError::Serialization(msg) => write!(f, "serialization: {msg}"),
             AppError::Regex(msg) => write!(f, "regex: {msg}"),
+            AppError::Remote(msg) => write!(f, "remote: {msg}"),
+            AppError::InvalidConfig(msg) => write!(f, "config: {msg}"),
+            AppError::InvalidQuery(msg) => write!(f, "{msg}"),
+            AppError::InvalidKeywords(msg) => write!(f, "{msg}"),
         }
     }
 }
@@ -61,7 +75,7 @@ impl AppError {
     /// - `2`  -- usage / input-validation failures the user can correct by
     ///   changing CLI arguments (matches clap's own argument-error exit).
     /// - `74` -- IO failures (`EX_IOERR` from `sysexits.h`): unreadable files,
-    ///   write failures.
+    ///   write failures, unreachable or malformed `--remote` sources.
     /// - `70` -- internal software errors (`EX_SOFTWARE`): a regex we built
     ///   ourselves did not compile, or our own serializer failed.
     pub fn exit_code(&self) -> i32 {
@@ -71,8 +85,11 @@ impl AppError {
             | AppError::InvalidDate(_)
             | AppError::InvalidTimezone(_)
             | AppError::InvalidOutput(_)
-            | AppError::DateRange(_) => 2,
-            AppError::Io { .. } => 74,
+            | AppError::DateRange(_)
+            | AppError::InvalidConfig(_)
+            | AppError::InvalidQuery(_)
+            | AppError::InvalidKeywords(_) => 2,
+            AppError::Io { .. } | AppError::Remote(_) => 74,
             AppError::Regex(_) | AppError::Serialization(_) => 70,
         }
     }
@@ -212,6 +229,8 @@ mod tests {
         assert_eq!(AppError::InvalidTimezone("x".into()).exit_code(), 2);
         assert_eq!(AppError::InvalidOutput("x".into()).exit_code(), 2);
         assert_eq!(AppError::DateRange("x".into()).exit_code(), 2);
+        assert_eq!(AppError::InvalidQuery("x".into()).exit_code(), 2);
+        assert_eq!(AppError::InvalidKeywords("x".into()).exit_code(), 2);
     }
 
     #[test]
@@ -1,4 +1,5 @@
 use clap::ValueEnum;
+use std::path::Path;
 
 /// Output format selectable via `--format` CLI flag
 #[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
@@ -11,4 +12,65 @@ pub enum OutputFormat {
     Markdown,
     /// HTML page
     Html,
+    /// iCalendar document with one event per agenda entry
+    Ics,
+    /// One CSV row per CLOCK entry across all tasks (no agenda filtering)
+    #[clap(name = "clocks-csv")]
+    ClocksCsv,
+    /// One JSON object per CLOCK entry across all tasks (no agenda filtering)
+    #[clap(name = "clocks-json")]
+    ClocksJson,
+}
+
+impl OutputFormat {
+    /// Raw clock-interval exports bypass the agenda and list every clock of
+    /// every scanned task.
+    pub fn is_clock_export(self) -> bool {
+        matches!(self, OutputFormat::ClocksCsv | OutputFormat::ClocksJson)
+    }
+
+    /// Format implied by the extension of an `--output` path, if any:
+    /// `.json`, `.md`/`.markdown`, `.html`/`.htm`, `.ics`, or `.csv` (the
+    /// only CSV output is the clock export). Case-insensitive.
+    pub fn from_extension(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "json" => Some(OutputFormat::Json),
+            "md" | "markdown" => Some(OutputFormat::Markdown),
+            "html" | "htm" => Some(OutputFormat::Html),
+            "ics" => Some(OutputFormat::Ics),
+            "csv" => Some(OutputFormat::ClocksCsv),
+            _ => None,
+        }
+    }
+
+    /// Name of the [`Renderer`](crate::render::Renderer) this format selects
+    /// in a [`RendererRegistry`](crate::render::RendererRegistry). `None` for
+    /// the clock exports, which render clock rows rather than an agenda.
+    pub fn renderer_name(self) -> Option<&'static str> {
+        match self {
+            OutputFormat::Json => Some("json"),
+            OutputFormat::Markdown => Some("markdown"),
+            OutputFormat::Html => Some("html"),
+            OutputFormat::Ics => Some("ics"),
+            OutputFormat::ClocksCsv | OutputFormat::ClocksJson => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_extension_maps_known_extensions_case_insensitively() {
+        let format = |p: &str| OutputFormat::from_extension(Path::new(p));
+        assert_eq!(format("report.HTML"), Some(OutputFormat::Html));
+        assert_eq!(format("out/agenda.md"), Some(OutputFormat::Markdown));
+        assert_eq!(format("tasks.json"), Some(OutputFormat::Json));
+        assert_eq!(format("clocks.csv"), Some(OutputFormat::ClocksCsv));
+        assert_eq!(format("team.ics"), Some(OutputFormat::Ics));
+        assert_eq!(format("agenda.txt"), None);
+        assert_eq!(format("-"), None);
+    }
 }
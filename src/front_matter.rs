@@ -0,0 +1,185 @@
+//! YAML front matter: file-wide defaults for the tasks of one document.
+//!
+//! Only the flat subset of YAML that note apps write is read — `key: value`
+//! lines, `[a, b]` flow lists and `- item` block lists — so no YAML crate is
+//! needed. Unknown keys and nested values are ignored.
+//!
+//! ```yaml
+//! ---
+//! lang: ru            # weekday names, see `cli::mappings_for_content`
+//! category: work      # `category` of every task without a CATEGORY property
+//! tags: [home, q3]    # inherited by every task, like Org `#+FILETAGS`
+//! agenda: false       # leave the file out of the run
+//! ---
+//! ```
+
+use crate::types::Task;
+
+/// Keys of the `---` block that opens a document.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FrontMatter<'a> {
+    /// `lang:` or `language:` value.
+    pub lang: Option<&'a str>,
+    pub category: Option<&'a str>,
+    pub tags: Vec<&'a str>,
+    /// `agenda:` as a YAML boolean; `false` excludes the file.
+    pub agenda: Option<bool>,
+}
+
+impl<'a> FrontMatter<'a> {
+    /// Front matter of `content`, or `None` when it does not open with a
+    /// closed `---` block. The first value of a repeated key wins.
+    pub fn parse(content: &'a str) -> Option<Self> {
+        let mut lines = content.trim_start_matches('\u{feff}').lines();
+        if lines.next()?.trim_end() != "---" {
+            return None;
+        }
+        let mut front = Self::default();
+        let mut seen_tags = false;
+        // Key whose value continues on the following `- item` lines.
+        let mut list_key = None;
+        for line in lines {
+            let trimmed = line.trim();
+            if trimmed == "---" || trimmed == "..." {
+                return Some(front);
+            }
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            if line.starts_with([' ', '\t', '-']) {
+                if let (Some("tags"), Some(item)) = (list_key, trimmed.strip_prefix('-')) {
+                    front.tags.extend(tag(item));
+                }
+                continue;
+            }
+            list_key = None;
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let key = key.trim();
+            let value = scalar(value);
+            match key {
+                "lang" | "language" if front.lang.is_none() => front.lang = value,
+                "category" if front.category.is_none() => front.category = value,
+                "agenda" if front.agenda.is_none() => front.agenda = value.and_then(boolean),
+                "tags" if !seen_tags => {
+                    seen_tags = true;
+                    match value {
+                        None => list_key = Some("tags"),
+                        Some(value) => {
+                            match value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+                                Some(items) => front.tags.extend(items.split(',').filter_map(tag)),
+                                None => front.tags.extend(tag(value)),
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Whether the file's tasks take part in the run.
+    pub fn included(&self) -> bool {
+        self.agenda != Some(false)
+    }
+
+    /// Give `task` the front matter category unless it has one, and the
+    /// tags ahead of those inherited from its headings.
+    pub fn apply(&self, task: &mut Task) {
+        if task.category.is_none() {
+            task.category = self.category.map(str::to_string);
+        }
+        if self.tags.is_empty() {
+            return;
+        }
+        let mut tags: Vec<String> = Vec::with_capacity(self.tags.len());
+        for tag in &self.tags {
+            if !tags.iter().any(|t| t == tag) {
+                tags.push((*tag).to_string());
+            }
+        }
+        for tag in task.inherited_tags.take().into_iter().flatten() {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        task.inherited_tags = Some(tags);
+    }
+}
+
+/// Unquoted value of a `key: value` line, `None` when empty.
+fn scalar(value: &str) -> Option<&str> {
+    let value = value.trim();
+    let value = match value.split_once(" #") {
+        Some((value, _)) => value.trim_end(),
+        None => value,
+    };
+    let value = value.trim_matches(['"', '\'']);
+    (!value.is_empty()).then_some(value)
+}
+
+/// One tag of a list, without quotes or an Obsidian-style `#`.
+fn tag(item: &str) -> Option<&str> {
+    scalar(item).map(|t| t.trim_start_matches('#'))
+}
+
+fn boolean(value: &str) -> Option<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "true" | "yes" | "on" => Some(true),
+        "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_reads_scalars_and_both_list_styles() {
+        let front = FrontMatter::parse(
+            "---\ntitle: Plan\nlang: \"ru-RU\"\ncategory: work # team\nagenda: No\ntags: [home, '#q3']\n---\n# Body\n",
+        )
+        .unwrap();
+        assert_eq!(front.lang, Some("ru-RU"));
+        assert_eq!(front.category, Some("work"));
+        assert_eq!(front.agenda, Some(false));
+        assert!(!front.included());
+        assert_eq!(front.tags, ["home", "q3"]);
+
+        let front =
+            FrontMatter::parse("\u{feff}---\ntags:\n  - a\n  - \"b\"\nkey: v\n  - c\n...\n")
+                .unwrap();
+        assert_eq!(front.tags, ["a", "b"]);
+        assert!(front.included());
+
+        assert_eq!(
+            FrontMatter::parse("---\ntags: solo\n---\n").unwrap().tags,
+            ["solo"]
+        );
+    }
+
+    #[test]
+    fn parse_needs_an_opening_and_closing_delimiter() {
+        assert_eq!(FrontMatter::parse("# Title\n---\nlang: ru\n---\n"), None);
+        assert_eq!(FrontMatter::parse("---\nlang: ru\n"), None);
+    }
+
+    #[test]
+    fn apply_defaults_category_and_prepends_tags() {
+        let content = "---\ncategory: work\ntags: [file]\n---\n\
+            # Project :proj:\n\n## TODO Own\n```org-properties\nCATEGORY: infra\n```\n\n## TODO Plain\n";
+        let front = FrontMatter::parse(content).unwrap();
+        let mut tasks = crate::parse_file(content);
+        tasks.iter_mut().for_each(|task| front.apply(task));
+        assert_eq!(tasks[0].category.as_deref(), Some("infra"));
+        assert_eq!(tasks[1].category.as_deref(), Some("work"));
+        assert_eq!(
+            tasks[1].inherited_tags,
+            Some(vec!["file".to_string(), "proj".to_string()])
+        );
+        assert_eq!(tasks[1].outline_path.as_deref().map(<[_]>::len), Some(1));
+    }
+}
@@ -54,6 +54,21 @@ impl HolidayCalendar {
         !matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
     }
 
+    /// Return `date` itself when it is a workday, otherwise the nearest
+    /// workday after it (`forward`) or before it. `None` only past the end
+    /// of chrono's date range.
+    pub fn roll_to_workday(&self, date: NaiveDate, forward: bool) -> Option<NaiveDate> {
+        let mut current = date;
+        while !self.is_workday(current) {
+            current = if forward {
+                current.succ_opt()?
+            } else {
+                current.pred_opt()?
+            };
+        }
+        Some(current)
+    }
+
     /// Return the next workday strictly after the given date.
     /// Test-only: production code reaches workday occurrences through the
     /// O(log n) `nth_workday_after` / `workdays_between_exclusive` helpers.
@@ -365,4 +380,23 @@ mod tests {
             "workdays must be sorted and unique"
         );
     }
+
+    #[test]
+    fn test_roll_to_workday() {
+        let calendar = HolidayCalendar::global();
+        let date = |m, d| NaiveDate::from_ymd_opt(2025, m, d).unwrap();
+        // Saturday 2025-06-07 rolls to Monday / back to Friday.
+        assert_eq!(calendar.roll_to_workday(date(6, 7), true), Some(date(6, 9)));
+        assert_eq!(
+            calendar.roll_to_workday(date(6, 7), false),
+            Some(date(6, 6))
+        );
+        // New Year holidays 2025 run through January 8.
+        assert_eq!(calendar.roll_to_workday(date(1, 1), true), Some(date(1, 9)));
+        // A workday stays put.
+        assert_eq!(
+            calendar.roll_to_workday(date(6, 10), true),
+            Some(date(6, 10))
+        );
+    }
 }
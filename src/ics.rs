@@ -0,0 +1,680 @@
+//! iCalendar in and out: `--busy-ics` reads busy intervals from iCalendar
+//! files, e.g. teammates' exported calendars, and `--format ics` writes the
+//! agenda's entries as events.
+//!
+//! On import, every `VEVENT` becomes one untyped task per day it covers, carrying an
+//! active plain timestamp in the home time zone (the first `--tz`). The day
+//! agenda lists them like markdown appointments and `--agenda free`
+//! subtracts them. An all-day event covers `00:00-23:59`. Transparent
+//! (`TRANSP:TRANSPARENT`) and cancelled events are skipped, and a recurring
+//! event (`RRULE`) contributes its first occurrence only.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use chrono_tz::Tz;
+
+use crate::agenda::AgendaOutput;
+use crate::error::AppError;
+use crate::types::{DayAgenda, Task, TaskWithOffset, MAX_FILE_SIZE};
+
+/// Read `path` and return its events as busy tasks in `home`.
+pub fn load_busy(path: &Path, home: Tz) -> Result<Vec<Task>, AppError> {
+    let context = path.display().to_string();
+    let size = std::fs::metadata(path)
+        .map_err(|e| AppError::io(&context, e))?
+        .len();
+    if size > MAX_FILE_SIZE {
+        return Err(AppError::io(
+            &context,
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("larger than {MAX_FILE_SIZE} bytes"),
+            ),
+        ));
+    }
+    let text = std::fs::read_to_string(path).map_err(|e| AppError::io(&context, e))?;
+    Ok(busy_tasks(&text, &context, home))
+}
+
+/// Busy tasks of every event in `ics`, tagged with `file` and the line of
+/// the event's `BEGIN:VEVENT`.
+pub fn busy_tasks(ics: &str, file: &str, home: Tz) -> Vec<Task> {
+    let file: Arc<str> = Arc::from(file);
+    let mut tasks = Vec::new();
+    let mut event: Option<Event> = None;
+
+    for (line, text) in unfold(ics) {
+        let Some((name, params, value)) = split_property(&text) else {
+            continue;
+        };
+        match (name.to_ascii_uppercase().as_str(), event.as_mut()) {
+            ("BEGIN", None) if value.eq_ignore_ascii_case("VEVENT") => {
+                event = Some(Event::new(line));
+            }
+            ("END", Some(_)) if value.eq_ignore_ascii_case("VEVENT") => {
+                if let Some(done) = event.take() {
+                    done.push_tasks(&file, &mut tasks);
+                }
+            }
+            ("DTSTART", Some(ev)) => ev.start = parse_stamp(value, params, home),
+            ("DTEND", Some(ev)) => ev.end = parse_stamp(value, params, home),
+            ("DURATION", Some(ev)) => ev.duration = parse_duration(value),
+            ("SUMMARY", Some(ev)) => ev.summary = Some(unescape(value)),
+            ("TRANSP", Some(ev)) => ev.skip |= value.eq_ignore_ascii_case("TRANSPARENT"),
+            ("STATUS", Some(ev)) => ev.skip |= value.eq_ignore_ascii_case("CANCELLED"),
+            ("RRULE", Some(ev)) => ev.recurring = true,
+            _ => {}
+        }
+    }
+    tasks
+}
+
+/// Start or end of an event, already converted to the home time zone.
+#[derive(Debug, Clone, Copy)]
+enum Stamp {
+    Date(NaiveDate),
+    DateTime(NaiveDateTime),
+}
+
+struct Event {
+    line: u32,
+    summary: Option<String>,
+    start: Option<Stamp>,
+    end: Option<Stamp>,
+    duration: Option<Duration>,
+    skip: bool,
+    recurring: bool,
+}
+
+impl Event {
+    fn new(line: u32) -> Self {
+        Self {
+            line,
+            summary: None,
+            start: None,
+            end: None,
+            duration: None,
+            skip: false,
+            recurring: false,
+        }
+    }
+
+    fn push_tasks(self, file: &Arc<str>, tasks: &mut Vec<Task>) {
+        if self.skip {
+            return;
+        }
+        if self.recurring {
+            tracing::warn!(
+                file = %file,
+                line = self.line,
+                "recurring event: only its first occurrence is imported"
+            );
+        }
+        let heading = self.summary.as_deref().unwrap_or("Busy");
+        let mut push = |date: NaiveDate, start: NaiveTime, end: NaiveTime| {
+            tasks.push(busy_task(file, self.line, heading, date, start, end));
+        };
+        let midnight = NaiveTime::MIN;
+        let last_minute = NaiveTime::from_hms_opt(23, 59, 0).expect("valid time");
+
+        match self.start {
+            Some(Stamp::Date(first)) => {
+                // DTEND of an all-day event is exclusive; one day by default.
+                let end = match self.end {
+                    Some(Stamp::Date(end)) => end,
+                    Some(Stamp::DateTime(end)) => end.date(),
+                    None => first + self.duration.unwrap_or(Duration::days(1)),
+                };
+                let mut day = first;
+                loop {
+                    push(day, midnight, last_minute);
+                    day = day.succ_opt().unwrap_or(day);
+                    if day >= end || day == first {
+                        break;
+                    }
+                }
+            }
+            Some(Stamp::DateTime(start)) => {
+                let end = match self.end {
+                    Some(Stamp::DateTime(end)) => end,
+                    Some(Stamp::Date(end)) => end.and_time(midnight),
+                    None => match self.duration {
+                        Some(duration) => start + duration,
+                        None => return,
+                    },
+                };
+                let mut day_start = start;
+                while day_start < end {
+                    let next_midnight = day_start
+                        .date()
+                        .succ_opt()
+                        .map_or(NaiveDateTime::MAX, |d| d.and_time(midnight));
+                    let segment_end = if end < next_midnight {
+                        end.time()
+                    } else {
+                        last_minute
+                    };
+                    push(day_start.date(), day_start.time(), segment_end);
+                    day_start = next_midnight;
+                }
+            }
+            None => {}
+        }
+    }
+}
+
+fn busy_task(
+    file: &Arc<str>,
+    line: u32,
+    heading: &str,
+    date: NaiveDate,
+    start: NaiveTime,
+    end: NaiveTime,
+) -> Task {
+    let start = start.format("%H:%M").to_string();
+    let end = end.format("%H:%M").to_string();
+    Task {
+        file: Arc::clone(file),
+        line,
+        heading: heading.to_string(),
+        content: String::new(),
+        task_type: None,
+        priority: None,
+        created: None,
+        closed: None,
+        timestamp: Some(format!("<{} {start}-{end}>", date.format("%Y-%m-%d %a"))),
+        timestamp_type: Some("PLAIN".to_string()),
+        timestamp_active: Some(true),
+        timestamp_date: Some(date.format("%Y-%m-%d").to_string()),
+        timestamp_time: Some(start),
+        timestamp_end_time: Some(end),
+        timestamp_repeater: None,
+        clocks: None,
+        total_clock_time: None,
+        log: None,
+        effort_minutes: None,
+        properties: None,
+        timestamp_overnight: None,
+        planning: None,
+        inactive_timestamps: None,
+        rescheduled_count: None,
+        effective_date: None,
+        attendees: None,
+        location: None,
+        url: None,
+        links: None,
+        subtasks: None,
+        subtasks_percent: None,
+        subtasks_progress: None,
+        progress_cookie: None,
+        progress: None,
+        children_todo: None,
+        children_done: None,
+        level: None,
+        waiting_since: None,
+        assignee: None,
+        paused: None,
+        paused_until: None,
+        effective_priority: None,
+        category: None,
+        tags: None,
+        outline_path: None,
+        inherited_priority: None,
+        inherited_tags: None,
+    }
+}
+
+/// Logical content lines with their 1-based starting line number: a
+/// physical line starting with a space or tab continues the previous one
+/// (RFC 5545 folding).
+fn unfold(ics: &str) -> Vec<(u32, String)> {
+    let mut lines: Vec<(u32, String)> = Vec::new();
+    for (index, line) in ics.lines().enumerate() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some((_, previous))) => previous.push_str(rest),
+            _ => lines.push((index as u32 + 1, line.to_string())),
+        }
+    }
+    lines
+}
+
+/// `NAME;PARAM=V:VALUE` split into name, parameters and value. The value
+/// starts at the first `:` outside a quoted parameter value.
+fn split_property(line: &str) -> Option<(&str, &str, &str)> {
+    let mut quoted = false;
+    let colon = line.char_indices().find_map(|(i, c)| match c {
+        '"' => {
+            quoted = !quoted;
+            None
+        }
+        ':' if !quoted => Some(i),
+        _ => None,
+    })?;
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+    let (name, params) = head.split_once(';').unwrap_or((head, ""));
+    Some((name, params, value))
+}
+
+/// Value of parameter `key` (`TZID`, `VALUE`) in `params`, unquoted.
+fn param<'a>(params: &'a str, key: &str) -> Option<&'a str> {
+    params.split(';').find_map(|p| {
+        let (k, v) = p.split_once('=')?;
+        k.eq_ignore_ascii_case(key).then(|| v.trim_matches('"'))
+    })
+}
+
+/// `DTSTART` / `DTEND` value in the home time zone: a UTC (`...Z`) or
+/// `TZID` time is converted, a floating time taken as is. An unknown
+/// `TZID` counts as floating.
+fn parse_stamp(value: &str, params: &str, home: Tz) -> Option<Stamp> {
+    let value = value.trim();
+    if param(params, "VALUE").is_some_and(|v| v.eq_ignore_ascii_case("DATE")) || value.len() == 8 {
+        return NaiveDate::parse_from_str(value, "%Y%m%d")
+            .ok()
+            .map(Stamp::Date);
+    }
+    let (local, utc) = match value.strip_suffix(['Z', 'z']) {
+        Some(rest) => (rest, true),
+        None => (value, false),
+    };
+    let naive = NaiveDateTime::parse_from_str(local, "%Y%m%dT%H%M%S").ok()?;
+    let converted = if utc {
+        chrono::Utc
+            .from_utc_datetime(&naive)
+            .with_timezone(&home)
+            .naive_local()
+    } else if let Some(zone) = param(params, "TZID").and_then(|id| id.parse::<Tz>().ok()) {
+        zone.from_local_datetime(&naive)
+            .earliest()
+            .map_or(naive, |dt| dt.with_timezone(&home).naive_local())
+    } else {
+        naive
+    };
+    Some(Stamp::DateTime(converted))
+}
+
+/// RFC 5545 duration (`PT1H30M`, `P1D`, `P2W`); negative durations are
+/// rejected.
+fn parse_duration(value: &str) -> Option<Duration> {
+    let rest = value.trim().strip_prefix('+').unwrap_or(value.trim());
+    let rest = rest.strip_prefix('P')?;
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    for c in rest.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => {}
+            unit => {
+                let n: i64 = number.parse().ok()?;
+                number.clear();
+                total += match unit {
+                    'W' => Duration::weeks(n),
+                    'D' => Duration::days(n),
+                    'H' => Duration::hours(n),
+                    'M' => Duration::minutes(n),
+                    'S' => Duration::seconds(n),
+                    _ => return None,
+                };
+            }
+        }
+    }
+    number.is_empty().then_some(total)
+}
+
+/// RFC 5545 TEXT value on one line: `\,` `\;` `\\` unescaped, `\n` as a
+/// space.
+fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => out.push(' '),
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+    out.trim().to_string()
+}
+
+/// RFC 5545 TEXT value: `\\`, `;`, `,` and line breaks escaped.
+fn escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// One content line, folded at 75 octets (never inside a character) and
+/// ended with CRLF.
+fn push_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+/// `ATTENDEE` line of one `attendees` entry: `Alice <alice@example.com>`
+/// gives the name as `CN` and the address as a `mailto:` URI, a bare
+/// address only the URI. A name without an address gets the placeholder
+/// `invalid:nomail`, as Thunderbird writes it.
+fn attendee_line(entry: &str) -> String {
+    let (name, address) = match entry.rsplit_once('<') {
+        Some((name, rest)) => (name.trim(), rest.trim_end_matches('>').trim()),
+        None if entry.contains('@') => ("", entry.trim()),
+        None => (entry.trim(), ""),
+    };
+    let uri = if address.is_empty() {
+        "invalid:nomail".to_string()
+    } else {
+        format!("mailto:{address}")
+    };
+    if name.is_empty() {
+        return format!("ATTENDEE:{uri}");
+    }
+    // A parameter value is quoted when it holds a separator; it cannot
+    // hold a quote at all.
+    let name = name.replace('"', "");
+    if name.contains([',', ';', ':']) {
+        format!("ATTENDEE;CN=\"{name}\":{uri}")
+    } else {
+        format!("ATTENDEE;CN={name}:{uri}")
+    }
+}
+
+/// Render the entries of an agenda as an iCalendar document, one `VEVENT`
+/// per entry: the entries of each day in the day views (not the overdue
+/// or upcoming ones listed under it), the tasks with an active timestamp in
+/// the task list. Closing times are not events. Times are floating local
+/// times, read in the zone of the calendar that imports them; an entry
+/// without a time is an all-day event. The other views have no events.
+pub fn render_calendar(output: &AgendaOutput) -> String {
+    let mut out = String::new();
+    push_line(&mut out, "BEGIN:VCALENDAR");
+    push_line(&mut out, "VERSION:2.0");
+    push_line(&mut out, "PRODID:-//markdown-org-extract//EN");
+    push_line(&mut out, "CALSCALE:GREGORIAN");
+    let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    write_events(&mut out, output, &stamp);
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+fn write_events(out: &mut String, output: &AgendaOutput, stamp: &str) {
+    match output {
+        AgendaOutput::Days(days) => {
+            for entry in days.iter().flat_map(day_entries) {
+                if let Some(date) = entry.timestamp_date() {
+                    let (time, end) = (entry.timestamp_time(), entry.timestamp_end_time());
+                    write_event(out, &entry.task, date, time, end, stamp);
+                }
+            }
+        }
+        AgendaOutput::Tasks(tasks) => {
+            for task in tasks.iter().filter(|t| t.timestamp_active == Some(true)) {
+                if let Some(date) = task.timestamp_date.as_deref() {
+                    let time = task.timestamp_time.as_deref();
+                    let end = task.timestamp_end_time.as_deref();
+                    write_event(out, task, date, time, end, stamp);
+                }
+            }
+        }
+        AgendaOutput::Grouped(groups) => {
+            for group in groups {
+                write_events(out, &group.agenda, stamp);
+            }
+        }
+        AgendaOutput::Blocks(blocks) => {
+            for block in blocks {
+                write_events(out, &block.agenda, stamp);
+            }
+        }
+        AgendaOutput::Forecast(_)
+        | AgendaOutput::Quarter(_)
+        | AgendaOutput::Simulation(_)
+        | AgendaOutput::FreeSlots(_)
+        | AgendaOutput::Habits(_) => {}
+    }
+}
+
+/// Entries dated on `day` itself, whichever way the day is laid out.
+fn day_entries(day: &DayAgenda) -> impl Iterator<Item = &TaskWithOffset> {
+    let groups = day
+        .categories
+        .iter()
+        .flat_map(|c| c.scheduled_timed.iter().chain(&c.scheduled_no_time));
+    day.scheduled_timed
+        .iter()
+        .chain(&day.scheduled_no_time)
+        .chain(groups)
+        .chain(day.timeline.iter().filter(|e| e.days_offset.is_none()))
+        .filter(|e| e.task.timestamp_type.as_deref() != Some("CLOSED"))
+}
+
+fn write_event(
+    out: &mut String,
+    task: &Task,
+    date: &str,
+    time: Option<&str>,
+    end: Option<&str>,
+    stamp: &str,
+) {
+    let Ok(day) = NaiveDate::parse_from_str(date, "%Y-%m-%d") else {
+        return;
+    };
+    let time = time.and_then(|t| NaiveTime::parse_from_str(t, "%H:%M").ok());
+    push_line(out, "BEGIN:VEVENT");
+    let uid = format!(
+        "{}-{}-{}@markdown-org-extract",
+        day.format("%Y%m%d"),
+        task.line,
+        task.file
+    );
+    push_line(out, &format!("UID:{}", escape(&uid)));
+    push_line(out, &format!("DTSTAMP:{stamp}"));
+    match time {
+        Some(start) => {
+            let start = day.and_time(start);
+            push_line(out, &format!("DTSTART:{}", start.format("%Y%m%dT%H%M%S")));
+            let end = end.and_then(|t| NaiveTime::parse_from_str(t, "%H:%M").ok());
+            if let Some(end) = end {
+                // An end before the start runs past midnight.
+                let end_day = if end < start.time() {
+                    day + Duration::days(1)
+                } else {
+                    day
+                };
+                let end = end_day.and_time(end);
+                push_line(out, &format!("DTEND:{}", end.format("%Y%m%dT%H%M%S")));
+            }
+        }
+        None => {
+            push_line(out, &format!("DTSTART;VALUE=DATE:{}", day.format("%Y%m%d")));
+            let next = day + Duration::days(1);
+            push_line(out, &format!("DTEND;VALUE=DATE:{}", next.format("%Y%m%d")));
+        }
+    }
+    push_line(out, &format!("SUMMARY:{}", escape(&task.heading)));
+    if let Some(location) = task.location.as_deref() {
+        push_line(out, &format!("LOCATION:{}", escape(location)));
+    }
+    for attendee in task.attendees.iter().flatten() {
+        push_line(out, &attendee_line(attendee));
+    }
+    push_line(out, "END:VEVENT");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CALENDAR: &str = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+BEGIN:VEVENT\r
+DTSTART:20260105T090000Z\r
+DTEND:20260105T100000Z\r
+SUMMARY:Design review\\, round 2\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+DTSTART;TZID=Europe/Berlin:20260105T150000\r
+DURATION:PT1H30M\r
+SUMMARY:Long\r
+  call\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+DTSTART;VALUE=DATE:20260107\r
+DTEND;VALUE=DATE:20260109\r
+SUMMARY:Conference\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+DTSTART:20260105T220000\r
+DTEND:20260106T013000\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+DTSTART:20260105T120000Z\r
+DTEND:20260105T130000Z\r
+TRANSP:TRANSPARENT\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+    fn summary(tasks: &[Task]) -> Vec<String> {
+        tasks
+            .iter()
+            .map(|t| {
+                format!(
+                    "{} {} {}-{}",
+                    t.heading,
+                    t.timestamp_date.as_deref().unwrap(),
+                    t.timestamp_time.as_deref().unwrap(),
+                    t.timestamp_end_time.as_deref().unwrap()
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn events_become_per_day_busy_tasks_in_home_zone() {
+        let tasks = busy_tasks(CALENDAR, "team.ics", "Europe/Moscow".parse().unwrap());
+        assert_eq!(
+            summary(&tasks),
+            [
+                "Design review, round 2 2026-01-05 12:00-13:00",
+                "Long call 2026-01-05 17:00-18:30",
+                "Conference 2026-01-07 00:00-23:59",
+                "Conference 2026-01-08 00:00-23:59",
+                "Busy 2026-01-05 22:00-23:59",
+                "Busy 2026-01-06 00:00-01:30",
+            ]
+        );
+        assert_eq!(tasks[0].line, 3);
+        assert_eq!(&*tasks[0].file, "team.ics");
+        assert_eq!(
+            tasks[0].timestamp.as_deref(),
+            Some("<2026-01-05 Mon 12:00-13:00>")
+        );
+    }
+
+    fn calendar(markdown: &str) -> Vec<String> {
+        let mut tasks = crate::parse_file(markdown);
+        for task in &mut tasks {
+            task.file = "t.md".into();
+        }
+        render_calendar(&AgendaOutput::Tasks(tasks))
+            .split("\r\n")
+            .filter(|line| !line.starts_with("DTSTAMP:"))
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn exports_meetings_with_one_attendee_line_each() {
+        let lines = calendar(
+            "## Sync; weekly\n`<2025-04-01 Tue 23:30-00:30>`\n\
+             ```org-properties\nATTENDEES: Alice <alice@example.com>, bob@example.com, \
+             Doe, Carol\n```\n\n\
+             ## TODO Notes\n`[2025-04-02 Wed]`\n\n\
+             ## Holiday\n`<2025-04-03 Thu>`\n",
+        );
+        assert_eq!(
+            lines,
+            [
+                "BEGIN:VCALENDAR",
+                "VERSION:2.0",
+                "PRODID:-//markdown-org-extract//EN",
+                "CALSCALE:GREGORIAN",
+                "BEGIN:VEVENT",
+                "UID:20250401-1-t.md@markdown-org-extract",
+                "DTSTART:20250401T233000",
+                "DTEND:20250402T003000",
+                "SUMMARY:Sync\\; weekly",
+                "ATTENDEE;CN=Alice:mailto:alice@example.com",
+                "ATTENDEE:mailto:bob@example.com",
+                "ATTENDEE;CN=Doe:invalid:nomail",
+                "ATTENDEE;CN=Carol:invalid:nomail",
+                "END:VEVENT",
+                "BEGIN:VEVENT",
+                "UID:20250403-10-t.md@markdown-org-extract",
+                "DTSTART;VALUE=DATE:20250403",
+                "DTEND;VALUE=DATE:20250404",
+                "SUMMARY:Holiday",
+                "END:VEVENT",
+                "END:VCALENDAR",
+                "",
+            ]
+        );
+    }
+
+    #[test]
+    fn exports_location_escaped() {
+        let lines = calendar(
+            "## Visit\n`<2025-04-01 Tue 10:00>`\n\
+             ```org-properties\nLOCATION: Room 4, floor 2; east\\wing\n```\n",
+        );
+        assert!(
+            lines.contains(&"LOCATION:Room 4\\, floor 2\\; east\\\\wing".to_string()),
+            "{lines:?}"
+        );
+    }
+
+    #[test]
+    fn long_lines_fold_between_characters() {
+        let mut out = String::new();
+        push_line(&mut out, &format!("SUMMARY:{}", "ü".repeat(40)));
+        let lines: Vec<_> = out.split("\r\n").collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].len(), 74);
+        assert!(lines[1].starts_with(' ') && lines[1].len() <= 75);
+        assert_eq!(escape("a\\b,c;d\r\ne"), "a\\\\b\\,c\\;d\\ne");
+    }
+
+    #[test]
+    fn parses_durations_and_rejects_garbage() {
+        assert_eq!(parse_duration("PT1H30M"), Some(Duration::minutes(90)));
+        assert_eq!(parse_duration("P1DT2H"), Some(Duration::hours(26)));
+        assert_eq!(parse_duration("P2W"), Some(Duration::weeks(2)));
+        assert_eq!(parse_duration("-PT1H"), None);
+        assert_eq!(parse_duration("PT1X"), None);
+        assert_eq!(parse_duration("PT1"), None);
+    }
+}
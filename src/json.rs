@@ -0,0 +1,244 @@
+//! Loaders for the JSON this crate prints, so tools can feed the output of
+//! an earlier run (or another machine, see `--remote`) back into the crate's
+//! types.
+//!
+//! Compatibility follows [ADR-0023](../docs/adr/0023-json-loaders.md): the
+//! loaders read the output of this release and of every earlier release
+//! since the last breaking change under ADR-0015, and of later releases that
+//! only added fields. Missing optional fields load as `None` or empty,
+//! unknown fields are ignored.
+
+use serde::de::Error as _;
+use serde_json::Value;
+
+use clap::ValueEnum;
+
+use crate::agenda::{AgendaBlock, AgendaGroup, AgendaOutput, GroupBy};
+use crate::types::Task;
+
+/// Load the flat task list printed by `--agenda tasks --format json`.
+pub fn tasks_from_json(json: &str) -> serde_json::Result<Vec<Task>> {
+    serde_json::from_str(json)
+}
+
+/// Load any agenda result printed with `--format json`, recognising its
+/// shape from the first element: day agendas, a task list, a forecast, a
+/// quarter, a simulation, free slots, habits, `--group-by` groups or
+/// `--custom-agenda` blocks. An empty array
+/// loads as an empty task list.
+pub fn from_json(json: &str) -> serde_json::Result<AgendaOutput> {
+    from_value(serde_json::from_str(json)?)
+}
+
+fn from_value(value: Value) -> serde_json::Result<AgendaOutput> {
+    let Some(items) = value.as_array() else {
+        return Err(serde_json::Error::custom("expected a JSON array"));
+    };
+    let Some(first) = items.first() else {
+        return Ok(AgendaOutput::Tasks(Vec::new()));
+    };
+    let has = |key: &str| first.get(key).is_some();
+    if has("scheduled_timed") {
+        serde_json::from_value(value).map(AgendaOutput::Days)
+    } else if has("month") {
+        serde_json::from_value(value).map(AgendaOutput::Forecast)
+    } else if has("deadlines") {
+        serde_json::from_value(value).map(AgendaOutput::Quarter)
+    } else if has("date") && has("occurrences") {
+        serde_json::from_value(value).map(AgendaOutput::Simulation)
+    } else if has("slots") {
+        serde_json::from_value(value).map(AgendaOutput::FreeSlots)
+    } else if has("graph") {
+        serde_json::from_value(value).map(AgendaOutput::Habits)
+    } else if has("heading") {
+        serde_json::from_value(value).map(AgendaOutput::Tasks)
+    } else if has("title") && has("agenda") {
+        let field = |block: &Value, key| block.get(key).cloned().unwrap_or_default();
+        items
+            .iter()
+            .map(|block| {
+                Ok(AgendaBlock {
+                    title: serde_json::from_value(field(block, "title"))?,
+                    agenda: from_value(field(block, "agenda"))?,
+                })
+            })
+            .collect::<serde_json::Result<_>>()
+            .map(AgendaOutput::Blocks)
+    } else if has("agenda") {
+        let field = |group: &Value, key| group.get(key).cloned().unwrap_or_default();
+        // Only the group without a value lacks the field naming the attribute.
+        let by = GroupBy::value_variants()
+            .iter()
+            .copied()
+            .find(|by| items.iter().any(|group| group.get(by.field()).is_some()))
+            .unwrap_or(GroupBy::Assignee);
+        items
+            .iter()
+            .map(|group| {
+                Ok(AgendaGroup {
+                    by,
+                    name: serde_json::from_value(field(group, by.field()))?,
+                    agenda: from_value(field(group, "agenda"))?,
+                })
+            })
+            .collect::<serde_json::Result<_>>()
+            .map(AgendaOutput::Grouped)
+    } else {
+        Err(serde_json::Error::custom(
+            "not an agenda, task list, forecast, quarter, simulation, free slots, habits, group or block list",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agenda::{filter_agenda, AgendaDates, AgendaScope, FiscalYearStart};
+    use crate::render::{JsonRenderer, Renderer};
+
+    const NOTES: &str = "\
+## TODO Daily standup
+`SCHEDULED: <2026-01-05 Mon 10:00-10:15 +1d>`
+
+## TODO Report
+`DEADLINE: <2026-01-09 Fri>`
+
+```org-properties
+Effort: 1:30
+```
+
+## TODO Overdue thing
+`SCHEDULED: <2026-01-02 Fri>`
+
+## DONE Finished
+`CLOSED: [2026-01-05 Mon 12:00]`
+
+## TODO Stretch
+`SCHEDULED: <2026-01-05 Mon .+1d>`
+```org-properties
+STYLE: habit
+```
+";
+
+    fn to_json(output: &AgendaOutput) -> String {
+        JsonRenderer.render(output).unwrap()
+    }
+
+    #[test]
+    fn every_json_output_round_trips() {
+        let today = AgendaDates {
+            current_date: Some("2026-01-06"),
+            ..AgendaDates::default()
+        };
+        let range = AgendaDates {
+            from: Some("2026-01-05"),
+            to: Some("2026-01-08"),
+            ..today
+        };
+        let fiscal_year_start = FiscalYearStart { month: 1, day: 1 };
+        for (scope, dates) in [
+            (AgendaScope::Day, today),
+            (AgendaScope::Week, range),
+            (AgendaScope::Tasks, AgendaDates::default()),
+            (AgendaScope::Forecast { months: 1 }, today),
+            (AgendaScope::Quarter { fiscal_year_start }, today),
+            (AgendaScope::Simulate, range),
+            (AgendaScope::Habits { days: 7 }, today),
+            (
+                AgendaScope::Free {
+                    work_hours: crate::agenda::WorkHours {
+                        start: chrono::NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+                        end: chrono::NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
+                    },
+                    min_slot: 30,
+                },
+                // 2026-01-05..08 are public holidays in the built-in calendar.
+                AgendaDates {
+                    date: Some("2026-01-12"),
+                    ..today
+                },
+            ),
+        ] {
+            let tasks = crate::parse_file(NOTES);
+            let output = filter_agenda(tasks, scope, dates, "UTC", false, false).unwrap();
+            assert!(!output.is_empty(), "{scope:?} fixture must not be empty");
+            let json = to_json(&output);
+            let loaded = from_json(&json).unwrap_or_else(|e| panic!("{scope:?}: {e}\n{json}"));
+            assert_eq!(to_json(&loaded), json, "{scope:?} must round-trip");
+        }
+    }
+
+    #[test]
+    fn grouped_output_round_trips() {
+        let tasks = crate::parse_file("## TODO Draft @ann\n\n## TODO Review\n");
+        let grouped = |by| {
+            let groups = crate::agenda::group_tasks(tasks.clone(), by)
+                .into_iter()
+                .map(|(name, tasks)| AgendaGroup {
+                    by,
+                    name,
+                    agenda: AgendaOutput::Tasks(tasks),
+                })
+                .collect();
+            to_json(&AgendaOutput::Grouped(groups))
+        };
+        let json = grouped(GroupBy::Assignee);
+        let loaded = from_json(&json).unwrap();
+        assert!(matches!(&loaded, AgendaOutput::Grouped(g)
+            if g.len() == 2 && g[0].name.as_deref() == Some("ann") && g[1].name.is_none()));
+        assert_eq!(to_json(&loaded), json);
+
+        let json = grouped(GroupBy::TodoState);
+        let loaded = from_json(&json).unwrap();
+        assert!(matches!(&loaded, AgendaOutput::Grouped(g)
+            if g.len() == 1 && g[0].by == GroupBy::TodoState && g[0].name.as_deref() == Some("TODO")));
+        assert_eq!(to_json(&loaded), json);
+    }
+
+    #[test]
+    fn block_output_round_trips() {
+        let tasks = crate::parse_file("## TODO Draft @ann\n\n## TODO Review\n");
+        let blocks = AgendaOutput::Blocks(vec![
+            AgendaBlock {
+                title: "All".to_string(),
+                agenda: AgendaOutput::Tasks(tasks.clone()),
+            },
+            AgendaBlock {
+                title: "Nothing".to_string(),
+                agenda: AgendaOutput::Tasks(Vec::new()),
+            },
+        ]);
+        let json = to_json(&blocks);
+        let loaded = from_json(&json).unwrap();
+        assert!(matches!(&loaded, AgendaOutput::Blocks(b)
+            if b.len() == 2 && b[1].title == "Nothing" && b[1].agenda.is_empty()));
+        assert_eq!(to_json(&loaded), json);
+    }
+
+    #[test]
+    fn loaders_accept_older_and_newer_shapes() {
+        // An early-release task (no optional fields) and a future one with
+        // a field this release does not know.
+        let json = r#"[
+            {"file": "a.md", "line": 1, "heading": "Old", "content": ""},
+            {"file": "b.md", "line": 2, "heading": "New", "content": "",
+             "task_type": "TODO", "field_from_the_future": [1, 2]}
+        ]"#;
+        let tasks = tasks_from_json(json).unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert!(tasks[0].task_type.is_none());
+        assert!(matches!(from_json(json), Ok(AgendaOutput::Tasks(t)) if t.len() == 2));
+
+        // Day agendas omit an empty `overdue` bucket.
+        let day = r#"[{"date": "2026-01-06", "scheduled_timed": [],
+            "scheduled_no_time": [], "upcoming": []}]"#;
+        assert!(matches!(from_json(day), Ok(AgendaOutput::Days(d)) if d[0].overdue.is_empty()));
+    }
+
+    #[test]
+    fn from_json_rejects_unknown_shapes() {
+        assert!(from_json("{}").is_err());
+        assert!(from_json(r#"[{"something": 1}]"#).is_err());
+        assert!(matches!(from_json("[]"), Ok(AgendaOutput::Tasks(t)) if t.is_empty()));
+    }
+}
@@ -0,0 +1,58 @@
+//! Library side of `markdown-org-extract`. The CLI in `main.rs` is built
+//! on these modules; [`parse_file`] is the stable entry point for callers
+//! (and the criterion benches) that only need the parser. Output formats are
+//! [`render::Renderer`]s looked up in a [`render::RendererRegistry`], where
+//! callers can register formats of their own.
+
+pub mod agenda;
+pub mod cli;
+pub mod clock;
+pub mod config;
+pub mod dedup;
+pub mod error;
+pub mod format;
+pub mod front_matter;
+pub mod holidays;
+pub mod ics;
+pub mod json;
+pub mod parser;
+pub mod regex_limits;
+pub mod remote;
+pub mod render;
+pub mod search;
+pub mod tag_match;
+pub mod timestamp;
+pub mod types;
+
+use std::path::Path;
+
+use crate::cli::{get_weekday_mappings, mappings_for_content};
+use crate::types::{Task, DEFAULT_MAX_TASKS};
+
+/// Locale applied by [`parse_file`]; the same as the CLI's `--locale` default.
+const PARSE_FILE_LOCALE: &str = "ru,en";
+
+/// Extract the tasks of one markdown document.
+///
+/// Uses the CLI defaults: weekday names in `ru,en` (the Russian table only
+/// for a Russian document, see [`cli::mappings_for_content`]), at most
+/// `DEFAULT_MAX_TASKS` tasks. The `file` field of every task is empty since
+/// no path is known; use [`parser::extract_tasks`] to set one.
+pub fn parse_file(content: &str) -> Vec<Task> {
+    let mappings = get_weekday_mappings(PARSE_FILE_LOCALE);
+    let mappings = mappings_for_content(&mappings, content);
+    parser::extract_tasks(Path::new(""), content, mappings, DEFAULT_MAX_TASKS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_file_uses_cli_defaults() {
+        let tasks = parse_file("### TODO Звонок\n`SCHEDULED: <2026-03-05 Чт>`\n");
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(&*tasks[0].file, "");
+        assert_eq!(tasks[0].timestamp_date.as_deref(), Some("2026-03-05"));
+    }
+}
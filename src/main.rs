@@ -2,45 +2,54 @@
 //! CLI utility for extracting tasks from markdown files with Emacs
 //! Org-mode support. See [`README.md`] at the repository root for the
 //! user-facing description; this binary's entry point lives in
-//! [`main`] and the public surface used by integration tests is the
-//! CLI itself, not a Rust API.
+//! [`main`]; the modules it drives live in the library crate, whose
+//! public entry point for parsing is `markdown_org_extract::parse_file`.
 //!
 //! [`README.md`]: https://github.com/VitalyOstanin/markdown-org-extract
 
-mod agenda;
-mod cli;
-mod clock;
-mod error;
-mod format;
-mod holidays;
-mod parser;
-mod regex_limits;
-mod render;
-mod timestamp;
-mod types;
+use markdown_org_extract::{
+    agenda, cli, clock, config, dedup, error, format, front_matter, holidays, ics, parser, remote,
+    render, search, timestamp, types,
+};
 
+use chrono::NaiveDate;
 use clap::Parser;
 use grep_regex::RegexMatcher;
 use grep_searcher::{Searcher, Sink, SinkMatch};
 use ignore::WalkBuilder;
+use std::collections::hash_map::{Entry, HashMap};
 use std::fs::{self, File};
 use std::io::{self, Read, Write};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use crate::agenda::filter_agenda;
-use crate::cli::{get_weekday_mappings, Cli};
+use crate::cli::{get_weekday_mappings, mappings_for_content, Cli};
 use crate::error::AppError;
 use crate::format::OutputFormat;
-use crate::parser::extract_tasks_with_counter;
-use crate::render::{render_html, render_markdown};
+use crate::front_matter::FrontMatter;
+use crate::parser::{extract_tasks_with_counter, ExtractOptions};
+use crate::render::RendererRegistry;
+use crate::search::SearchQuery;
 use crate::types::{ProcessingStats, MAX_FILE_SIZE};
 
 /// Exit code for a scan aborted by SIGINT/SIGTERM. Follows the shell
 /// convention `128 + signum` so `$?` after Ctrl-C is the familiar `130`.
 const EXIT_INTERRUPTED: i32 = 130;
 
+/// Exit code for `--fail-if-empty` when the result holds no task. Mirrors
+/// `grep`'s "no match" status so scripts can branch on it.
+const EXIT_EMPTY: i32 = 1;
+
+/// Exit code for `--lint` when at least one timestamp problem was found.
+const EXIT_LINT_FINDINGS: i32 = 1;
+
+/// Minimum wall time `--bench-corpus` keeps re-parsing the corpus, so a
+/// small corpus still yields a stable MB/s figure.
+const BENCH_MIN_DURATION: Duration = Duration::from_secs(1);
+
 /// Initial capacity of the read buffer reused across the walk. Sized at 64 KiB
 /// to cover most source files in a single allocation while still amortising to
 /// one buffer for the whole tree; the buffer grows on demand for larger files.
@@ -97,7 +106,8 @@ fn is_broken_pipe(e: &AppError) -> bool {
 }
 
 fn run(interrupt: &AtomicBool) -> Result<(), AppError> {
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+    cli.resolve_week();
     cli.init_tracing();
 
     // Warn once when the user piles on more -v's than the level mapping
@@ -121,13 +131,26 @@ fn run(interrupt: &AtomicBool) -> Result<(), AppError> {
         return handle_holidays(year);
     }
 
-    if let Some(ref out_path) = cli.output {
-        if !is_stdout_sigil(out_path) {
-            validate_output_path(out_path)?;
+    if cli.capabilities {
+        return handle_capabilities();
+    }
+
+    if let Some(ref corpus) = cli.bench_corpus {
+        return handle_bench_corpus(&cli, corpus);
+    }
+
+    let targets = cli.output_targets().map_err(AppError::InvalidOutput)?;
+    for (_, path) in &targets {
+        if let Some(path) = path {
+            validate_output_path(path)?;
         }
     }
 
-    let dir_canonical = validate_dir(&cli.dir)?;
+    let roots = cli
+        .dir
+        .iter()
+        .map(|dir| validate_dir(dir))
+        .collect::<Result<Vec<_>, _>>()?;
 
     // Root span for the whole run, carrying the scanned directory. Every
     // event from here on — the per-file spans, `scan finished`, the summary,
@@ -136,12 +159,34 @@ fn run(interrupt: &AtomicBool) -> Result<(), AppError> {
     // O4). It is an `info_span`, so at the default `warn` level it is inactive
     // and adds nothing to the default output; the context appears from `-v`
     // upward, the same threshold at which `scan finished` becomes visible.
-    let run_span = tracing::info_span!("run", dir = %dir_canonical.display());
+    let dirs: Vec<_> = roots
+        .iter()
+        .map(|root| root.display().to_string())
+        .collect();
+    let run_span = tracing::info_span!("run", dir = %dirs.join(","));
     let _run = run_span.enter();
 
     let mappings = get_weekday_mappings(&cli.locale);
-
-    let (tasks, stats) = scan_files(&cli, &dir_canonical, &mappings, interrupt)?;
+    let path_filters = cli
+        .path_filter
+        .iter()
+        .map(|pattern| PathFilter::new(pattern))
+        .collect::<Result<Vec<_>, _>>()?;
+    let query = search_query(&cli)?;
+    let blocks = cli
+        .custom_agenda
+        .as_deref()
+        .map(|name| custom_blocks(&cli, &roots[0], name))
+        .transpose()?;
+
+    let mut origins = Vec::new();
+    let (mut tasks, stats) = scan_files(
+        &cli,
+        &roots,
+        &mappings,
+        interrupt,
+        cli.dedup.is_some().then_some(&mut origins),
+    )?;
 
     tracing::info!(
         files = stats.files_processed,
@@ -165,7 +210,297 @@ fn run(interrupt: &AtomicBool) -> Result<(), AppError> {
         stats.print_summary();
     }
 
-    let agenda_output = filter_agenda(
+    if cli.lint {
+        let diagnostics = &stats.ts_diagnostics;
+        write_targets(&cli, &targets, |format| {
+            Ok(if format == OutputFormat::Json {
+                serde_json::to_string_pretty(diagnostics)?
+            } else {
+                diagnostics
+                    .iter()
+                    .map(|d| {
+                        format!(
+                            "{}:{}: {}: {} ({})\n",
+                            d.file, d.line, d.severity, d.message, d.timestamp
+                        )
+                    })
+                    .collect()
+            })
+        })?;
+        if !diagnostics.is_empty() {
+            std::process::exit(EXIT_LINT_FINDINGS);
+        }
+        return Ok(());
+    }
+
+    if cli.heading_offset != 0 {
+        for task in &mut tasks {
+            task.level = task
+                .level
+                .map(|level| (i16::from(level) + i16::from(cli.heading_offset)).max(1) as u8);
+        }
+    }
+
+    if cli.inherit_priority {
+        for task in &mut tasks {
+            if task.priority.is_none() {
+                task.priority = task.inherited_priority.take();
+            }
+        }
+    }
+
+    if !cli.no_tag_inheritance {
+        for task in &mut tasks {
+            let Some(mut tags) = task.inherited_tags.take() else {
+                continue;
+            };
+            // Outer headings' tags first, as Org lists them.
+            for tag in task.tags.take().into_iter().flatten() {
+                if !tags.contains(&tag) {
+                    tags.push(tag);
+                }
+            }
+            task.tags = Some(tags);
+        }
+    }
+
+    let keywords = cli.keywords().map_err(AppError::InvalidKeywords)?;
+    for (i, url) in cli.remote.iter().enumerate() {
+        let mut fetched = remote::fetch_tasks(url)?;
+        tracing::info!(url = %remote::redact(url), tasks = fetched.len(), "remote fetched");
+        // JSON does not say whether a custom keyword is open or done.
+        for task_type in fetched.iter_mut().filter_map(|t| t.task_type.as_mut()) {
+            keywords.reclassify(task_type);
+        }
+        if cli.dedup.is_some() {
+            // Each source is a root of its own; remote tasks carry no mtime
+            // and lose every "newest" comparison.
+            let prefix = format!("{}:", remote::host_of(url));
+            origins.resize(tasks.len(), dedup::Origin::default());
+            origins.extend(fetched.iter().map(|task| {
+                dedup::Origin {
+                    root: roots.len() + i,
+                    path: task
+                        .file
+                        .strip_prefix(&*prefix)
+                        .unwrap_or(&task.file)
+                        .to_string(),
+                    mtime: None,
+                }
+            }));
+        }
+        tasks.extend(fetched);
+    }
+
+    if !cli.busy_ics.is_empty() {
+        let home: chrono_tz::Tz = cli
+            .home_tz()
+            .parse()
+            .map_err(|_| AppError::InvalidTimezone(cli.home_tz().to_string()))?;
+        for path in &cli.busy_ics {
+            let events = ics::load_busy(path, home)?;
+            tracing::info!(path = %path.display(), events = events.len(), "calendar imported");
+            tasks.extend(events);
+        }
+    }
+
+    if let Some(policy) = cli.dedup {
+        // Imported calendar events have no origin and are all kept.
+        let (kept, dropped) = dedup::dedup_tasks(tasks, &origins, policy == cli::Dedup::Newest);
+        tracing::info!(dropped, "duplicate tasks dropped");
+        tasks = kept;
+    }
+
+    if let Some(days) = cli.priority_aging {
+        let today = current_date(&cli, agenda::today_in_tz(cli.home_tz())?)?;
+        agenda::age_priorities(&mut tasks, today, days, cli.priorities.as_ref());
+    }
+    if let Some(ref scheme) = cli.priorities {
+        agenda::default_priorities(&mut tasks, scheme);
+    }
+    if !path_filters.is_empty() {
+        tasks.retain(|task| {
+            let path = relative_to_roots(Path::new(&*task.file), &roots);
+            path_filters.iter().any(|filter| filter.matches(path))
+        });
+    }
+    narrow_tasks(&cli, &mut tasks, query.as_ref());
+
+    if cli.clocks_from.is_some() || cli.clocks_to.is_some() {
+        let (from, to) = parse_clock_window(
+            cli.clocks_from.as_deref(),
+            cli.clocks_to.as_deref(),
+            agenda::today_in_tz(cli.home_tz())?,
+        )?;
+        clock::apply_clock_window(&mut tasks, from, to);
+    }
+
+    // Raw clock exports list every clock of every scanned task; agenda
+    // windowing and TODO filtering do not apply to a time-tracking feed.
+    // `output_targets` keeps clock exports and agenda formats apart, so the
+    // first target decides for the whole run.
+    if targets[0].0.is_clock_export() {
+        let rows = clock::clock_rows(&tasks);
+        return write_targets(&cli, &targets, |format| {
+            Ok(match format {
+                OutputFormat::ClocksCsv => render::render_clocks_csv(&rows),
+                _ => serde_json::to_string_pretty(&rows)?,
+            })
+        });
+    }
+
+    let tasks_found = tasks.len();
+    let agenda_output = match blocks {
+        Some(blocks) => {
+            let mut output = Vec::new();
+            for (title, block_cli, query) in blocks {
+                let mut block_tasks = tasks.clone();
+                narrow_tasks(&block_cli, &mut block_tasks, query.as_ref());
+                let agenda = build_grouped_agenda(&block_cli, block_tasks)?;
+                output.push(agenda::AgendaBlock { title, agenda });
+            }
+            agenda::AgendaOutput::Blocks(output)
+        }
+        None => build_grouped_agenda(&cli, tasks)?,
+    };
+
+    let renderers = RendererRegistry::default();
+    let empty = agenda_output.is_empty();
+    if empty && cli.explain_empty {
+        let hints = empty_hints(&cli, &roots, &stats, tasks_found);
+        // JSON targets carry the hints in the document; any other format
+        // renders the empty agenda and reports the hints on stderr.
+        if targets
+            .iter()
+            .any(|(format, _)| *format != OutputFormat::Json)
+        {
+            for hint in &hints {
+                tracing::warn!("empty result: {hint}");
+            }
+        }
+        let result = types::EmptyResult {
+            empty: true,
+            files_matched: stats.files_matched,
+            files_processed: stats.files_processed,
+            tasks_found,
+            hints,
+        };
+        write_targets(&cli, &targets, |format| match format {
+            OutputFormat::Json => Ok(serde_json::to_string_pretty(&result)?),
+            _ => render_agenda(&renderers, format, &agenda_output),
+        })?;
+    } else {
+        write_targets(&cli, &targets, |format| {
+            render_agenda(&renderers, format, &agenda_output)
+        })?;
+    }
+
+    if empty && cli.fail_if_empty {
+        std::process::exit(EXIT_EMPTY);
+    }
+    Ok(())
+}
+
+/// `--query` compiled, if given.
+fn search_query(cli: &Cli) -> Result<Option<SearchQuery>, AppError> {
+    cli.query
+        .as_deref()
+        .map(|query| SearchQuery::new(query, cli.regex))
+        .transpose()
+        .map_err(AppError::InvalidQuery)
+}
+
+/// Blocks of the custom agenda `name` from the config file in `root`, each
+/// with its title, the options it runs with and its compiled `--query`.
+/// Every block is checked here, before the scan.
+fn custom_blocks(
+    cli: &Cli,
+    root: &Path,
+    name: &str,
+) -> Result<Vec<(String, Cli, Option<SearchQuery>)>, AppError> {
+    config::custom_agenda(root, name)?
+        .into_iter()
+        .map(|block| {
+            let block_cli = cli.with_block(&block).map_err(|e| {
+                AppError::InvalidConfig(format!("agenda '{name}', block '{}': {e}", block.title))
+            })?;
+            let query = search_query(&block_cli)?;
+            Ok((block.title, block_cli, query))
+        })
+        .collect()
+}
+
+/// Keep the tasks passing the task filters of `cli`: `--min-priority`,
+/// `--assignee`, `--match`, `--todo-state` and `--query`.
+fn narrow_tasks(cli: &Cli, tasks: &mut Vec<types::Task>, query: Option<&SearchQuery>) {
+    if let Some(ref min) = cli.min_priority {
+        tasks.retain(|task| agenda::meets_min_priority(task, min));
+    }
+
+    if let Some(ref name) = cli.assignee {
+        tasks.retain(|task| agenda::is_assigned_to(task, name));
+    }
+
+    if let Some(ref expr) = cli.tag_match {
+        tasks.retain(|task| expr.matches(task));
+    }
+
+    if !cli.todo_state.is_empty() {
+        tasks.retain(|task| agenda::in_todo_state(task, &cli.todo_state));
+    }
+
+    if cli.hide_done || cli.only_done {
+        tasks.retain(|task| {
+            task.task_type
+                .as_ref()
+                .is_some_and(types::TaskType::is_done)
+                == cli.only_done
+        });
+    }
+
+    if let Some(query) = query {
+        tasks.retain(|task| query.matches(task));
+    }
+}
+
+/// Build the agenda of `tasks`, split into one agenda per value of
+/// `--group-by` when it is given.
+fn build_grouped_agenda(
+    cli: &Cli,
+    tasks: Vec<types::Task>,
+) -> Result<agenda::AgendaOutput, AppError> {
+    let Some(by) = cli.group_by else {
+        return build_agenda(cli, tasks);
+    };
+    let partitions = agenda::group_tasks(tasks, by);
+    if partitions.is_empty() {
+        // Nothing to group, but bad date arguments must still fail.
+        build_agenda(cli, Vec::new())?;
+    }
+    let mut groups = Vec::new();
+    for (name, tasks) in partitions {
+        let agenda = build_agenda(cli, tasks)?;
+        if !agenda.is_empty() {
+            groups.push(agenda::AgendaGroup { by, name, agenda });
+        }
+    }
+    Ok(agenda::AgendaOutput::Grouped(groups))
+}
+
+/// `--current-date` resolved against `today`, or `today` itself.
+fn current_date(cli: &Cli, today: NaiveDate) -> Result<NaiveDate, AppError> {
+    match cli.current_date.as_deref() {
+        Some(value) => agenda::parse_date_expr(value, today)
+            .map_err(|e| AppError::InvalidDate(format!("current-date '{value}': {e}"))),
+        None => Ok(today),
+    }
+}
+
+/// Build the agenda of `tasks` for the selected view, with the deadline
+/// rolling, recurring-entry collapsing and local-time annotations applied.
+fn build_agenda(cli: &Cli, tasks: Vec<types::Task>) -> Result<agenda::AgendaOutput, AppError> {
+    let clocked = cli.log.then(|| clock::clock_rows(&tasks));
+    let mut agenda_output = filter_agenda(
         tasks,
         cli.agenda_scope(),
         crate::agenda::AgendaDates {
@@ -173,13 +508,129 @@ fn run(interrupt: &AtomicBool) -> Result<(), AppError> {
             from: cli.from.as_deref(),
             to: cli.to.as_deref(),
             current_date: cli.current_date.as_deref(),
+            week_start: Some(cli.week_start.weekday()),
+            repeat_horizon: Some(cli.repeat_horizon),
+            scheduled_warning_days: cli.scheduled_warning_days,
         },
-        &cli.tz,
-        cli.tasks_include_done,
-        cli.tasks_include_cancelled,
+        cli.home_tz(),
+        // States picked with --todo-state are shown even when closed.
+        cli.tasks_include_done || cli.only_done || !cli.todo_state.is_empty(),
+        cli.tasks_include_cancelled || !cli.todo_state.is_empty(),
     )?;
+    if cli.agenda_scope() == agenda::AgendaScope::Tasks {
+        if let agenda::AgendaOutput::Tasks(tasks) = &mut agenda_output {
+            agenda::sort_tasks(tasks, cli.sort, cli.reverse);
+        }
+    }
+    if let Some(roll) = cli.roll_deadlines {
+        let forward = roll == cli::RollDeadlines::Forward;
+        match &mut agenda_output {
+            agenda::AgendaOutput::Days(days) => agenda::roll_agenda_deadlines(days, forward),
+            agenda::AgendaOutput::Tasks(tasks) => agenda::roll_deadlines(tasks, forward),
+            agenda::AgendaOutput::Forecast(_)
+            | agenda::AgendaOutput::Quarter(_)
+            | agenda::AgendaOutput::Simulation(_)
+            | agenda::AgendaOutput::FreeSlots(_)
+            | agenda::AgendaOutput::Habits(_)
+            | agenda::AgendaOutput::Grouped(_)
+            | agenda::AgendaOutput::Blocks(_) => {}
+        }
+    }
+    if let agenda::AgendaOutput::Days(days) = &mut agenda_output {
+        if let Some(rows) = clocked {
+            clock::log_clocks(days, rows);
+        }
+        // Before collapsing, which removes later occurrences from their days.
+        if cli.show_gaps {
+            agenda::show_gaps(days, cli.work_hours, cli.min_slot);
+        }
+        if cli.collapse_recurring {
+            agenda::collapse_recurring(days);
+        }
+        // After collapsing, which can empty the days of later occurrences.
+        if cli.skip_empty_days {
+            days.retain(|day| !day.is_empty());
+        }
+        agenda::annotate_local_times(days, cli.home_tz(), &cli.extra_tzs())?;
+        for day in days.iter_mut() {
+            day.summary = day.totals();
+        }
+        if cli.by_category {
+            agenda::group_by_category(days);
+        }
+        if let Some(max) = cli.max_entries_per_day {
+            agenda::cap_entries(days, max as usize);
+        }
+        if cli.timeline {
+            let now = agenda::now_in_tz(cli.home_tz())?;
+            agenda::merge_timeline(days, current_date(cli, now.date())?.and_time(now.time()));
+        }
+    }
+    Ok(agenda_output)
+}
 
-    render_output(&cli, agenda_output)
+/// Explain why a run produced no task, from the most to the least
+/// fundamental cause: nothing matched the glob, nothing in the matched files
+/// was a task, or the tasks found all fall outside the selected view.
+fn empty_hints(
+    cli: &Cli,
+    roots: &[PathBuf],
+    stats: &ProcessingStats,
+    tasks_found: usize,
+) -> Vec<String> {
+    use crate::agenda::AgendaScope;
+
+    let dirs: Vec<_> = roots
+        .iter()
+        .map(|root| root.display().to_string())
+        .collect();
+    let dirs = dirs.join(", ");
+    let mut hints = Vec::new();
+    if stats.files_matched == 0 {
+        hints.push(format!("no files matched glob {} under {dirs}", cli.glob));
+    } else if tasks_found == 0 {
+        hints.push(format!(
+            "{} file(s) matched glob {} under {dirs}, but none contains a TODO/DONE heading or an Org timestamp",
+            stats.files_matched, cli.glob
+        ));
+    } else {
+        hints.push(match cli.agenda_scope() {
+            AgendaScope::Tasks => format!(
+                "{tasks_found} task(s) found, but none is an open TODO; add --tasks-include-done to list finished ones"
+            ),
+            AgendaScope::Search => format!(
+                "{tasks_found} task(s) found, but none mentions the --query text in its heading or content"
+            ),
+            AgendaScope::Stuck => format!(
+                "{tasks_found} task(s) found, but every project has a scheduled, due or NEXT subtask"
+            ),
+            AgendaScope::Rescheduled => format!(
+                "{tasks_found} task(s) found, but no open TODO carries a `Rescheduled from` note"
+            ),
+            AgendaScope::Waiting { days } => format!(
+                "{tasks_found} task(s) found, but no open task has a `WAITING_SINCE` more than {days} day(s) ago"
+            ),
+            AgendaScope::Forecast { .. } | AgendaScope::Quarter { .. } | AgendaScope::Simulate => format!(
+                "{tasks_found} task(s) found, but no open TODO has a deadline or repeater in this period"
+            ),
+            AgendaScope::Free { min_slot, .. } => format!(
+                "no open slot of {min_slot} minute(s) or more within --work-hours on a workday of the selected window"
+            ),
+            AgendaScope::Habits { .. } => format!(
+                "{tasks_found} task(s) found, but none is a `STYLE: habit` task with a repeating SCHEDULED timestamp"
+            ),
+            _ => format!(
+                "{tasks_found} task(s) found, but none falls in the selected agenda window; try --agenda tasks or a wider --from/--to"
+            ),
+        });
+    }
+    if stats.has_warnings() {
+        hints.push(
+            "some files were skipped or failed to read; see the processing summary on stderr"
+                .to_string(),
+        );
+    }
+    hints
 }
 
 /// Handle the `--holidays YEAR` short-circuit: emit a JSON array of
@@ -199,6 +650,113 @@ fn handle_holidays(year: i32) -> Result<(), AppError> {
     Ok(())
 }
 
+/// Handle the `--capabilities` short-circuit: print the feature set of this
+/// build as JSON. The crate version doubles as the JSON schema version (see
+/// ADR-0015), so no separate schema number is reported.
+fn handle_capabilities() -> Result<(), AppError> {
+    fn value_names<T: clap::ValueEnum>() -> Vec<String> {
+        T::value_variants()
+            .iter()
+            .filter_map(|v| v.to_possible_value())
+            .map(|v| v.get_name().to_string())
+            .collect()
+    }
+
+    let capabilities = serde_json::json!({
+        "name": env!("CARGO_PKG_NAME"),
+        "version": env!("CARGO_PKG_VERSION"),
+        "formats": value_names::<OutputFormat>(),
+        "agenda_modes": value_names::<cli::AgendaMode>(),
+        "group_by": value_names::<agenda::GroupBy>(),
+        "repeater_types": timestamp::RepeaterType::ALL.map(|t| t.prefix()),
+        "repeater_units": timestamp::RepeaterUnit::ALL.map(|u| u.suffix()),
+        "task_keywords": types::TaskType::KEYWORDS,
+        "locales": cli::SUPPORTED_LOCALES,
+    });
+    let mut output = serde_json::to_string_pretty(&capabilities)?;
+    ensure_trailing_newline(&mut output);
+    io::stdout()
+        .write_all(output.as_bytes())
+        .map_err(|e| AppError::io("<stdout>", e))?;
+    Ok(())
+}
+
+/// Handle the `--bench-corpus DIR` short-circuit: load every file under
+/// `dir` that matches `--glob`, then parse the whole set repeatedly for at
+/// least [`BENCH_MIN_DURATION`] and print the throughput as JSON. Unreadable,
+/// oversized and non-UTF-8 files are skipped, as in a normal scan.
+fn handle_bench_corpus(cli: &Cli, dir: &Path) -> Result<(), AppError> {
+    let root = validate_dir(dir)?;
+    let glob_matcher = compile_glob(&cli.glob)?;
+    let mappings = get_weekday_mappings(&cli.locale);
+
+    let walker = WalkBuilder::new(&root)
+        .standard_filters(true)
+        .follow_links(false)
+        .same_file_system(true)
+        .build();
+    let mut corpus: Vec<(PathBuf, String)> = Vec::new();
+    let mut buf: Vec<u8> = Vec::with_capacity(READ_BUF_INITIAL_CAP);
+    for entry in walker.flatten() {
+        let path = entry.path();
+        if !entry.file_type().is_some_and(|ft| ft.is_file())
+            || !glob_match(&glob_matcher, path, &root)
+        {
+            continue;
+        }
+        match read_capped_into(path, MAX_FILE_SIZE, &mut buf) {
+            Ok(true) => {}
+            Ok(false) => continue,
+            Err(e) => {
+                tracing::debug!(file = %path.display(), error = %e, "file read failed; skipping");
+                continue;
+            }
+        }
+        match String::from_utf8(std::mem::take(&mut buf)) {
+            Ok(content) => corpus.push((path.to_path_buf(), content)),
+            Err(e) => buf = e.into_bytes(),
+        }
+    }
+
+    let parse_all = || {
+        corpus
+            .iter()
+            .map(|(path, content)| {
+                let mappings = mappings_for_content(&mappings, content);
+                parser::extract_tasks(path, content, mappings, cli.max_tasks).len()
+            })
+            .sum::<usize>()
+    };
+    let start = Instant::now();
+    let tasks = parse_all();
+    let mut passes = 1_u32;
+    while start.elapsed() < BENCH_MIN_DURATION && !corpus.is_empty() {
+        parse_all();
+        passes += 1;
+    }
+    let seconds = start.elapsed().as_secs_f64();
+
+    let bytes: usize = corpus.iter().map(|(_, content)| content.len()).sum();
+    let report = types::BenchReport {
+        files: corpus.len(),
+        bytes,
+        tasks,
+        passes,
+        seconds,
+        mb_per_sec: if bytes == 0 {
+            0.0
+        } else {
+            (bytes as f64 * f64::from(passes)) / 1e6 / seconds
+        },
+    };
+    let mut output = serde_json::to_string_pretty(&report)?;
+    ensure_trailing_newline(&mut output);
+    io::stdout()
+        .write_all(output.as_bytes())
+        .map_err(|e| AppError::io("<stdout>", e))?;
+    Ok(())
+}
+
 /// Ensure `s` ends with exactly one `\n`. Renderers vary: `serde_json` and
 /// the HTML/JSON-array formatters return a string with no trailing newline,
 /// while the Markdown formatter already adds one. Calling this before every
@@ -240,34 +798,45 @@ fn validate_dir(dir: &Path) -> Result<PathBuf, AppError> {
     })
 }
 
-/// Walk `dir_canonical`, apply the `--glob` filter and a keyword pre-filter,
-/// then parse matching files into `Task`s. Returns the accumulated tasks and
-/// a `ProcessingStats` recording skipped/failed files.
+/// Walk every root in `roots`, apply the `--glob` filter and a keyword
+/// pre-filter, then parse matching files into `Task`s. Returns the
+/// accumulated tasks and a `ProcessingStats` recording skipped/failed files.
+/// When `origins` is given, it receives the root, relative path and
+/// modification time of the source file of every task, index-aligned with
+/// the returned tasks.
 fn scan_files(
     cli: &Cli,
-    dir_canonical: &Path,
+    roots: &[PathBuf],
     mappings: &[(&'static str, &'static str)],
     interrupt: &AtomicBool,
+    mut origins: Option<&mut Vec<dedup::Origin>>,
 ) -> Result<(Vec<types::Task>, ProcessingStats), AppError> {
     let glob_matcher = compile_glob(&cli.glob)?;
+    // `--monorepo`: directory scopes and the matchers of their globs, each
+    // built once per run.
+    let mut configs = config::ConfigTree::new();
+    let mut scope_matchers: HashMap<String, globset::GlobMatcher> = HashMap::new();
+    let keywords = cli.keywords().map_err(AppError::InvalidKeywords)?;
+    let matcher = keyword_matcher(&keywords)?;
+    let mut scope_keyword_matchers: HashMap<types::TodoKeywords, RegexMatcher> = HashMap::new();
 
     let mut tasks = Vec::new();
     let mut stats = ProcessingStats {
         max_tasks_limit: cli.max_tasks,
         ..ProcessingStats::default()
     };
-    let matcher = RegexMatcher::new(
-        r"(?m)(^[#*]+\s+(TODO|DONE)\s|DEADLINE:|SCHEDULED:|CREATED:|CLOSED:|CLOCK:)",
-    )
-    .map_err(|e| AppError::Regex(e.to_string()))?;
-
     // Defense-in-depth: refuse to follow symlinks and stay within the chosen
-    // filesystem. Pass `dir_canonical` (absolute) so every emitted path is an
-    // absolute descendant of the root, which lets `strip_prefix(dir_canonical)`
+    // filesystem. Pass the canonical roots (absolute) so every emitted path is
+    // an absolute descendant of a root, which lets `strip_prefix(dir_canonical)`
     // succeed downstream for both glob matching and display-path computation.
     // Using `&cli.dir` (often relative) would silently break multi-segment
-    // glob patterns like `notes/*.md` against a relative `--dir`.
-    let walker = WalkBuilder::new(dir_canonical)
+    // glob patterns like `notes/*.md` against a relative `--dir`. Several
+    // roots are walked one after another, in `--dir` order.
+    let mut builder = WalkBuilder::new(&roots[0]);
+    for root in &roots[1..] {
+        builder.add(root);
+    }
+    let walker = builder
         .standard_filters(true)
         .follow_links(false)
         .same_file_system(true)
@@ -310,10 +879,32 @@ fn scan_files(
         }
 
         let path = entry.path();
-
-        if !glob_match(&glob_matcher, path, dir_canonical) {
+        // The deepest root containing the path, so nested `--dir` values
+        // still yield the shortest relative path.
+        let dir_canonical = roots
+            .iter()
+            .filter(|root| path.starts_with(root))
+            .max_by_key(|root| root.components().count())
+            .unwrap_or(&roots[0]);
+
+        let scope = match path.parent() {
+            Some(dir) if cli.monorepo => Some(configs.scope(dir_canonical, dir)?),
+            _ => None,
+        };
+        let matched = match scope.as_ref().and_then(|s| s.glob.as_ref()) {
+            Some((glob, base)) => {
+                let matcher = match scope_matchers.entry(glob.clone()) {
+                    Entry::Occupied(slot) => slot.into_mut(),
+                    Entry::Vacant(slot) => slot.insert(compile_glob(glob)?),
+                };
+                glob_match(matcher, path, base)
+            }
+            None => glob_match(&glob_matcher, path, dir_canonical),
+        };
+        if !matched {
             continue;
         }
+        stats.files_matched += 1;
 
         // Read once with a hard cap into the reusable buffer. Avoids the
         // TOCTOU window where a separate metadata() check might say a file is
@@ -340,8 +931,26 @@ fn scan_files(
             }
         }
 
+        let file_keywords = match scope {
+            Some(ref scope) => scope
+                .keywords(&keywords)
+                .map_err(|e| AppError::InvalidKeywords(format!("{}: {e}", path.display())))?,
+            None => std::borrow::Cow::Borrowed(&keywords),
+        };
+        let file_matcher = if *file_keywords == keywords {
+            &matcher
+        } else {
+            match scope_keyword_matchers.entry(file_keywords.as_ref().clone()) {
+                Entry::Occupied(slot) => slot.into_mut(),
+                Entry::Vacant(slot) => {
+                    let matcher = keyword_matcher(&file_keywords)?;
+                    slot.insert(matcher)
+                }
+            }
+        };
+
         let mut found = false;
-        if let Err(e) = searcher.search_slice(&matcher, &buf, FoundSink { found: &mut found }) {
+        if let Err(e) = searcher.search_slice(file_matcher, &buf, FoundSink { found: &mut found }) {
             stats.files_failed_search += 1;
             stats.record_failed_path(&path.display().to_string());
             tracing::debug!(file = %path.display(), error = %e, "content search failed; skipping");
@@ -393,18 +1002,51 @@ fn scan_files(
         // key is `file` (not `path`) so the span agrees with the parser events
         // and the `Task.file` output field — one path, one key (2026-05-25
         // review, O3).
+        let front_matter = FrontMatter::parse(content);
+        if front_matter.as_ref().is_some_and(|front| !front.included()) {
+            tracing::debug!(file = %display_path, "front matter sets agenda: false; skipping");
+            continue;
+        }
+
         let span = tracing::debug_span!("file", file = %display_path);
-        let extracted = span.in_scope(|| {
+        let mut extracted = span.in_scope(|| {
             extract_tasks_with_counter(
                 Path::new(&display_path),
                 content,
-                mappings,
+                mappings_for_content(mappings, content),
+                &file_keywords,
+                ExtractOptions {
+                    list_items_as_tasks: cli.list_items_as_tasks,
+                    full_content: cli.full_content,
+                },
                 cli.max_tasks,
-                &mut stats.ts_warnings_emitted,
+                &mut stats.ts_diagnostics,
                 &mut stats.prop_warnings_emitted,
             )
         });
+        if let Some(ref front) = front_matter {
+            for task in &mut extracted {
+                front.apply(task);
+            }
+        }
+        if let Some(ref scope) = scope {
+            for task in &mut extracted {
+                scope.apply(task);
+            }
+        }
         tasks.extend(extracted);
+        if let Some(origins) = origins.as_deref_mut() {
+            let origin = dedup::Origin {
+                root: roots.iter().position(|r| r == dir_canonical).unwrap_or(0),
+                path: path
+                    .strip_prefix(dir_canonical)
+                    .unwrap_or(path)
+                    .display()
+                    .to_string(),
+                mtime: entry.metadata().ok().and_then(|m| m.modified().ok()),
+            };
+            origins.resize(tasks.len(), origin);
+        }
         stats.files_processed += 1;
 
         if tasks.len() >= cli.max_tasks {
@@ -417,43 +1059,92 @@ fn scan_files(
     Ok((tasks, stats))
 }
 
-/// Serialize the agenda result into the requested format and either write it
-/// to `--output` or to stdout.
-fn render_output(cli: &Cli, agenda_output: agenda::AgendaOutput) -> Result<(), AppError> {
-    let mut output = match cli.format {
-        OutputFormat::Json => match agenda_output {
-            agenda::AgendaOutput::Days(days) => serde_json::to_string_pretty(&days)?,
-            agenda::AgendaOutput::Tasks(tasks) => serde_json::to_string_pretty(&tasks)?,
-        },
-        OutputFormat::Markdown => match agenda_output {
-            agenda::AgendaOutput::Days(days) => render::render_days_markdown(&days),
-            agenda::AgendaOutput::Tasks(tasks) => render_markdown(&tasks),
-        },
-        OutputFormat::Html => match agenda_output {
-            agenda::AgendaOutput::Days(days) => render::render_days_html(&days),
-            agenda::AgendaOutput::Tasks(tasks) => render_html(&tasks),
-        },
-    };
-    ensure_trailing_newline(&mut output);
+/// Pre-filter for files worth parsing: a heading or (for
+/// `--list-items-as-tasks`) list-item keyword from `keywords`, a planning
+/// or CLOCK line, an active timestamp (an appointment or a date range
+/// needs no keyword), or a `[!todo]` callout. Headings may sit
+/// inside blockquotes (`> ### TODO ...`) or be setext (`TODO ...`
+/// underlined by `===`), and an Obsidian `> [!todo]` callout is a task
+/// without any keyword line. Keywords are `[A-Z0-9_-]` words, so they need
+/// no escaping.
+fn keyword_matcher(keywords: &types::TodoKeywords) -> Result<RegexMatcher, AppError> {
+    let alternation = keywords.all().collect::<Vec<_>>().join("|");
+    RegexMatcher::new(&format!(
+        r"(?m)(^[ \t]*(>\s*)*([#*]+\s+|(?:[-+*]|\d+[.)])\s+(?:\[[ xX-]\]\s+)?)?({alternation})\s|(?i:\[!todo\])|DEADLINE:|SCHEDULED:|CREATED:|CLOSED:|CLOCK:|<\d{{4}}-\d{{1,2}}-\d{{1,2}})"
+    ))
+    .map_err(|e| AppError::Regex(e.to_string()))
+}
 
-    match cli.output.as_deref() {
-        Some(p) if !is_stdout_sigil(p) => {
-            fs::write(p, output).map_err(|e| AppError::io(p.display().to_string(), e))?
-        }
-        // None or `--output -` both mean stdout. The explicit `-` form is the
-        // standard unix sigil for stdout and lets shell pipelines target it
-        // unambiguously when stdout is otherwise reserved (e.g. tee chains).
-        _ => io::stdout()
-            .write_all(output.as_bytes())
+/// Serialize the agenda result into `format` with its registered renderer.
+fn render_agenda(
+    renderers: &RendererRegistry,
+    format: OutputFormat,
+    agenda_output: &agenda::AgendaOutput,
+) -> Result<String, AppError> {
+    let renderer = format
+        .renderer_name()
+        .and_then(|name| renderers.get(name))
+        .unwrap_or_else(|| {
+            unreachable!("clock exports are rendered in `run` before the agenda is built")
+        });
+    renderer.render(agenda_output)
+}
+
+/// Render and write one document per `--format`/`--output` pair, all from
+/// the same scan.
+fn write_targets(
+    cli: &Cli,
+    targets: &[(OutputFormat, Option<&Path>)],
+    mut render: impl FnMut(OutputFormat) -> Result<String, AppError>,
+) -> Result<(), AppError> {
+    for &(format, path) in targets {
+        write_output(cli, path, render(format)?)?;
+    }
+    Ok(())
+}
+
+/// Write the rendered document to `path` (or stdout), adding the trailing
+/// newline every format ends with and applying `--output-encoding`.
+fn write_output(cli: &Cli, path: Option<&Path>, mut output: String) -> Result<(), AppError> {
+    ensure_trailing_newline(&mut output);
+    let output = cli.output_encoding.encode(&output);
+
+    match path {
+        Some(p) => fs::write(p, &output).map_err(|e| AppError::io(p.display().to_string(), e))?,
+        // None covers both an absent `--output` and `--output -`. The explicit
+        // `-` form is the standard unix sigil for stdout and lets shell
+        // pipelines target it unambiguously when stdout is otherwise reserved
+        // (e.g. tee chains).
+        None => io::stdout()
+            .write_all(&output)
             .map_err(|e| AppError::io("<stdout>", e))?,
     }
 
     Ok(())
 }
 
-/// Returns true when the path is the standard unix sigil `-` meaning stdout.
-fn is_stdout_sigil(path: &Path) -> bool {
-    path.as_os_str() == "-"
+/// Parse `--clocks-from` / `--clocks-to` into an inclusive date window.
+/// Either edge may be absent (open-ended); an inverted window is a usage
+/// error rather than a silently empty clock report.
+fn parse_clock_window(
+    from: Option<&str>,
+    to: Option<&str>,
+    today: NaiveDate,
+) -> Result<(Option<NaiveDate>, Option<NaiveDate>), AppError> {
+    let parse = |label: &str, s: &str| {
+        agenda::parse_date_expr(s, today)
+            .map_err(|e| AppError::InvalidDate(format!("{label} '{s}': {e}")))
+    };
+    let from = from.map(|s| parse("clocks-from", s)).transpose()?;
+    let to = to.map(|s| parse("clocks-to", s)).transpose()?;
+    if let (Some(f), Some(t)) = (from, to) {
+        if f > t {
+            return Err(AppError::DateRange(format!(
+                "--clocks-from {f} is after --clocks-to {t}"
+            )));
+        }
+    }
+    Ok((from, to))
 }
 
 /// Validate that the `--output` target is safe to write:
@@ -581,6 +1272,45 @@ fn format_error_chain(pattern: &str, err: &dyn std::error::Error) -> String {
     msg
 }
 
+/// One `--path-filter` pattern: a glob when it holds a glob metacharacter,
+/// else a path prefix matched on whole components.
+enum PathFilter {
+    Prefix(PathBuf),
+    Glob(globset::GlobMatcher),
+}
+
+impl PathFilter {
+    fn new(pattern: &str) -> Result<Self, AppError> {
+        if pattern.contains(['*', '?', '[', '{']) {
+            compile_glob(pattern).map(PathFilter::Glob)
+        } else if pattern.trim_matches('/').is_empty() {
+            Err(AppError::InvalidGlob(format!(
+                "path filter '{pattern}' names no path"
+            )))
+        } else {
+            Ok(PathFilter::Prefix(PathBuf::from(
+                pattern.trim_end_matches('/'),
+            )))
+        }
+    }
+
+    fn matches(&self, path: &Path) -> bool {
+        match self {
+            PathFilter::Prefix(prefix) => path.starts_with(prefix),
+            PathFilter::Glob(matcher) => matcher.is_match(path),
+        }
+    }
+}
+
+/// `path` relative to the scanned root it lies under; a relative path (the
+/// default output) is returned as is.
+fn relative_to_roots<'a>(path: &'a Path, roots: &[PathBuf]) -> &'a Path {
+    roots
+        .iter()
+        .find_map(|root| path.strip_prefix(root).ok())
+        .unwrap_or(path)
+}
+
 /// Match a path against the compiled glob. The matcher is tried against:
 /// (1) the path relative to `dir_root` — supports patterns like `**/*.md`,
 /// (2) the file name — supports patterns like `*.md` regardless of depth.
@@ -607,6 +1337,39 @@ mod tests {
         glob_match(&matcher, &PathBuf::from(file), Path::new(""))
     }
 
+    #[test]
+    fn path_filter_matches_prefixes_on_components_and_globs() {
+        let matches = |pattern: &str, file: &str| {
+            PathFilter::new(pattern).unwrap().matches(relative_to_roots(
+                Path::new(file),
+                &[PathBuf::from("/vault")],
+            ))
+        };
+        assert!(matches("work", "work/a.md"));
+        assert!(matches("work/", "work/deep/b.md"));
+        assert!(matches("work", "/vault/work/a.md"));
+        assert!(!matches("work", "workshop/a.md"));
+        assert!(matches("projects/acme.md", "projects/acme.md"));
+        assert!(matches("work/**", "work/deep/b.md"));
+        assert!(matches("**/2025-*.md", "journal/2025-03-01.md"));
+        assert!(!matches("work/**", "home/work.md"));
+        assert!(PathFilter::new("/").is_err());
+        assert!(PathFilter::new("work/[").is_err());
+    }
+
+    #[test]
+    fn parse_clock_window_accepts_open_edges_and_rejects_inversion() {
+        let today = NaiveDate::from_ymd_opt(2024, 12, 16).unwrap();
+        assert_eq!(parse_clock_window(None, None, today).unwrap(), (None, None));
+        let (from, to) = parse_clock_window(Some("2024-12-09"), None, today).unwrap();
+        assert_eq!(from, NaiveDate::from_ymd_opt(2024, 12, 9));
+        assert_eq!(to, None);
+        let (from, _) = parse_clock_window(Some("today-1w"), None, today).unwrap();
+        assert_eq!(from, NaiveDate::from_ymd_opt(2024, 12, 9));
+        let err = parse_clock_window(Some("2024-12-15"), Some("2024-12-09"), today).unwrap_err();
+        assert!(matches!(err, AppError::DateRange(_)), "got {err:?}");
+    }
+
     #[test]
     fn glob_simple_extension_matches_at_any_depth() {
         assert!(m("*.md", "test.md"));
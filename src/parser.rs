@@ -1,62 +1,84 @@
-use comrak::nodes::{AstNode, NodeValue};
+use comrak::nodes::{AstNode, ListType, NodeValue, TableAlignment};
 use comrak::{parse_document, Arena, Options};
 use regex::Regex;
 use std::collections::BTreeMap;
+use std::fmt::Write as _;
 use std::path::Path;
-use std::sync::LazyLock;
+use std::sync::{Arc, LazyLock};
 
-use crate::clock::{calculate_total_minutes, extract_clocks, format_duration};
-use crate::regex_limits::compile_bounded;
+use crate::clock::{calculate_total_seconds, extract_clocks, format_duration, parse_duration};
+use crate::regex_limits::{compile_bounded, TS_BODY_MAX};
 use crate::timestamp::{
-    extract_created_normalized, extract_repeater_normalized, extract_timestamp_normalized,
-    normalize_weekdays, parse_timestamp_fields_normalized,
+    count_reschedules, extract_closed_normalized, extract_created_normalized,
+    extract_planning_normalized, extract_repeater_normalized, extract_timestamp_normalized,
+    is_overnight_normalized, normalize_weekdays, pad_timestamp_dates, parse_org_timestamp,
+    parse_timestamp_fields_normalized,
+};
+use crate::types::{
+    Link, LogEntry, Priority, Severity, Subtask, Task, TaskType, TimestampDiagnostic, TodoKeywords,
+    MAX_DIAGNOSTIC_ITEMS,
 };
-use crate::types::{Priority, Task, TaskType, MAX_DIAGNOSTIC_ITEMS};
 
-// Per-call cap on invalid-timestamp warnings reuses `MAX_DIAGNOSTIC_ITEMS` so
-// both diagnostic surfaces (failed-path list and parse-warning stream) stay
-// aligned: "20 entries is already noisy". The counter is owned by the caller
-// -- typically `ProcessingStats::ts_warnings_emitted` for a CLI run -- so
+// Per-call cap on timestamp warnings reuses `MAX_DIAGNOSTIC_ITEMS` so both
+// diagnostic surfaces (failed-path list and parse-warning stream) stay
+// aligned: "20 entries is already noisy". The list is owned by the caller
+// -- typically `ProcessingStats::ts_diagnostics` for a CLI run -- so
 // long-running library use cases and parallel scans do not pollute each
-// other's budget. The previous process-global `AtomicUsize` was replaced as
-// part of the 0.5.0 review (M1).
-fn warn_invalid_timestamp(counter: &mut usize, path: &Path, line: u32, ts: &str) {
-    let n = *counter;
-    *counter = counter.saturating_add(1);
+// other's budget, and its length doubles as the warning counter. Every
+// diagnostic is kept for `--lint` even once logging is suppressed. The
+// previous process-global `AtomicUsize` was replaced as part of the 0.5.0
+// review (M1).
+fn warn_invalid_timestamp(
+    diagnostics: &mut Vec<TimestampDiagnostic>,
+    file: &str,
+    line: u32,
+    ts: &str,
+    severity: Severity,
+    message: String,
+) {
+    let n = diagnostics.len();
     if n < MAX_DIAGNOSTIC_ITEMS {
         tracing::warn!(
-            file = %path.display(),
+            file = %file,
             line,
             timestamp = ts.trim(),
-            "cannot parse timestamp"
+            %severity,
+            "{message}"
         );
     } else if n == MAX_DIAGNOSTIC_ITEMS {
         tracing::warn!(
             limit = MAX_DIAGNOSTIC_ITEMS,
-            "more invalid timestamps suppressed (showed first {MAX_DIAGNOSTIC_ITEMS})"
+            "more timestamp problems suppressed (showed first {MAX_DIAGNOSTIC_ITEMS})"
         );
     }
+    diagnostics.push(TimestampDiagnostic {
+        file: file.to_string(),
+        line,
+        severity,
+        timestamp: ts.trim().to_string(),
+        message,
+    });
 }
 
 // Mirror of `warn_invalid_timestamp` for malformed `org-properties` lines
-// (a line that has no `:`). The counter is owned by the caller -- typically
+// (a line that has no `:`) and `:PROPERTIES:` drawer lines. The counter is owned by the caller -- typically
 // `ProcessingStats::prop_warnings_emitted` for a CLI run -- so the
 // per-`MAX_DIAGNOSTIC_ITEMS` cap spans the whole scan and parallel/library
 // uses do not pollute each other's budget. See ADR-0020.
-fn warn_invalid_property_line(counter: &mut usize, path: &Path, line: u32, raw: &str) {
+fn warn_invalid_property_line(counter: &mut usize, file: &str, line: u32, raw: &str) {
     let n = *counter;
     *counter = counter.saturating_add(1);
     if n < MAX_DIAGNOSTIC_ITEMS {
         tracing::warn!(
-            file = %path.display(),
+            file = %file,
             line,
             content = raw.trim(),
-            "org-properties line has no ':'; skipping"
+            "property line is not `KEY: value`; skipping"
         );
     } else if n == MAX_DIAGNOSTIC_ITEMS {
         tracing::warn!(
             limit = MAX_DIAGNOSTIC_ITEMS,
-            "more malformed org-properties lines suppressed (showed first {MAX_DIAGNOSTIC_ITEMS})"
+            "more malformed property lines suppressed (showed first {MAX_DIAGNOSTIC_ITEMS})"
         );
     }
 }
@@ -86,46 +108,125 @@ static HEADING_TODO_RE: LazyLock<Regex> =
 static HEADING_PRIORITY_RE: LazyLock<Regex> =
     LazyLock::new(|| compile_bounded(r"\[#([A-Z]|6[0-4]|[1-5][0-9]|[0-9])\] ?"));
 
-/// Extract tasks from markdown content with a caller-owned warning counter.
+/// Org link `[[target]]` or `[[target][description]]`. Markdown parses
+/// neither form, so it arrives as plain text.
+static ORG_LINK_RE: LazyLock<Regex> =
+    LazyLock::new(|| compile_bounded(r"\[\[([^\[\]]+)\](?:\[([^\[\]]+)\])?\]"));
+
+/// Org statistics cookie in a heading: `[%]`, `[42%]`, `[/]` or `[2/7]`.
+static STATS_COOKIE_RE: LazyLock<Regex> =
+    LazyLock::new(|| compile_bounded(r"\[(?:\d*%|\d*/\d*)\]"));
+
+/// Active timestamp or range written in a heading's text, as in
+/// `## TODO Standup <2025-03-03 Mon 09:30 +1d>`.
+static HEADING_TIMESTAMP_RE: LazyLock<Regex> = LazyLock::new(|| {
+    compile_bounded(&format!(
+        r"<\d{{4}}-\d{{1,2}}-\d{{1,2}}[^<>]{{0,{TS_BODY_MAX}}}>(?:--?-?<\d{{4}}-\d{{1,2}}-\d{{1,2}}[^<>]{{0,{TS_BODY_MAX}}}>)?"
+    ))
+});
+
+/// Paragraph line that is an Org planning line, `CREATED:` or `CLOCK:`
+/// typed as plain text instead of inline code.
+static PLAIN_PLANNING_RE: LazyLock<Regex> =
+    LazyLock::new(|| compile_bounded(r"^\s*(?:SCHEDULED|DEADLINE|CLOSED|CREATED|CLOCK):"));
+
+/// Source line holding nothing but inline code spans: a planning line,
+/// CLOCK or timestamp left out of `--full-content`.
+static CODE_SPAN_LINE_RE: LazyLock<Regex> =
+    LazyLock::new(|| compile_bounded(r"^\s*(?:`[^`]+`\s*)+$"));
+
+/// `@name` mention in a heading: an `@` at the start or after whitespace or
+/// an opening bracket (so `bob@example.com` is not one), then a name of
+/// letters, digits, `_`, `.` or `-`.
+static MENTION_RE: LazyLock<Regex> =
+    LazyLock::new(|| compile_bounded(r"(?:^|[\s(\[])@([\p{L}\p{N}_][\p{L}\p{N}_.-]*)"));
+
+/// Obsidian `todo` callout marker opening a blockquote's first line, with
+/// the optional fold suffix (`+`/`-`); captures the callout title.
+static TODO_CALLOUT_RE: LazyLock<Regex> =
+    LazyLock::new(|| compile_bounded(r"^(?i)\[!todo\][+-]?\s*(.*)$"));
+
+/// Optional extraction behaviour; the default is off for every switch.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ExtractOptions {
+    /// Also extract list items starting with a TODO keyword.
+    pub list_items_as_tasks: bool,
+    /// Keep every body block of a heading as markdown in `content`
+    /// instead of the text of its paragraphs, lists and quotes.
+    pub full_content: bool,
+}
+
+/// Extract tasks from markdown content with a caller-owned diagnostic list.
 ///
 /// Production callers (see `main.rs::scan_files`) pass
-/// `&mut ProcessingStats::ts_warnings_emitted` so the per-`MAX_DIAGNOSTIC_ITEMS`
-/// cap on invalid-timestamp warnings spans every file in the run. Library
-/// callers can pass their own counter to scope the budget per scan.
+/// `&mut ProcessingStats::ts_diagnostics` so the per-`MAX_DIAGNOSTIC_ITEMS`
+/// cap on timestamp warnings spans every file in the run. Library callers
+/// can pass their own list to scope the budget per scan.
 ///
 /// # Arguments
 /// * `path` - Path to the markdown file. Stored verbatim in `Task.file` for output.
 /// * `content` - File content (UTF-8).
 /// * `mappings` - Weekday name mappings for localization.
+/// * `keywords` - Custom TODO keywords recognised next to the built-in ones.
+/// * `options` - Optional behaviour, see [`ExtractOptions`].
 /// * `max_tasks` - Per-file cap. Parsing stops as soon as this many tasks accumulate.
-/// * `ts_warning_counter` - Mutable counter used to gate invalid-timestamp warnings.
+/// * `ts_diagnostics` - Receives every timestamp problem; its length gates the warnings.
 ///
 /// # Returns
 /// Vector of extracted tasks, capped at `max_tasks`.
+#[allow(clippy::too_many_arguments)]
 pub fn extract_tasks_with_counter(
     path: &Path,
     content: &str,
     mappings: &[(&str, &str)],
+    keywords: &TodoKeywords,
+    options: ExtractOptions,
     max_tasks: usize,
-    ts_warning_counter: &mut usize,
+    ts_diagnostics: &mut Vec<TimestampDiagnostic>,
     prop_warning_counter: &mut usize,
 ) -> Vec<Task> {
+    // One arena per document: comrak's arena cannot be cleared while the
+    // returned nodes borrow it, so it is not reused across files. The walk
+    // below avoids copying node values out of it instead.
     let arena = Arena::new();
     let root = parse_document(&arena, content, &safe_comrak_options());
+    // Shared by every task of the document instead of one copy per task.
+    let file: Arc<str> = Arc::from(path.display().to_string());
 
     let mut tasks = Vec::new();
     let mut current_heading: Option<HeadingInfo> = None;
+    let mut ancestors: Vec<Ancestor> = Vec::new();
+    let source_lines: Vec<&str> = if options.full_content {
+        content.lines().collect()
+    } else {
+        Vec::new()
+    };
 
+    // comrak moves footnote definitions to the end of the document; they
+    // are added to the tasks that cite them instead.
+    let mut footnotes = Vec::new();
     for node in root.children() {
+        if let NodeValue::FootnoteDefinition(ref def) = node.data.borrow().value {
+            footnotes.push((format!("[^{}]", def.name), item_text(node)));
+            continue;
+        }
         process_node(
             node,
-            path,
+            &file,
             &mut tasks,
             &mut current_heading,
+            &mut ancestors,
             mappings,
-            ts_warning_counter,
+            keywords,
+            options.list_items_as_tasks,
+            ts_diagnostics,
             prop_warning_counter,
         );
+        if options.full_content {
+            if let Some(ref mut info) = current_heading {
+                push_full_content(info, node, &source_lines);
+            }
+        }
 
         if tasks.len() >= max_tasks {
             tracing::warn!(
@@ -139,10 +240,13 @@ pub fn extract_tasks_with_counter(
 
     // Flush remaining heading
     if let Some(info) = current_heading.take() {
-        if let Some(task) = finalize_task(path, info, ts_warning_counter) {
+        if let Some(task) = finalize_task(&file, info, ts_diagnostics) {
             tasks.push(task);
         }
     }
+    fill_child_progress(&mut tasks);
+    append_footnotes(&mut tasks, &footnotes);
+    fill_descendant_counts(&mut tasks);
 
     tracing::debug!(
         file = %path.display(),
@@ -157,7 +261,7 @@ pub fn extract_tasks_with_counter(
 /// Extract tasks from markdown content with a per-call warning budget.
 ///
 /// Convenience wrapper around [`extract_tasks_with_counter`] that owns the
-/// counter for the duration of one call. Used by the unit-test suite and
+/// diagnostic list and counter for the duration of one call. Used by the unit-test suite and
 /// available to library callers that scope the invalid-timestamp warning
 /// cap per file. The production CLI (`main.rs::scan_files`) uses
 /// `extract_tasks_with_counter` directly so the cap spans the whole run.
@@ -168,34 +272,47 @@ pub fn extract_tasks(
     mappings: &[(&str, &str)],
     max_tasks: usize,
 ) -> Vec<Task> {
-    let mut counter = 0_usize;
+    let mut diagnostics = Vec::new();
     let mut prop_counter = 0_usize;
     extract_tasks_with_counter(
         path,
         content,
         mappings,
+        &TodoKeywords::default(),
+        ExtractOptions::default(),
         max_tasks,
-        &mut counter,
+        &mut diagnostics,
         &mut prop_counter,
     )
 }
 
 /// Comrak parsing options.
 ///
-/// **Security note**: this is `Options::default()` deliberately. Defaults:
+/// **Security note**: this is `Options::default()` deliberately, with only
+/// the front matter delimiter and the GFM table, strikethrough and footnote
+/// extensions set; none of them passes HTML through. Defaults:
 /// - `render.unsafe_ = false` — raw HTML in markdown is escaped, not passed through.
 /// - `extension.tagfilter = false` (filter not applied, since unsafe HTML is already escaped).
 /// - No extensions that interpret embedded HTML or scripts are enabled.
 ///
 /// **Do not enable `render.unsafe_` or `extension.tagfilter` here without a
-/// security review** — the HTML output goes through `html_escape`, but enabling
+/// security review** — the HTML output goes through `html_escape` (and
+/// `content` through comrak's own defaults in `render`), but enabling
 /// raw HTML would let untrusted markdown inject arbitrary tags into the rendered
 /// page bypassing that escape.
 fn safe_comrak_options() -> Options<'static> {
-    Options::default()
+    let mut options = Options::default();
+    // A leading `---` block is metadata (see `front_matter`), not a
+    // thematic break followed by a setext heading.
+    options.extension.front_matter_delimiter = Some("---".to_owned());
+    options.extension.table = true;
+    options.extension.strikethrough = true;
+    options.extension.footnotes = true;
+    options
 }
 
 /// Information extracted from a heading
+#[derive(Default)]
 struct HeadingInfo {
     heading: String,
     task_type: Option<TaskType>,
@@ -203,39 +320,122 @@ struct HeadingInfo {
     line: u32,
     content: String,
     created: Option<String>,
+    /// `CLOSED:` timestamp, kept apart from the one placing the task.
+    closed: Option<String>,
     timestamp: Option<String>,
+    /// `SCHEDULED:` / `DEADLINE:` timestamps seen so far, one per keyword.
+    planning: Vec<String>,
+    /// Plain inactive `[...]` timestamps seen so far.
+    inactive_timestamps: Vec<String>,
     clocks: Vec<crate::types::ClockEntry>,
+    /// Entries of the `:LOGBOOK:` drawers seen so far.
+    log: Vec<LogEntry>,
+    /// Value of the last inline `Effort:` code span.
+    inline_effort: Option<String>,
     properties: BTreeMap<String, String>,
+    /// `org-log-reschedule` notes seen so far under this heading.
+    reschedules: u32,
+    /// First `http(s)` link in the body paragraphs, the `url` fallback.
+    first_link: Option<String>,
+    /// Links of the heading and body so far.
+    links: Vec<Link>,
+    /// Checklist items from the body's lists.
+    subtasks: Vec<Subtask>,
+    /// Markdown heading level; `None` for a callout task.
+    level: Option<u8>,
+    /// Titles of the enclosing headings, outermost first.
+    outline_path: Vec<String>,
+    /// Priority of the nearest enclosing heading that has one.
+    inherited_priority: Option<Priority>,
+    /// Trailing `:tag:` list of the heading itself.
+    tags: Vec<String>,
+    /// Statistics cookie (`[2/7]`, `[%]`) as written in the heading.
+    progress_cookie: Option<String>,
+    /// Tags of the enclosing headings, outermost first.
+    inherited_tags: Vec<String>,
+    /// Markdown of the body blocks, collected for `--full-content`.
+    full_content: Option<String>,
+}
+
+/// A heading enclosing the current position of the walk.
+struct Ancestor {
+    level: u8,
+    /// Heading text without keyword and priority cookie.
+    title: String,
+    priority: Option<Priority>,
+    tags: Vec<String>,
+}
+
+/// Priority of the innermost heading in `ancestors` that carries one.
+fn nearest_priority(ancestors: &[Ancestor]) -> Option<Priority> {
+    ancestors.iter().rev().find_map(|a| a.priority.clone())
+}
+
+/// Tags of `ancestors`, outermost first, each once.
+fn ancestor_tags(ancestors: &[Ancestor]) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in ancestors.iter().flat_map(|a| &a.tags) {
+        if !tags.contains(tag) {
+            tags.push(tag.clone());
+        }
+    }
+    tags
+}
+
+/// Titles of `ancestors`, outermost first.
+fn outline_path(ancestors: &[Ancestor]) -> Vec<String> {
+    ancestors.iter().map(|a| a.title.clone()).collect()
 }
 
 /// Process a single markdown node
+// The walk state is passed piecemeal so each branch borrows only what it
+// touches; bundling it would not make the recursion any clearer.
+#[allow(clippy::too_many_arguments)]
 fn process_node<'a>(
     node: &'a AstNode<'a>,
-    path: &Path,
+    file: &Arc<str>,
     tasks: &mut Vec<Task>,
     current_heading: &mut Option<HeadingInfo>,
+    ancestors: &mut Vec<Ancestor>,
     mappings: &[(&str, &str)],
-    ts_warning_counter: &mut usize,
+    keywords: &TodoKeywords,
+    list_items_as_tasks: bool,
+    ts_diagnostics: &mut Vec<TimestampDiagnostic>,
     prop_warning_counter: &mut usize,
 ) {
-    // Snapshot the borrow once — clone the value (cheap for Heading/Paragraph) and
-    // read the sourcepos line in the same scope; drop before any code that
-    // recurses into children (which take their own borrows).
-    let (value_clone, line) = {
-        let data = node.data.borrow();
-        (data.value.clone(), data.sourcepos.start.line as u32)
-    };
-    match value_clone {
-        NodeValue::Heading(_) => {
+    // Hold a shared borrow for the whole match instead of cloning the value:
+    // a clone copied every code block's literal once per file. Nothing in the
+    // parser borrows a node mutably, so recursing into children (which take
+    // their own shared borrows) is safe.
+    let data = node.data.borrow();
+    let line = data.sourcepos.start.line as u32;
+    match &data.value {
+        NodeValue::Heading(node_heading) => {
             // Finalize previous heading first
             if let Some(info) = current_heading.take() {
-                if let Some(task) = finalize_task(path, info, ts_warning_counter) {
+                if let Some(task) = finalize_task(file, info, ts_diagnostics) {
                     tasks.push(task);
                 }
             }
 
             let text = extract_text(node);
-            let (task_type, priority, heading) = parse_heading(&text);
+            let mut links = Vec::new();
+            collect_links(node, &mut links);
+            let (task_type, priority, heading) = parse_heading(&text, keywords);
+            let (heading, tags) = split_heading_tags(heading);
+            let (heading, progress_cookie) = split_stats_cookie(heading);
+            let (heading, timestamp) = split_heading_timestamp(heading, mappings);
+            // Headings at the same or a deeper level close their sections.
+            ancestors.retain(|a| a.level < node_heading.level);
+            let inherited_priority = nearest_priority(ancestors);
+            let path = outline_path(ancestors);
+            let inherited_tags = ancestor_tags(ancestors);
+            ancestors.push(Ancestor {
+                level: node_heading.level,
+                title: heading.clone(),
+                priority: priority.clone(),
+                tags: tags.clone(),
+            });
             *current_heading = Some(HeadingInfo {
                 heading,
                 task_type,
@@ -243,99 +443,708 @@ fn process_node<'a>(
                 line,
                 content: String::new(),
                 created: None,
-                timestamp: None,
+                closed: None,
+                timestamp,
+                planning: Vec::new(),
+                inactive_timestamps: Vec::new(),
                 clocks: Vec::new(),
+                log: Vec::new(),
+                inline_effort: None,
                 properties: BTreeMap::new(),
+                reschedules: 0,
+                first_link: None,
+                links,
+                subtasks: Vec::new(),
+                level: Some(node_heading.level),
+                outline_path: path,
+                inherited_priority,
+                tags,
+                inherited_tags,
+                progress_cookie,
+                full_content: None,
             });
         }
         NodeValue::Paragraph => {
             if let Some(ref mut info) = current_heading {
-                let (created, timestamp) = extract_timestamps_from_node(node, mappings);
-                let content = extract_paragraph_text(node);
+                let (created, timestamp) = extract_timestamps_from_node(node, mappings, info);
+                // A `:PROPERTIES:` drawer typed as plain lines is metadata,
+                // not body text: only the lines around it are content.
+                let mut lines = String::new();
+                collect_line_text(node, &mut lines);
+                let content = match parse_property_drawer(
+                    &lines,
+                    &mut info.properties,
+                    file,
+                    line,
+                    prop_warning_counter,
+                ) {
+                    Some(rest) => rest.trim().to_string(),
+                    None => extract_paragraph_text(node),
+                };
+                // Lines left empty by a code span, and planning lines typed
+                // as plain text, are not body text.
+                let content = content
+                    .lines()
+                    .filter(|l| !l.trim().is_empty() && !PLAIN_PLANNING_RE.is_match(l))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                if info.first_link.is_none() {
+                    info.first_link = first_web_link(node);
+                }
+                collect_links(node, &mut info.links);
 
-                for child in node.children() {
+                for child in node.descendants() {
                     if let NodeValue::Code(code) = &child.data.borrow().value {
                         info.clocks.extend(extract_clocks(&code.literal));
+                        info.reschedules += count_reschedules(&code.literal);
+                        if let Some(effort) = inline_effort(&code.literal) {
+                            info.inline_effort = Some(effort);
+                        }
                     }
                 }
 
                 if created.is_some() {
                     info.created = created;
                 }
-                if timestamp.is_some() {
-                    info.timestamp = timestamp;
+                if let Some(ts) = timestamp {
+                    set_timestamp(info, ts);
                 }
-                if !content.is_empty() {
-                    if info.content.is_empty() {
-                        info.content = content;
-                    } else {
-                        info.content.push_str("\n\n");
-                        info.content.push_str(&content);
+                for plain in lines.lines().filter(|l| PLAIN_PLANNING_RE.is_match(l)) {
+                    apply_planning_code(info, plain.trim(), mappings);
+                }
+                push_content(info, &content);
+            }
+        }
+        NodeValue::BlockQuote => {
+            let title = node.first_child().and_then(todo_callout_title);
+            // A `> [!todo] Title` callout is a task of its own, scoped to the
+            // blockquote: the surrounding heading resumes after it. Any other
+            // blockquote is transparent and its content belongs to the
+            // current heading.
+            let outer = title.as_ref().map(|_| current_heading.take());
+            // Where a plain quote's text starts in the heading's content.
+            let quote_start = match (&outer, current_heading.as_ref()) {
+                (None, Some(info)) => Some((info.line, info.content.len())),
+                _ => None,
+            };
+            if let Some(title) = title {
+                let (task_type, priority, heading) = parse_heading(&title, keywords);
+                let (heading, tags) = split_heading_tags(heading);
+                let (heading, progress_cookie) = split_stats_cookie(heading);
+                *current_heading = Some(HeadingInfo {
+                    heading,
+                    task_type: task_type.or(Some(TaskType::Todo)),
+                    priority,
+                    line,
+                    content: String::new(),
+                    created: None,
+                    closed: None,
+                    timestamp: None,
+                    planning: Vec::new(),
+                    inactive_timestamps: Vec::new(),
+                    clocks: Vec::new(),
+                    log: Vec::new(),
+                    inline_effort: None,
+                    properties: BTreeMap::new(),
+                    reschedules: 0,
+                    first_link: None,
+                    links: Vec::new(),
+                    subtasks: Vec::new(),
+                    level: None,
+                    outline_path: outline_path(ancestors),
+                    inherited_priority: nearest_priority(ancestors),
+                    tags,
+                    inherited_tags: ancestor_tags(ancestors),
+                    progress_cookie,
+                    full_content: None,
+                });
+            }
+            for child in node.children() {
+                process_node(
+                    child,
+                    file,
+                    tasks,
+                    current_heading,
+                    ancestors,
+                    mappings,
+                    keywords,
+                    list_items_as_tasks,
+                    ts_diagnostics,
+                    prop_warning_counter,
+                );
+                // The callout's title paragraph may carry planning lines, but
+                // its text is the heading, not body content.
+                if outer.is_some() && child.previous_sibling().is_none() {
+                    if let Some(ref mut info) = current_heading {
+                        info.content.clear();
                     }
                 }
             }
+            if let Some(outer) = outer {
+                if let Some(info) = current_heading.take() {
+                    if let Some(task) = finalize_task(file, info, ts_diagnostics) {
+                        tasks.push(task);
+                    }
+                }
+                *current_heading = outer;
+            }
+            // A heading inside the quote started a new section; its text is
+            // not quoted.
+            if let (Some((heading_line, start)), Some(info)) = (quote_start, current_heading) {
+                if info.line == heading_line {
+                    quote_content(&mut info.content, start);
+                }
+            }
         }
-        NodeValue::CodeBlock(code) => {
+        NodeValue::List(_) => {
+            let mut task_items = Vec::new();
+            if list_items_as_tasks {
+                find_task_items(node, keywords, &mut task_items);
+            }
+            // A list item that is a task of its own keeps its checkboxes,
+            // links and planning lines away from the enclosing heading.
+            let outside = |n: &&'a AstNode<'a>| {
+                !n.ancestors()
+                    .any(|a| task_items.iter().any(|t| std::ptr::eq(*t, a)))
+            };
             if let Some(ref mut info) = current_heading {
-                // Performance: check the property-block info string first and
-                // return early on a match, so an org-properties block skips the
-                // backtick-strip / weekday-normalise / clock-extract work below.
-                // For every other code block the only added cost is this one
-                // `&str` comparison. The grep pre-filter (main.rs) is NOT widened
-                // for `org-properties`, so the set of scanned files is unchanged.
-                if code.info.trim() == "org-properties" {
-                    parse_org_properties(
-                        &code.literal,
-                        &mut info.properties,
-                        path,
-                        line,
-                        prop_warning_counter,
-                    );
-                } else {
-                    let raw = code.literal.trim();
-                    // An indented code block (4-space indent) reaches us with
-                    // the planning line still wrapped in inline-code backticks
-                    // (`    \`DEADLINE: <...>\``). Comrak strips the indent but
-                    // leaves the wrapping backticks in `code.literal`, which
-                    // would otherwise prevent the DEADLINE/SCHEDULED/CREATED
-                    // regex from anchoring on the keyword. Drop a matched
-                    // backtick pair before regex matching.
-                    let literal = strip_wrapping_backticks(raw);
-                    let normalized = normalize_weekdays(literal, mappings);
-                    let created = extract_created_normalized(&normalized);
-                    let timestamp = extract_timestamp_normalized(&normalized);
-
-                    info.clocks.extend(extract_clocks(literal));
-
-                    if created.is_some() {
-                        info.created = created;
-                    }
-                    if timestamp.is_some() {
-                        info.timestamp = timestamp;
+                info.subtasks.extend(
+                    node.descendants()
+                        .filter(outside)
+                        .filter_map(checklist_item),
+                );
+                let mut list = String::new();
+                write_list(node, 0, &task_items, &mut list);
+                push_content(info, list.trim_end());
+                for child in node.descendants().filter(outside) {
+                    if matches!(child.data.borrow().value, NodeValue::Paragraph) {
+                        collect_links(child, &mut info.links);
                     }
+                    apply_nested_node(child, info, file, mappings, prop_warning_counter);
+                }
+            }
+            for item in task_items {
+                let info = list_item_info(
+                    item,
+                    ancestors,
+                    keywords,
+                    file,
+                    mappings,
+                    prop_warning_counter,
+                );
+                if let Some(task) = info.and_then(|info| finalize_task(file, info, ts_diagnostics))
+                {
+                    tasks.push(task);
                 }
             }
         }
+        NodeValue::Table(_) => {
+            if let Some(ref mut info) = current_heading {
+                collect_nested_metadata(node, info, file, mappings, prop_warning_counter);
+                push_content(info, &table_markdown(node));
+            }
+        }
+        NodeValue::CodeBlock(code) => {
+            if let Some(ref mut info) = current_heading {
+                apply_code_block(
+                    info,
+                    &code.info,
+                    &code.literal,
+                    file,
+                    line,
+                    mappings,
+                    prop_warning_counter,
+                );
+            }
+        }
+        // Any other container (HTML blocks, footnotes, ...): planning lines,
+        // CLOCKs and property blocks count wherever they sit in the heading's
+        // section. Paragraph text inside stays out of `content`.
+        _ => {
+            if let Some(ref mut info) = current_heading {
+                collect_nested_metadata(node, info, file, mappings, prop_warning_counter);
+            }
+        }
+    }
+}
+
+/// Append one block of body text to the heading's `content`, after a blank
+/// line.
+fn push_content(info: &mut HeadingInfo, block: &str) {
+    if block.is_empty() {
+        return;
+    }
+    if !info.content.is_empty() {
+        info.content.push_str("\n\n");
+    }
+    info.content.push_str(block);
+}
+
+/// Turn the text appended to `content` from `start` on into a `> ` quote.
+fn quote_content(content: &mut String, start: usize) {
+    let quoted = content.split_off(start);
+    let body = quoted.strip_prefix("\n\n").unwrap_or(&quoted);
+    if body.is_empty() {
+        return;
+    }
+    if !content.is_empty() {
+        content.push_str("\n\n");
+    }
+    let lines: Vec<String> = body
+        .lines()
+        .map(|line| match line {
+            "" => ">".to_string(),
+            _ => format!("> {line}"),
+        })
+        .collect();
+    content.push_str(&lines.join("\n"));
+}
+
+/// Markdown of a list's items for `content`, nested lists indented by two
+/// spaces per `depth`. Checklist items (reported as `subtasks`), list items
+/// that are tasks of their own and items holding only inline code
+/// (planning lines, CLOCKs) are left out.
+fn write_list<'a>(
+    list: &'a AstNode<'a>,
+    depth: usize,
+    task_items: &[&'a AstNode<'a>],
+    out: &mut String,
+) {
+    let (ordered, start) = match &list.data.borrow().value {
+        NodeValue::List(l) => (l.list_type == ListType::Ordered, l.start),
+        _ => return,
+    };
+    for (index, item) in list.children().enumerate() {
+        if task_items.iter().any(|t| std::ptr::eq(*t, item)) {
+            continue;
+        }
+        let text = match checklist_item(item) {
+            Some(_) => String::new(),
+            None => item_text(item),
+        };
+        // Items under a left-out one move up to its depth.
+        let mut nested = depth;
+        if !text.is_empty() {
+            let indent = "  ".repeat(depth);
+            if ordered {
+                let _ = writeln!(out, "{indent}{}. {text}", start + index);
+            } else {
+                let _ = writeln!(out, "{indent}- {text}");
+            }
+            nested += 1;
+        }
+        for child in item.children() {
+            if matches!(child.data.borrow().value, NodeValue::List(_)) {
+                write_list(child, nested, task_items, out);
+            }
+        }
+    }
+}
+
+/// A GFM table as markdown for `content`: one `| a | b |` line per row,
+/// the alignment rule after the header row.
+fn table_markdown<'a>(table: &'a AstNode<'a>) -> String {
+    let rule: Vec<&str> = match &table.data.borrow().value {
+        NodeValue::Table(t) => t
+            .alignments
+            .iter()
+            .map(|alignment| match alignment {
+                TableAlignment::Left => ":---",
+                TableAlignment::Center => ":---:",
+                TableAlignment::Right => "---:",
+                TableAlignment::None => "---",
+            })
+            .collect(),
+        _ => return String::new(),
+    };
+    let mut lines = Vec::new();
+    for (index, row) in table.children().enumerate() {
+        let cells: Vec<String> = row
+            .children()
+            .map(|cell| {
+                let mut text = String::new();
+                collect_item_text(cell, &mut text);
+                text.trim().replace('|', "\\|")
+            })
+            .collect();
+        lines.push(format!("| {} |", cells.join(" | ")));
+        if index == 0 {
+            lines.push(format!("| {} |", rule.join(" | ")));
+        }
+    }
+    lines.join("\n")
+}
+
+/// Add `[^name]: text` to the `content` of every task citing a footnote.
+fn append_footnotes(tasks: &mut [Task], footnotes: &[(String, String)]) {
+    for task in tasks {
+        let cited: Vec<String> = footnotes
+            .iter()
+            .filter(|(label, _)| task.content.contains(label.as_str()))
+            .map(|(label, text)| format!("{label}: {text}"))
+            .collect();
+        if cited.is_empty() {
+            continue;
+        }
+        if !task.content.is_empty() {
+            task.content.push_str("\n\n");
+        }
+        task.content.push_str(&cited.join("\n"));
+    }
+}
+
+/// Text of a list item's own paragraphs, joined by spaces; a paragraph of
+/// nothing but code spans is metadata and left out.
+fn item_text<'a>(item: &'a AstNode<'a>) -> String {
+    let mut parts = Vec::new();
+    for child in item.children() {
+        if !matches!(child.data.borrow().value, NodeValue::Paragraph) {
+            continue;
+        }
+        let mut plain = String::new();
+        collect_text_recursive(child, &mut plain);
+        if plain.trim().is_empty() {
+            continue;
+        }
+        let mut text = String::new();
+        collect_item_text(child, &mut text);
+        parts.push(text.trim().to_string());
+    }
+    parts.join(" ")
+}
+
+/// Append the markdown source of a top-level body block to the heading's
+/// `--full-content`. Property blocks and drawers, `[!todo]` callouts (tasks
+/// of their own) and paragraph lines holding only code spans are metadata
+/// and stay out.
+fn push_full_content<'a>(info: &mut HeadingInfo, node: &'a AstNode<'a>, source_lines: &[&str]) {
+    let data = node.data.borrow();
+    let paragraph = match &data.value {
+        NodeValue::Heading(_) => return,
+        NodeValue::CodeBlock(code) if code.info.trim() == "org-properties" => return,
+        NodeValue::BlockQuote if node.first_child().and_then(todo_callout_title).is_some() => {
+            return
+        }
+        NodeValue::Paragraph => true,
+        _ => false,
+    };
+    let start = data.sourcepos.start.line.max(1);
+    let end = data.sourcepos.end.line.min(source_lines.len());
+    let Some(lines) = source_lines.get(start - 1..end) else {
+        return;
+    };
+    let mut kept = Vec::with_capacity(lines.len());
+    let mut in_drawer = false;
+    for line in lines {
+        let line = line.trim_end();
+        if paragraph {
+            let trimmed = line.trim_start();
+            if trimmed.eq_ignore_ascii_case(":PROPERTIES:") {
+                in_drawer = true;
+            }
+            if in_drawer || CODE_SPAN_LINE_RE.is_match(line) || PLAIN_PLANNING_RE.is_match(line) {
+                in_drawer &= !trimmed.eq_ignore_ascii_case(":END:");
+                continue;
+            }
+        }
+        kept.push(line);
+    }
+    let block = kept.join("\n");
+    // A list's source range ends after the blank line that closes it.
+    let block = block.trim_matches('\n');
+    if block.trim().is_empty() {
+        return;
+    }
+    let full = info.full_content.get_or_insert_with(String::new);
+    if !full.is_empty() {
+        full.push_str("\n\n");
+    }
+    full.push_str(block);
+}
+
+/// Fold one fenced or indented code block into the current heading: an
+/// `org-properties` block feeds `properties`, any other block is scanned for
+/// planning lines, CLOCKs and reschedule notes.
+fn apply_code_block(
+    info: &mut HeadingInfo,
+    code_info: &str,
+    literal: &str,
+    file: &str,
+    line: u32,
+    mappings: &[(&str, &str)],
+    prop_warning_counter: &mut usize,
+) {
+    // Performance: check the property-block info string first and
+    // return early on a match, so an org-properties block skips the
+    // backtick-strip / weekday-normalise / clock-extract work below.
+    // For every other code block the only added cost is this one
+    // `&str` comparison. The grep pre-filter (main.rs) is NOT widened
+    // for `org-properties`, so the set of scanned files is unchanged.
+    if code_info.trim() == "org-properties" {
+        parse_org_properties(
+            literal,
+            &mut info.properties,
+            file,
+            line,
+            prop_warning_counter,
+        );
+        return;
+    }
+    // `:LOGBOOK:` and `:PROPERTIES:` drawers pasted into a plain (or
+    // `org`) code block. The logbook's CLOCK lines also reach `clocks`
+    // through the planning scan below.
+    let language = code_info.split_whitespace().next().unwrap_or("");
+    if matches!(language, "" | "org") {
+        if let Some(entries) = parse_logbook_drawer(literal) {
+            info.log.extend(entries);
+        }
+        let rest = parse_property_drawer(
+            literal,
+            &mut info.properties,
+            file,
+            line.saturating_add(1),
+            prop_warning_counter,
+        );
+        // An `org` block holds Org text, one planning line, CLOCK or
+        // timestamp per line (`CLOSED:`, then `CREATED:` on the next), so
+        // each line is read on its own, as a run of inline code would be.
+        if language == "org" {
+            let text = rest.as_deref().unwrap_or(literal);
+            for org_line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+                apply_planning_code(info, org_line, mappings);
+            }
+            return;
+        }
+        if let Some(rest) = rest {
+            apply_planning_code(info, strip_wrapping_backticks(rest.trim()), mappings);
+            return;
+        }
+    }
+    // An indented code block (4-space indent) reaches us with
+    // the planning line still wrapped in inline-code backticks
+    // (`    \`DEADLINE: <...>\``). Comrak strips the indent but
+    // leaves the wrapping backticks in `code.literal`, which
+    // would otherwise prevent the DEADLINE/SCHEDULED/CREATED
+    // regex from anchoring on the keyword. Drop a matched
+    // backtick pair before regex matching.
+    apply_planning_code(info, strip_wrapping_backticks(literal.trim()), mappings);
+}
+
+/// Planning lines, CLOCKs and reschedule notes from one code literal. A
+/// later planning line overrides an earlier one, as across paragraphs.
+fn apply_planning_code(info: &mut HeadingInfo, literal: &str, mappings: &[(&str, &str)]) {
+    let normalized = normalize_weekdays(literal, mappings);
+    let created = extract_created_normalized(&normalized);
+    let timestamp = extract_timestamp_normalized(&normalized);
+    record_planning(&mut info.planning, &normalized);
+    if let Some(closed) = extract_closed_normalized(&normalized) {
+        info.closed = Some(closed);
+    }
+
+    info.clocks.extend(extract_clocks(literal));
+    info.reschedules += count_reschedules(literal);
+    if let Some(effort) = inline_effort(literal) {
+        info.inline_effort = Some(effort);
+    }
+
+    if created.is_some() {
+        info.created = created;
+    }
+    if let Some(ts) = timestamp {
+        if is_plain_inactive(&ts) {
+            info.inactive_timestamps.push(ts.clone());
+        }
+        set_timestamp(info, ts);
+    }
+}
+
+/// Add the planning timestamps of a normalized literal; a later `SCHEDULED:`
+/// or `DEADLINE:` replaces an earlier one of the same keyword.
+fn record_planning(planning: &mut Vec<String>, normalized: &str) {
+    for ts in extract_planning_normalized(normalized) {
+        let keyword = &ts[..ts.find(':').unwrap_or(0)];
+        match planning.iter_mut().find(|seen| seen.starts_with(keyword)) {
+            Some(seen) => *seen = ts,
+            None => planning.push(ts),
+        }
+    }
+}
+
+/// Metadata from every inline code span and code block nested anywhere
+/// below `node`, in document order.
+fn collect_nested_metadata<'a>(
+    node: &'a AstNode<'a>,
+    info: &mut HeadingInfo,
+    file: &str,
+    mappings: &[(&str, &str)],
+    prop_warning_counter: &mut usize,
+) {
+    for child in node.descendants() {
+        apply_nested_node(child, info, file, mappings, prop_warning_counter);
+    }
+}
+
+/// Apply one node of [`collect_nested_metadata`]'s walk.
+fn apply_nested_node<'a>(
+    node: &'a AstNode<'a>,
+    info: &mut HeadingInfo,
+    file: &str,
+    mappings: &[(&str, &str)],
+    prop_warning_counter: &mut usize,
+) {
+    let data = node.data.borrow();
+    match &data.value {
+        NodeValue::Code(code) => apply_planning_code(info, &code.literal, mappings),
+        NodeValue::CodeBlock(code) => apply_code_block(
+            info,
+            &code.info,
+            &code.literal,
+            file,
+            data.sourcepos.start.line as u32,
+            mappings,
+            prop_warning_counter,
+        ),
         _ => {}
     }
 }
 
-fn finalize_task(path: &Path, info: HeadingInfo, ts_warning_counter: &mut usize) -> Option<Task> {
+/// First line of a list item, after an optional checkbox: `TODO Call
+/// dentist` for `- [ ] TODO Call dentist`. Inline code is left out, as in
+/// a heading.
+fn list_item_title<'a>(item: &'a AstNode<'a>) -> Option<String> {
+    if !matches!(item.data.borrow().value, NodeValue::Item(_)) {
+        return None;
+    }
+    let paragraph = item.first_child()?;
+    if !matches!(paragraph.data.borrow().value, NodeValue::Paragraph) {
+        return None;
+    }
+    let mut text = String::new();
+    collect_line_text(paragraph, &mut text);
+    let line = text.lines().next().unwrap_or("").trim();
+    let line = match line.get(..3) {
+        Some("[ ]" | "[x]" | "[X]" | "[-]") => line[3..].trim_start(),
+        _ => line,
+    };
+    Some(line.to_string())
+}
+
+/// `--list-items-as-tasks`: the items under `node` whose first line starts
+/// with a TODO keyword, optionally after a checkbox. The items nested in
+/// such an item belong to it and are not searched.
+fn find_task_items<'a>(
+    node: &'a AstNode<'a>,
+    keywords: &TodoKeywords,
+    found: &mut Vec<&'a AstNode<'a>>,
+) {
+    for child in node.children() {
+        let is_task =
+            list_item_title(child).is_some_and(|title| parse_heading(&title, keywords).0.is_some());
+        if is_task {
+            found.push(child);
+        } else {
+            find_task_items(child, keywords, found);
+        }
+    }
+}
+
+/// Task of a list item found by [`find_task_items`]. The item is read
+/// like a heading section: its first line is the heading, the code spans
+/// and blocks anywhere in it are its planning lines and properties, and
+/// its nested checkboxes are its checklist. The keyword decides the state,
+/// not the item's own checkbox.
+fn list_item_info<'a>(
+    item: &'a AstNode<'a>,
+    ancestors: &[Ancestor],
+    keywords: &TodoKeywords,
+    file: &str,
+    mappings: &[(&str, &str)],
+    prop_warning_counter: &mut usize,
+) -> Option<HeadingInfo> {
+    let (task_type, priority, heading) = parse_heading(&list_item_title(item)?, keywords);
+    let (heading, tags) = split_heading_tags(heading);
+    let (heading, progress_cookie) = split_stats_cookie(heading);
+    let mut info = HeadingInfo {
+        heading,
+        task_type,
+        priority,
+        line: item.data.borrow().sourcepos.start.line as u32,
+        outline_path: outline_path(ancestors),
+        inherited_priority: nearest_priority(ancestors),
+        tags,
+        inherited_tags: ancestor_tags(ancestors),
+        progress_cookie,
+        ..HeadingInfo::default()
+    };
+    for child in item.descendants() {
+        if matches!(child.data.borrow().value, NodeValue::Paragraph) {
+            collect_links(child, &mut info.links);
+        }
+        apply_nested_node(child, &mut info, file, mappings, prop_warning_counter);
+    }
+    info.subtasks
+        .extend(item.descendants().skip(1).filter_map(checklist_item));
+    Some(info)
+}
+
+fn finalize_task(
+    file: &Arc<str>,
+    info: HeadingInfo,
+    ts_diagnostics: &mut Vec<TimestampDiagnostic>,
+) -> Option<Task> {
     if info.task_type.is_none() && info.created.is_none() && info.timestamp.is_none() {
         return None;
     }
 
     let line = info.line;
+    let mut warn = |ts: &str, severity, message| {
+        warn_invalid_timestamp(ts_diagnostics, file, line, ts, severity, message)
+    };
+    // Normalization pass: the extractors accept a one-digit month or day so
+    // the planning line is not lost; everything downstream sees the
+    // canonical form.
+    let mut pad = |ts: String| match pad_timestamp_dates(&ts) {
+        Some(padded) => {
+            warn(&ts, Severity::Warning, format!("date padded to {padded}"));
+            padded
+        }
+        None => ts,
+    };
+    let created = info.created.map(&mut pad);
+    let closed = info.closed.map(&mut pad);
+    let timestamp = info.timestamp.map(&mut pad);
+    // Diagnostics stay with the primary timestamp; the planning entries are
+    // only brought into the canonical form.
+    let planning = (info.planning.len() > 1).then(|| {
+        info.planning
+            .into_iter()
+            .map(|ts| pad_timestamp_dates(&ts).unwrap_or(ts))
+            .collect()
+    });
+    let inactive_timestamps = (!info.inactive_timestamps.is_empty()).then(|| {
+        info.inactive_timestamps
+            .into_iter()
+            .map(|ts| pad_timestamp_dates(&ts).unwrap_or(ts))
+            .collect()
+    });
+    if let Some(ref ts) = timestamp {
+        match parse_org_timestamp(ts, None) {
+            Err(e) => warn(ts, Severity::Error, format!("cannot parse timestamp: {e}")),
+            Ok(parsed) => {
+                for issue in parsed.issues {
+                    warn(ts, Severity::Warning, issue.to_string());
+                }
+            }
+        }
+    }
+
     let (ts_type, ts_date, ts_time, ts_end_time, ts_active, ts_repeater) =
-        if let Some(ref ts) = info.timestamp {
-            // `info.timestamp` is assembled from `extract_timestamp_normalized`
+        if let Some(ref ts) = timestamp {
+            // `timestamp` is assembled from `extract_timestamp_normalized`
             // regex captures over an already-`normalize_weekdays`d string in
             // both `process_node` branches, so a second normalisation here
             // would be redundant work on every task.
             let parsed = parse_timestamp_fields_normalized(ts);
-            if parsed.1.is_none() {
-                warn_invalid_timestamp(ts_warning_counter, path, line, ts);
-            }
             // The repeater is extracted via a second, fuller pass
             // (`parse_org_timestamp`) rather than the light regex path above:
             // the repeater grammar (prefix/value/unit, `wd`) lives in that
@@ -346,13 +1155,86 @@ fn finalize_task(path: &Path, info: HeadingInfo, ts_warning_counter: &mut usize)
         } else {
             (None, None, None, None, None, None)
         };
+    let ts_overnight = timestamp
+        .as_deref()
+        .filter(|ts| is_overnight_normalized(ts))
+        .map(|_| true);
 
     let (clocks_opt, total_time) = if !info.clocks.is_empty() {
-        let total = calculate_total_minutes(&info.clocks).map(format_duration);
+        let total = calculate_total_seconds(&info.clocks).map(format_duration);
         (Some(info.clocks), total)
     } else {
         (None, None)
     };
+    let log = (!info.log.is_empty()).then_some(info.log);
+
+    let attendees = info
+        .properties
+        .get(ATTENDEES_PROPERTY)
+        .map(|raw| split_attendees(raw))
+        .filter(|list| !list.is_empty());
+
+    let location = info
+        .properties
+        .get(LOCATION_PROPERTY)
+        .filter(|raw| !raw.is_empty())
+        .cloned();
+
+    let url = info
+        .properties
+        .get(URL_PROPERTY)
+        .filter(|raw| is_web_url(raw))
+        .cloned()
+        .or(info.first_link);
+
+    let assignee = info
+        .properties
+        .get(ASSIGNEE_PROPERTY)
+        .map(|raw| raw.trim_start_matches('@').trim().to_string())
+        .filter(|name| !name.is_empty())
+        .or_else(|| heading_mention(&info.heading));
+
+    let waiting_since = info
+        .properties
+        .get(WAITING_SINCE_PROPERTY)
+        .and_then(|raw| parse_property_date(raw));
+
+    let paused = info
+        .properties
+        .get(PAUSED_PROPERTY)
+        .filter(|raw| !matches!(raw.to_ascii_lowercase().as_str(), "nil" | "false" | "no"))
+        .map(|_| true);
+    let paused_until = PAUSE_UNTIL_PROPERTIES
+        .iter()
+        .find_map(|key| info.properties.get(*key))
+        .and_then(|raw| parse_property_date(raw));
+
+    let category = info
+        .properties
+        .get(CATEGORY_PROPERTY)
+        .filter(|raw| !raw.is_empty())
+        .cloned();
+
+    let effort_minutes = info
+        .properties
+        .get(EFFORT_PROPERTY)
+        .or(info.inline_effort.as_ref())
+        .and_then(|raw| parse_duration(raw.trim()))
+        .map(|seconds| seconds / 60);
+
+    let done = info.subtasks.iter().filter(|s| s.done).count();
+    let subtasks_percent =
+        (!info.subtasks.is_empty()).then(|| (done * 100 / info.subtasks.len()) as u8);
+    let subtasks_progress =
+        (!info.subtasks.is_empty()).then(|| format!("{done}/{}", info.subtasks.len()));
+    // Checkboxes take precedence, as in Org; a cookie over child tasks is
+    // filled in once the whole file is parsed (`fill_child_progress`).
+    let progress = info
+        .progress_cookie
+        .as_deref()
+        .filter(|_| !info.subtasks.is_empty())
+        .map(|cookie| format_progress(cookie, done, info.subtasks.len()));
+    let subtasks = (!info.subtasks.is_empty()).then_some(info.subtasks);
 
     let properties = if info.properties.is_empty() {
         None
@@ -361,14 +1243,15 @@ fn finalize_task(path: &Path, info: HeadingInfo, ts_warning_counter: &mut usize)
     };
 
     Some(Task {
-        file: path.display().to_string(),
+        file: Arc::clone(file),
         line,
         heading: info.heading,
-        content: info.content,
+        content: info.full_content.unwrap_or(info.content),
         task_type: info.task_type,
         priority: info.priority,
-        created: info.created,
-        timestamp: info.timestamp,
+        created,
+        closed,
+        timestamp,
         timestamp_type: ts_type,
         timestamp_active: ts_active,
         timestamp_date: ts_date,
@@ -377,16 +1260,233 @@ fn finalize_task(path: &Path, info: HeadingInfo, ts_warning_counter: &mut usize)
         timestamp_repeater: ts_repeater,
         clocks: clocks_opt,
         total_clock_time: total_time,
+        log,
+        effort_minutes,
         properties,
+        timestamp_overnight: ts_overnight,
+        planning,
+        inactive_timestamps,
+        rescheduled_count: (info.reschedules > 0).then_some(info.reschedules),
+        effective_date: None,
+        attendees,
+        location,
+        url,
+        links: (!info.links.is_empty()).then_some(info.links),
+        subtasks,
+        subtasks_percent,
+        subtasks_progress,
+        progress_cookie: info.progress_cookie,
+        progress,
+        children_todo: None,
+        children_done: None,
+        level: info.level,
+        waiting_since,
+        assignee,
+        paused,
+        paused_until,
+        effective_priority: None,
+        category,
+        tags: (!info.tags.is_empty()).then_some(info.tags),
+        outline_path: (!info.outline_path.is_empty()).then_some(info.outline_path),
+        inherited_priority: info.inherited_priority,
+        inherited_tags: (!info.inherited_tags.is_empty()).then_some(info.inherited_tags),
     })
 }
 
+/// Property key listing a meeting's attendees, comma-separated.
+const ATTENDEES_PROPERTY: &str = "ATTENDEES";
+
+/// Property key holding an event's place (address, room, or `lat,lon`).
+const LOCATION_PROPERTY: &str = "LOCATION";
+
+/// Property key holding the task's external reference (a PR, a ticket).
+const URL_PROPERTY: &str = "URL";
+
+/// Property key holding the task's category, as in Org.
+const CATEGORY_PROPERTY: &str = "CATEGORY";
+
+/// Property key naming the person a task is assigned to.
+const ASSIGNEE_PROPERTY: &str = "ASSIGNEE";
+
+/// Property key holding the task's estimated duration as `H:MM`, as in Org.
+pub(crate) const EFFORT_PROPERTY: &str = "Effort";
+
+/// Value of an inline `Effort: 2:00` code span, the property written
+/// without a properties block. The key is case-insensitive, like Org's.
+fn inline_effort(literal: &str) -> Option<String> {
+    let (key, value) = literal.trim().split_once(':')?;
+    key.eq_ignore_ascii_case(EFFORT_PROPERTY)
+        .then(|| value.trim().to_string())
+}
+
+/// First `@name` mentioned in `heading`, without the `@` and any trailing
+/// sentence punctuation (`@alice.` names `alice`).
+fn heading_mention(heading: &str) -> Option<String> {
+    let caps = MENTION_RE.captures(heading)?;
+    let name = caps.get(1)?.as_str().trim_end_matches(['.', '-']);
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Property key holding the date a task was handed off to someone else.
+const WAITING_SINCE_PROPERTY: &str = "WAITING_SINCE";
+
+/// Property key suspending a recurring task; any value but `nil`, `false`
+/// or `no` (including none at all) pauses it.
+const PAUSED_PROPERTY: &str = "PAUSED";
+
+/// Property keys holding the date a paused recurring task resumes on. The
+/// hyphenated spelling is accepted as well.
+const PAUSE_UNTIL_PROPERTIES: [&str; 2] = ["PAUSE_UNTIL", "PAUSE-UNTIL"];
+
+/// Date of a property value written bare (`2026-01-05`) or as an Org
+/// timestamp (`[2026-01-05 Mon]`, `<2026-01-05 Mon>`), as `YYYY-MM-DD`.
+fn parse_property_date(raw: &str) -> Option<String> {
+    let inner = raw.trim_start_matches(['[', '<']);
+    let token = inner
+        .split(|c: char| c.is_whitespace() || c == ']' || c == '>')
+        .next()?;
+    chrono::NaiveDate::parse_from_str(token, "%Y-%m-%d")
+        .ok()
+        .map(|date| date.format("%Y-%m-%d").to_string())
+}
+
+/// Only `http`/`https` URLs become a task's `url`: the value is emitted as an
+/// HTML `href`, where a `javascript:` or `data:` scheme would be an injection.
+fn is_web_url(url: &str) -> bool {
+    let lower = url.get(..8).unwrap_or(url).to_ascii_lowercase();
+    lower.starts_with("http://") || lower.starts_with("https://")
+}
+
+/// URL of the first `http(s)` link inside a paragraph, at any nesting depth.
+fn first_web_link<'a>(node: &'a AstNode<'a>) -> Option<String> {
+    node.descendants()
+        .find_map(|child| match &child.data.borrow().value {
+            NodeValue::Link(link) if is_web_url(&link.url) => Some(link.url.clone()),
+            _ => None,
+        })
+}
+
+/// Add the links of a heading or paragraph to `links`: markdown links
+/// first, then Org `[[...]]` links found in its text. A link already in
+/// `links` is not added again.
+fn collect_links<'a>(node: &'a AstNode<'a>, links: &mut Vec<Link>) {
+    let mut push = |target: &str, text: String| {
+        let link = Link {
+            target: target.to_string(),
+            text: (!text.is_empty()).then_some(text),
+        };
+        if !link.target.is_empty() && !links.contains(&link) {
+            links.push(link);
+        }
+    };
+    for child in node.descendants() {
+        if let NodeValue::Link(link) = &child.data.borrow().value {
+            push(&link.url, extract_text(child).trim().to_string());
+        }
+    }
+    let text = extract_text(node);
+    if !text.contains("[[") {
+        return;
+    }
+    for caps in ORG_LINK_RE.captures_iter(&text) {
+        let desc = caps.get(2).map_or("", |m| m.as_str().trim());
+        push(caps[1].trim(), desc.to_string());
+    }
+}
+
+/// A list item whose first paragraph opens with an Org checkbox (`[ ]`,
+/// `[x]`, `[X]`, `[-]`). Parsed from the text rather than comrak's tasklist
+/// extension, which does not know the `[-]` state.
+fn checklist_item<'a>(node: &'a AstNode<'a>) -> Option<Subtask> {
+    if !matches!(node.data.borrow().value, NodeValue::Item(_)) {
+        return None;
+    }
+    let paragraph = node.first_child()?;
+    if !matches!(paragraph.data.borrow().value, NodeValue::Paragraph) {
+        return None;
+    }
+    let mut text = String::new();
+    collect_item_text(paragraph, &mut text);
+    let (done, rest) = match text.get(..3)? {
+        "[x]" | "[X]" => (true, &text[3..]),
+        "[ ]" | "[-]" => (false, &text[3..]),
+        _ => return None,
+    };
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some(Subtask {
+        text: rest.trim().to_string(),
+        done,
+    })
+}
+
+/// Title of a `> [!todo] Title` callout, given the blockquote's first child.
+fn todo_callout_title<'a>(node: &'a AstNode<'a>) -> Option<String> {
+    if !matches!(node.data.borrow().value, NodeValue::Paragraph) {
+        return None;
+    }
+    let mut first_line = String::new();
+    for child in node.children() {
+        match &child.data.borrow().value {
+            NodeValue::Text(t) => first_line.push_str(t),
+            NodeValue::SoftBreak | NodeValue::LineBreak => break,
+            _ => {}
+        }
+    }
+    TODO_CALLOUT_RE
+        .captures(first_line.trim())
+        .map(|caps| caps[1].trim().to_string())
+}
+
+/// Like `collect_text_recursive`, but ends a line at every soft or hard
+/// break, so line-oriented syntax such as a property drawer survives.
+fn collect_line_text<'a>(node: &'a AstNode<'a>, out: &mut String) {
+    for child in node.children() {
+        match &child.data.borrow().value {
+            NodeValue::Text(t) => out.push_str(t),
+            NodeValue::SoftBreak | NodeValue::LineBreak => out.push('\n'),
+            NodeValue::Emph | NodeValue::Strong | NodeValue::Link(_) | NodeValue::Strikethrough => {
+                collect_line_text(child, out)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Like `collect_text_recursive`, but keeps inline code and soft breaks:
+/// checklist steps often name a command in inline code.
+fn collect_item_text<'a>(node: &'a AstNode<'a>, out: &mut String) {
+    for child in node.children() {
+        match &child.data.borrow().value {
+            NodeValue::Text(t) => out.push_str(t),
+            NodeValue::Code(code) => out.push_str(&code.literal),
+            NodeValue::SoftBreak => out.push(' '),
+            NodeValue::Emph | NodeValue::Strong | NodeValue::Link(_) | NodeValue::Strikethrough => {
+                collect_item_text(child, out)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Split an `ATTENDEES` value on `,` into trimmed, non-empty entries
+/// (`Alice <alice@example.com>, bob@example.com`).
+fn split_attendees(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
 /// Parse heading text to extract task type, priority, and title.
 ///
 /// Follows the emacs org-mode parser
 /// (`org-element--headline-parse-title` / `org-priority-regexp`):
 ///
-/// 1. Strip an optional `TODO` / `DONE` keyword anchored at the start.
+/// 1. Strip an optional `TODO` / `DONE` keyword anchored at the start: a
+///    built-in one, or a custom one from `keywords`.
 /// 2. Search the remaining text for the first `[#X]` cookie at any position,
 ///    where `X` is `A-Z` or an integer `0..=64`. If found, that becomes the
 ///    priority; the title is everything after `[#X]` + optional space.
@@ -396,8 +1496,11 @@ fn finalize_task(path: &Path, info: HeadingInfo, ts_warning_counter: &mut usize)
 ///
 /// A heading without TODO/DONE and without a priority cookie is returned
 /// verbatim (trimmed).
-fn parse_heading(text: &str) -> (Option<TaskType>, Option<Priority>, String) {
-    // Step 1: optional TODO/DONE prefix.
+fn parse_heading(
+    text: &str,
+    keywords: &TodoKeywords,
+) -> (Option<TaskType>, Option<Priority>, String) {
+    // Step 1: optional TODO/DONE prefix, built-in or custom.
     let (task_type, rest) = if let Some(caps) = HEADING_TODO_RE.captures(text) {
         let kw = caps.get(1).map(|m| m.as_str()).unwrap_or("");
         let m = caps
@@ -405,7 +1508,13 @@ fn parse_heading(text: &str) -> (Option<TaskType>, Option<Priority>, String) {
             .expect("Captures::get(0) is Some when captures() succeeds");
         (TaskType::from_keyword(kw), &text[m.end()..])
     } else {
-        (None, text)
+        match text.split_once(char::is_whitespace) {
+            Some((word, rest)) => match keywords.task_type(word) {
+                Some(task_type) => (Some(task_type), rest.trim_start()),
+                None => (None, text),
+            },
+            None => (None, text),
+        }
     };
 
     // Step 2: optional priority cookie anywhere in the remainder.
@@ -423,6 +1532,156 @@ fn parse_heading(text: &str) -> (Option<TaskType>, Option<Priority>, String) {
     (task_type, None, rest.trim().to_string())
 }
 
+/// Take the first statistics cookie out of a heading.
+fn split_stats_cookie(heading: String) -> (String, Option<String>) {
+    let Some(m) = STATS_COOKIE_RE.find(&heading) else {
+        return (heading, None);
+    };
+    (
+        cut_heading(&heading, m.range()),
+        Some(m.as_str().to_string()),
+    )
+}
+
+/// Take the first active timestamp out of a heading, normalized with the
+/// same weekday `mappings` as body timestamps. A timestamp in the body
+/// replaces it, as a later one does there.
+fn split_heading_timestamp(heading: String, mappings: &[(&str, &str)]) -> (String, Option<String>) {
+    let Some(m) = HEADING_TIMESTAMP_RE.find(&heading) else {
+        return (heading, None);
+    };
+    match extract_timestamp_normalized(&normalize_weekdays(m.as_str(), mappings)) {
+        Some(ts) => (cut_heading(&heading, m.range()), Some(ts)),
+        None => (heading, None),
+    }
+}
+
+/// `heading` without the text at `range`, the words around it joined by
+/// one space.
+fn cut_heading(heading: &str, range: std::ops::Range<usize>) -> String {
+    let (before, after) = (
+        heading[..range.start].trim_end(),
+        heading[range.end..].trim_start(),
+    );
+    match (before.is_empty(), after.is_empty()) {
+        (false, false) => format!("{before} {after}"),
+        _ => format!("{before}{after}"),
+    }
+}
+
+/// `cookie` rewritten with `closed` of `total`: `[3/7]` for a fraction
+/// cookie, `[42%]` (rounded down) for a percent one.
+fn format_progress(cookie: &str, closed: usize, total: usize) -> String {
+    if cookie.contains('%') {
+        format!("[{}%]", (closed * 100).checked_div(total).unwrap_or(0))
+    } else {
+        format!("[{closed}/{total}]")
+    }
+}
+
+/// Compute the cookies that count child tasks: the task headings one
+/// outline level below, up to the next heading at the task's own level or
+/// above. Done and cancelled children count as closed.
+fn fill_child_progress(tasks: &mut [Task]) {
+    for i in 0..tasks.len() {
+        let (Some(level), Some(cookie)) = (tasks[i].level, tasks[i].progress_cookie.as_deref())
+        else {
+            continue;
+        };
+        if tasks[i].progress.is_some() {
+            continue;
+        }
+        let depth = tasks[i].outline_path.as_ref().map_or(0, Vec::len) + 1;
+        let (mut closed, mut total) = (0, 0);
+        for child in &tasks[i + 1..] {
+            match child.level {
+                Some(child_level) if child_level <= level => break,
+                Some(_) if child.outline_path.as_ref().map_or(0, Vec::len) == depth => {}
+                _ => continue,
+            }
+            let Some(ref task_type) = child.task_type else {
+                continue;
+            };
+            total += 1;
+            if task_type.is_done() || matches!(task_type, TaskType::Cancelled(_)) {
+                closed += 1;
+            }
+        }
+        tasks[i].progress = Some(format_progress(cookie, closed, total));
+    }
+}
+
+/// Count the open and the closed task headings nested below each task
+/// heading, at any depth, up to the next heading at its own level or
+/// above. A nested task counts only when the task is among its enclosing
+/// headings, so a task under a plain sibling heading is left out.
+fn fill_descendant_counts(tasks: &mut [Task]) {
+    for i in 0..tasks.len() {
+        let Some(level) = tasks[i].level else {
+            continue;
+        };
+        let depth = tasks[i].outline_path.as_ref().map_or(0, Vec::len);
+        let (mut todo, mut done) = (0, 0);
+        for child in &tasks[i + 1..] {
+            match child.level {
+                Some(child_level) if child_level <= level => break,
+                Some(_) => {}
+                None => continue,
+            }
+            let under = child
+                .outline_path
+                .as_ref()
+                .and_then(|path| path.get(depth))
+                .is_some_and(|title| *title == tasks[i].heading);
+            match child.task_type {
+                Some(ref t) if under && (t.is_done() || matches!(t, TaskType::Cancelled(_))) => {
+                    done += 1
+                }
+                Some(_) if under => todo += 1,
+                _ => {}
+            }
+        }
+        if todo + done > 0 {
+            tasks[i].children_todo = Some(todo);
+            tasks[i].children_done = Some(done);
+        }
+    }
+}
+
+/// Split a trailing Org tag list (`Title :work:urgent:`) off a heading.
+///
+/// The list must be the last whitespace-separated word and hold only
+/// letters, digits and `_@#%`; a heading that is nothing but a tag list
+/// keeps it as its title.
+fn split_heading_tags(heading: String) -> (String, Vec<String>) {
+    let Some((title, word)) = heading.rsplit_once(char::is_whitespace) else {
+        return (heading, Vec::new());
+    };
+    let Some(inner) = word
+        .strip_prefix(':')
+        .and_then(|w| w.strip_suffix(':'))
+        .filter(|inner| !inner.is_empty())
+    else {
+        return (heading, Vec::new());
+    };
+    let valid = inner.split(':').all(|tag| {
+        !tag.is_empty()
+            && tag
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '_' | '@' | '#' | '%'))
+    });
+    if !valid {
+        return (heading, Vec::new());
+    }
+    let mut tags: Vec<String> = Vec::new();
+    for tag in inner.split(':') {
+        if !tags.iter().any(|t| t == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+    (title.trim_end().to_string(), tags)
+}
+
 /// Strip a matched pair of inline-code backtick fences from the trimmed
 /// content of an indented code block.
 ///
@@ -463,7 +1722,7 @@ fn strip_wrapping_backticks(s: &str) -> &str {
 fn parse_org_properties(
     literal: &str,
     props: &mut BTreeMap<String, String>,
-    path: &Path,
+    file: &str,
     block_start_line: u32,
     prop_warning_counter: &mut usize,
 ) {
@@ -479,28 +1738,128 @@ fn parse_org_properties(
             Some((key, value)) => {
                 let key = key.trim();
                 if key.is_empty() {
-                    warn_invalid_property_line(prop_warning_counter, path, src_line, line);
+                    warn_invalid_property_line(prop_warning_counter, file, src_line, line);
                     continue;
                 }
                 props.insert(key.to_string(), value.trim().to_string());
             }
             None => {
-                warn_invalid_property_line(prop_warning_counter, path, src_line, line);
+                warn_invalid_property_line(prop_warning_counter, file, src_line, line);
+            }
+        }
+    }
+}
+
+/// Opening line of the Org drawer holding state changes and clocks.
+const LOGBOOK_DRAWER_START: &str = ":LOGBOOK:";
+
+/// `- State "DONE"       from "TODO"       [2025-01-10 Fri 10:00]`, Org's
+/// `org-log-note-headings` state entry. `from` is blank for a task that
+/// had no state.
+static STATE_CHANGE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    compile_bounded(&format!(
+        r#"^-\s+State\s+"([^"]+)"\s+from\s*(?:"([^"]*)")?\s*\[(\d{{4}}-\d{{2}}-\d{{2}}[^\]]{{0,{TS_BODY_MAX}}})\]"#
+    ))
+});
+
+/// Entries of the first `:LOGBOOK:` ... `:END:` drawer in `text`, or `None`
+/// when it holds no closed drawer. State changes and `CLOCK:` lines are
+/// kept; notes and other lines are skipped.
+fn parse_logbook_drawer(text: &str) -> Option<Vec<LogEntry>> {
+    let mut lines = text
+        .lines()
+        .map(str::trim)
+        .skip_while(|l| !l.eq_ignore_ascii_case(LOGBOOK_DRAWER_START))
+        .skip(1);
+    let mut entries = Vec::new();
+    for line in lines.by_ref() {
+        if line.eq_ignore_ascii_case(PROPERTY_DRAWER_END) {
+            return Some(entries);
+        }
+        if let Some(caps) = STATE_CHANGE_RE.captures(line) {
+            entries.push(LogEntry::State {
+                to: caps[1].to_string(),
+                from: caps
+                    .get(2)
+                    .map(|m| m.as_str().to_string())
+                    .filter(|from| !from.is_empty()),
+                timestamp: caps[3].to_string(),
+            });
+        } else {
+            entries.extend(extract_clocks(line).into_iter().map(LogEntry::Clock));
+        }
+    }
+    None
+}
+
+/// Opening line of an Org property drawer (case-insensitive, like Org).
+const PROPERTY_DRAWER_START: &str = ":PROPERTIES:";
+
+/// Closing line of an Org drawer (case-insensitive, like Org).
+const PROPERTY_DRAWER_END: &str = ":END:";
+
+/// Parse the first Org `:PROPERTIES:` ... `:END:` drawer in `text` into
+/// `props`, merging with last-wins on duplicate keys like
+/// `parse_org_properties`. Returns the lines outside the drawer, or `None`
+/// (leaving `props` untouched) when `text` holds no closed drawer.
+///
+/// Each drawer line is `:KEY: value`; the key is kept as written and the
+/// value trimmed, an empty value allowed. Any other non-blank line is
+/// skipped and reported via `warn_invalid_property_line`. `first_line` is
+/// the source line of the first line of `text`.
+fn parse_property_drawer(
+    text: &str,
+    props: &mut BTreeMap<String, String>,
+    file: &str,
+    first_line: u32,
+    prop_warning_counter: &mut usize,
+) -> Option<String> {
+    let lines: Vec<&str> = text.lines().collect();
+    let start = lines
+        .iter()
+        .position(|l| l.trim().eq_ignore_ascii_case(PROPERTY_DRAWER_START))?;
+    let len = lines[start + 1..]
+        .iter()
+        .position(|l| l.trim().eq_ignore_ascii_case(PROPERTY_DRAWER_END))?;
+
+    for (offset, raw) in lines[start + 1..start + 1 + len].iter().enumerate() {
+        let entry = raw.trim();
+        if entry.is_empty() {
+            continue;
+        }
+        let src_line = first_line.saturating_add((start + 1 + offset) as u32);
+        match entry.strip_prefix(':').and_then(|e| e.split_once(':')) {
+            Some((key, value)) if !key.trim().is_empty() => {
+                props.insert(key.trim().to_string(), value.trim().to_string());
             }
+            _ => warn_invalid_property_line(prop_warning_counter, file, src_line, raw),
         }
     }
+
+    let rest: Vec<&str> = lines[..start]
+        .iter()
+        .chain(&lines[start + len + 2..])
+        .copied()
+        .collect();
+    Some(rest.join("\n"))
 }
 
-/// Extract timestamps (CREATED and others) from paragraph node
+/// Extract timestamps (CREATED and others) from paragraph node. Plain
+/// inactive timestamps are recorded on `info` and returned only when the
+/// paragraph has no other timestamp.
 fn extract_timestamps_from_node<'a>(
     node: &'a AstNode<'a>,
     mappings: &[(&str, &str)],
+    info: &mut HeadingInfo,
 ) -> (Option<String>, Option<String>) {
     let mut created = None;
     let mut timestamp = None;
+    let mut note = None;
 
     if let NodeValue::Paragraph = &node.data.borrow().value {
-        for child in node.children() {
+        // Descendants, not children: a code span may sit inside emphasis or
+        // a link (`**\`SCHEDULED: <...>\`**`).
+        for child in node.descendants() {
             if let NodeValue::Code(code) = &child.data.borrow().value {
                 // Normalize the literal once per inline-code node; both extractors
                 // would otherwise scan the same string in lockstep.
@@ -508,20 +1867,56 @@ fn extract_timestamps_from_node<'a>(
                 if created.is_none() {
                     created = extract_created_normalized(&normalized);
                 }
-                if timestamp.is_none() {
-                    timestamp = extract_timestamp_normalized(&normalized);
+                match extract_timestamp_normalized(&normalized) {
+                    Some(ts) if is_note(&ts) => {
+                        if is_plain_inactive(&ts) {
+                            info.inactive_timestamps.push(ts.clone());
+                        }
+                        note.get_or_insert(ts);
+                    }
+                    Some(ts) if timestamp.is_none() => timestamp = Some(ts),
+                    _ => {}
+                }
+                record_planning(&mut info.planning, &normalized);
+                if let Some(closed) = extract_closed_normalized(&normalized) {
+                    info.closed = Some(closed);
                 }
             }
         }
     }
-    (created, timestamp)
+    (created, timestamp.or(note))
+}
+
+/// A bare `[...]` timestamp: a note of when something happened, which never
+/// feeds the agenda (ADR-0014). `CLOSED: [...]` is not plain.
+fn is_plain_inactive(ts: &str) -> bool {
+    ts.starts_with('[')
+}
+
+/// A timestamp recording the past rather than placing the task: a plain
+/// inactive one or `CLOSED:`.
+fn is_note(ts: &str) -> bool {
+    is_plain_inactive(ts) || ts.starts_with("CLOSED:")
+}
+
+/// Make `ts` the heading's timestamp, unless it is a note and the heading
+/// already has an active or keyword timestamp: a logged date or a
+/// `CLOSED:` line below `SCHEDULED:` must not take the task off its day.
+fn set_timestamp(info: &mut HeadingInfo, ts: String) {
+    if !is_note(&ts) || info.timestamp.as_deref().is_none_or(is_note) {
+        info.timestamp = Some(ts);
+    }
 }
 
 /// Extract plain text from paragraph, including text inside Emph/Strong/Link nodes
 fn extract_paragraph_text<'a>(node: &'a AstNode<'a>) -> String {
     let mut text = String::new();
     collect_text_recursive(node, &mut text);
-    text.trim().to_string()
+    // Trim in place rather than copying the trimmed slice into a new String.
+    text.truncate(text.trim_end().len());
+    let leading = text.len() - text.trim_start().len();
+    text.drain(..leading);
+    text
 }
 
 /// Extract all text from a heading node, including text inside Emph/Strong
@@ -533,10 +1928,19 @@ fn extract_text<'a>(node: &'a AstNode<'a>) -> String {
 
 fn collect_text_recursive<'a>(node: &'a AstNode<'a>, out: &mut String) {
     for child in node.children() {
-        let value = child.data.borrow().value.clone();
-        match value {
-            NodeValue::Text(t) => out.push_str(&t),
-            NodeValue::Emph | NodeValue::Strong | NodeValue::Link(_) | NodeValue::Strikethrough => {
+        match &child.data.borrow().value {
+            NodeValue::Text(t) => out.push_str(t),
+            NodeValue::SoftBreak => out.push('\n'),
+            NodeValue::FootnoteReference(r) => {
+                let _ = write!(out, "[^{}]", r.name);
+            }
+            // Struck-out text keeps its markers: it means the opposite.
+            NodeValue::Strikethrough => {
+                out.push_str("~~");
+                collect_text_recursive(child, out);
+                out.push_str("~~");
+            }
+            NodeValue::Emph | NodeValue::Strong | NodeValue::Link(_) => {
                 collect_text_recursive(child, out)
             }
             _ => {}
@@ -544,9 +1948,6 @@ fn collect_text_recursive<'a>(node: &'a AstNode<'a>, out: &mut String) {
     }
 }
 
-// Need clone for NodeValue match — comrak nodes are RefCell-borrowed
-// Re-import for clone derive if not present.
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -555,16 +1956,24 @@ mod tests {
     #[test]
     fn warn_invalid_timestamp_advances_per_call_counter() {
         // The 0.5.0 review (M1) replaced a process-global
-        // `TS_WARNINGS_EMITTED: AtomicUsize` with a counter owned by the
-        // caller (typically `ProcessingStats::ts_warnings_emitted`).
-        // This test pins the per-call advance: each call bumps the
-        // counter by exactly one.
-        let mut counter = 0_usize;
-        let path = Path::new("t.md");
+        // `TS_WARNINGS_EMITTED: AtomicUsize` with state owned by the
+        // caller (typically `ProcessingStats::ts_diagnostics`).
+        // This test pins the per-call advance: each call records exactly
+        // one diagnostic, including those past the logging cap.
+        let mut diagnostics = Vec::new();
+        let file = "t.md";
         for i in 1..=25 {
-            warn_invalid_timestamp(&mut counter, path, i, "<bad>");
+            warn_invalid_timestamp(
+                &mut diagnostics,
+                file,
+                i,
+                "<bad>",
+                Severity::Error,
+                "bad".into(),
+            );
         }
-        assert_eq!(counter, 25);
+        assert_eq!(diagnostics.len(), 25);
+        assert_eq!(diagnostics[24].line, 25);
     }
 
     #[test]
@@ -573,9 +1982,9 @@ mod tests {
         // call bumps the caller-owned counter by exactly one, so the
         // MAX_DIAGNOSTIC_ITEMS cap spans the whole run (ADR-0020).
         let mut counter = 0_usize;
-        let path = Path::new("t.md");
+        let file = "t.md";
         for i in 1..=25 {
-            warn_invalid_property_line(&mut counter, path, i, "no-colon-here");
+            warn_invalid_property_line(&mut counter, file, i, "no-colon-here");
         }
         assert_eq!(counter, 25);
     }
@@ -587,20 +1996,35 @@ mod tests {
         // binary, each see a fresh budget. With the previous global
         // static this assertion would not hold across runs in one
         // process.
-        let mut counter_a = 0_usize;
-        let mut counter_b = 0_usize;
-        let path = Path::new("t.md");
+        let mut diagnostics_a = Vec::new();
+        let mut diagnostics_b = Vec::new();
+        let file = "t.md";
         for _ in 0..MAX_DIAGNOSTIC_ITEMS {
-            warn_invalid_timestamp(&mut counter_a, path, 1, "<bad>");
+            warn_invalid_timestamp(
+                &mut diagnostics_a,
+                file,
+                1,
+                "<bad>",
+                Severity::Error,
+                "bad".into(),
+            );
         }
-        warn_invalid_timestamp(&mut counter_b, path, 1, "<bad>");
-        assert_eq!(counter_a, MAX_DIAGNOSTIC_ITEMS);
-        assert_eq!(counter_b, 1);
+        warn_invalid_timestamp(
+            &mut diagnostics_b,
+            file,
+            1,
+            "<bad>",
+            Severity::Error,
+            "bad".into(),
+        );
+        assert_eq!(diagnostics_a.len(), MAX_DIAGNOSTIC_ITEMS);
+        assert_eq!(diagnostics_b.len(), 1);
     }
 
     #[test]
     fn test_parse_heading_with_priority() {
-        let (task_type, priority, heading) = parse_heading("TODO [#A] Important task");
+        let (task_type, priority, heading) =
+            parse_heading("TODO [#A] Important task", &TodoKeywords::default());
         assert_eq!(task_type, Some(TaskType::Todo));
         assert_eq!(priority, Some(Priority::A));
         assert_eq!(heading, "Important task");
@@ -608,7 +2032,8 @@ mod tests {
 
     #[test]
     fn test_parse_heading_without_priority() {
-        let (task_type, priority, heading) = parse_heading("DONE Simple task");
+        let (task_type, priority, heading) =
+            parse_heading("DONE Simple task", &TodoKeywords::default());
         assert_eq!(task_type, Some(TaskType::Done));
         assert_eq!(priority, None);
         assert_eq!(heading, "Simple task");
@@ -616,7 +2041,8 @@ mod tests {
 
     #[test]
     fn test_parse_heading_no_task() {
-        let (task_type, priority, heading) = parse_heading("Regular heading");
+        let (task_type, priority, heading) =
+            parse_heading("Regular heading", &TodoKeywords::default());
         assert_eq!(task_type, None);
         assert_eq!(priority, None);
         assert_eq!(heading, "Regular heading");
@@ -627,10 +2053,27 @@ mod tests {
     // We follow emacs org-mode semantics (`org-priority-regexp`) wherever the
     // bug report diverged from it — concretely case 8 below.
 
+    #[test]
+    fn parse_heading_custom_keywords() {
+        let keywords = TodoKeywords {
+            todo: vec!["NEXT".to_string()],
+            done: vec!["SHIPPED".to_string()],
+        };
+        let (task_type, priority, heading) = parse_heading("NEXT [#A] Call Bob", &keywords);
+        assert_eq!(task_type, Some(TaskType::Other("NEXT".to_string())));
+        assert_eq!(priority, Some(Priority::A));
+        assert_eq!(heading, "Call Bob");
+        let (task_type, _, _) = parse_heading("SHIPPED Release", &keywords);
+        assert_eq!(task_type, Some(TaskType::OtherDone("SHIPPED".to_string())));
+        let (task_type, _, heading) = parse_heading("NEXT Call Bob", &TodoKeywords::default());
+        assert_eq!(task_type, None);
+        assert_eq!(heading, "NEXT Call Bob");
+    }
+
     #[test]
     fn parse_heading_priority_without_todo() {
         // Case 1: `### [#A] Заголовок`.
-        let (tt, p, h) = parse_heading("[#A] Заголовок");
+        let (tt, p, h) = parse_heading("[#A] Заголовок", &TodoKeywords::default());
         assert_eq!(tt, None);
         assert_eq!(p, Some(Priority::A));
         assert_eq!(h, "Заголовок");
@@ -639,7 +2082,7 @@ mod tests {
     #[test]
     fn parse_heading_todo_with_priority() {
         // Case 2: `### TODO [#A] Заголовок`.
-        let (tt, p, h) = parse_heading("TODO [#A] Заголовок");
+        let (tt, p, h) = parse_heading("TODO [#A] Заголовок", &TodoKeywords::default());
         assert_eq!(tt, Some(TaskType::Todo));
         assert_eq!(p, Some(Priority::A));
         assert_eq!(h, "Заголовок");
@@ -648,7 +2091,7 @@ mod tests {
     #[test]
     fn parse_heading_done_with_priority_b() {
         // Case 3: `### DONE [#B] Заголовок`.
-        let (tt, p, h) = parse_heading("DONE [#B] Заголовок");
+        let (tt, p, h) = parse_heading("DONE [#B] Заголовок", &TodoKeywords::default());
         assert_eq!(tt, Some(TaskType::Done));
         assert_eq!(p, Some(Priority::B));
         assert_eq!(h, "Заголовок");
@@ -657,7 +2100,7 @@ mod tests {
     #[test]
     fn parse_heading_plain_text_no_markers() {
         // Case 4: `### Заголовок`.
-        let (tt, p, h) = parse_heading("Заголовок");
+        let (tt, p, h) = parse_heading("Заголовок", &TodoKeywords::default());
         assert_eq!(tt, None);
         assert_eq!(p, None);
         assert_eq!(h, "Заголовок");
@@ -666,7 +2109,7 @@ mod tests {
     #[test]
     fn parse_heading_todo_no_priority() {
         // Case 5: `### TODO Заголовок`.
-        let (tt, p, h) = parse_heading("TODO Заголовок");
+        let (tt, p, h) = parse_heading("TODO Заголовок", &TodoKeywords::default());
         assert_eq!(tt, Some(TaskType::Todo));
         assert_eq!(p, None);
         assert_eq!(h, "Заголовок");
@@ -675,7 +2118,7 @@ mod tests {
     #[test]
     fn parse_heading_numeric_priority() {
         // Case 6: `### [#1] Заголовок`.
-        let (tt, p, h) = parse_heading("[#1] Заголовок");
+        let (tt, p, h) = parse_heading("[#1] Заголовок", &TodoKeywords::default());
         assert_eq!(tt, None);
         assert_eq!(p, Some(Priority::Numeric(1)));
         assert_eq!(h, "Заголовок");
@@ -687,7 +2130,7 @@ mod tests {
         // whitespace after the `###` marker, so the heading text reaching us
         // starts at `[#A]`. Trailing extra spaces around the heading are
         // trimmed.
-        let (tt, p, h) = parse_heading("[#A]     Заголовок");
+        let (tt, p, h) = parse_heading("[#A]     Заголовок", &TodoKeywords::default());
         assert_eq!(tt, None);
         assert_eq!(p, Some(Priority::A));
         assert_eq!(h, "Заголовок");
@@ -701,7 +2144,7 @@ mod tests {
         // title as the priority via the `.*?` prefix in `org-priority-regexp`
         // and drops the text that precedes it. By project decision we follow
         // the reference parser here.
-        let (tt, p, h) = parse_heading("Без приоритета и [#A] внутри");
+        let (tt, p, h) = parse_heading("Без приоритета и [#A] внутри", &TodoKeywords::default());
         assert_eq!(tt, None);
         assert_eq!(p, Some(Priority::A));
         assert_eq!(h, "внутри");
@@ -709,12 +2152,12 @@ mod tests {
 
     #[test]
     fn parse_heading_two_digit_numeric_priority() {
-        let (tt, p, h) = parse_heading("[#15] Mid range");
+        let (tt, p, h) = parse_heading("[#15] Mid range", &TodoKeywords::default());
         assert_eq!(tt, None);
         assert_eq!(p, Some(Priority::Numeric(15)));
         assert_eq!(h, "Mid range");
 
-        let (tt, p, h) = parse_heading("[#64] At upper bound");
+        let (tt, p, h) = parse_heading("[#64] At upper bound", &TodoKeywords::default());
         assert_eq!(tt, None);
         assert_eq!(p, Some(Priority::Numeric(64)));
         assert_eq!(h, "At upper bound");
@@ -724,7 +2167,7 @@ mod tests {
     fn parse_heading_rejects_numeric_out_of_range() {
         // `[#65]` and higher are not a valid org-mode priority. The cookie
         // stays inside the heading text verbatim.
-        let (tt, p, h) = parse_heading("[#65] Above range");
+        let (tt, p, h) = parse_heading("[#65] Above range", &TodoKeywords::default());
         assert_eq!(tt, None);
         assert_eq!(p, None);
         assert_eq!(h, "[#65] Above range");
@@ -732,7 +2175,7 @@ mod tests {
 
     #[test]
     fn parse_heading_rejects_lowercase_priority() {
-        let (tt, p, h) = parse_heading("[#a] Lowercase");
+        let (tt, p, h) = parse_heading("[#a] Lowercase", &TodoKeywords::default());
         assert_eq!(tt, None);
         assert_eq!(p, None);
         assert_eq!(h, "[#a] Lowercase");
@@ -742,7 +2185,7 @@ mod tests {
     fn parse_heading_todo_then_priority_with_intervening_text() {
         // Direct consequence of the emacs `.*?` semantics: the text between
         // TODO and `[#X]` is discarded.
-        let (tt, p, h) = parse_heading("TODO Купить [#A] фильтр");
+        let (tt, p, h) = parse_heading("TODO Купить [#A] фильтр", &TodoKeywords::default());
         assert_eq!(tt, Some(TaskType::Todo));
         assert_eq!(p, Some(Priority::A));
         assert_eq!(h, "фильтр");
@@ -751,7 +2194,7 @@ mod tests {
     #[test]
     fn parse_heading_priority_without_trailing_space() {
         // `\] ?` makes the post-cookie space optional.
-        let (tt, p, h) = parse_heading("[#A]NoSpace");
+        let (tt, p, h) = parse_heading("[#A]NoSpace", &TodoKeywords::default());
         assert_eq!(tt, None);
         assert_eq!(p, Some(Priority::A));
         assert_eq!(h, "NoSpace");
@@ -759,7 +2202,7 @@ mod tests {
 
     #[test]
     fn parse_heading_cancelled_simple() {
-        let (tt, p, h) = parse_heading("CANCELLED Foo");
+        let (tt, p, h) = parse_heading("CANCELLED Foo", &TodoKeywords::default());
         assert_eq!(tt, Some(TaskType::Cancelled(CancelledSpelling::DoubleL)));
         assert_eq!(p, None);
         assert_eq!(h, "Foo");
@@ -767,7 +2210,7 @@ mod tests {
 
     #[test]
     fn parse_heading_cancelled_with_priority() {
-        let (tt, p, h) = parse_heading("CANCELLED [#A] Foo");
+        let (tt, p, h) = parse_heading("CANCELLED [#A] Foo", &TodoKeywords::default());
         assert_eq!(tt, Some(TaskType::Cancelled(CancelledSpelling::DoubleL)));
         assert_eq!(p, Some(Priority::A));
         assert_eq!(h, "Foo");
@@ -776,7 +2219,7 @@ mod tests {
     #[test]
     fn parse_heading_cancelled_without_whitespace() {
         // No whitespace after the keyword: not recognised, stays in title.
-        let (tt, p, h) = parse_heading("CANCELLEDFoo");
+        let (tt, p, h) = parse_heading("CANCELLEDFoo", &TodoKeywords::default());
         assert_eq!(tt, None);
         assert_eq!(p, None);
         assert_eq!(h, "CANCELLEDFoo");
@@ -785,78 +2228,414 @@ mod tests {
     #[test]
     fn parse_heading_cancelled_lowercase_not_recognised() {
         // Case-sensitive, like TODO/DONE.
-        let (tt, p, h) = parse_heading("cancelled Foo");
+        let (tt, p, h) = parse_heading("cancelled Foo", &TodoKeywords::default());
         assert_eq!(tt, None);
         assert_eq!(p, None);
         assert_eq!(h, "cancelled Foo");
     }
 
     #[test]
-    fn parse_heading_todo_cancelled_first_keyword_wins() {
-        // First keyword wins; the rest goes into the title (existing rule).
-        let (tt, p, h) = parse_heading("TODO CANCELLED Foo");
-        assert_eq!(tt, Some(TaskType::Todo));
-        assert_eq!(p, None);
-        assert_eq!(h, "CANCELLED Foo");
+    fn parse_heading_todo_cancelled_first_keyword_wins() {
+        // First keyword wins; the rest goes into the title (existing rule).
+        let (tt, p, h) = parse_heading("TODO CANCELLED Foo", &TodoKeywords::default());
+        assert_eq!(tt, Some(TaskType::Todo));
+        assert_eq!(p, None);
+        assert_eq!(h, "CANCELLED Foo");
+    }
+
+    #[test]
+    fn parse_heading_canceled_single_l() {
+        // Upstream Emacs Org-mode spells the keyword with a single L. See
+        // ADR-0021; recognised alongside the double-L `CANCELLED`.
+        let (tt, p, h) = parse_heading("CANCELED Foo", &TodoKeywords::default());
+        assert_eq!(tt, Some(TaskType::Cancelled(CancelledSpelling::SingleL)));
+        assert_eq!(p, None);
+        assert_eq!(h, "Foo");
+    }
+
+    #[test]
+    fn parse_heading_canceled_with_priority() {
+        let (tt, p, h) = parse_heading("CANCELED [#A] Foo", &TodoKeywords::default());
+        assert_eq!(tt, Some(TaskType::Cancelled(CancelledSpelling::SingleL)));
+        assert_eq!(p, Some(Priority::A));
+        assert_eq!(h, "Foo");
+    }
+
+    #[test]
+    fn parse_heading_canceled_lowercase_not_recognised() {
+        // Case-sensitive, like TODO/DONE/CANCELLED.
+        let (tt, p, h) = parse_heading("canceled Foo", &TodoKeywords::default());
+        assert_eq!(tt, None);
+        assert_eq!(p, None);
+        assert_eq!(h, "canceled Foo");
+    }
+
+    #[test]
+    fn parse_heading_canceled_without_whitespace_not_recognised() {
+        // No whitespace after the keyword: not recognised, stays in title.
+        let (tt, p, h) = parse_heading("CANCELEDfoo", &TodoKeywords::default());
+        assert_eq!(tt, None);
+        assert_eq!(p, None);
+        assert_eq!(h, "CANCELEDfoo");
+    }
+
+    #[test]
+    fn extract_tasks_keeps_inactive_timestamps_apart() {
+        let content = "\
+### TODO Scheduled
+`SCHEDULED: <2025-01-10 Fri>`
+
+Logged `[2025-01-09 Thu]` and `[2025-1-8 Wed]`
+
+### TODO Note only
+`[2025-01-10 Fri]`
+";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        assert_eq!(
+            tasks[0].timestamp.as_deref(),
+            Some("SCHEDULED: <2025-01-10 Fri>")
+        );
+        assert_eq!(
+            tasks[0].inactive_timestamps,
+            Some(vec![
+                "[2025-01-09 Thu]".to_string(),
+                "[2025-01-08 Wed]".to_string(),
+            ])
+        );
+        assert_eq!(tasks[1].timestamp.as_deref(), Some("[2025-01-10 Fri]"));
+        assert_eq!(tasks[1].timestamp_active, Some(false));
+        assert_eq!(
+            tasks[1].inactive_timestamps,
+            Some(vec!["[2025-01-10 Fri]".to_string()])
+        );
+    }
+
+    #[test]
+    fn extract_tasks_keeps_every_planning_line() {
+        let content = "\
+### TODO Both
+`SCHEDULED: <2026-05-21 Thu>`
+`DEADLINE: <2026-5-28 Thu>`
+
+### TODO One
+`SCHEDULED: <2026-05-21 Thu>`
+";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        assert_eq!(
+            tasks[0].planning,
+            Some(vec![
+                "SCHEDULED: <2026-05-21 Thu>".to_string(),
+                "DEADLINE: <2026-05-28 Thu>".to_string(),
+            ])
+        );
+        assert_eq!(
+            tasks[0].timestamp.as_deref(),
+            Some("SCHEDULED: <2026-05-21 Thu>")
+        );
+        assert_eq!(tasks[1].planning, None);
+    }
+
+    #[test]
+    fn extract_tasks_marks_scheduled_angle_bracket_as_active() {
+        // End-to-end: a SCHEDULED line with `<...>` must surface
+        // `timestamp_active = Some(true)` in the resulting Task, so
+        // downstream consumers can branch on bracket form without
+        // re-parsing the timestamp string. See ADR-0014.
+        let content = "### TODO Pin me\n`SCHEDULED: <2026-05-21 Thu>`\n";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].timestamp_active, Some(true));
+    }
+
+    #[test]
+    fn extract_tasks_marks_missing_timestamp_active_as_none() {
+        // Heading without a timestamp must keep `timestamp_active = None`,
+        // matching the rule that absent optional fields skip JSON
+        // serialisation (ADR-0015).
+        let content = "### Project kickoff\n\n`CREATED: [2025-09-01 Mon]`\n";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].timestamp_active, None);
+    }
+
+    #[test]
+    fn extract_tasks_flags_overnight_time_range() {
+        let content = "### TODO Night shift\n`SCHEDULED: <2026-01-05 Mon 22:00-01:30>`\n";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        assert_eq!(tasks[0].timestamp_time.as_deref(), Some("22:00"));
+        assert_eq!(tasks[0].timestamp_end_time.as_deref(), Some("01:30"));
+        assert_eq!(tasks[0].timestamp_overnight, Some(true));
+
+        let content = "### TODO Day shift\n`SCHEDULED: <2026-01-05 Mon 09:00-17:00>`\n";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        assert_eq!(tasks[0].timestamp_overnight, None);
+    }
+
+    #[test]
+    fn extract_tasks_splits_attendees_property() {
+        let content = "### Sprint review\n`<2026-02-03 Tue 15:00-16:00>`\n```org-properties\nATTENDEES: Alice <alice@example.com>, bob@example.com ,\n```\n";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        assert_eq!(
+            tasks[0].attendees.as_deref(),
+            Some(
+                &[
+                    "Alice <alice@example.com>".to_string(),
+                    "bob@example.com".to_string()
+                ][..]
+            )
+        );
+
+        let content = "### Solo focus\n`<2026-02-03 Tue>`\n```org-properties\nATTENDEES:\n```\n";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        assert_eq!(tasks[0].attendees, None);
+    }
+
+    #[test]
+    fn extract_tasks_waiting_since_property() {
+        // WAITING is not a built-in keyword: the property works on any task.
+        let content = "### TODO Reply from legal\n```org-properties\nWAITING_SINCE: [2026-01-05 Mon]\n```\n\n### TODO Quote\n```org-properties\nWAITING_SINCE: 2026-01-09\n```\n\n### TODO Invoice\n```org-properties\nWAITING_SINCE: last week\n```\n\n### WAITING Not a keyword\n";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        assert_eq!(tasks.len(), 3);
+        assert_eq!(tasks[0].task_type, Some(TaskType::Todo));
+        assert_eq!(tasks[0].heading, "Reply from legal");
+        let since: Vec<_> = tasks.iter().map(|t| t.waiting_since.as_deref()).collect();
+        assert_eq!(since, [Some("2026-01-05"), Some("2026-01-09"), None]);
+    }
+
+    #[test]
+    fn extract_tasks_paused_and_pause_until_properties() {
+        let content = "### TODO Water plants\n`SCHEDULED: <2026-01-05 Mon +1w>`\n```org-properties\nPAUSED:\n```\n\n### TODO Clean gutters\n`SCHEDULED: <2026-01-05 Mon +1m>`\n```org-properties\nPAUSE-UNTIL: <2026-04-01 Wed>\n```\n\n### TODO Mow lawn\n```org-properties\nPAUSED: nil\nPAUSE_UNTIL: 2026-05-01\n```\n";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        let paused: Vec<_> = tasks
+            .iter()
+            .map(|t| (t.paused, t.paused_until.as_deref()))
+            .collect();
+        assert_eq!(
+            paused,
+            [
+                (Some(true), None),
+                (None, Some("2026-04-01")),
+                (None, Some("2026-05-01"))
+            ]
+        );
+    }
+
+    #[test]
+    fn extract_tasks_assignee_from_property_or_heading_mention() {
+        let content = "### TODO Review budget @alice.\n\n### TODO Ship release @bob\n```org-properties\nASSIGNEE: @Carol\n```\n\n### TODO Mail bob@example.com\n\n### TODO Sync (@дмитрий)\n";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        let assignees: Vec<_> = tasks.iter().map(|t| t.assignee.as_deref()).collect();
+        assert_eq!(
+            assignees,
+            [Some("alice"), Some("Carol"), None, Some("дмитрий")]
+        );
+        assert_eq!(tasks[0].heading, "Review budget @alice.");
+    }
+
+    #[test]
+    fn extract_tasks_url_from_property_or_first_body_link() {
+        let content = "### TODO Review PR\n`SCHEDULED: <2026-02-03 Tue>`\n\nSee [the PR](https://example.com/pr/42) and [docs](https://example.com/docs).\n";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        assert_eq!(tasks[0].url.as_deref(), Some("https://example.com/pr/42"));
+
+        let content = "### TODO Review PR\n`SCHEDULED: <2026-02-03 Tue>`\n```org-properties\nURL: https://example.com/pr/7\n```\n\nSee [docs](https://example.com/docs).\n";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        assert_eq!(tasks[0].url.as_deref(), Some("https://example.com/pr/7"));
+
+        let content =
+            "### TODO Click me\n`SCHEDULED: <2026-02-03 Tue>`\n\n[x](javascript:alert(1))\n";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        assert_eq!(tasks[0].url, None);
+    }
+
+    #[test]
+    fn extract_tasks_checklist_items_become_subtasks() {
+        let content = "### TODO Release 1.0\n\n- [x] Bump *version*\n- [ ] Run `cargo publish`\n  - [-] Tag\n- plain note\n\n### TODO No steps\n";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        let subtasks = tasks[0].subtasks.as_ref().unwrap();
+        let items: Vec<(&str, bool)> = subtasks.iter().map(|s| (s.text.as_str(), s.done)).collect();
+        assert_eq!(
+            items,
+            [
+                ("Bump version", true),
+                ("Run cargo publish", false),
+                ("Tag", false)
+            ]
+        );
+        assert_eq!(tasks[0].subtasks_percent, Some(33));
+        assert_eq!(tasks[0].subtasks_progress.as_deref(), Some("1/3"));
+        assert!(tasks[1].subtasks.is_none());
+        assert_eq!(tasks[1].subtasks_percent, None);
+    }
+
+    #[test]
+    fn extract_tasks_inside_blockquotes_and_todo_callouts() {
+        let content = "> ### TODO Quoted task\n> `DEADLINE: <2026-02-05 Thu>`\n\n## Notes\n\n> [!todo]- Weekly review\n> `SCHEDULED: <2026-02-06 Fri>`\n> - [x] Inbox zero\n> - [ ] Plan week\n\n> [!note] Not a task\n";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        assert_eq!(tasks.len(), 2, "{tasks:?}");
+        assert_eq!(tasks[0].heading, "Quoted task");
+        assert_eq!(tasks[0].timestamp_date.as_deref(), Some("2026-02-05"));
+        let callout = &tasks[1];
+        assert_eq!(callout.heading, "Weekly review");
+        assert_eq!(callout.task_type, Some(TaskType::Todo));
+        assert_eq!(callout.line, 6);
+        assert_eq!(callout.timestamp_date.as_deref(), Some("2026-02-06"));
+        assert_eq!(callout.content, "");
+        assert_eq!(callout.subtasks_percent, Some(50));
+    }
+
+    #[test]
+    fn extract_tasks_outline_path_follows_heading_nesting() {
+        let content = "# Project A\n\n## [#B] Sprint 3\n\n### TODO Fix bug\n\n> [!todo] Callout\n\n## TODO Sprint 4 prep\n\n# TODO Top level\n";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        // A callout is emitted before the heading it sits under.
+        let paths: Vec<_> = tasks
+            .iter()
+            .map(|t| {
+                (
+                    t.heading.as_str(),
+                    t.outline_path.clone().unwrap_or_default(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                (
+                    "Callout",
+                    vec![
+                        "Project A".to_string(),
+                        "Sprint 3".to_string(),
+                        "Fix bug".to_string()
+                    ]
+                ),
+                (
+                    "Fix bug",
+                    vec!["Project A".to_string(), "Sprint 3".to_string()]
+                ),
+                ("Sprint 4 prep", vec!["Project A".to_string()]),
+                ("Top level", vec![]),
+            ]
+        );
+        assert_eq!(tasks[3].outline_path, None);
+        assert_eq!(tasks[1].level, Some(3));
+    }
+
+    #[test]
+    fn extract_tasks_metadata_nested_in_lists_and_emphasis() {
+        let content = "### TODO In a list\n\n- Plan:\n  - `SCHEDULED: <2026-02-03 Tue>`\n  - `CLOCK: [2026-02-02 Mon 10:00]--[2026-02-02 Mon 11:00] =>  1:00`\n\n### TODO In emphasis\n**`DEADLINE: <2026-02-04 Wed>`**\n\n### TODO Fenced in a list\n\n- step\n\n  ```org-properties\n  Effort: 1:30\n  ```\n";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        assert_eq!(tasks.len(), 3);
+        assert_eq!(tasks[0].timestamp_date.as_deref(), Some("2026-02-03"));
+        assert_eq!(tasks[0].total_clock_time.as_deref(), Some("1:00"));
+        // The planning items are metadata; the plain item stays.
+        assert_eq!(tasks[0].content, "- Plan:");
+        assert_eq!(tasks[1].timestamp_date.as_deref(), Some("2026-02-04"));
+        let props = tasks[2].properties.as_ref().unwrap();
+        assert_eq!(props.get("Effort").map(String::as_str), Some("1:30"));
     }
 
     #[test]
-    fn parse_heading_canceled_single_l() {
-        // Upstream Emacs Org-mode spells the keyword with a single L. See
-        // ADR-0021; recognised alongside the double-L `CANCELLED`.
-        let (tt, p, h) = parse_heading("CANCELED Foo");
-        assert_eq!(tt, Some(TaskType::Cancelled(CancelledSpelling::SingleL)));
-        assert_eq!(p, None);
-        assert_eq!(h, "Foo");
+    fn extract_tasks_counts_reschedule_notes() {
+        let content = "### TODO Write report\n`SCHEDULED: <2026-01-20 Tue>`\n\n```\n- Rescheduled from \"[2026-01-12 Mon]\" on [2026-01-12 Mon 18:02]\n- Rescheduled from \"[2026-01-15 Thu]\" on [2026-01-16 Fri 09:10]\n```\n\n### TODO Never moved\n`SCHEDULED: <2026-01-20 Tue>`\n";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        assert_eq!(tasks[0].rescheduled_count, Some(2));
+        assert_eq!(tasks[1].rescheduled_count, None);
     }
 
     #[test]
-    fn parse_heading_canceled_with_priority() {
-        let (tt, p, h) = parse_heading("CANCELED [#A] Foo");
-        assert_eq!(tt, Some(TaskType::Cancelled(CancelledSpelling::SingleL)));
-        assert_eq!(p, Some(Priority::A));
-        assert_eq!(h, "Foo");
+    fn extract_tasks_pads_dates_and_records_timestamp_diagnostics() {
+        let content = "### TODO Dentist\n`SCHEDULED: <2026-3-5 Thu 9:00>`\n\n### TODO Taxes\n`DEADLINE: <2026-02-30 Mon +0d>`\n";
+        let mut diagnostics = Vec::new();
+        let tasks = extract_tasks_with_counter(
+            Path::new("t.md"),
+            content,
+            &[],
+            &TodoKeywords::default(),
+            ExtractOptions::default(),
+            DEFAULT_MAX_TASKS,
+            &mut diagnostics,
+            &mut 0,
+        );
+        assert_eq!(
+            tasks[0].timestamp.as_deref(),
+            Some("SCHEDULED: <2026-03-05 Thu 9:00>")
+        );
+        assert_eq!(tasks[0].timestamp_date.as_deref(), Some("2026-03-05"));
+        // An impossible date keeps the task but is reported as an error.
+        assert_eq!(tasks[1].heading, "Taxes");
+        let found: Vec<_> = diagnostics
+            .iter()
+            .map(|d| (d.line, d.severity, d.message.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    1,
+                    Severity::Warning,
+                    "date padded to SCHEDULED: <2026-03-05 Thu 9:00>"
+                ),
+                (
+                    4,
+                    Severity::Error,
+                    "cannot parse timestamp: date 2026-02-30 does not exist"
+                ),
+            ]
+        );
     }
 
     #[test]
-    fn parse_heading_canceled_lowercase_not_recognised() {
-        // Case-sensitive, like TODO/DONE/CANCELLED.
-        let (tt, p, h) = parse_heading("canceled Foo");
-        assert_eq!(tt, None);
-        assert_eq!(p, None);
-        assert_eq!(h, "canceled Foo");
+    fn extract_tasks_trims_text_left_around_planning_code() {
+        let content = "### TODO Call\n`SCHEDULED: <2026-03-05 Thu>` ask about *the* [offer](https://example.com) \n";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        assert_eq!(tasks[0].content, "ask about the offer");
     }
 
     #[test]
-    fn parse_heading_canceled_without_whitespace_not_recognised() {
-        // No whitespace after the keyword: not recognised, stays in title.
-        let (tt, p, h) = parse_heading("CANCELEDfoo");
-        assert_eq!(tt, None);
-        assert_eq!(p, None);
-        assert_eq!(h, "CANCELEDfoo");
+    fn extract_tasks_share_one_file_path_per_document() {
+        let content = "# TODO One\n\n# TODO Two\n";
+        let tasks = extract_tasks(Path::new("notes/a.md"), content, &[], DEFAULT_MAX_TASKS);
+        assert_eq!(&*tasks[0].file, "notes/a.md");
+        assert!(Arc::ptr_eq(&tasks[0].file, &tasks[1].file));
     }
 
     #[test]
-    fn extract_tasks_marks_scheduled_angle_bracket_as_active() {
-        // End-to-end: a SCHEDULED line with `<...>` must surface
-        // `timestamp_active = Some(true)` in the resulting Task, so
-        // downstream consumers can branch on bracket form without
-        // re-parsing the timestamp string. See ADR-0014.
-        let content = "### TODO Pin me\n`SCHEDULED: <2026-05-21 Thu>`\n";
+    fn extract_tasks_setext_headings_match_atx() {
+        let content = "TODO [#A] Write docs\n====================\n`DEADLINE: <2025-12-10 Wed>`\n\nDONE Review\n-----------\n\n### TODO [#A] Write docs\n`DEADLINE: <2025-12-10 Wed>`\n";
         let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
-        assert_eq!(tasks.len(), 1);
-        assert_eq!(tasks[0].timestamp_active, Some(true));
+        assert_eq!(tasks.len(), 3);
+        let (setext, atx) = (&tasks[0], &tasks[2]);
+        assert_eq!(setext.heading, atx.heading);
+        assert_eq!(setext.task_type, atx.task_type);
+        assert_eq!(setext.priority, atx.priority);
+        assert_eq!(setext.timestamp_date, atx.timestamp_date);
+        assert_eq!(setext.level, Some(1));
+        assert_eq!(tasks[1].task_type, Some(TaskType::Done));
+        assert_eq!(tasks[1].level, Some(2));
+        assert_eq!(atx.level, Some(3));
     }
 
     #[test]
-    fn extract_tasks_marks_missing_timestamp_active_as_none() {
-        // Heading without a timestamp must keep `timestamp_active = None`,
-        // matching the rule that absent optional fields skip JSON
-        // serialisation (ADR-0015).
-        let content = "### Project kickoff\n\n`CREATED: [2025-09-01 Mon]`\n";
+    fn extract_tasks_records_nearest_ancestor_priority() {
+        let content = "# [#A] Project\n\n## Backlog\n\n### TODO Draft\n\n### TODO [#C] Polish\n\n#### TODO Proofread\n\n> [!todo] Callout\n\n# Other\n\n## TODO Loose\n";
         let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
-        assert_eq!(tasks.len(), 1);
-        assert_eq!(tasks[0].timestamp_active, None);
+        let inherited: Vec<_> = tasks
+            .iter()
+            .map(|t| (t.heading.as_str(), t.inherited_priority.clone()))
+            .collect();
+        assert_eq!(
+            inherited,
+            [
+                ("Draft", Some(Priority::A)),
+                ("Polish", Some(Priority::A)),
+                // A callout is emitted before the heading around it.
+                ("Callout", Some(Priority::C)),
+                ("Proofread", Some(Priority::C)),
+                ("Loose", None),
+            ]
+        );
+        assert_eq!(tasks[0].priority, None);
     }
 
     #[test]
@@ -1197,4 +2976,535 @@ Second paragraph.\n\
         assert_eq!(props.get("K").map(String::as_str), Some("two"));
         assert_eq!(props.get("L").map(String::as_str), Some("three"));
     }
+
+    #[test]
+    fn extract_tasks_property_drawer_in_paragraph() {
+        let content = "### TODO T\n`SCHEDULED: <2026-06-01 Mon>`\n:PROPERTIES:\n:ID: 4f2a-91\n:Effort: 1:30\n:EMPTY:\nno colon\n:END:\nBody text.\n";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        let props = tasks[0].properties.as_ref().unwrap();
+        assert_eq!(props.get("ID").map(String::as_str), Some("4f2a-91"));
+        assert_eq!(props.get("Effort").map(String::as_str), Some("1:30"));
+        assert_eq!(props.get("EMPTY").map(String::as_str), Some(""));
+        assert_eq!(props.len(), 3);
+        assert_eq!(tasks[0].content, "Body text.");
+        assert_eq!(tasks[0].timestamp_date.as_deref(), Some("2026-06-01"));
+    }
+
+    #[test]
+    fn extract_tasks_property_drawer_in_code_block() {
+        let content = "### TODO T\n```\n:properties:\n:CATEGORY: ops\n:end:\nDEADLINE: <2026-06-02 Tue>\n```\n\n### TODO Not a drawer\n```\n:PROPERTIES:\n:K: v\n```\n";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        assert_eq!(
+            tasks[0]
+                .properties
+                .as_ref()
+                .unwrap()
+                .get("CATEGORY")
+                .map(String::as_str),
+            Some("ops")
+        );
+        assert_eq!(tasks[0].category.as_deref(), Some("ops"));
+        assert_eq!(tasks[0].timestamp_date.as_deref(), Some("2026-06-02"));
+        // Without `:END:` the block is an ordinary code block.
+        assert_eq!(tasks[1].properties, None);
+    }
+
+    #[test]
+    fn extract_tasks_logbook_drawer() {
+        let content = "\
+### DONE Ship
+```org
+:LOGBOOK:
+- State \"DONE\"       from \"TODO\"       [2025-01-10 Fri 10:00]
+- Note taken on [2025-01-09 Thu 18:00] \\\\
+  blocked on review
+CLOCK: [2025-01-09 Thu 09:00]--[2025-01-09 Thu 10:30] =>  1:30
+- State \"TODO\"       from              [2025-01-08 Wed 12:00]
+:END:
+```
+";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        let log = tasks[0].log.as_ref().unwrap();
+        assert_eq!(log.len(), 3);
+        assert_eq!(
+            log[0],
+            LogEntry::State {
+                to: "DONE".to_string(),
+                from: Some("TODO".to_string()),
+                timestamp: "2025-01-10 Fri 10:00".to_string(),
+            }
+        );
+        assert!(
+            matches!(&log[1], LogEntry::Clock(clock) if clock.duration.as_deref() == Some("1:30"))
+        );
+        assert!(matches!(&log[2], LogEntry::State { from: None, to, .. } if to == "TODO"));
+        assert_eq!(tasks[0].total_clock_time.as_deref(), Some("1:30"));
+    }
+
+    #[test]
+    fn extract_tasks_heading_tags_and_ancestor_tags() {
+        let content = "\
+## Project :acme:work:
+
+### TODO Ship it :urgent:work:
+
+#### TODO Nested
+
+## Ratio 1:2:
+
+### TODO Plain :not a tag:
+";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        let tags = |t: &Task| (t.tags.clone(), t.inherited_tags.clone());
+        let v = |tags: &[&str]| Some(tags.iter().map(|t| t.to_string()).collect::<Vec<_>>());
+        assert_eq!(tasks[0].heading, "Ship it");
+        assert_eq!(
+            tags(&tasks[0]),
+            (v(&["urgent", "work"]), v(&["acme", "work"]))
+        );
+        assert_eq!(
+            tasks[1].outline_path,
+            v(&["Project", "Ship it"]),
+            "tags stay out of the outline path"
+        );
+        assert_eq!(tags(&tasks[1]), (None, v(&["acme", "work", "urgent"])));
+        assert_eq!(tasks[2].heading, "Plain :not a tag:");
+        assert_eq!(tags(&tasks[2]), (None, None));
+    }
+
+    #[test]
+    fn extract_tasks_statistics_cookies() {
+        let content = "\
+## TODO Launch [%] :acme:
+
+### DONE Design
+
+### TODO Build [1/2]
+
+- [x] backend
+- [ ] frontend
+
+#### DONE Nested, not a direct child
+
+### CANCELLED Marketing
+
+## TODO [/] Empty
+";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        let progress = |t: &Task| {
+            (
+                t.heading.clone(),
+                t.progress_cookie.clone(),
+                t.progress.clone(),
+            )
+        };
+        let s = |v: &str| Some(v.to_string());
+        assert_eq!(
+            progress(&tasks[0]),
+            ("Launch".to_string(), s("[%]"), s("[66%]"))
+        );
+        assert_eq!(
+            progress(&tasks[2]),
+            ("Build".to_string(), s("[1/2]"), s("[1/2]"))
+        );
+        assert_eq!(
+            progress(&tasks[5]),
+            ("Empty".to_string(), s("[/]"), s("[0/0]"))
+        );
+        assert_eq!(tasks[1].progress, None);
+    }
+
+    #[test]
+    fn extract_tasks_reads_a_timestamp_in_the_heading() {
+        let content = "\
+## TODO Standup <2025-03-03 Mon 09:30 +1d> :team:
+
+## TODO Встреча <2025-03-04 Вт 10:00>--<2025-03-05 Ср>
+
+## TODO Review <2025-03-06 Thu>
+`SCHEDULED: <2025-03-07 Fri>`
+
+## TODO Compare a < b and c > d
+";
+        let ru = [("Вт", "Tue"), ("Ср", "Wed")];
+        let tasks = extract_tasks(Path::new("t.md"), content, &ru, DEFAULT_MAX_TASKS);
+        fn ts(t: &Task) -> (&str, Option<&str>) {
+            (t.heading.as_str(), t.timestamp.as_deref())
+        }
+        assert_eq!(
+            ts(&tasks[0]),
+            ("Standup", Some("<2025-03-03 Mon 09:30 +1d>"))
+        );
+        assert_eq!(tasks[0].timestamp_repeater.as_deref(), Some("+1d"));
+        assert_eq!(tasks[0].timestamp_time.as_deref(), Some("09:30"));
+        assert_eq!(tasks[0].tags, Some(vec!["team".to_string()]));
+        assert_eq!(
+            ts(&tasks[1]),
+            ("Встреча", Some("<2025-03-04 Tue 10:00>--<2025-03-05 Wed>"))
+        );
+        // A planning line in the body wins over the heading.
+        assert_eq!(
+            ts(&tasks[2]),
+            ("Review", Some("SCHEDULED: <2025-03-07 Fri>"))
+        );
+        assert_eq!(ts(&tasks[3]), ("Compare a < b and c > d", None));
+    }
+
+    #[test]
+    fn extract_tasks_reads_every_line_of_a_fenced_org_block() {
+        let content = "\
+## TODO Fenced
+
+```org
+:PROPERTIES:
+:OWNER: ann
+:END:
+CLOSED: [2025-03-01 Sat 10:00]
+CREATED: [2025-02-01 Sat]
+DEADLINE: <2025-03-10 Mon>
+CLOCK: [2025-03-01 Sat 09:00]--[2025-03-01 Sat 10:00] =>  1:00
+```
+
+## TODO Attributes
+
+~~~org :exports none
+  SCHEDULED: <2025-03-11 Tue>
+~~~
+
+## TODO Code
+
+```rust
+// SCHEDULED: <2025-03-12 Wed>
+```
+";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        let fenced = &tasks[0];
+        assert_eq!(fenced.created.as_deref(), Some("CREATED: [2025-02-01 Sat]"));
+        assert_eq!(
+            fenced.timestamp.as_deref(),
+            Some("DEADLINE: <2025-03-10 Mon>")
+        );
+        assert_eq!(
+            fenced.closed.as_deref(),
+            Some("CLOSED: [2025-03-01 Sat 10:00]")
+        );
+        assert_eq!(fenced.clocks.as_ref().map(Vec::len), Some(1));
+        assert_eq!(
+            fenced
+                .properties
+                .as_ref()
+                .and_then(|p| p.get("OWNER"))
+                .map(String::as_str),
+            Some("ann")
+        );
+        assert_eq!(
+            tasks[1].timestamp.as_deref(),
+            Some("SCHEDULED: <2025-03-11 Tue>")
+        );
+        assert_eq!(tasks[2].timestamp, None);
+    }
+
+    #[test]
+    fn extract_tasks_keeps_lists_and_quotes_in_content() {
+        let content = "\
+## TODO Plan
+`SCHEDULED: <2025-03-10 Mon>`
+
+Intro
+text.
+
+- [ ] buy milk
+- [x] call **Bob**
+  - ask about `make`
+- `CLOCK: [2025-03-01 Sat 09:00]--[2025-03-01 Sat 10:00] =>  1:00`
+  - nested note
+
+3. third
+4. fourth
+
+> Quoted remark
+> second line
+>
+> - quoted item
+
+Outro.
+";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        assert_eq!(
+            tasks[0].content,
+            "Intro\ntext.\n\n- ask about make\n- nested note\n\n3. third\n4. fourth\n\n\
+             > Quoted remark\n> second line\n>\n> - quoted item\n\nOutro."
+        );
+        assert_eq!(tasks[0].subtasks.as_ref().map(Vec::len), Some(2));
+    }
+
+    #[test]
+    fn extract_tasks_reads_planning_lines_typed_as_plain_text() {
+        let content = "\
+## TODO Plain planning
+SCHEDULED: <2025-04-01 Вт> DEADLINE: <2025-04-05 Sat>
+CREATED: [2025-03-01 Sat]
+CLOCK: [2025-03-02 Sun 09:00]--[2025-03-02 Sun 10:00] =>  1:00
+Body text.
+`CLOSED: [2025-03-03 Mon 12:00]`
+More text.
+
+## TODO Mention
+We said SCHEDULED: <2025-04-02 Wed> once.
+";
+        let ru = [("Вт", "Tue")];
+        let tasks = extract_tasks(Path::new("t.md"), content, &ru, DEFAULT_MAX_TASKS);
+        let plain = &tasks[0];
+        assert_eq!(
+            plain.timestamp.as_deref(),
+            Some("SCHEDULED: <2025-04-01 Tue>")
+        );
+        assert_eq!(
+            plain.planning.as_deref(),
+            Some(
+                &[
+                    "SCHEDULED: <2025-04-01 Tue>".to_string(),
+                    "DEADLINE: <2025-04-05 Sat>".to_string()
+                ][..]
+            )
+        );
+        assert_eq!(plain.created.as_deref(), Some("CREATED: [2025-03-01 Sat]"));
+        assert_eq!(
+            plain.closed.as_deref(),
+            Some("CLOSED: [2025-03-03 Mon 12:00]")
+        );
+        assert_eq!(plain.total_clock_time.as_deref(), Some("1:00"));
+        assert_eq!(plain.content, "Body text.\nMore text.");
+
+        // Only a line that starts with the keyword is a planning line.
+        assert_eq!(tasks[1].timestamp, None);
+        assert_eq!(
+            tasks[1].content,
+            "We said SCHEDULED: <2025-04-02 Wed> once."
+        );
+    }
+
+    #[test]
+    fn extract_tasks_keeps_gfm_tables_strikethrough_and_footnotes() {
+        let content = "\
+## TODO Release
+`SCHEDULED: <2025-04-01 Tue>`
+
+Ship ~~Friday~~ Monday.[^why]
+
+| Step | Owner |
+|:-----|:-----:|
+| Tag `v1` | ann \\| bob |
+| `CLOCK: [2025-03-02 Sun 09:00]--[2025-03-02 Sun 10:00] =>  1:00` | |
+
+## TODO Later
+
+No footnote here.
+
+[^why]: Friday is a freeze.
+";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        assert_eq!(
+            tasks[0].content,
+            "Ship ~~Friday~~ Monday.[^why]\n\n\
+             | Step | Owner |\n| :--- | :---: |\n| Tag v1 | ann \\| bob |\n\
+             | CLOCK: [2025-03-02 Sun 09:00]--[2025-03-02 Sun 10:00] =>  1:00 |  |\n\n\
+             [^why]: Friday is a freeze."
+        );
+        // Planning lines in a table cell still count.
+        assert_eq!(tasks[0].total_clock_time.as_deref(), Some("1:00"));
+        assert_eq!(tasks[1].content, "No footnote here.");
+    }
+
+    #[test]
+    fn extract_tasks_counts_nested_open_and_closed_tasks() {
+        let content = "\
+## TODO Launch
+
+### DONE Design
+
+### Build
+
+#### TODO Backend
+
+##### CANCELLED Old API
+
+#### TODO Frontend
+
+## Other
+
+### TODO Unrelated
+
+## TODO Alone
+";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        assert_eq!(
+            tasks
+                .iter()
+                .map(|t| (t.heading.as_str(), t.children_todo, t.children_done))
+                .collect::<Vec<_>>(),
+            [
+                ("Launch", Some(2), Some(2)),
+                ("Design", None, None),
+                ("Backend", Some(0), Some(1)),
+                ("Old API", None, None),
+                ("Frontend", None, None),
+                ("Unrelated", None, None),
+                ("Alone", None, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn extract_tasks_collects_markdown_and_org_links() {
+        let content = "\
+### TODO Review [spec](https://example.com/spec)
+
+See [[file:notes/db.org][the schema]] and [[https://example.com/spec]].
+
+- [ ] compare with [spec](https://example.com/spec)
+- [ ] read [[notes/old.md]]
+";
+        let tasks = extract_tasks(Path::new("t.md"), content, &[], DEFAULT_MAX_TASKS);
+        let links: Vec<_> = tasks[0]
+            .links
+            .iter()
+            .flatten()
+            .map(|l| (l.target.as_str(), l.text.as_deref()))
+            .collect();
+        assert_eq!(
+            links,
+            [
+                ("https://example.com/spec", Some("spec")),
+                ("file:notes/db.org", Some("the schema")),
+                ("https://example.com/spec", None),
+                ("notes/old.md", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn list_items_with_a_keyword_become_tasks_on_request() {
+        let content = "\
+## TODO Errands
+
+- [ ] TODO [#A] Call dentist `SCHEDULED: <2025-03-10 Mon>`
+  - [x] find the number
+- [x] DONE Buy milk
+- [ ] plain checkbox
+- NEXT Not a keyword here
+";
+        let extract = |as_tasks: bool| {
+            extract_tasks_with_counter(
+                Path::new("t.md"),
+                content,
+                &[],
+                &TodoKeywords::default(),
+                ExtractOptions {
+                    list_items_as_tasks: as_tasks,
+                    ..ExtractOptions::default()
+                },
+                DEFAULT_MAX_TASKS,
+                &mut Vec::new(),
+                &mut 0,
+            )
+        };
+
+        let tasks = extract(false);
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].subtasks.as_ref().map(Vec::len), Some(4));
+        assert!(
+            tasks[0].timestamp.is_some(),
+            "planning line leaks by default"
+        );
+
+        let tasks = extract(true);
+        let summary: Vec<_> = tasks
+            .iter()
+            .map(|t| (t.heading.as_str(), t.line, t.task_type.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("Call dentist", 3, Some(TaskType::Todo)),
+                ("Buy milk", 5, Some(TaskType::Done)),
+                ("Errands", 1, Some(TaskType::Todo)),
+            ]
+        );
+        let dentist = &tasks[0];
+        assert_eq!(dentist.priority, Some(Priority::A));
+        assert_eq!(
+            dentist.timestamp.as_deref(),
+            Some("SCHEDULED: <2025-03-10 Mon>")
+        );
+        assert_eq!(dentist.subtasks_progress.as_deref(), Some("1/1"));
+        assert_eq!(dentist.outline_path, Some(vec!["Errands".to_string()]));
+        assert_eq!(dentist.level, None);
+        let errands = &tasks[2];
+        assert_eq!(errands.timestamp, None);
+        assert_eq!(errands.subtasks.as_ref().map(Vec::len), Some(1));
+    }
+
+    #[test]
+    fn full_content_keeps_every_body_block_as_markdown() {
+        let content = "\
+### TODO Plan release
+`SCHEDULED: <2026-02-03 Tue>`
+:PROPERTIES:
+:ID: 42
+:END:
+See *the notes*.
+
+```org-properties
+Effort: 1:00
+```
+
+- [ ] Bump version
+- [ ] Tag
+
+```sh
+cargo publish
+```
+
+> [!todo] Separate task
+
+> Quoted remark
+
+### TODO Next
+";
+        let extract = |full_content: bool| {
+            extract_tasks_with_counter(
+                Path::new("t.md"),
+                content,
+                &[],
+                &TodoKeywords::default(),
+                ExtractOptions {
+                    full_content,
+                    ..ExtractOptions::default()
+                },
+                DEFAULT_MAX_TASKS,
+                &mut Vec::new(),
+                &mut 0,
+            )
+        };
+
+        let plan = |tasks: Vec<Task>| {
+            assert_eq!(tasks.len(), 3);
+            // The callout is finalized before the heading around it.
+            assert_eq!(tasks[0].heading, "Separate task");
+            assert_eq!(tasks[2].content, "");
+            tasks.into_iter().nth(1).unwrap()
+        };
+        assert_eq!(
+            plan(extract(false)).content,
+            "See the notes.\n\n> Quoted remark"
+        );
+
+        let task = plan(extract(true));
+        assert_eq!(
+            task.content,
+            "See *the notes*.\n\n- [ ] Bump version\n- [ ] Tag\n\n```sh\ncargo publish\n```\n\n> Quoted remark"
+        );
+        assert_eq!(task.effort_minutes, Some(60));
+    }
 }
@@ -46,6 +46,17 @@ pub fn compile_bounded(pattern: &str) -> Regex {
         .unwrap_or_else(|e| panic!("Failed to compile regex {pattern:?}: {e}"))
 }
 
+/// Compile a user-supplied pattern (`--query --regex`) case-insensitively,
+/// under the same size limits. Unlike [`compile_bounded`] an invalid
+/// pattern is the user's error, so it is returned rather than panicking.
+pub fn compile_user_pattern(pattern: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .size_limit(SIZE_LIMIT_BYTES)
+        .dfa_size_limit(DFA_SIZE_LIMIT_BYTES)
+        .build()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
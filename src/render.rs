@@ -1,6 +1,17 @@
 use std::fmt::Write;
 
-use crate::types::{ClockEntry, DayAgenda, Task, TaskWithOffset};
+mod registry;
+
+pub use registry::{
+    HtmlRenderer, IcsRenderer, JsonRenderer, MarkdownRenderer, Renderer, RendererRegistry,
+};
+
+use crate::agenda::{AgendaBlock, AgendaGroup, AgendaOutput};
+use crate::clock::format_duration;
+use crate::types::{
+    ClockEntry, ClockRow, DayAgenda, DaySummary, ForecastEntry, ForecastMonth, FreeDay, FreeSlot,
+    HabitRow, QuarterMonth, SimulatedDay, Subtask, Task, TaskWithOffset,
+};
 
 /// Escape markdown special characters in plain text. Used for headings and
 /// labels that originate from user input — keeps formatting from being broken
@@ -19,6 +30,15 @@ fn md_escape(s: &str) -> String {
     out
 }
 
+/// Make a URL safe inside a Markdown `<...>` link destination, which ends at
+/// the first `>` and may not contain a line break.
+fn md_href(href: &str) -> String {
+    href.replace('<', "%3C")
+        .replace('>', "%3E")
+        .replace('\n', "%0A")
+        .replace('\r', "%0D")
+}
+
 /// Escape HTML special characters in pre-existing text content.
 /// Also drops C0 control characters (except `\t \n \r`) to protect downstream
 /// renderers from null bytes and other invisible glyphs sneaked through markdown.
@@ -49,34 +69,127 @@ fn offset_suffix(days_offset: Option<i64>) -> Option<String> {
     })
 }
 
+/// `href` for a task link: web and mail URLs, and note paths with no
+/// scheme or Org's `file:` prefix. Any other scheme (`javascript:`,
+/// `data:`) would be an injection in HTML, so it stays plain text.
+fn link_href(target: &str) -> Option<&str> {
+    let target = target.strip_prefix("file:").unwrap_or(target);
+    let scheme_end = target.find([':', '/', '?', '#']);
+    match scheme_end.map(|i| (&target[..i], target[i..].starts_with(':'))) {
+        Some((scheme, true)) => matches!(
+            scheme.to_ascii_lowercase().as_str(),
+            "http" | "https" | "mailto"
+        )
+        .then_some(target),
+        _ => Some(target),
+    }
+}
+
+/// OpenStreetMap link for a `LOCATION` value: a pin for `lat,lon`
+/// coordinates, a place search for anything else.
+fn map_link(location: &str) -> String {
+    let coords = location.split_once(',').and_then(|(lat, lon)| {
+        let lat: f64 = lat.trim().parse().ok()?;
+        let lon: f64 = lon.trim().parse().ok()?;
+        ((-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lon)).then_some((lat, lon))
+    });
+    match coords {
+        Some((lat, lon)) => {
+            format!("https://www.openstreetmap.org/?mlat={lat}&mlon={lon}#map=16/{lat}/{lon}")
+        }
+        None => format!(
+            "https://www.openstreetmap.org/search?query={}",
+            percent_encode(location)
+        ),
+    }
+}
+
+/// Percent-encode everything but RFC 3986 unreserved characters.
+fn percent_encode(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            out.push(byte as char);
+        } else {
+            let _ = write!(out, "%{byte:02X}");
+        }
+    }
+    out
+}
+
+/// Quote one CSV field per RFC 4180 (wrap in `"` and double embedded quotes
+/// when it contains `,`, `"`, CR or LF). A field starting with `=`, `+`,
+/// `-`, `@`, tab or CR is prefixed with `'` so a spreadsheet opening the
+/// export treats a hostile heading as text, not as a formula (OWASP CSV
+/// injection guidance).
+fn csv_field(s: &str) -> String {
+    let mut value = String::with_capacity(s.len() + 1);
+    if s.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        value.push('\'');
+    }
+    value.push_str(s);
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
 /// Common formatting strategy for one output format (Markdown or HTML).
 ///
 /// All `render_*` entry points delegate field traversal to `write_task`, which
 /// drives this trait's methods. Adding a new `Task` field means touching
 /// `write_task` once instead of four renderers.
 trait TaskFormat {
-    fn doc_open(&self, title: &str) -> String;
+    /// Start of the document frame, before its first title.
+    fn doc_start(&self) -> String;
+    /// Document-level title; a grouped document has one per group.
+    fn title(&self, out: &mut String, title: &str);
+    fn doc_open(&self, title: &str) -> String {
+        let mut out = self.doc_start();
+        self.title(&mut out, title);
+        out
+    }
     fn doc_close(&self, out: &mut String);
     fn day_header(&self, out: &mut String, date: &str);
     fn section(&self, out: &mut String, title: &str);
     fn after_section(&self, out: &mut String);
-    fn task_heading(&self, out: &mut String, level: u8, heading: &str, days_offset: Option<i64>);
+    /// Task heading; with `href` the heading text links to the task's `url`.
+    fn task_heading(
+        &self,
+        out: &mut String,
+        level: u8,
+        heading: &str,
+        days_offset: Option<i64>,
+        href: Option<&str>,
+    );
     /// Single `Label: value` field. `code` requests inline-code wrapping
     /// for formats that support it (Markdown); HTML ignores the hint.
     fn field(&self, out: &mut String, label: &str, value: &str, code: bool);
+    /// `Label: value` where the value links to `href`.
+    fn link_field(&self, out: &mut String, label: &str, value: &str, href: &str);
     fn clocks_open(&self, out: &mut String);
     fn clock_complete(&self, out: &mut String, start: &str, end: &str, duration: Option<&str>);
     fn clock_active(&self, out: &mut String, start: &str);
     fn clocks_close(&self, out: &mut String);
+    /// `Checklist: <summary>` followed by the items as a checkbox list.
+    fn checklist(&self, out: &mut String, summary: &str, items: &[Subtask]);
     fn content(&self, out: &mut String, body: &str);
+    /// `…and N more` closing a section cut short by `--max-entries-per-day`.
+    fn more(&self, out: &mut String, count: usize);
+    /// The `── now ──` marker of a `--timeline` day at `time` (`HH:MM`).
+    fn now(&self, out: &mut String, time: &str);
 }
 
 struct MdFormat;
 struct HtmlFormat;
 
 impl TaskFormat for MdFormat {
-    fn doc_open(&self, title: &str) -> String {
-        format!("# {title}\n\n")
+    fn doc_start(&self) -> String {
+        String::new()
+    }
+    fn title(&self, out: &mut String, title: &str) {
+        let _ = write!(out, "# {}\n\n", md_escape(title));
     }
     fn doc_close(&self, _out: &mut String) {}
 
@@ -84,15 +197,34 @@ impl TaskFormat for MdFormat {
         let _ = writeln!(out, "## {date}\n");
     }
     fn section(&self, out: &mut String, title: &str) {
-        let _ = write!(out, "### {title}\n\n");
+        let _ = write!(out, "### {}\n\n", md_escape(title));
     }
     fn after_section(&self, out: &mut String) {
         out.push('\n');
     }
 
-    fn task_heading(&self, out: &mut String, level: u8, heading: &str, days_offset: Option<i64>) {
+    fn task_heading(
+        &self,
+        out: &mut String,
+        level: u8,
+        heading: &str,
+        days_offset: Option<i64>,
+        href: Option<&str>,
+    ) {
         let hashes: String = "#".repeat(level as usize);
-        let _ = write!(out, "{hashes} {}", md_escape(heading));
+        match href {
+            Some(href) => {
+                let _ = write!(
+                    out,
+                    "{hashes} [{}](<{}>)",
+                    md_escape(heading),
+                    md_href(href)
+                );
+            }
+            None => {
+                let _ = write!(out, "{hashes} {}", md_escape(heading));
+            }
+        }
         if let Some(suffix) = offset_suffix(days_offset) {
             let _ = write!(out, "{suffix}");
         }
@@ -106,6 +238,14 @@ impl TaskFormat for MdFormat {
             let _ = writeln!(out, "**{label}:** {value}");
         }
     }
+    fn link_field(&self, out: &mut String, label: &str, value: &str, href: &str) {
+        let _ = writeln!(
+            out,
+            "**{label}:** [{}](<{}>)",
+            md_escape(value),
+            md_href(href)
+        );
+    }
 
     fn clocks_open(&self, out: &mut String) {
         out.push_str("\n**Clock:**\n");
@@ -125,6 +265,14 @@ impl TaskFormat for MdFormat {
     }
     fn clocks_close(&self, _out: &mut String) {}
 
+    fn checklist(&self, out: &mut String, summary: &str, items: &[Subtask]) {
+        let _ = writeln!(out, "\n**Checklist:** {summary}");
+        for item in items {
+            let mark = if item.done { 'x' } else { ' ' };
+            let _ = writeln!(out, "- [{mark}] {}", md_escape(&item.text));
+        }
+    }
+
     fn content(&self, out: &mut String, body: &str) {
         if body.is_empty() {
             out.push('\n');
@@ -132,11 +280,20 @@ impl TaskFormat for MdFormat {
             let _ = write!(out, "\n{body}\n\n");
         }
     }
+    fn more(&self, out: &mut String, count: usize) {
+        let _ = writeln!(out, "_…and {count} more_");
+    }
+    fn now(&self, out: &mut String, time: &str) {
+        let _ = write!(out, "**── now {time} ──**\n\n");
+    }
 }
 
 impl TaskFormat for HtmlFormat {
-    fn doc_open(&self, title: &str) -> String {
-        format!("<html><body><h1>{title}</h1>\n")
+    fn doc_start(&self) -> String {
+        "<html><body>".to_string()
+    }
+    fn title(&self, out: &mut String, title: &str) {
+        let _ = writeln!(out, "<h1>{}</h1>", html_escape(title));
     }
     fn doc_close(&self, out: &mut String) {
         out.push_str("</body></html>");
@@ -146,12 +303,31 @@ impl TaskFormat for HtmlFormat {
         let _ = writeln!(out, "<h2>{}</h2>", html_escape(date));
     }
     fn section(&self, out: &mut String, title: &str) {
-        let _ = writeln!(out, "<h3>{title}</h3>");
+        let _ = writeln!(out, "<h3>{}</h3>", html_escape(title));
     }
     fn after_section(&self, _out: &mut String) {}
 
-    fn task_heading(&self, out: &mut String, level: u8, heading: &str, days_offset: Option<i64>) {
-        let _ = write!(out, "<h{level}>{}", html_escape(heading));
+    fn task_heading(
+        &self,
+        out: &mut String,
+        level: u8,
+        heading: &str,
+        days_offset: Option<i64>,
+        href: Option<&str>,
+    ) {
+        match href {
+            Some(href) => {
+                let _ = write!(
+                    out,
+                    "<h{level}><a href=\"{}\">{}</a>",
+                    html_escape(href),
+                    html_escape(heading)
+                );
+            }
+            None => {
+                let _ = write!(out, "<h{level}>{}", html_escape(heading));
+            }
+        }
         if let Some(suffix) = offset_suffix(days_offset) {
             let _ = write!(out, "{}", html_escape(&suffix));
         }
@@ -165,6 +341,14 @@ impl TaskFormat for HtmlFormat {
             html_escape(value)
         );
     }
+    fn link_field(&self, out: &mut String, label: &str, value: &str, href: &str) {
+        let _ = writeln!(
+            out,
+            "<p><strong>{label}:</strong> <a href=\"{}\">{}</a></p>",
+            html_escape(href),
+            html_escape(value)
+        );
+    }
 
     fn clocks_open(&self, out: &mut String) {
         out.push_str("<p><strong>Clock:</strong></p>\n<ul>\n");
@@ -197,10 +381,50 @@ impl TaskFormat for HtmlFormat {
         out.push_str("</ul>\n");
     }
 
+    fn checklist(&self, out: &mut String, summary: &str, items: &[Subtask]) {
+        let _ = writeln!(
+            out,
+            "<p><strong>Checklist:</strong> {}</p>\n<ul>",
+            html_escape(summary)
+        );
+        for item in items {
+            let checked = if item.done { " checked" } else { "" };
+            let _ = writeln!(
+                out,
+                "<li><input type=\"checkbox\" disabled{checked}> {}</li>",
+                html_escape(&item.text)
+            );
+        }
+        out.push_str("</ul>\n");
+    }
+
     fn content(&self, out: &mut String, body: &str) {
-        if !body.is_empty() {
-            let _ = writeln!(out, "<p>{}</p>", html_escape(body));
+        if body.is_empty() {
+            return;
         }
+        // `content` is markdown (a whole section with `--full-content`).
+        // comrak's defaults omit raw HTML and unsafe link schemes, and the
+        // GFM extensions the parser reads add none; control characters are
+        // dropped first, as `html_escape` does.
+        let body: String = body
+            .chars()
+            .filter(|&c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+            .collect();
+        let mut options = comrak::Options::default();
+        options.extension.table = true;
+        options.extension.strikethrough = true;
+        options.extension.footnotes = true;
+        out.push_str(&comrak::markdown_to_html(&body, &options));
+    }
+    fn more(&self, out: &mut String, count: usize) {
+        let _ = writeln!(out, "<p><em>…and {count} more</em></p>");
+    }
+    fn now(&self, out: &mut String, time: &str) {
+        let _ = writeln!(
+            out,
+            "<p><strong>── now {} ──</strong></p>",
+            html_escape(time)
+        );
     }
 }
 
@@ -209,16 +433,18 @@ impl TaskFormat for HtmlFormat {
 /// `level` controls heading depth (2 for top-level lists, 4 for day-agenda
 /// sub-sections). `include_history` toggles fields that are only meaningful in
 /// the "all tasks" view -- `Created`, `Total Time`, `Clock:` -- so day agendas
-/// stay focused on the schedule.
+/// stay focused on the schedule. `extra_fields` are agenda-entry annotations
+/// (`Conflict`, `Repeats`, `Occurs`, `Local Time`) written after the timestamp.
 fn write_task<F: TaskFormat>(
     out: &mut String,
     task: &Task,
     days_offset: Option<i64>,
+    extra_fields: &[(&str, String)],
     level: u8,
     include_history: bool,
     fmt: &F,
 ) {
-    fmt.task_heading(out, level, &task.heading, days_offset);
+    fmt.task_heading(out, level, &task.heading, days_offset, task.url.as_deref());
 
     let file_value = format!("{}:{}", task.file, task.line);
     fmt.field(out, "File", &file_value, true);
@@ -229,6 +455,9 @@ fn write_task<F: TaskFormat>(
     if let Some(ref p) = task.priority {
         fmt.field(out, "Priority", &p.to_string(), false);
     }
+    if let Some(ref p) = task.effective_priority {
+        fmt.field(out, "Effective Priority", &p.to_string(), false);
+    }
     if include_history {
         if let Some(ref c) = task.created {
             fmt.field(out, "Created", c, true);
@@ -237,7 +466,68 @@ fn write_task<F: TaskFormat>(
     if let Some(ref ts) = task.timestamp {
         fmt.field(out, "Time", ts, true);
     }
+    if let Some(ref date) = task.effective_date {
+        fmt.field(out, "Effective Date", date, false);
+    }
+    if let Some(minutes) = task.effort_minutes {
+        fmt.field(
+            out,
+            "Effort",
+            &format_duration(u64::from(minutes) * 60),
+            false,
+        );
+    }
+    if let Some(ref progress) = task.progress {
+        fmt.field(out, "Progress", progress, false);
+    }
+    if let (Some(todo), Some(done)) = (task.children_todo, task.children_done) {
+        fmt.field(
+            out,
+            "Child Tasks",
+            &format!("{todo} open, {done} done"),
+            false,
+        );
+    }
+    if let Some(ref attendees) = task.attendees {
+        fmt.field(out, "Attendees", &attendees.join(", "), false);
+    }
+    if let Some(ref location) = task.location {
+        fmt.link_field(out, "Location", location, &map_link(location));
+    }
+    for link in task.links.iter().flatten() {
+        let text = link.text.as_deref().unwrap_or(&link.target);
+        match link_href(&link.target) {
+            Some(href) => fmt.link_field(out, "Link", text, href),
+            None => fmt.field(out, "Link", text, false),
+        }
+    }
+    if let Some(ref assignee) = task.assignee {
+        fmt.field(out, "Assignee", assignee, false);
+    }
+    if let Some(ref category) = task.category {
+        fmt.field(out, "Category", category, false);
+    }
+    if let Some(ref tags) = task.tags {
+        fmt.field(out, "Tags", &tags.join(", "), false);
+    }
+    if let Some(ref path) = task.outline_path {
+        fmt.field(out, "Path", &path.join(" > "), false);
+    }
+    if let Some(ref since) = task.waiting_since {
+        fmt.field(out, "Waiting Since", since, false);
+    }
+    if task.paused == Some(true) {
+        fmt.field(out, "Paused", "yes", false);
+    } else if let Some(ref until) = task.paused_until {
+        fmt.field(out, "Paused Until", until, false);
+    }
+    for (label, value) in extra_fields {
+        fmt.field(out, label, value, false);
+    }
     if include_history {
+        if let Some(count) = task.rescheduled_count {
+            fmt.field(out, "Rescheduled", &count.to_string(), false);
+        }
         if let Some(ref total) = task.total_clock_time {
             fmt.field(out, "Total Time", total, false);
         }
@@ -245,10 +535,74 @@ fn write_task<F: TaskFormat>(
             write_clocks(out, clocks, fmt);
         }
     }
+    // After the history block: a Markdown field line right below the list
+    // would be read as a lazy continuation of its last item.
+    if let (Some(ref subtasks), Some(ref progress), Some(percent)) = (
+        &task.subtasks,
+        &task.subtasks_progress,
+        task.subtasks_percent,
+    ) {
+        let summary = format!("[{progress}] ({percent}%)");
+        fmt.checklist(out, &summary, subtasks);
+    }
 
     fmt.content(out, &task.content);
 }
 
+/// One day-agenda entry: heading at level 4, no history fields.
+fn write_agenda_entry<F: TaskFormat>(out: &mut String, entry: &TaskWithOffset, fmt: &F) {
+    let mut extra_fields = Vec::new();
+    if entry.conflict {
+        extra_fields.push(("Conflict", "overlaps another timed entry".to_string()));
+    }
+    if let (Some(repeater), Some(next)) = (
+        entry.task.timestamp_repeater.as_deref(),
+        entry.next_occurrence.as_deref(),
+    ) {
+        extra_fields.push(("Repeats", format!("{repeater}, next {next}")));
+    }
+    if let Some(ref dates) = entry.occurrence_days {
+        extra_fields.push(("Occurs", format_occurrence_days(dates)));
+    }
+    for local in entry.local_times.iter().flatten() {
+        let mut value = format!("{} {} {}", local.tz, local.date, local.time);
+        if let Some(ref end) = local.end_time {
+            let _ = write!(value, "-{end}");
+        }
+        extra_fields.push(("Local Time", value));
+    }
+    let mut task = entry.resolved();
+    if let Some(range) = entry.range_day {
+        let heading = format!("{} (day {}/{})", task.heading, range.day, range.days);
+        task.to_mut().heading = heading;
+    }
+    if let Some(years) = entry.anniversary_years {
+        let unit = if years == 1 { "year" } else { "years" };
+        let heading = format!("{} ({years} {unit})", task.heading);
+        task.to_mut().heading = heading;
+    }
+    write_task(out, &task, entry.days_offset, &extra_fields, 4, false, fmt);
+}
+
+/// `Mon, Wed, Fri` when every date falls within one 7-day span (a week
+/// view), otherwise the full `YYYY-MM-DD` dates so a month view stays
+/// unambiguous.
+fn format_occurrence_days(dates: &[String]) -> String {
+    let parsed: Option<Vec<chrono::NaiveDate>> = dates
+        .iter()
+        .map(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+        .collect();
+    match parsed.as_deref() {
+        Some([first, .., last]) if (*last - *first).num_days() < 7 => parsed
+            .iter()
+            .flatten()
+            .map(|d| d.format("%a").to_string())
+            .collect::<Vec<_>>()
+            .join(", "),
+        _ => dates.join(", "),
+    }
+}
+
 fn write_clocks<F: TaskFormat>(out: &mut String, clocks: &[ClockEntry], fmt: &F) {
     fmt.clocks_open(out);
     for clock in clocks {
@@ -265,6 +619,7 @@ fn write_day_section<F: TaskFormat>(
     out: &mut String,
     title: &str,
     tasks: &[TaskWithOffset],
+    more: usize,
     fmt: &F,
 ) {
     if tasks.is_empty() {
@@ -272,46 +627,445 @@ fn write_day_section<F: TaskFormat>(
     }
     fmt.section(out, title);
     for two in tasks {
-        write_task(out, &two.task, two.days_offset, 4, false, fmt);
+        write_agenda_entry(out, two, fmt);
+    }
+    if more > 0 {
+        fmt.more(out, more);
     }
     fmt.after_section(out);
 }
 
+/// One framed document: `title`, then whatever `body` writes.
+fn document<F: TaskFormat>(fmt: &F, title: &str, body: impl FnOnce(&mut String)) -> String {
+    let mut output = fmt.doc_open(title);
+    body(&mut output);
+    fmt.doc_close(&mut output);
+    output
+}
+
+/// One document holding every group's agenda, each under its own title
+/// naming the group's value.
+fn render_groups<F: TaskFormat>(groups: &[AgendaGroup], fmt: &F) -> String {
+    let mut output = fmt.doc_start();
+    write_groups(&mut output, groups, fmt);
+    fmt.doc_close(&mut output);
+    output
+}
+
+fn write_groups<F: TaskFormat>(output: &mut String, groups: &[AgendaGroup], fmt: &F) {
+    for group in groups {
+        let title = |kind: &str| format!("{kind}: {}", group.title());
+        write_titled(output, title, &group.agenda, fmt);
+    }
+}
+
+/// One document holding every block of a custom agenda under its title.
+fn render_blocks<F: TaskFormat>(blocks: &[AgendaBlock], fmt: &F) -> String {
+    let mut output = fmt.doc_start();
+    write_blocks(&mut output, blocks, fmt);
+    fmt.doc_close(&mut output);
+    output
+}
+
+fn write_blocks<F: TaskFormat>(output: &mut String, blocks: &[AgendaBlock], fmt: &F) {
+    for block in blocks {
+        write_titled(output, |_| block.title.clone(), &block.agenda, fmt);
+    }
+}
+
+/// `agenda` under a title built from the name of its kind of view
+/// (`Agenda`, `Tasks`, ...).
+fn write_titled<F: TaskFormat>(
+    output: &mut String,
+    title: impl Fn(&str) -> String,
+    agenda: &AgendaOutput,
+    fmt: &F,
+) {
+    match agenda {
+        AgendaOutput::Days(days) => {
+            fmt.title(output, &title("Agenda"));
+            write_days(output, days, fmt);
+        }
+        AgendaOutput::Tasks(tasks) => {
+            fmt.title(output, &title("Tasks"));
+            write_tasks(output, tasks, fmt);
+        }
+        AgendaOutput::Forecast(months) => {
+            fmt.title(output, &title("Forecast"));
+            write_forecast(output, months, fmt);
+        }
+        AgendaOutput::Quarter(months) => {
+            fmt.title(output, &title("Quarter"));
+            write_quarter(output, months, fmt);
+        }
+        AgendaOutput::Simulation(days) => {
+            fmt.title(output, &title("Simulation"));
+            write_simulation(output, days, fmt);
+        }
+        AgendaOutput::FreeSlots(days) => {
+            fmt.title(output, &title("Free"));
+            write_free(output, days, fmt);
+        }
+        AgendaOutput::Habits(rows) => {
+            fmt.title(output, &title("Habits"));
+            write_habits(output, rows, fmt);
+        }
+        AgendaOutput::Grouped(inner) => {
+            fmt.title(output, &title("Groups"));
+            write_groups(output, inner, fmt);
+        }
+        AgendaOutput::Blocks(inner) => write_blocks(output, inner, fmt),
+    }
+}
+
 fn render_days<F: TaskFormat>(days: &[DayAgenda], fmt: &F) -> String {
-    let mut output = fmt.doc_open("Agenda");
+    document(fmt, "Agenda", |output| write_days(output, days, fmt))
+}
 
+fn write_days<F: TaskFormat>(output: &mut String, days: &[DayAgenda], fmt: &F) {
     for day in days {
-        fmt.day_header(&mut output, &day.date);
+        fmt.day_header(output, &day.date);
+        // The stored summary counts the entries `--max-entries-per-day`
+        // left out; a day built without one is counted as it stands.
+        let summary = if day.summary == DaySummary::default() {
+            day.totals()
+        } else {
+            day.summary.clone()
+        };
+        write_day_summary(output, &summary, fmt);
 
-        write_day_section(&mut output, "Overdue", &day.overdue, fmt);
+        let cut = day.truncated;
+        if !day.timeline.is_empty() || day.now.is_some() {
+            write_timeline(output, day, cut.overdue + cut.scheduled + cut.upcoming, fmt);
+        }
+        write_overdue(output, "", &day.overdue, cut.overdue, fmt);
+        write_scheduled(
+            output,
+            "",
+            &day.scheduled_timed,
+            &day.scheduled_no_time,
+            cut.scheduled,
+            fmt,
+        );
 
-        // "Scheduled" header is shared by timed + no-time groups: print it once
-        // if either is non-empty, then list both without a second header.
-        if !day.scheduled_timed.is_empty() || !day.scheduled_no_time.is_empty() {
-            fmt.section(&mut output, "Scheduled");
-            for two in &day.scheduled_timed {
-                write_task(&mut output, &two.task, two.days_offset, 4, false, fmt);
+        // With `--by-category` the day's own buckets are empty and every
+        // category gets its own run of sections.
+        for group in &day.categories {
+            let prefix = format!("{}: ", group.category);
+            let cut = group.truncated;
+            write_overdue(output, &prefix, &group.overdue, cut.overdue, fmt);
+            write_scheduled(
+                output,
+                &prefix,
+                &group.scheduled_timed,
+                &group.scheduled_no_time,
+                cut.scheduled,
+                fmt,
+            );
+            let title = format!("{prefix}Upcoming");
+            write_day_section(output, &title, &group.upcoming, cut.upcoming, fmt);
+        }
+
+        if !day.gaps.is_empty() {
+            fmt.section(output, "Free");
+            for slot in &day.gaps {
+                write_slot(output, slot, fmt);
             }
-            for two in &day.scheduled_no_time {
-                write_task(&mut output, &two.task, two.days_offset, 4, false, fmt);
+            fmt.after_section(output);
+        }
+
+        write_day_section(output, "Upcoming", &day.upcoming, cut.upcoming, fmt);
+
+        if !day.clocked.is_empty() {
+            fmt.section(output, "Clocked");
+            for row in &day.clocked {
+                write_clocked(output, row, fmt);
             }
-            fmt.after_section(&mut output);
+            fmt.after_section(output);
         }
+    }
+}
 
-        write_day_section(&mut output, "Upcoming", &day.upcoming, fmt);
+/// The `--timeline` list of a day under one section, the `now` marker
+/// between its entries.
+fn write_timeline<F: TaskFormat>(out: &mut String, day: &DayAgenda, more: usize, fmt: &F) {
+    fmt.section(out, "Timeline");
+    for (i, entry) in day.timeline.iter().enumerate() {
+        if let Some(now) = day.now.as_ref().filter(|now| now.index == i) {
+            fmt.now(out, &now.time);
+        }
+        write_agenda_entry(out, entry, fmt);
+    }
+    if let Some(now) = day
+        .now
+        .as_ref()
+        .filter(|now| now.index >= day.timeline.len())
+    {
+        fmt.now(out, &now.time);
     }
+    if more > 0 {
+        fmt.more(out, more);
+    }
+    fmt.after_section(out);
+}
 
-    fmt.doc_close(&mut output);
-    output
+/// Overdue entries under one section per age bucket, titles led by `prefix`.
+/// The `more` marker closes the last bucket.
+fn write_overdue<F: TaskFormat>(
+    out: &mut String,
+    prefix: &str,
+    overdue: &[TaskWithOffset],
+    more: usize,
+    fmt: &F,
+) {
+    let buckets: Vec<_> = overdue
+        .chunk_by(|a, b| a.overdue_age == b.overdue_age)
+        .collect();
+    for (i, bucket) in buckets.iter().enumerate() {
+        let title = match bucket[0].overdue_age {
+            Some(age) => format!("{prefix}Overdue: {}", age.label()),
+            None => format!("{prefix}Overdue"),
+        };
+        let more = if i + 1 == buckets.len() { more } else { 0 };
+        write_day_section(out, &title, bucket, more, fmt);
+    }
+}
+
+/// "Scheduled" header is shared by timed + no-time groups: print it once
+/// if either is non-empty, then list both without a second header.
+fn write_scheduled<F: TaskFormat>(
+    out: &mut String,
+    prefix: &str,
+    timed: &[TaskWithOffset],
+    no_time: &[TaskWithOffset],
+    more: usize,
+    fmt: &F,
+) {
+    if timed.is_empty() && no_time.is_empty() {
+        return;
+    }
+    fmt.section(out, &format!("{prefix}Scheduled"));
+    for two in timed.iter().chain(no_time) {
+        write_agenda_entry(out, two, fmt);
+    }
+    if more > 0 {
+        fmt.more(out, more);
+    }
+    fmt.after_section(out);
+}
+
+/// Day totals above the sections: planned effort, entry counts and clocked
+/// time, each only when non-zero.
+fn write_day_summary<F: TaskFormat>(out: &mut String, summary: &DaySummary, fmt: &F) {
+    let mut written = false;
+    if summary.effort_minutes > 0 {
+        let planned = format_duration(u64::from(summary.effort_minutes) * 60);
+        fmt.field(out, "Planned Effort", &planned, false);
+        written = true;
+    }
+    if summary.overdue + summary.scheduled + summary.upcoming > 0 {
+        let counts = format!(
+            "{} overdue, {} scheduled, {} upcoming",
+            summary.overdue, summary.scheduled, summary.upcoming
+        );
+        fmt.field(out, "Summary", &counts, false);
+        written = true;
+    }
+    if summary.clocked_minutes > 0 {
+        let clocked = format_duration(u64::from(summary.clocked_minutes) * 60);
+        fmt.field(out, "Clocked Time", &clocked, false);
+        written = true;
+    }
+    if written {
+        fmt.after_section(out);
+    }
+}
+
+/// One `--log` clock interval: the task, then its start and end.
+fn write_clocked<F: TaskFormat>(out: &mut String, row: &ClockRow, fmt: &F) {
+    fmt.task_heading(out, 4, &row.heading, None, None);
+    let file_value = format!("{}:{}", row.file, row.line);
+    fmt.field(out, "File", &file_value, true);
+    fmt.clocks_open(out);
+    match row.end {
+        Some(ref end) => {
+            let duration = row
+                .minutes
+                .map(|m| format_duration((m * 60.0).round() as u64));
+            fmt.clock_complete(out, &row.start, end, duration.as_deref());
+        }
+        None => fmt.clock_active(out, &row.start),
+    }
+    fmt.clocks_close(out);
 }
 
 fn render_tasks<F: TaskFormat>(tasks: &[Task], fmt: &F) -> String {
-    let mut output = fmt.doc_open("Tasks");
+    document(fmt, "Tasks", |output| write_tasks(output, tasks, fmt))
+}
+
+fn write_tasks<F: TaskFormat>(output: &mut String, tasks: &[Task], fmt: &F) {
     for task in tasks {
-        write_task(&mut output, task, None, 2, true, fmt);
+        write_task(output, task, None, &[], 2, true, fmt);
+    }
+}
+
+fn render_forecast<F: TaskFormat>(months: &[ForecastMonth], fmt: &F) -> String {
+    document(fmt, "Forecast", |output| {
+        write_forecast(output, months, fmt)
+    })
+}
+
+fn write_forecast<F: TaskFormat>(output: &mut String, months: &[ForecastMonth], fmt: &F) {
+    for month in months {
+        fmt.day_header(output, &month.month);
+        fmt.field(output, "Occurrences", &month.occurrences.to_string(), false);
+        if let Some(ref total) = month.total_effort {
+            fmt.field(output, "Total Effort", total, false);
+        }
+        fmt.after_section(output);
+
+        for entry in &month.tasks {
+            write_forecast_entry(output, entry, 3, fmt);
+        }
+    }
+}
+
+fn write_forecast_entry<F: TaskFormat>(
+    out: &mut String,
+    entry: &ForecastEntry,
+    level: u8,
+    fmt: &F,
+) {
+    fmt.task_heading(out, level, &entry.heading, None, None);
+    let file_value = format!("{}:{}", entry.file, entry.line);
+    fmt.field(out, "File", &file_value, true);
+    fmt.field(out, "Repeater", &entry.repeater, true);
+    fmt.field(out, "Occurrences", &entry.occurrences.to_string(), false);
+    if let Some(ref effort) = entry.effort {
+        fmt.field(out, "Effort", effort, false);
+    }
+    if let Some(ref total) = entry.total_effort {
+        fmt.field(out, "Total Effort", total, false);
+    }
+    fmt.after_section(out);
+}
+
+fn render_quarter<F: TaskFormat>(months: &[QuarterMonth], fmt: &F) -> String {
+    document(fmt, "Quarter", |output| write_quarter(output, months, fmt))
+}
+
+fn write_quarter<F: TaskFormat>(output: &mut String, months: &[QuarterMonth], fmt: &F) {
+    for month in months {
+        fmt.day_header(output, &format!("{} – {}", month.start, month.end));
+
+        if !month.deadlines.is_empty() {
+            fmt.section(output, "Deadlines");
+            for deadline in &month.deadlines {
+                fmt.task_heading(output, 4, &deadline.heading, None, None);
+                let file_value = format!("{}:{}", deadline.file, deadline.line);
+                fmt.field(output, "File", &file_value, true);
+                fmt.field(output, "Date", &deadline.date, false);
+                if let Some(ref p) = deadline.priority {
+                    fmt.field(output, "Priority", &p.to_string(), false);
+                }
+                fmt.after_section(output);
+            }
+        }
+
+        if !month.recurring.is_empty() {
+            fmt.section(output, "Recurring");
+            for entry in &month.recurring {
+                write_forecast_entry(output, entry, 4, fmt);
+            }
+        }
     }
-    fmt.doc_close(&mut output);
-    output
+}
+
+fn render_simulation<F: TaskFormat>(days: &[SimulatedDay], fmt: &F) -> String {
+    document(fmt, "Simulation", |output| {
+        write_simulation(output, days, fmt)
+    })
+}
+
+fn write_simulation<F: TaskFormat>(output: &mut String, days: &[SimulatedDay], fmt: &F) {
+    for day in days {
+        fmt.day_header(output, &day.date);
+        fmt.field(output, "Occurrences", &day.occurrences.to_string(), false);
+        if let Some(ref total) = day.total_effort {
+            fmt.field(output, "Total Effort", total, false);
+        }
+        fmt.after_section(output);
+
+        for entry in &day.tasks {
+            fmt.task_heading(output, 3, &entry.heading, None, None);
+            let file_value = format!("{}:{}", entry.file, entry.line);
+            fmt.field(output, "File", &file_value, true);
+            if let Some(ref effort) = entry.effort {
+                fmt.field(output, "Effort", effort, false);
+            }
+            fmt.after_section(output);
+        }
+    }
+}
+
+fn render_free<F: TaskFormat>(days: &[FreeDay], fmt: &F) -> String {
+    document(fmt, "Free", |output| write_free(output, days, fmt))
+}
+
+fn write_free<F: TaskFormat>(output: &mut String, days: &[FreeDay], fmt: &F) {
+    for day in days {
+        fmt.day_header(output, &day.date);
+        for slot in &day.slots {
+            write_slot(output, slot, fmt);
+        }
+        fmt.after_section(output);
+    }
+}
+
+fn write_slot<F: TaskFormat>(out: &mut String, slot: &FreeSlot, fmt: &F) {
+    let value = format!("{}–{} ({} min)", slot.start, slot.end, slot.minutes);
+    fmt.field(out, "Free", &value, false);
+}
+
+fn render_habits<F: TaskFormat>(rows: &[HabitRow], fmt: &F) -> String {
+    document(fmt, "Habits", |output| write_habits(output, rows, fmt))
+}
+
+fn write_habits<F: TaskFormat>(output: &mut String, rows: &[HabitRow], fmt: &F) {
+    for row in rows {
+        fmt.task_heading(output, 2, &row.heading, None, None);
+        let file_value = format!("{}:{}", row.file, row.line);
+        fmt.field(output, "File", &file_value, true);
+        fmt.field(output, "Repeater", &row.repeater, true);
+        fmt.field(output, "Scheduled", &row.scheduled, false);
+        let graph = format!("{} ({} – {})", row.graph, row.start, row.end);
+        fmt.field(output, "Graph", &graph, false);
+        fmt.field(output, "Done Days", &row.done_days.to_string(), false);
+        if let Some(ref last) = row.last_done {
+            fmt.field(output, "Last Done", last, false);
+        }
+        fmt.after_section(output);
+    }
+}
+
+/// Render `--group-by` agendas as one Markdown document
+pub fn render_groups_markdown(groups: &[AgendaGroup]) -> String {
+    render_groups(groups, &MdFormat)
+}
+
+/// Render `--group-by` agendas as one HTML page
+pub fn render_groups_html(groups: &[AgendaGroup]) -> String {
+    render_groups(groups, &HtmlFormat)
+}
+
+/// Render `--custom-agenda` blocks as Markdown
+pub fn render_blocks_markdown(blocks: &[AgendaBlock]) -> String {
+    render_blocks(blocks, &MdFormat)
+}
+
+/// Render `--custom-agenda` blocks as one HTML page
+pub fn render_blocks_html(blocks: &[AgendaBlock]) -> String {
+    render_blocks(blocks, &HtmlFormat)
 }
 
 /// Render day agendas as Markdown
@@ -334,6 +1088,76 @@ pub fn render_html(tasks: &[Task]) -> String {
     render_tasks(tasks, &HtmlFormat)
 }
 
+/// Render clock intervals as CSV with a header row. `minutes` and `end` are
+/// empty for open clocks.
+pub fn render_clocks_csv(rows: &[ClockRow]) -> String {
+    let mut out = String::from("file,line,heading,start,end,minutes,tags\n");
+    for row in rows {
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{},{}",
+            csv_field(&row.file),
+            row.line,
+            csv_field(&row.heading),
+            csv_field(&row.start),
+            csv_field(row.end.as_deref().unwrap_or("")),
+            row.minutes.map(|m| m.to_string()).unwrap_or_default(),
+            csv_field(&row.tags.join(":"))
+        );
+    }
+    out
+}
+
+/// Render a recurring-workload forecast as Markdown
+pub fn render_forecast_markdown(months: &[ForecastMonth]) -> String {
+    render_forecast(months, &MdFormat)
+}
+
+/// Render a recurring-workload forecast as HTML
+pub fn render_forecast_html(months: &[ForecastMonth]) -> String {
+    render_forecast(months, &HtmlFormat)
+}
+
+/// Render a per-day workload simulation as Markdown
+pub fn render_simulation_markdown(days: &[SimulatedDay]) -> String {
+    render_simulation(days, &MdFormat)
+}
+
+/// Render a per-day workload simulation as HTML
+pub fn render_simulation_html(days: &[SimulatedDay]) -> String {
+    render_simulation(days, &HtmlFormat)
+}
+
+/// Render open time slots as Markdown
+pub fn render_free_markdown(days: &[FreeDay]) -> String {
+    render_free(days, &MdFormat)
+}
+
+/// Render open time slots as HTML
+pub fn render_free_html(days: &[FreeDay]) -> String {
+    render_free(days, &HtmlFormat)
+}
+
+/// Render habit consistency graphs as Markdown
+pub fn render_habits_markdown(rows: &[HabitRow]) -> String {
+    render_habits(rows, &MdFormat)
+}
+
+/// Render habit consistency graphs as HTML
+pub fn render_habits_html(rows: &[HabitRow]) -> String {
+    render_habits(rows, &HtmlFormat)
+}
+
+/// Render a quarter summary as Markdown
+pub fn render_quarter_markdown(months: &[QuarterMonth]) -> String {
+    render_quarter(months, &MdFormat)
+}
+
+/// Render a quarter summary as HTML
+pub fn render_quarter_html(months: &[QuarterMonth]) -> String {
+    render_quarter(months, &HtmlFormat)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -356,13 +1180,14 @@ mod tests {
     #[test]
     fn test_render_markdown_basic() {
         let tasks = vec![Task {
-            file: "test.md".to_string(),
+            file: "test.md".into(),
             line: 1,
             heading: "Test Task".to_string(),
             content: "Description".to_string(),
             task_type: Some(TaskType::Todo),
             priority: Some(Priority::A),
             created: None,
+            closed: None,
             timestamp: None,
             timestamp_type: None,
             timestamp_active: None,
@@ -372,7 +1197,36 @@ mod tests {
             timestamp_repeater: None,
             clocks: None,
             total_clock_time: None,
+            log: None,
+            effort_minutes: None,
             properties: None,
+            timestamp_overnight: None,
+            planning: None,
+            inactive_timestamps: None,
+            rescheduled_count: None,
+            effective_date: None,
+            attendees: None,
+            location: None,
+            url: None,
+            links: None,
+            subtasks: None,
+            subtasks_percent: None,
+            subtasks_progress: None,
+            progress_cookie: None,
+            progress: None,
+            children_todo: None,
+            children_done: None,
+            level: None,
+            waiting_since: None,
+            assignee: None,
+            paused: None,
+            paused_until: None,
+            effective_priority: None,
+            category: None,
+            tags: None,
+            outline_path: None,
+            inherited_priority: None,
+            inherited_tags: None,
         }];
 
         let output = render_markdown(&tasks);
@@ -398,13 +1252,14 @@ mod tests {
     #[test]
     fn test_render_markdown_escapes_heading() {
         let tasks = vec![Task {
-            file: "test.md".to_string(),
+            file: "test.md".into(),
             line: 1,
             heading: "Fix *important* [#issue]".to_string(),
             content: String::new(),
             task_type: None,
             priority: None,
             created: None,
+            closed: None,
             timestamp: None,
             timestamp_type: None,
             timestamp_active: None,
@@ -414,7 +1269,36 @@ mod tests {
             timestamp_repeater: None,
             clocks: None,
             total_clock_time: None,
+            log: None,
+            effort_minutes: None,
             properties: None,
+            timestamp_overnight: None,
+            planning: None,
+            inactive_timestamps: None,
+            rescheduled_count: None,
+            effective_date: None,
+            attendees: None,
+            location: None,
+            url: None,
+            links: None,
+            subtasks: None,
+            subtasks_percent: None,
+            subtasks_progress: None,
+            progress_cookie: None,
+            progress: None,
+            children_todo: None,
+            children_done: None,
+            level: None,
+            waiting_since: None,
+            assignee: None,
+            paused: None,
+            paused_until: None,
+            effective_priority: None,
+            category: None,
+            tags: None,
+            outline_path: None,
+            inherited_priority: None,
+            inherited_tags: None,
         }];
         let out = render_markdown(&tasks);
         assert!(
@@ -425,13 +1309,14 @@ mod tests {
 
     fn fixture_task() -> Task {
         Task {
-            file: "notes.md".to_string(),
+            file: "notes.md".into(),
             line: 42,
             heading: "Test task".to_string(),
             content: "Body text.".to_string(),
             task_type: Some(TaskType::Todo),
             priority: Some(Priority::A),
             created: Some("CREATED: [2025-09-01 Mon]".to_string()),
+            closed: None,
             timestamp: Some("DEADLINE: <2025-10-01 Wed>".to_string()),
             timestamp_type: Some("DEADLINE".to_string()),
             timestamp_active: Some(true),
@@ -441,7 +1326,36 @@ mod tests {
             timestamp_repeater: None,
             clocks: None,
             total_clock_time: None,
+            log: None,
+            effort_minutes: None,
             properties: None,
+            timestamp_overnight: None,
+            planning: None,
+            inactive_timestamps: None,
+            rescheduled_count: None,
+            effective_date: None,
+            attendees: None,
+            location: None,
+            url: None,
+            links: None,
+            subtasks: None,
+            subtasks_percent: None,
+            subtasks_progress: None,
+            progress_cookie: None,
+            progress: None,
+            children_todo: None,
+            children_done: None,
+            level: None,
+            waiting_since: None,
+            assignee: None,
+            paused: None,
+            paused_until: None,
+            effective_priority: None,
+            category: None,
+            tags: None,
+            outline_path: None,
+            inherited_priority: None,
+            inherited_tags: None,
         }
     }
 
@@ -475,6 +1389,104 @@ Body text.\n\n";
         assert_eq!(out, expected);
     }
 
+    #[test]
+    fn checklist_renders_as_nested_checkbox_list() {
+        let mut task = fixture_task();
+        task.subtasks = Some(vec![
+            Subtask {
+                text: "Bump *version*".to_string(),
+                done: true,
+            },
+            Subtask {
+                text: "Publish".to_string(),
+                done: false,
+            },
+        ]);
+        task.subtasks_percent = Some(50);
+        task.subtasks_progress = Some("1/2".to_string());
+
+        let md = render_markdown(std::slice::from_ref(&task));
+        assert!(
+            md.contains(
+                "\n**Checklist:** [1/2] (50%)\n- [x] Bump \\*version\\*\n- [ ] Publish\n\nBody text."
+            ),
+            "{md}"
+        );
+        let html = render_html(&[task]);
+        assert!(
+            html.contains("<li><input type=\"checkbox\" disabled checked> Bump *version*</li>"),
+            "{html}"
+        );
+        assert!(
+            html.contains("<strong>Checklist:</strong> [1/2] (50%)"),
+            "{html}"
+        );
+    }
+
+    #[test]
+    fn child_task_counts_render_as_a_field() {
+        let mut task = fixture_task();
+        task.children_todo = Some(3);
+        task.children_done = Some(1);
+
+        let md = render_markdown(std::slice::from_ref(&task));
+        assert!(md.contains("**Child Tasks:** 3 open, 1 done"), "{md}");
+        let html = render_html(&[task]);
+        assert!(
+            html.contains("<strong>Child Tasks:</strong> 3 open, 1 done"),
+            "{html}"
+        );
+    }
+
+    #[test]
+    fn day_agenda_shows_effort_and_planned_total() {
+        use crate::agenda::{filter_agenda, AgendaDates, AgendaScope};
+
+        let tasks = crate::parse_file(
+            "\
+## TODO Review
+`SCHEDULED: <2026-01-12 Mon 10:00>`
+
+```org-properties
+Effort: 1:30
+```
+
+## TODO Write report
+`SCHEDULED: <2026-01-12 Mon>` `Effort: 0:45`
+
+## DONE Already shipped
+`SCHEDULED: <2026-01-12 Mon>` `Effort: 3:00`
+",
+        );
+        assert_eq!(tasks[1].effort_minutes, Some(45));
+        let dates = AgendaDates {
+            date: Some("2026-01-12"),
+            current_date: Some("2026-01-12"),
+            ..AgendaDates::default()
+        };
+        let Ok(AgendaOutput::Days(days)) =
+            filter_agenda(tasks, AgendaScope::Day, dates, "UTC", false, false)
+        else {
+            panic!("expected a day agenda");
+        };
+        let md = render_days_markdown(&days);
+        assert!(
+            md.contains("## 2026-01-12\n\n**Planned Effort:** 2:15\n"),
+            "{md}"
+        );
+        assert!(md.contains("**Effort:** 1:30\n"), "{md}");
+        assert!(
+            md.contains("**Summary:** 0 overdue, 3 scheduled, 0 upcoming\n"),
+            "{md}"
+        );
+        assert_eq!(days[0].totals().effort_minutes, 135);
+        let html = render_days_html(&days);
+        assert!(
+            html.contains("<p><strong>Planned Effort:</strong> 2:15</p>"),
+            "{html}"
+        );
+    }
+
     #[test]
     fn render_task_cancelled_json_serialises_correctly() {
         // ADR-0015 wire contract: the cancelled TaskType variant must serialise
@@ -515,13 +1527,14 @@ Body text.\n\n";
     #[test]
     fn test_render_html_escapes() {
         let tasks = vec![Task {
-            file: "<script>.md".to_string(),
+            file: "<script>.md".into(),
             line: 1,
             heading: "Test & Task".to_string(),
             content: String::new(),
             task_type: None,
             priority: None,
             created: None,
+            closed: None,
             timestamp: None,
             timestamp_type: None,
             timestamp_active: None,
@@ -531,11 +1544,234 @@ Body text.\n\n";
             timestamp_repeater: None,
             clocks: None,
             total_clock_time: None,
+            log: None,
+            effort_minutes: None,
             properties: None,
+            timestamp_overnight: None,
+            planning: None,
+            inactive_timestamps: None,
+            rescheduled_count: None,
+            effective_date: None,
+            attendees: None,
+            location: None,
+            url: None,
+            links: None,
+            subtasks: None,
+            subtasks_percent: None,
+            subtasks_progress: None,
+            progress_cookie: None,
+            progress: None,
+            children_todo: None,
+            children_done: None,
+            level: None,
+            waiting_since: None,
+            assignee: None,
+            paused: None,
+            paused_until: None,
+            effective_priority: None,
+            category: None,
+            tags: None,
+            outline_path: None,
+            inherited_priority: None,
+            inherited_tags: None,
         }];
 
         let output = render_html(&tasks);
         assert!(output.contains("&lt;script&gt;"));
         assert!(output.contains("Test &amp; Task"));
     }
+
+    fn forecast_fixture() -> Vec<ForecastMonth> {
+        vec![ForecastMonth {
+            month: "2026-01".to_string(),
+            occurrences: 4,
+            total_effort: Some("2:00".to_string()),
+            tasks: vec![crate::types::ForecastEntry {
+                file: "chores.md".to_string(),
+                line: 3,
+                heading: "Water <plants>".to_string(),
+                repeater: "+1w".to_string(),
+                occurrences: 4,
+                effort: Some("0:30".to_string()),
+                total_effort: Some("2:00".to_string()),
+            }],
+        }]
+    }
+
+    #[test]
+    fn snapshot_render_forecast_markdown() {
+        let out = render_forecast_markdown(&forecast_fixture());
+        assert_eq!(
+            out,
+            "# Forecast\n\n\
+             ## 2026-01\n\n\
+             **Occurrences:** 4\n\
+             **Total Effort:** 2:00\n\
+             \n\
+             ### Water \\<plants\\>\n\
+             **File:** `chores.md:3`\n\
+             **Repeater:** `+1w`\n\
+             **Occurrences:** 4\n\
+             **Effort:** 0:30\n\
+             **Total Effort:** 2:00\n\
+             \n"
+        );
+    }
+
+    #[test]
+    fn render_forecast_html_escapes_heading() {
+        let out = render_forecast_html(&forecast_fixture());
+        assert!(out.starts_with("<html><body><h1>Forecast</h1>"));
+        assert!(out.contains("<h2>2026-01</h2>"));
+        assert!(out.contains("<h3>Water &lt;plants&gt;</h3>"));
+        assert!(out.contains("<p><strong>Repeater:</strong> +1w</p>"));
+    }
+
+    #[test]
+    fn format_occurrence_days_uses_weekdays_within_a_week() {
+        let week = ["2024-12-02", "2024-12-04", "2024-12-06"].map(String::from);
+        assert_eq!(format_occurrence_days(&week), "Mon, Wed, Fri");
+        let month = ["2024-12-02", "2024-12-16"].map(String::from);
+        assert_eq!(format_occurrence_days(&month), "2024-12-02, 2024-12-16");
+    }
+
+    #[test]
+    fn snapshot_render_quarter_markdown() {
+        let months = vec![QuarterMonth {
+            start: "2026-04-01".to_string(),
+            end: "2026-04-30".to_string(),
+            deadlines: vec![crate::types::QuarterDeadline {
+                file: "finance.md".to_string(),
+                line: 7,
+                heading: "File VAT return".to_string(),
+                date: "2026-04-20".to_string(),
+                priority: Some(Priority::A),
+            }],
+            recurring: forecast_fixture().remove(0).tasks,
+        }];
+        let out = render_quarter_markdown(&months);
+        assert_eq!(
+            out,
+            "# Quarter\n\n\
+             ## 2026-04-01 – 2026-04-30\n\n\
+             ### Deadlines\n\n\
+             #### File VAT return\n\
+             **File:** `finance.md:7`\n\
+             **Date:** 2026-04-20\n\
+             **Priority:** A\n\
+             \n\
+             ### Recurring\n\n\
+             #### Water \\<plants\\>\n\
+             **File:** `chores.md:3`\n\
+             **Repeater:** `+1w`\n\
+             **Occurrences:** 4\n\
+             **Effort:** 0:30\n\
+             **Total Effort:** 2:00\n\
+             \n"
+        );
+        let html = render_quarter_html(&months);
+        assert!(html.contains("<h3>Deadlines</h3>"));
+        assert!(html.contains("<h4>Water &lt;plants&gt;</h4>"));
+    }
+
+    #[test]
+    fn map_link_pins_coordinates_and_searches_addresses() {
+        assert_eq!(
+            map_link("55.7539, 37.6208"),
+            "https://www.openstreetmap.org/?mlat=55.7539&mlon=37.6208#map=16/55.7539/37.6208"
+        );
+        assert_eq!(
+            map_link("Room 4, Main St & 5th"),
+            "https://www.openstreetmap.org/search?query=Room%204%2C%20Main%20St%20%26%205th"
+        );
+        // Out-of-range numbers are an address, not coordinates.
+        assert!(map_link("123, 456").contains("search?query=123%2C%20456"));
+    }
+
+    #[test]
+    fn links_render_as_anchors_unless_the_scheme_is_unsafe() {
+        let mut task = fixture_task();
+        let link = |target: &str, text: Option<&str>| crate::types::Link {
+            target: target.to_string(),
+            text: text.map(str::to_string),
+        };
+        task.links = Some(vec![
+            link("https://example.com/spec", Some("Spec")),
+            link("file:notes/db.md", None),
+            link("javascript:alert(1)", Some("x")),
+        ]);
+        let html = render_html(&[task]);
+        assert!(html.contains(
+            "<p><strong>Link:</strong> <a href=\"https://example.com/spec\">Spec</a></p>"
+        ));
+        assert!(html.contains(
+            "<p><strong>Link:</strong> <a href=\"notes/db.md\">file:notes/db.md</a></p>"
+        ));
+        assert!(html.contains("<p><strong>Link:</strong> x</p>"));
+        assert!(!html.contains("javascript"));
+    }
+
+    #[test]
+    fn html_content_is_rendered_as_markdown_without_raw_html() {
+        let mut task = fixture_task();
+        task.content =
+            "Steps:\n\n- [ ] *Bump*\n\n<script>x</script>\n\n[go](javascript:alert(1))\u{1}"
+                .to_string();
+        let html = render_html(&[task]);
+        assert!(html.contains("<p>Steps:</p>\n<ul>\n<li>[ ] <em>Bump</em></li>\n</ul>"));
+        assert!(!html.contains("<script>"));
+        assert!(!html.contains("javascript"));
+        assert!(!html.contains('\u{1}'));
+    }
+
+    #[test]
+    fn content_tables_and_strikethrough_render_through() {
+        let mut task = fixture_task();
+        task.content = "| Step | Owner |\n| --- | ---: |\n| ~~Draft~~ | <b>ann</b> |".to_string();
+        let md = render_markdown(std::slice::from_ref(&task));
+        assert!(md.contains("\n| Step | Owner |\n| --- | ---: |\n"), "{md}");
+        let html = render_html(&[task]);
+        assert!(html.contains("<th align=\"right\">Owner</th>"), "{html}");
+        assert!(html.contains("<td><del>Draft</del></td>"), "{html}");
+        assert!(!html.contains("<b>"), "{html}");
+    }
+
+    #[test]
+    fn csv_field_quotes_and_neutralises_formulas() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("=SUM(A1)"), "'=SUM(A1)");
+        assert_eq!(csv_field("-1,2"), "\"'-1,2\"");
+    }
+
+    #[test]
+    fn snapshot_render_clocks_csv() {
+        let rows = vec![
+            ClockRow {
+                file: "t.md".to_string(),
+                line: 3,
+                heading: "Fix, then ship".to_string(),
+                start: "2024-12-09 Mon 10:00".to_string(),
+                end: Some("2024-12-09 Mon 12:30".to_string()),
+                minutes: Some(150.0),
+                tags: vec!["work".to_string(), "billing".to_string()],
+            },
+            ClockRow {
+                file: "t.md".to_string(),
+                line: 9,
+                heading: "Open".to_string(),
+                start: "2024-12-10 Tue 09:00".to_string(),
+                end: None,
+                minutes: None,
+                tags: Vec::new(),
+            },
+        ];
+        assert_eq!(
+            render_clocks_csv(&rows),
+            "file,line,heading,start,end,minutes,tags\n\
+             t.md,3,\"Fix, then ship\",2024-12-09 Mon 10:00,2024-12-09 Mon 12:30,150,work:billing\n\
+             t.md,9,Open,2024-12-10 Tue 09:00,,,\n"
+        );
+    }
 }
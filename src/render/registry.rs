@@ -0,0 +1,212 @@
+//! Output renderers looked up by format name.
+//!
+//! Every document format is a [`Renderer`]. A [`RendererRegistry`] maps
+//! names to renderers; its [`Default`] holds the built-in `json`, `markdown`,
+//! `html` and `ics` ones, which is what `--format` resolves against. Library users
+//! can [`register`](RendererRegistry::register) their own format, or replace
+//! a built-in, without touching [`OutputFormat`](crate::format::OutputFormat).
+
+use crate::agenda::AgendaOutput;
+use crate::error::AppError;
+
+/// Turns an agenda result into one output document.
+pub trait Renderer: Send + Sync {
+    /// Name the renderer is registered under, e.g. `"markdown"`.
+    fn name(&self) -> &str;
+
+    /// Render `output` as a complete document. The caller adds the trailing
+    /// newline, so the result may or may not end with one.
+    fn render(&self, output: &AgendaOutput) -> Result<String, AppError>;
+}
+
+/// Renderers by name, in registration order.
+pub struct RendererRegistry {
+    renderers: Vec<Box<dyn Renderer>>,
+}
+
+impl RendererRegistry {
+    /// A registry with no renderer at all.
+    pub fn empty() -> Self {
+        Self {
+            renderers: Vec::new(),
+        }
+    }
+
+    /// Add `renderer`, replacing and returning any renderer already
+    /// registered under the same name.
+    pub fn register(&mut self, renderer: Box<dyn Renderer>) -> Option<Box<dyn Renderer>> {
+        match self
+            .renderers
+            .iter_mut()
+            .find(|r| r.name() == renderer.name())
+        {
+            Some(slot) => Some(std::mem::replace(slot, renderer)),
+            None => {
+                self.renderers.push(renderer);
+                None
+            }
+        }
+    }
+
+    /// The renderer registered under `name`.
+    pub fn get(&self, name: &str) -> Option<&dyn Renderer> {
+        self.renderers
+            .iter()
+            .find(|r| r.name() == name)
+            .map(|r| r.as_ref())
+    }
+
+    /// Registered names, in registration order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.renderers.iter().map(|r| r.name())
+    }
+}
+
+impl Default for RendererRegistry {
+    /// The built-in `json`, `markdown`, `html` and `ics` renderers.
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register(Box::new(JsonRenderer));
+        registry.register(Box::new(MarkdownRenderer));
+        registry.register(Box::new(HtmlRenderer));
+        registry.register(Box::new(IcsRenderer));
+        registry
+    }
+}
+
+/// Pretty-printed JSON, the `--format json` default.
+pub struct JsonRenderer;
+
+impl Renderer for JsonRenderer {
+    fn name(&self) -> &str {
+        "json"
+    }
+
+    fn render(&self, output: &AgendaOutput) -> Result<String, AppError> {
+        Ok(serde_json::to_string_pretty(output)?)
+    }
+}
+
+/// Markdown document, `--format markdown`.
+pub struct MarkdownRenderer;
+
+impl Renderer for MarkdownRenderer {
+    fn name(&self) -> &str {
+        "markdown"
+    }
+
+    fn render(&self, output: &AgendaOutput) -> Result<String, AppError> {
+        Ok(match output {
+            AgendaOutput::Days(days) => super::render_days_markdown(days),
+            AgendaOutput::Tasks(tasks) => super::render_markdown(tasks),
+            AgendaOutput::Forecast(months) => super::render_forecast_markdown(months),
+            AgendaOutput::Quarter(months) => super::render_quarter_markdown(months),
+            AgendaOutput::Simulation(days) => super::render_simulation_markdown(days),
+            AgendaOutput::FreeSlots(days) => super::render_free_markdown(days),
+            AgendaOutput::Habits(rows) => super::render_habits_markdown(rows),
+            AgendaOutput::Grouped(groups) => super::render_groups_markdown(groups),
+            AgendaOutput::Blocks(blocks) => super::render_blocks_markdown(blocks),
+        })
+    }
+}
+
+/// HTML page, `--format html`.
+pub struct HtmlRenderer;
+
+impl Renderer for HtmlRenderer {
+    fn name(&self) -> &str {
+        "html"
+    }
+
+    fn render(&self, output: &AgendaOutput) -> Result<String, AppError> {
+        Ok(match output {
+            AgendaOutput::Days(days) => super::render_days_html(days),
+            AgendaOutput::Tasks(tasks) => super::render_html(tasks),
+            AgendaOutput::Forecast(months) => super::render_forecast_html(months),
+            AgendaOutput::Quarter(months) => super::render_quarter_html(months),
+            AgendaOutput::Simulation(days) => super::render_simulation_html(days),
+            AgendaOutput::FreeSlots(days) => super::render_free_html(days),
+            AgendaOutput::Habits(rows) => super::render_habits_html(rows),
+            AgendaOutput::Grouped(groups) => super::render_groups_html(groups),
+            AgendaOutput::Blocks(blocks) => super::render_blocks_html(blocks),
+        })
+    }
+}
+
+/// iCalendar events, `--format ics`.
+pub struct IcsRenderer;
+
+impl Renderer for IcsRenderer {
+    fn name(&self) -> &str {
+        "ics"
+    }
+
+    fn render(&self, output: &AgendaOutput) -> Result<String, AppError> {
+        Ok(crate::ics::render_calendar(output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Third-party style renderer: one line per task heading.
+    struct Headings;
+
+    impl Renderer for Headings {
+        fn name(&self) -> &str {
+            "headings"
+        }
+
+        fn render(&self, output: &AgendaOutput) -> Result<String, AppError> {
+            match output {
+                AgendaOutput::Tasks(tasks) => Ok(tasks
+                    .iter()
+                    .map(|t| t.heading.as_str())
+                    .collect::<Vec<_>>()
+                    .join("\n")),
+                _ => Ok(String::new()),
+            }
+        }
+    }
+
+    #[test]
+    fn default_registry_holds_the_builtin_formats() {
+        let registry = RendererRegistry::default();
+        assert_eq!(
+            registry.names().collect::<Vec<_>>(),
+            ["json", "markdown", "html", "ics"]
+        );
+        let empty = AgendaOutput::Tasks(Vec::new());
+        assert_eq!(registry.get("json").unwrap().render(&empty).unwrap(), "[]");
+        assert!(registry.get("org").is_none());
+    }
+
+    #[test]
+    fn register_adds_custom_and_replaces_same_name() {
+        let mut registry = RendererRegistry::default();
+        assert!(registry.register(Box::new(Headings)).is_none());
+        let tasks = crate::parse_file("## TODO Write report\n");
+        let out = registry
+            .get("headings")
+            .unwrap()
+            .render(&AgendaOutput::Tasks(tasks))
+            .unwrap();
+        assert_eq!(out, "Write report");
+
+        struct QuietJson;
+        impl Renderer for QuietJson {
+            fn name(&self) -> &str {
+                "json"
+            }
+            fn render(&self, _: &AgendaOutput) -> Result<String, AppError> {
+                Ok("{}".to_string())
+            }
+        }
+        let replaced = registry.register(Box::new(QuietJson)).unwrap();
+        assert_eq!(replaced.name(), "json");
+        assert_eq!(registry.names().filter(|n| *n == "json").count(), 1);
+        let empty = AgendaOutput::Tasks(Vec::new());
+        assert_eq!(registry.get("json").unwrap().render(&empty).unwrap(), "{}");
+    }
+}
@@ -0,0 +1,52 @@
+//! `--query`: full-text search over task headings and content, as Org's
+//! `org-search-view`. A query is a plain substring unless `--regex` is
+//! given; either way it ignores case.
+
+use regex::Regex;
+
+use crate::regex_limits::compile_user_pattern;
+use crate::types::Task;
+
+/// A compiled `--query`.
+#[derive(Debug, Clone)]
+pub struct SearchQuery {
+    regex: Regex,
+}
+
+impl SearchQuery {
+    /// Compile `query`, a regular expression when `is_regex` is set and a
+    /// plain substring otherwise. The error describes an invalid pattern.
+    pub fn new(query: &str, is_regex: bool) -> Result<Self, String> {
+        let pattern = if is_regex {
+            query.to_string()
+        } else {
+            regex::escape(query)
+        };
+        compile_user_pattern(&pattern)
+            .map(|regex| Self { regex })
+            .map_err(|e| format!("invalid --query pattern: {e}"))
+    }
+
+    /// Whether the query occurs in the heading or the content of `task`.
+    pub fn matches(&self, task: &Task) -> bool {
+        self.regex.is_match(&task.heading) || self.regex.is_match(&task.content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substring_and_regex_queries_ignore_case() {
+        let task = crate::parse_file("## TODO Pay invoice 42\nAsk [finance] first.\n").remove(0);
+        assert!(SearchQuery::new("INVOICE", false).unwrap().matches(&task));
+        assert!(SearchQuery::new("[finance]", false).unwrap().matches(&task));
+        assert!(!SearchQuery::new("receipt", false).unwrap().matches(&task));
+        assert!(SearchQuery::new(r"invoice \d+$", true)
+            .unwrap()
+            .matches(&task));
+        assert!(!SearchQuery::new(r"^invoice", true).unwrap().matches(&task));
+        assert!(SearchQuery::new("(unclosed", true).is_err());
+    }
+}
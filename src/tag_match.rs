@@ -0,0 +1,217 @@
+//! `--match`: Org tags/property match strings.
+//!
+//! A match string is a list of alternatives separated by `|`; a task
+//! matches when every term of one alternative holds. Terms follow each
+//! other directly or joined by `&`, and a `-` or `!` prefix negates one
+//! (`+` is accepted and means nothing):
+//!
+//! ```text
+//! work&!someday|urgent          tagged work but not someday, or urgent
+//! +work-meeting                 Org's own spelling of the same idea
+//! work&TODO="WAITING"           a property (or TODO, PRIORITY, CATEGORY)
+//! CATEGORY<>"home"              ... that differs from a value
+//! ```
+//!
+//! Tags compare case-sensitively, as in Org; property names do not.
+
+use std::fmt;
+
+use crate::types::Task;
+
+/// A parsed match string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagMatch {
+    /// Alternatives, each a list of terms that must all hold.
+    alternatives: Vec<Vec<Term>>,
+    source: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Term {
+    negated: bool,
+    test: Test,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Test {
+    Tag(String),
+    /// `KEY="value"`, or `KEY<>"value"` when `equal` is false.
+    Property {
+        key: String,
+        value: String,
+        equal: bool,
+    },
+}
+
+impl TagMatch {
+    /// Parse a match string; the error names what is wrong and where.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let mut alternatives = Vec::new();
+        for alternative in s.split('|') {
+            let terms = parse_terms(alternative)?;
+            if terms.is_empty() {
+                return Err(format!("empty alternative in '{s}'"));
+            }
+            alternatives.push(terms);
+        }
+        Ok(Self {
+            alternatives,
+            source: s.to_string(),
+        })
+    }
+
+    /// Whether `task` satisfies the match string.
+    pub fn matches(&self, task: &Task) -> bool {
+        self.alternatives.iter().any(|terms| {
+            terms
+                .iter()
+                .all(|term| term.test.holds(task) != term.negated)
+        })
+    }
+}
+
+impl fmt::Display for TagMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl Test {
+    fn holds(&self, task: &Task) -> bool {
+        match self {
+            Test::Tag(tag) => task.tags.iter().flatten().any(|t| t == tag),
+            Test::Property { key, value, equal } => {
+                (property(task, key).as_deref() == Some(value.as_str())) == *equal
+            }
+        }
+    }
+}
+
+/// Value of `key` for `task`: the special Org properties `TODO`,
+/// `PRIORITY` and `CATEGORY`, else the task's own property.
+fn property(task: &Task, key: &str) -> Option<String> {
+    match key.to_ascii_uppercase().as_str() {
+        "TODO" => task.task_type.as_ref().map(ToString::to_string),
+        "PRIORITY" => task.priority.as_ref().map(ToString::to_string),
+        "CATEGORY" => task.category.clone(),
+        _ => task
+            .properties
+            .as_ref()?
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.trim().to_string()),
+    }
+}
+
+/// Characters of a tag or property name, as in an Org tag list.
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '@' | '#' | '%')
+}
+
+fn parse_terms(alternative: &str) -> Result<Vec<Term>, String> {
+    let mut terms = Vec::new();
+    let mut rest = alternative.trim_start();
+    while !rest.is_empty() {
+        if !terms.is_empty() {
+            rest = rest.strip_prefix('&').unwrap_or(rest).trim_start();
+        }
+        let negated = match rest.chars().next() {
+            Some(c @ ('+' | '-' | '!')) => {
+                rest = rest[1..].trim_start();
+                c != '+'
+            }
+            _ => false,
+        };
+        let (test, after) = parse_test(rest)?;
+        terms.push(Term { negated, test });
+        rest = after.trim_start();
+    }
+    Ok(terms)
+}
+
+/// One tag or property comparison at the start of `s`, and the rest.
+fn parse_test(s: &str) -> Result<(Test, &str), String> {
+    let end = s.find(|c| !is_name_char(c)).unwrap_or(s.len());
+    if end == 0 {
+        return Err(match s.chars().next() {
+            Some(c) => format!("expected a tag or property name at '{c}'"),
+            None => "expected a tag or property name at the end".to_string(),
+        });
+    }
+    let (name, rest) = s.split_at(end);
+    let (equal, rest) = if let Some(rest) = rest.strip_prefix("<>") {
+        (false, rest)
+    } else if let Some(rest) = rest.strip_prefix('=') {
+        (true, rest)
+    } else {
+        return Ok((Test::Tag(name.to_string()), rest));
+    };
+    let (value, rest) = match rest.strip_prefix('"') {
+        Some(quoted) => {
+            let close = quoted
+                .find('"')
+                .ok_or_else(|| format!("unclosed quote after {name}"))?;
+            (&quoted[..close], &quoted[close + 1..])
+        }
+        None => {
+            let end = rest.find(|c| !is_name_char(c)).unwrap_or(rest.len());
+            rest.split_at(end)
+        }
+    };
+    Ok((
+        Test::Property {
+            key: name.to_string(),
+            value: value.to_string(),
+            equal,
+        },
+        rest,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(tags: &[&str]) -> Task {
+        let mut task =
+            crate::parse_file("## TODO [#B] Call\n```org-properties\nOwner: ann\n```\n").remove(0);
+        // WAITING is a custom keyword (`--todo-keywords WAITING`).
+        task.task_type = Some(crate::types::TaskType::Other("WAITING".to_string()));
+        task.tags = Some(tags.iter().map(ToString::to_string).collect());
+        task
+    }
+
+    #[test]
+    fn alternatives_negation_and_conjunction() {
+        let m = TagMatch::parse("work&!someday|urgent").unwrap();
+        assert!(m.matches(&task(&["work"])));
+        assert!(!m.matches(&task(&["work", "someday"])));
+        assert!(m.matches(&task(&["someday", "urgent"])));
+        assert!(!m.matches(&task(&["home"])));
+
+        let org = TagMatch::parse("+work-meeting").unwrap();
+        assert!(org.matches(&task(&["work"])));
+        assert!(!org.matches(&task(&["work", "meeting"])));
+        assert!(!TagMatch::parse("Work").unwrap().matches(&task(&["work"])));
+    }
+
+    #[test]
+    fn properties_and_special_keys() {
+        let t = task(&["work"]);
+        assert!(TagMatch::parse("work&TODO=\"WAITING\"")
+            .unwrap()
+            .matches(&t));
+        assert!(TagMatch::parse("PRIORITY=B&owner=ann").unwrap().matches(&t));
+        assert!(TagMatch::parse("OWNER<>\"bob\"").unwrap().matches(&t));
+        assert!(!TagMatch::parse("CATEGORY=\"home\"").unwrap().matches(&t));
+        assert!(TagMatch::parse("CATEGORY<>\"home\"").unwrap().matches(&t));
+    }
+
+    #[test]
+    fn parse_rejects_malformed_strings() {
+        for bad in ["", "work|", "work&", "!", "a&(b)", "OWNER=\"ann"] {
+            assert!(TagMatch::parse(bad).is_err(), "{bad}");
+        }
+        assert_eq!(TagMatch::parse("a|b").unwrap().to_string(), "a|b");
+    }
+}
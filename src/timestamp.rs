@@ -12,9 +12,14 @@ mod repeater;
 mod weekdays;
 
 pub use extract::{
-    extract_created_normalized, extract_repeater_normalized, extract_timestamp_normalized,
-    parse_timestamp_fields_normalized,
+    count_reschedules, extract_closed_normalized, extract_created_normalized,
+    extract_planning_normalized, extract_repeater_normalized, extract_timestamp_normalized,
+    is_overnight_normalized, pad_timestamp_dates, parse_timestamp_fields_normalized,
+    range_end_date_normalized,
 };
 pub use parser::{parse_org_timestamp, ParsedTimestamp};
-pub use repeater::{closest_date, DatePreference, Repeater, RepeaterUnit};
+pub use repeater::{
+    closest_date, parse_repeater, DatePreference, Repeater, RepeaterType, RepeaterUnit,
+    MAX_REPEAT_HORIZON_YEARS,
+};
 pub(crate) use weekdays::normalize_weekdays;
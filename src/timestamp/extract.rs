@@ -18,13 +18,29 @@ use crate::regex_limits::{compile_bounded, TS_BODY_MAX};
 // mixed pairs `<...]` / `[...>` from matching by construction.
 static KEYWORD_ANGLE_RE: LazyLock<Regex> = LazyLock::new(|| {
     compile_bounded(&format!(
-        r"^\s*((?:SCHEDULED|DEADLINE):\s*)<(\d{{4}}-\d{{2}}-\d{{2}}[^>]{{0,{TS_BODY_MAX}}})>"
+        r"^\s*((?:SCHEDULED|DEADLINE):\s*)<(\d{{4}}-\d{{1,2}}-\d{{1,2}}[^>]{{0,{TS_BODY_MAX}}})>"
+    ))
+});
+
+// Unanchored twin of KEYWORD_ANGLE_RE: every planning keyword of a literal,
+// including a second one on the same line (`SCHEDULED: <...> DEADLINE: <...>`).
+static PLANNING_ANGLE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    compile_bounded(&format!(
+        r"\b((?:SCHEDULED|DEADLINE):\s*)<(\d{{4}}-\d{{1,2}}-\d{{1,2}}[^>]{{0,{TS_BODY_MAX}}})>"
     ))
 });
 
 static CLOSED_SQUARE_RE: LazyLock<Regex> = LazyLock::new(|| {
     compile_bounded(&format!(
-        r"^\s*(CLOSED:\s*)\[(\d{{4}}-\d{{2}}-\d{{2}}[^\]]{{0,{TS_BODY_MAX}}})\]"
+        r"^\s*(CLOSED:\s*)\[(\d{{4}}-\d{{1,2}}-\d{{1,2}}[^\]]{{0,{TS_BODY_MAX}}})\]"
+    ))
+});
+
+// Unanchored twin of CLOSED_SQUARE_RE: Org writes `CLOSED:` first on the
+// planning line, but a hand-edited line may put it after the others.
+static CLOSED_ANY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    compile_bounded(&format!(
+        r"\bCLOSED:\s*\[(\d{{4}}-\d{{1,2}}-\d{{1,2}}[^\]]{{0,{TS_BODY_MAX}}})\]"
     ))
 });
 
@@ -34,41 +50,60 @@ static CLOSED_SQUARE_RE: LazyLock<Regex> = LazyLock::new(|| {
 // Both endpoints must share the bracket form (no mixed pairs).
 static RANGE_ANGLE_RE: LazyLock<Regex> = LazyLock::new(|| {
     compile_bounded(&format!(
-        r"^\s*<(\d{{4}}-\d{{2}}-\d{{2}}[^>]{{0,{TS_BODY_MAX}}})>--?-?<(\d{{4}}-\d{{2}}-\d{{2}}[^>]{{0,{TS_BODY_MAX}}})>"
+        r"^\s*<(\d{{4}}-\d{{1,2}}-\d{{1,2}}[^>]{{0,{TS_BODY_MAX}}})>--?-?<(\d{{4}}-\d{{1,2}}-\d{{1,2}}[^>]{{0,{TS_BODY_MAX}}})>"
     ))
 });
 
 static RANGE_SQUARE_RE: LazyLock<Regex> = LazyLock::new(|| {
     compile_bounded(&format!(
-        r"^\s*\[(\d{{4}}-\d{{2}}-\d{{2}}[^\]]{{0,{TS_BODY_MAX}}})\]--?-?\[(\d{{4}}-\d{{2}}-\d{{2}}[^\]]{{0,{TS_BODY_MAX}}})\]"
+        r"^\s*\[(\d{{4}}-\d{{1,2}}-\d{{1,2}}[^\]]{{0,{TS_BODY_MAX}}})\]--?-?\[(\d{{4}}-\d{{1,2}}-\d{{1,2}}[^\]]{{0,{TS_BODY_MAX}}})\]"
     ))
 });
 
 static SIMPLE_ANGLE_RE: LazyLock<Regex> = LazyLock::new(|| {
     compile_bounded(&format!(
-        r"^\s*<(\d{{4}}-\d{{2}}-\d{{2}}[^>]{{0,{TS_BODY_MAX}}})>"
+        r"^\s*<(\d{{4}}-\d{{1,2}}-\d{{1,2}}[^>]{{0,{TS_BODY_MAX}}})>"
     ))
 });
 
 static SIMPLE_SQUARE_RE: LazyLock<Regex> = LazyLock::new(|| {
     compile_bounded(&format!(
-        r"^\s*\[(\d{{4}}-\d{{2}}-\d{{2}}[^\]]{{0,{TS_BODY_MAX}}})\]"
+        r"^\s*\[(\d{{4}}-\d{{1,2}}-\d{{1,2}}[^\]]{{0,{TS_BODY_MAX}}})\]"
     ))
 });
 
 static CREATED_RE: LazyLock<Regex> = LazyLock::new(|| {
     compile_bounded(&format!(
-        r"^\s*CREATED:\s*\[(\d{{4}}-\d{{2}}-\d{{2}}[^\]]{{0,{TS_BODY_MAX}}})\]"
+        r"^\s*CREATED:\s*\[(\d{{4}}-\d{{1,2}}-\d{{1,2}}[^\]]{{0,{TS_BODY_MAX}}})\]"
     ))
 });
 
 static DATE_RE: LazyLock<Regex> = LazyLock::new(|| compile_bounded(r"\b(\d{4}-\d{2}-\d{2})"));
 
+// Date right after a timestamp's opening bracket, with the month and day
+// allowed one digit. The extractor patterns above accept `<2026-3-5 Thu>`
+// so such a planning line is not silently dropped; `pad_timestamp_dates`
+// then rewrites it into the canonical two-digit form every later stage
+// (DATE_RE, `parse_org_timestamp`) expects.
+static LOOSE_DATE_RE: LazyLock<Regex> =
+    LazyLock::new(|| compile_bounded(r"([<\[])(\d{4})-(\d{1,2})-(\d{1,2})\b"));
+
 static TIME_RANGE_RE: LazyLock<Regex> =
     LazyLock::new(|| compile_bounded(r"\b(\d{1,2}:\d{2})-(\d{1,2}:\d{2})\b"));
 
 static TIME_SINGLE_RE: LazyLock<Regex> = LazyLock::new(|| compile_bounded(r"\b(\d{1,2}:\d{2})\b"));
 
+// `org-log-reschedule` note: `- Rescheduled from "[2024-12-01 Sun]" on
+// [2024-12-03 Tue 10:00]`. Only the quoted previous timestamp is required;
+// the trailing `on [...]` note time is not needed to count the move.
+const RESCHEDULED_PREFIX: &str = "Rescheduled from \"";
+
+static RESCHEDULED_RE: LazyLock<Regex> = LazyLock::new(|| {
+    compile_bounded(&format!(
+        r#"{RESCHEDULED_PREFIX}(?:<\d{{4}}-\d{{1,2}}-\d{{1,2}}[^>]{{0,{TS_BODY_MAX}}}>|\[\d{{4}}-\d{{1,2}}-\d{{1,2}}[^\]]{{0,{TS_BODY_MAX}}}\])""#
+    ))
+});
+
 /// Extract CREATED timestamp from already-weekday-normalized text. Callers in
 /// the parser pre-normalize so multiple extractors share one scan; tests pass
 /// already-English input.
@@ -84,6 +119,29 @@ pub fn extract_created_normalized(text: &str) -> Option<String> {
         .map(|caps| format!("CREATED: [{}]", &caps[1]))
 }
 
+/// Every `SCHEDULED:` / `DEADLINE:` timestamp in already-weekday-normalized
+/// text, in order, formatted like [`extract_timestamp_normalized`].
+pub fn extract_planning_normalized(text: &str) -> Vec<String> {
+    if !text.contains("SCHEDULED:") && !text.contains("DEADLINE:") {
+        return Vec::new();
+    }
+    PLANNING_ANGLE_RE
+        .captures_iter(text)
+        .map(|caps| format!("{}<{}>", &caps[1], &caps[2]))
+        .collect()
+}
+
+/// Extract the `CLOSED:` timestamp from already-weekday-normalized text,
+/// wherever it sits on the planning line.
+pub fn extract_closed_normalized(text: &str) -> Option<String> {
+    if !text.contains("CLOSED:") {
+        return None;
+    }
+    CLOSED_ANY_RE
+        .captures(text)
+        .map(|caps| format!("CLOSED: [{}]", &caps[1]))
+}
+
 /// Extract non-CREATED timestamp from already-weekday-normalized text.
 pub fn extract_timestamp_normalized(text: &str) -> Option<String> {
     // Fast path: every regex below anchors to one of the keyword prefixes
@@ -105,6 +163,12 @@ pub fn extract_timestamp_normalized(text: &str) -> Option<String> {
         return Some(format!("{}<{}>", &caps[1], &caps[2]));
     }
     if let Some(caps) = CLOSED_SQUARE_RE.captures(text) {
+        // Org's own order is `CLOSED: [...] SCHEDULED: <...>`; the planning
+        // timestamp after it is the one that places the task.
+        let rest = &text[caps.get(0).map_or(0, |m| m.end())..];
+        if let Some(planning) = extract_planning_normalized(rest).into_iter().next() {
+            return Some(planning);
+        }
         return Some(format!("{}[{}]", &caps[1], &caps[2]));
     }
 
@@ -166,7 +230,7 @@ pub fn parse_timestamp_fields(
     parse_timestamp_fields_normalized(&normalized)
 }
 
-/// Fast-path companion to [`parse_timestamp_fields`] for callers that have
+/// Fast-path companion to `parse_timestamp_fields` for callers that have
 /// already weekday-normalised the input (e.g. `parser::finalize_task`, where
 /// `info.timestamp` was assembled from `extract_timestamp_normalized`'s
 /// regex captures over a `normalize_weekdays` output). Skipping the second
@@ -210,11 +274,87 @@ pub fn parse_timestamp_fields_normalized(
 /// by `extract_timestamp_normalized`); a timestamp whose date does not
 /// parse yields `None` (an invalid timestamp is not synced downstream).
 pub fn extract_repeater_normalized(timestamp: &str) -> Option<String> {
-    super::parser::parse_org_timestamp(timestamp, None)?
+    super::parser::parse_org_timestamp(timestamp, None)
+        .ok()?
         .repeater
         .map(|r| r.canonical())
 }
 
+/// Zero-pad a one-digit month or day in every bracketed date of an extracted
+/// timestamp (`<2026-3-5 Thu>` → `<2026-03-05 Thu>`). Returns `None` when
+/// the timestamp is already canonical.
+pub fn pad_timestamp_dates(timestamp: &str) -> Option<String> {
+    let padded = LOOSE_DATE_RE.replace_all(timestamp, |c: &regex::Captures<'_>| {
+        format!("{}{}-{:0>2}-{:0>2}", &c[1], &c[2], &c[3], &c[4])
+    });
+    (padded != timestamp).then(|| padded.into_owned())
+}
+
+/// Count `org-log-reschedule` notes (`Rescheduled from "[...]"`) in `text`.
+pub fn count_reschedules(text: &str) -> u32 {
+    // Almost no code span is a reschedule note; a substring check is far
+    // cheaper than running the bounded-repetition regex over every one.
+    if !text.contains(RESCHEDULED_PREFIX) {
+        return 0;
+    }
+    RESCHEDULED_RE.find_iter(text).count() as u32
+}
+
+/// Whether the timestamp's time range crosses midnight: either a
+/// single-bracket `HH:MM-HH:MM` whose end precedes its start
+/// (`<2026-01-05 Mon 22:00-01:30>`), or a `--` range whose second bracket
+/// is the following day at an earlier time of day. A multi-day range is not
+/// "overnight" — its end genuinely lies days later. Input is expected
+/// already weekday-normalised.
+pub fn is_overnight_normalized(timestamp: &str) -> bool {
+    let (first, second) = match split_range(timestamp) {
+        Some(pair) => pair,
+        None => {
+            let (start, end) = extract_time_pair(timestamp);
+            return ends_before(start.as_deref(), end.as_deref());
+        }
+    };
+    let (start, end_in_first) = extract_time_pair(first);
+    if end_in_first.is_some() {
+        return ends_before(start.as_deref(), end_in_first.as_deref());
+    }
+    let parse_date = |s: &str| {
+        DATE_RE
+            .captures(s)
+            .and_then(|c| chrono::NaiveDate::parse_from_str(&c[1], "%Y-%m-%d").ok())
+    };
+    let next_day = match (parse_date(first), parse_date(second)) {
+        (Some(d1), Some(d2)) => d1.succ_opt() == Some(d2),
+        _ => false,
+    };
+    next_day && ends_before(start.as_deref(), extract_time_pair(second).0.as_deref())
+}
+
+/// Last date of a `<d1>--<d2>` range that spans more than one day, so the
+/// agenda can list it on every day it covers. `None` for a single
+/// timestamp, a same-day range and an overnight range, which
+/// [`is_overnight_normalized`] handles. Input is expected already
+/// weekday-normalised.
+pub fn range_end_date_normalized(timestamp: &str) -> Option<chrono::NaiveDate> {
+    let (first, second) = split_range(timestamp)?;
+    let parse_date = |s: &str| {
+        DATE_RE
+            .captures(s)
+            .and_then(|c| chrono::NaiveDate::parse_from_str(&c[1], "%Y-%m-%d").ok())
+    };
+    let (start, end) = (parse_date(first)?, parse_date(second)?);
+    (end > start && !is_overnight_normalized(timestamp)).then_some(end)
+}
+
+/// `true` when both times parse and `end` is strictly earlier than `start`.
+fn ends_before(start: Option<&str>, end: Option<&str>) -> bool {
+    let parse = |t: &str| chrono::NaiveTime::parse_from_str(t, "%H:%M").ok();
+    match (start.and_then(parse), end.and_then(parse)) {
+        (Some(s), Some(e)) => e < s,
+        _ => false,
+    }
+}
+
 fn detect_active(timestamp: &str) -> Option<bool> {
     // The first `<` or `[` after any keyword prefix decides the form.
     // Whichever comes first wins; a string with neither yields `None`.
@@ -293,6 +433,43 @@ mod tests {
         extract_created_normalized(&normalize_weekdays(text, mappings))
     }
 
+    #[test]
+    fn single_digit_dates_are_extracted_then_padded() {
+        let ts = extract_timestamp("SCHEDULED: <2026-3-5 Thu 9:00>", &[]).unwrap();
+        assert_eq!(ts, "SCHEDULED: <2026-3-5 Thu 9:00>");
+        assert_eq!(
+            pad_timestamp_dates(&ts).as_deref(),
+            Some("SCHEDULED: <2026-03-05 Thu 9:00>")
+        );
+        assert_eq!(
+            pad_timestamp_dates("<2026-12-1>--<2026-12-10>").as_deref(),
+            Some("<2026-12-01>--<2026-12-10>")
+        );
+        assert_eq!(pad_timestamp_dates("<2026-03-05 Thu>"), None);
+        assert_eq!(
+            extract_created("CREATED: [2026-1-9 Fri]", &[]).as_deref(),
+            Some("CREATED: [2026-1-9 Fri]")
+        );
+    }
+
+    #[test]
+    fn count_reschedules_matches_org_notes_only() {
+        assert_eq!(
+            count_reschedules(r#"- Rescheduled from "[2024-12-01 Sun]" on [2024-12-03 Tue 10:00]"#),
+            1
+        );
+        assert_eq!(
+            count_reschedules(r#"Rescheduled from "<2024-12-01 Sun 09:00>""#),
+            1
+        );
+        // Mixed bracket pairs and free prose do not count.
+        assert_eq!(
+            count_reschedules(r#"Rescheduled from "<2024-12-01 Sun]""#),
+            0
+        );
+        assert_eq!(count_reschedules("Rescheduled from Monday"), 0);
+    }
+
     #[test]
     fn extract_repeater_normalized_covers_flavours_units_and_absence() {
         // All three prefix flavours round-trip to their canonical form.
@@ -350,6 +527,37 @@ mod tests {
         assert_eq!(ts, "SCHEDULED: <2024-12-05 Thu 10:00>");
     }
 
+    #[test]
+    fn extract_planning_finds_every_keyword_on_the_line() {
+        assert_eq!(
+            extract_planning_normalized(
+                "SCHEDULED: <2024-12-05 Thu> DEADLINE: <2024-12-09 Mon -2d> CLOSED: [2024-12-06 Fri]"
+            ),
+            ["SCHEDULED: <2024-12-05 Thu>", "DEADLINE: <2024-12-09 Mon -2d>"]
+        );
+        assert!(extract_planning_normalized("<2024-12-05 Thu>").is_empty());
+        assert!(extract_planning_normalized("SCHEDULED: [2024-12-05 Thu]").is_empty());
+    }
+
+    #[test]
+    fn extract_closed_anywhere_on_the_planning_line() {
+        let line = "CLOSED: [2024-12-06 Fri 17:30] SCHEDULED: <2024-12-05 Thu>";
+        assert_eq!(
+            extract_closed_normalized(line).as_deref(),
+            Some("CLOSED: [2024-12-06 Fri 17:30]")
+        );
+        assert_eq!(
+            extract_timestamp(line, &[]).as_deref(),
+            Some("SCHEDULED: <2024-12-05 Thu>")
+        );
+        assert_eq!(
+            extract_closed_normalized("DEADLINE: <2024-12-09 Mon> CLOSED: [2024-12-06 Fri]")
+                .as_deref(),
+            Some("CLOSED: [2024-12-06 Fri]")
+        );
+        assert_eq!(extract_closed_normalized("CLOSED: <2024-12-06 Fri>"), None);
+    }
+
     #[test]
     fn extract_timestamp_range() {
         let ts = extract_timestamp("<2024-12-05 Thu 10:00>--<2024-12-06 Fri 14:00>", &[]).unwrap();
@@ -673,4 +881,31 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn is_overnight_single_bracket_range() {
+        assert!(is_overnight_normalized("<2026-01-05 Mon 22:00-01:30>"));
+        assert!(is_overnight_normalized(
+            "SCHEDULED: <2026-01-05 Mon 23:30-0:15>"
+        ));
+        assert!(!is_overnight_normalized("<2026-01-05 Mon 10:00-12:00>"));
+        // `9:00` vs `10:00` must compare as times, not strings.
+        assert!(!is_overnight_normalized("<2026-01-05 Mon 9:00-10:00>"));
+        assert!(!is_overnight_normalized("<2026-01-05 Mon 22:00>"));
+        assert!(!is_overnight_normalized("<2026-01-05 Mon>"));
+    }
+
+    #[test]
+    fn is_overnight_two_bracket_range_requires_next_day() {
+        assert!(is_overnight_normalized(
+            "<2026-01-05 Mon 22:00>--<2026-01-06 Tue 01:30>"
+        ));
+        // Multi-day range: end lies days later, not overnight.
+        assert!(!is_overnight_normalized(
+            "<2026-01-05 Mon 22:00>--<2026-01-08 Thu 01:30>"
+        ));
+        assert!(!is_overnight_normalized(
+            "<2026-01-05 Mon 10:00>--<2026-01-06 Tue 14:00>"
+        ));
+    }
 }
@@ -1,6 +1,7 @@
 use chrono::NaiveDate;
 use regex::Regex;
 use std::borrow::Cow;
+use std::fmt;
 use std::sync::LazyLock;
 
 use super::repeater::{parse_repeater, Repeater};
@@ -79,6 +80,44 @@ static REPEATER_BODY_RE: LazyLock<Regex> =
 static WARNING_BODY_RE: LazyLock<Regex> =
     LazyLock::new(|| compile_bounded(r"\s-(\d+)([hdwmy])(?:[\s>\]]|$)"));
 
+/// Why a timestamp has no usable date. The task keeps its raw string but
+/// drops out of every agenda bucket.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimestampError {
+    /// No `<YYYY-MM-DD ...>` or `[YYYY-MM-DD ...]` bracket at all.
+    MissingDate,
+    /// The date is shaped right but names no calendar day (`2026-02-30`).
+    InvalidDate(String),
+}
+
+impl fmt::Display for TimestampError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimestampError::MissingDate => write!(f, "no <YYYY-MM-DD> or [YYYY-MM-DD] date"),
+            TimestampError::InvalidDate(date) => write!(f, "date {date} does not exist"),
+        }
+    }
+}
+
+/// A problem the parser recovered from: the date is usable, but one part of
+/// the timestamp was ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimestampIssue {
+    /// A repeater-shaped token `parse_repeater` rejected (`+0d`, a value
+    /// past `u32`). The timestamp is treated as non-repeating.
+    InvalidRepeater(String),
+}
+
+impl fmt::Display for TimestampIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimestampIssue::InvalidRepeater(token) => {
+                write!(f, "repeater {token} is invalid; treated as non-repeating")
+            }
+        }
+    }
+}
+
 /// Result of parsing a single org-mode timestamp string.
 #[derive(Debug, Clone)]
 pub struct ParsedTimestamp {
@@ -95,6 +134,9 @@ pub struct ParsedTimestamp {
     /// `[...]`. See ADR-0014 for which keywords accept which forms and
     /// for the agenda invariant (inactive timestamps never feed agenda).
     pub active: bool,
+    /// Parts of the timestamp that were ignored. Empty for a well-formed
+    /// timestamp.
+    pub issues: Vec<TimestampIssue>,
 }
 
 /// Convert a warning cookie's value/unit pair into whole days, mirroring
@@ -120,7 +162,15 @@ fn warning_cookie_to_days(value: i64, unit: &str) -> Option<i64> {
 /// on the bracket body, so they may appear in either order
 /// (`<... +1y -3d>` or `<... -3d +1y>`), matching upstream Org-mode's
 /// position-agnostic handling in `org-get-wdays`.
-pub fn parse_org_timestamp(ts: &str, mappings: Option<&[(&str, &str)]>) -> Option<ParsedTimestamp> {
+///
+/// Only a missing or impossible date is an error. A bad repeater is recorded
+/// in [`ParsedTimestamp::issues`] and the rest of the timestamp is still
+/// returned. The weekday name is optional and never checked: Org treats it
+/// as decoration and the date always wins.
+pub fn parse_org_timestamp(
+    ts: &str,
+    mappings: Option<&[(&str, &str)]>,
+) -> Result<ParsedTimestamp, TimestampError> {
     let ts = if let Some(m) = mappings {
         normalize_weekdays(ts, m)
     } else {
@@ -142,14 +192,21 @@ pub fn parse_org_timestamp(ts: &str, mappings: Option<&[(&str, &str)]>) -> Optio
         }
         (Some(_), None) => angle.as_ref().unwrap(),
         (None, Some(_)) => square.as_ref().unwrap(),
-        (None, None) => return None,
+        (None, None) => return Err(TimestampError::MissingDate),
     };
-    let date = NaiveDate::parse_from_str(&caps[1], "%Y-%m-%d").ok()?;
+    let date = NaiveDate::parse_from_str(&caps[1], "%Y-%m-%d")
+        .map_err(|_| TimestampError::InvalidDate(caps[1].to_string()))?;
     let bracket = caps.get(0).map(|m| m.as_str()).unwrap_or("");
+    let mut issues = Vec::new();
 
-    let repeater = REPEATER_BODY_RE
-        .captures(bracket)
-        .and_then(|c| parse_repeater(c.get(1)?.as_str()));
+    let repeater = REPEATER_BODY_RE.captures(bracket).and_then(|c| {
+        let token = c.get(1)?.as_str();
+        let repeater = parse_repeater(token);
+        if repeater.is_none() {
+            issues.push(TimestampIssue::InvalidRepeater(token.to_string()));
+        }
+        repeater
+    });
 
     let warning_days = WARNING_BODY_RE.captures(bracket).and_then(|c| {
         let value: i64 = c.get(1)?.as_str().parse().ok()?;
@@ -161,11 +218,12 @@ pub fn parse_org_timestamp(ts: &str, mappings: Option<&[(&str, &str)]>) -> Optio
     // produce mixed pairs.
     let active = bracket.starts_with('<');
 
-    Some(ParsedTimestamp {
+    Ok(ParsedTimestamp {
         date,
         repeater,
         warning_days,
         active,
+        issues,
     })
 }
 
@@ -210,7 +268,7 @@ mod tests {
         for sep in ["-", "--", "---"] {
             let ts = format!("<2025-12-05 Thu>{sep}<2025-12-06 Fri>");
             let parsed = parse_org_timestamp(&ts, None)
-                .unwrap_or_else(|| panic!("must parse range with {sep:?} as separator"));
+                .unwrap_or_else(|_| panic!("must parse range with {sep:?} as separator"));
             assert_eq!(
                 parsed.date,
                 NaiveDate::from_ymd_opt(2025, 12, 5).unwrap(),
@@ -408,4 +466,38 @@ mod tests {
         assert_eq!(parsed.date, NaiveDate::from_ymd_opt(2025, 12, 5).unwrap());
         assert!(!parsed.active);
     }
+
+    #[test]
+    fn parse_org_timestamp_reports_what_failed() {
+        assert_eq!(
+            parse_org_timestamp("<soon>", None).unwrap_err(),
+            TimestampError::MissingDate
+        );
+        assert_eq!(
+            parse_org_timestamp("<2026-02-30 Mon>", None).unwrap_err(),
+            TimestampError::InvalidDate("2026-02-30".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_org_timestamp_recovers_from_bad_repeater() {
+        let parsed = parse_org_timestamp("<2026-03-05 Thu +0d>", None).unwrap();
+        assert_eq!(parsed.date, NaiveDate::from_ymd_opt(2026, 3, 5).unwrap());
+        assert!(parsed.repeater.is_none());
+        assert_eq!(
+            parsed.issues,
+            [TimestampIssue::InvalidRepeater("+0d".to_string())]
+        );
+        // A missing, full or even wrong weekday name is not an issue.
+        for ts in [
+            "<2026-03-05>",
+            "<2026-03-05 Thursday 10:00>",
+            "[2026-03-05 Mon]",
+        ] {
+            assert!(
+                parse_org_timestamp(ts, None).unwrap().issues.is_empty(),
+                "{ts}"
+            );
+        }
+    }
 }
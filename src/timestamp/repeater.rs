@@ -1,6 +1,18 @@
 use crate::holidays::HolidayCalendar;
 use chrono::NaiveDate;
 
+/// Upper bound of `--repeat-horizon`, in years. Also the longest repeater
+/// step occurrence math accepts: a longer step can never land a second
+/// occurrence inside any horizon, so it is treated as a one-off instead of
+/// being pushed past chrono's date range.
+pub const MAX_REPEAT_HORIZON_YEARS: u32 = 1000;
+
+/// How many year-grid periods `bracket_year` searches for the next valid
+/// occurrence. Only a Feb-29 base skips periods; the worst realistic grid
+/// (`+25y` from a leap year) needs 16, so the bound is never hit for a
+/// well-formed timestamp.
+const LEAP_DAY_SEARCH_PERIODS: i32 = 200;
+
 /// Repeater type and interval
 #[derive(Debug, Clone, PartialEq)]
 pub struct Repeater {
@@ -21,6 +33,13 @@ pub enum RepeaterType {
 }
 
 impl RepeaterType {
+    /// Every repeater type, in prefix order.
+    pub const ALL: [RepeaterType; 3] = [
+        RepeaterType::Cumulative,
+        RepeaterType::CatchUp,
+        RepeaterType::Restart,
+    ];
+
     /// Org-mode prefix string (`+`, `++`, `.+`)
     pub fn prefix(&self) -> &'static str {
         match self {
@@ -47,6 +66,16 @@ pub enum RepeaterUnit {
 }
 
 impl RepeaterUnit {
+    /// Every repeater unit, shortest first.
+    pub const ALL: [RepeaterUnit; 6] = [
+        RepeaterUnit::Hour,
+        RepeaterUnit::Day,
+        RepeaterUnit::Workday,
+        RepeaterUnit::Week,
+        RepeaterUnit::Month,
+        RepeaterUnit::Year,
+    ];
+
     /// Org-mode suffix string (`d`, `w`, `m`, `y`, `h`, `wd`)
     pub fn suffix(&self) -> &'static str {
         match self {
@@ -250,14 +279,14 @@ fn bracket_year(
 
     // Next valid occurrence strictly after `current`.
     let mut k2 = (n1.year() - base_year) / value + 1;
-    // Accommodate Feb-29 (gap up to 8 years). `max_complete` is >= 0 whenever
+    // Accommodate Feb-29 gaps. `max_complete` is >= 0 whenever
     // `current >= base_date`, which `closest_date` guarantees before
-    // dispatching here, so `+ 200` is already a positive ceiling. The
+    // dispatching here, so the ceiling is already positive. The
     // `.max(0)` is defense-in-depth (F6, 2026-05-25 logic review): a direct
     // call with `current < base_date` would otherwise yield a negative
     // `max_complete` and a ceiling below `k2`, returning None silently
     // instead of looping with a sane bound.
-    let safety_limit = max_complete.max(0) + 200;
+    let safety_limit = max_complete.max(0) + LEAP_DAY_SEARCH_PERIODS;
     let n2 = loop {
         if k2 > safety_limit {
             return None;
@@ -362,6 +391,8 @@ fn bracket_workday(base_date: NaiveDate, current: NaiveDate, value: u32) -> (Nai
 ///   - `Future` returns `Some(base_date)` (first occurrence).
 /// - Otherwise, returns the closest occurrence on or before / on or after `current`
 ///   according to `prefer`.
+/// - A step longer than [`MAX_REPEAT_HORIZON_YEARS`] has no second
+///   occurrence: `Past` returns `base_date`, `Future` returns `None`.
 pub fn closest_date(
     base_date: NaiveDate,
     current: NaiveDate,
@@ -377,6 +408,13 @@ pub fn closest_date(
             DatePreference::Future => Some(base_date),
         };
     }
+    if step_exceeds_horizon(repeater) {
+        tracing::trace!(repeater = %repeater.canonical(), "repeater step exceeds horizon; one-off");
+        return match prefer {
+            DatePreference::Past => Some(base_date),
+            DatePreference::Future => None,
+        };
+    }
 
     let (n1, n2) = match repeater.unit {
         RepeaterUnit::Year => bracket_year(base_date, current, repeater.value)?,
@@ -396,6 +434,22 @@ pub fn closest_date(
     pick(prefer, current, n1, n2)
 }
 
+/// True when one step of `repeater` is longer than
+/// [`MAX_REPEAT_HORIZON_YEARS`]. Keeps every bracket builder inside chrono's
+/// date range and `i32` year arithmetic, whatever value `parse_repeater`
+/// accepted.
+fn step_exceeds_horizon(repeater: &Repeater) -> bool {
+    let years = u64::from(MAX_REPEAT_HORIZON_YEARS);
+    let value = u64::from(repeater.value);
+    match repeater.unit {
+        RepeaterUnit::Year => value > years,
+        RepeaterUnit::Month => value > years * 12,
+        RepeaterUnit::Week => value > years * 53,
+        RepeaterUnit::Day | RepeaterUnit::Workday => value > years * 366,
+        RepeaterUnit::Hour => false,
+    }
+}
+
 /// Add `months` to a date, truncating the day to fit the destination month.
 /// Constant-time (no per-month loops), correct for negative `months`.
 pub fn add_months(date: NaiveDate, months: i32) -> Option<NaiveDate> {
@@ -852,6 +906,33 @@ mod tests {
         assert!(got <= current);
     }
 
+    #[test]
+    fn closest_date_huge_step_is_a_one_off() {
+        // `+4294967295d` parses, but stepping it would overflow chrono's date
+        // range (and `i32` years for `y`). It must degrade to a one-off.
+        let base = NaiveDate::from_ymd_opt(2026, 1, 5).unwrap();
+        let current = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        for unit in ["d", "w", "m", "y", "wd"] {
+            let repeater = parse_repeater(&format!("+{}{unit}", u32::MAX)).unwrap();
+            assert_eq!(
+                closest_date(base, current, DatePreference::Past, &repeater),
+                Some(base),
+                "unit {unit}"
+            );
+            assert_eq!(
+                closest_date(base, current, DatePreference::Future, &repeater),
+                None,
+                "unit {unit}"
+            );
+        }
+        // The largest accepted yearly step still repeats.
+        let repeater = parse_repeater(&format!("+{MAX_REPEAT_HORIZON_YEARS}y")).unwrap();
+        assert_eq!(
+            closest_date(base, current, DatePreference::Future, &repeater),
+            NaiveDate::from_ymd_opt(3026, 1, 5)
+        );
+    }
+
     #[test]
     fn test_workdays_between_exclusive_basic() {
         use crate::holidays::HolidayCalendar;
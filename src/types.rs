@@ -1,9 +1,11 @@
 use chrono::NaiveDate;
 use serde::de::{self, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
 use std::collections::BTreeMap;
 use std::fmt;
 use std::str::FromStr;
+use std::sync::Arc;
 
 /// Original spelling of the cancelled TODO keyword, preserved verbatim.
 ///
@@ -19,19 +21,25 @@ pub enum CancelledSpelling {
     SingleL,
 }
 
-/// Task status type (TODO, DONE, or cancelled).
+/// Task status type (TODO, DONE, cancelled, or a custom keyword).
 ///
 /// The cancelled variant carries the original spelling from the source
 /// file (`CANCELLED` / `CANCELED`) so it can be reproduced on output. See
 /// ADR-0021. Serialises to a plain JSON string (`"TODO"` / `"DONE"` /
-/// `"CANCELLED"` / `"CANCELED"`), not the default externally-tagged object
-/// form, which is why `Serialize` / `Deserialize` are hand-written rather
-/// than derived.
+/// `"CANCELLED"` / `"CANCELED"`, or the custom keyword), not the
+/// default externally-tagged object form, which is why `Serialize` /
+/// `Deserialize` are hand-written rather than derived. The JSON does not
+/// say whether a custom keyword is open or done: one loaded back comes in as
+/// `Other` until [`TodoKeywords::reclassify`] sorts it. See ADR-0025.
 #[derive(Debug, Clone, PartialEq)]
 pub enum TaskType {
     Todo,
     Done,
     Cancelled(CancelledSpelling),
+    /// Custom open state from `--todo-keywords` (`NEXT`, `WAITING`).
+    Other(String),
+    /// Custom closed state from `--done-keywords` (`SHIPPED`).
+    OtherDone(String),
 }
 
 impl fmt::Display for TaskType {
@@ -41,11 +49,15 @@ impl fmt::Display for TaskType {
             TaskType::Done => "DONE",
             TaskType::Cancelled(CancelledSpelling::DoubleL) => "CANCELLED",
             TaskType::Cancelled(CancelledSpelling::SingleL) => "CANCELED",
+            TaskType::Other(keyword) | TaskType::OtherDone(keyword) => keyword,
         })
     }
 }
 
 impl TaskType {
+    /// Every keyword [`TaskType::from_keyword`] accepts.
+    pub const KEYWORDS: &'static [&'static str] = &["TODO", "DONE", "CANCELLED", "CANCELED"];
+
     /// Parse task type from an org-mode keyword
     /// (`TODO` / `DONE` / `CANCELLED` / `CANCELED`). The two cancelled
     /// spellings map to distinct `CancelledSpelling` variants so the
@@ -59,6 +71,111 @@ impl TaskType {
             _ => None,
         }
     }
+
+    /// Still to be acted on: `TODO` or a custom open keyword.
+    pub fn is_open(&self) -> bool {
+        matches!(self, TaskType::Todo | TaskType::Other(_))
+    }
+
+    /// Completed: `DONE` or a custom done keyword. Cancelled tasks are
+    /// neither open nor done.
+    pub fn is_done(&self) -> bool {
+        matches!(self, TaskType::Done | TaskType::OtherDone(_))
+    }
+}
+
+/// TODO keywords recognised on top of the built-in [`TaskType::KEYWORDS`],
+/// from `--todo-keywords` / `--done-keywords` or a `.mdextract.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct TodoKeywords {
+    /// Custom open states, parsed into [`TaskType::Other`].
+    pub todo: Vec<String>,
+    /// Custom closed states, parsed into [`TaskType::OtherDone`].
+    pub done: Vec<String>,
+}
+
+impl TodoKeywords {
+    /// Keyword lists as configured, with repeats and the built-in keywords
+    /// dropped: naming `TODO` or `CANCELLED` again is harmless. A keyword
+    /// that ends up both open and done, custom or built-in, is an error.
+    pub fn new(todo: &[String], done: &[String]) -> Result<Self, String> {
+        let mut keywords = Self::default();
+        for (keyword, open) in todo
+            .iter()
+            .map(|k| (k, true))
+            .chain(done.iter().map(|k| (k, false)))
+        {
+            let declared_open = match TaskType::from_keyword(keyword) {
+                Some(task_type) => Some(task_type.is_open()),
+                None if keywords.todo.contains(keyword) => Some(true),
+                None if keywords.done.contains(keyword) => Some(false),
+                None => None,
+            };
+            match declared_open {
+                Some(was_open) if was_open != open => {
+                    return Err(format!(
+                        "'{keyword}' is declared both as a todo and a done keyword"
+                    ));
+                }
+                Some(_) => {}
+                None if open => keywords.todo.push(keyword.clone()),
+                None => keywords.done.push(keyword.clone()),
+            }
+        }
+        Ok(keywords)
+    }
+
+    /// Task type of a heading keyword: a built-in one, or one of the custom
+    /// keywords.
+    pub fn task_type(&self, keyword: &str) -> Option<TaskType> {
+        if let Some(task_type) = TaskType::from_keyword(keyword) {
+            Some(task_type)
+        } else if self.todo.iter().any(|k| k == keyword) {
+            Some(TaskType::Other(keyword.to_string()))
+        } else if self.done.iter().any(|k| k == keyword) {
+            Some(TaskType::OtherDone(keyword.to_string()))
+        } else {
+            None
+        }
+    }
+
+    /// Every recognised keyword, built-in ones first.
+    pub fn all(&self) -> impl Iterator<Item = &str> {
+        TaskType::KEYWORDS
+            .iter()
+            .copied()
+            .chain(self.todo.iter().map(String::as_str))
+            .chain(self.done.iter().map(String::as_str))
+    }
+
+    /// Sort a custom keyword read back from JSON, which always arrives as
+    /// [`TaskType::Other`], into open or done by these keywords.
+    pub fn reclassify(&self, task_type: &mut TaskType) {
+        if let TaskType::Other(keyword) | TaskType::OtherDone(keyword) = task_type {
+            if let Some(sorted) = self.task_type(keyword) {
+                *task_type = sorted;
+            }
+        }
+    }
+
+    /// Check the shape of one keyword: upper-case ASCII letters, digits,
+    /// `_` and `-`, starting with a letter. Built-in names pass; [`Self::new`]
+    /// drops them.
+    pub fn validate(keyword: &str) -> Result<(), String> {
+        if !is_keyword_token(keyword) {
+            return Err(format!(
+                "invalid keyword '{keyword}': use upper-case letters, digits, '_' or '-', starting with a letter"
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Shape of a TODO keyword, built-in or custom.
+fn is_keyword_token(s: &str) -> bool {
+    s.starts_with(|c: char| c.is_ascii_uppercase())
+        && s.chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_' || c == '-')
 }
 
 impl Serialize for TaskType {
@@ -73,12 +190,14 @@ impl<'de> Deserialize<'de> for TaskType {
         impl Visitor<'_> for TaskTypeVisitor {
             type Value = TaskType;
             fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
-                f.write_str("one of \"TODO\", \"DONE\", \"CANCELLED\", \"CANCELED\"")
+                f.write_str("one of \"TODO\", \"DONE\", \"CANCELLED\", \"CANCELED\" or an upper-case custom keyword")
             }
             fn visit_str<E: de::Error>(self, v: &str) -> Result<TaskType, E> {
-                TaskType::from_keyword(v).ok_or_else(|| {
-                    de::Error::unknown_variant(v, &["TODO", "DONE", "CANCELLED", "CANCELED"])
-                })
+                match TaskType::from_keyword(v) {
+                    Some(task_type) => Ok(task_type),
+                    None if is_keyword_token(v) => Ok(TaskType::Other(v.to_string())),
+                    None => Err(de::Error::unknown_variant(v, TaskType::KEYWORDS)),
+                }
             }
         }
         de.deserialize_str(TaskTypeVisitor)
@@ -157,6 +276,25 @@ impl Priority {
             Priority::Other(c) => *c as u32,
         }
     }
+
+    /// Whether this is a numeric `[#N]` priority rather than a letter.
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, Priority::Numeric(_))
+    }
+
+    /// This priority raised by `levels` steps: a number towards `0`, a letter
+    /// towards `A`. Neither scale crosses into the other.
+    pub fn raised(&self, levels: u32) -> Self {
+        match self {
+            Priority::Numeric(n) => Priority::Numeric(n.saturating_sub(levels.min(64) as u8)),
+            letter => {
+                let order = letter.order().saturating_sub(levels).max('A' as u32);
+                // `order` stays within 'A'..='Z', so both conversions hold.
+                let c = char::from_u32(order).unwrap_or('A');
+                Priority::parse(&c.to_string()).unwrap_or(Priority::A)
+            }
+        }
+    }
 }
 
 impl fmt::Display for Priority {
@@ -179,6 +317,86 @@ impl FromStr for Priority {
     }
 }
 
+/// Priority range of `--priorities`, Org's `org-priority-highest`,
+/// `org-priority-lowest` and `org-priority-default`. Cookies outside the
+/// range are still read, as Org reads them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriorityScheme {
+    pub highest: Priority,
+    pub lowest: Priority,
+    /// Priority of a task without a cookie.
+    pub default: Priority,
+}
+
+impl PriorityScheme {
+    /// Parse `HIGHEST-LOWEST[:DEFAULT]`, e.g. `A-E`, `1-9:5`. Both ends are
+    /// letters or both numbers; the default is the middle of the range
+    /// unless given, as `B` is for Org's `A-C`.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let (range, default) = match s.split_once(':') {
+            Some((range, default)) => (range, Some(default)),
+            None => (s, None),
+        };
+        let priority = |value: &str| {
+            Priority::parse(value.trim()).ok_or_else(|| {
+                format!("'{value}' is not a priority: use a letter A-Z or a number 0-64")
+            })
+        };
+        let (highest, lowest) = range
+            .split_once('-')
+            .ok_or_else(|| format!("'{s}' is not a range like A-E or 1-9"))?;
+        let (highest, lowest) = (priority(highest)?, priority(lowest)?);
+        if highest.is_numeric() != lowest.is_numeric() {
+            return Err("both ends must be letters or both numbers".to_string());
+        }
+        if highest.order() > lowest.order() {
+            return Err(format!(
+                "the highest priority comes first: {lowest}-{highest}"
+            ));
+        }
+        let default = match default {
+            Some(value) => priority(value)?,
+            None => {
+                let middle = (highest.order() + lowest.order()) / 2;
+                let text = match highest {
+                    Priority::Numeric(_) => middle.to_string(),
+                    _ => char::from_u32(middle).unwrap_or('A').to_string(),
+                };
+                priority(&text)?
+            }
+        };
+        let scheme = Self {
+            highest,
+            lowest,
+            default,
+        };
+        if !scheme.contains(&scheme.default) {
+            return Err(format!(
+                "default {} lies outside {}-{}",
+                scheme.default, scheme.highest, scheme.lowest
+            ));
+        }
+        Ok(scheme)
+    }
+
+    /// Whether `priority` lies within the range.
+    pub fn contains(&self, priority: &Priority) -> bool {
+        priority.is_numeric() == self.highest.is_numeric()
+            && (self.highest.order()..=self.lowest.order()).contains(&priority.order())
+    }
+
+    /// `priority` raised by `levels` steps, never above `highest`.
+    pub fn raised(&self, priority: &Priority, levels: u32) -> Priority {
+        let raised = priority.raised(levels);
+        if raised.is_numeric() == self.highest.is_numeric() && raised.order() < self.highest.order()
+        {
+            self.highest.clone()
+        } else {
+            raised
+        }
+    }
+}
+
 impl Serialize for Priority {
     fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
         ser.collect_str(self)
@@ -224,7 +442,7 @@ impl<'de> Deserialize<'de> for Priority {
 ///   `duration` are `None`. An open clock represents an in-progress
 ///   interval whose endpoint has not been recorded yet, so the consumer
 ///   is responsible for deciding how (or whether) to render it.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ClockEntry {
     pub start: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -233,13 +451,34 @@ pub struct ClockEntry {
     pub duration: Option<String>,
 }
 
+/// One entry of a task's `:LOGBOOK:` drawer, in the order written
+/// (Org puts the newest first). Timestamps are kept without brackets,
+/// like [`ClockEntry`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LogEntry {
+    /// `- State "DONE" from "TODO" [2025-01-10 Fri 10:00]`, as written by
+    /// `org-log-done` / `org-log-into-drawer`. `from` is `None` when the
+    /// task had no state before.
+    State {
+        to: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        from: Option<String>,
+        timestamp: String,
+    },
+    /// A `CLOCK:` line of the drawer.
+    Clock(ClockEntry),
+}
+
 /// A single task extracted from a markdown file.
 ///
 /// All optional fields are skipped on serialization when `None`, so the JSON
 /// output stays compact and stable.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Task {
-    pub file: String,
+    /// Path of the source file. Shared by all tasks of the file, so cloning
+    /// a task (once per day it appears on in an agenda) does not copy it.
+    pub file: Arc<str>,
     pub line: u32,
     pub heading: String,
     pub content: String,
@@ -249,6 +488,12 @@ pub struct Task {
     pub priority: Option<Priority>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub created: Option<String>,
+    /// `CLOSED: [...]` timestamp of a finished task, as
+    /// `CLOSED: [2025-01-10 Fri 17:30]`, wherever it sits on the planning
+    /// line. The day views list a done task on this date too. Non-breaking
+    /// optional field under ADR-0015.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub closed: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timestamp: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -276,6 +521,18 @@ pub struct Task {
     pub clocks: Option<Vec<ClockEntry>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub total_clock_time: Option<String>,
+    /// State changes and clock lines of the task's `:LOGBOOK:` drawer, in
+    /// the order written. The clock lines are in `clocks` as well. `None`
+    /// when the task has no drawer. Non-breaking optional field under
+    /// ADR-0015.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log: Option<Vec<LogEntry>>,
+    /// Estimated duration in whole minutes, from the `Effort` property or
+    /// an inline `` `Effort: 2:00` `` code span (`H:MM`). `None` when the
+    /// task has no valid estimate. Non-breaking optional field under
+    /// ADR-0015.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effort_minutes: Option<u32>,
     /// Per-task properties parsed from an `org-properties` fenced code
     /// block (bare `KEY: value` lines). `None` when the task has no such
     /// block. Added as a non-breaking optional field under ADR-0015; the
@@ -283,6 +540,202 @@ pub struct Task {
     /// deterministic key order for snapshot/JSON assertions.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub properties: Option<BTreeMap<String, String>>,
+    /// `Some(true)` when the timestamp's time range crosses midnight
+    /// (`<2026-01-05 Mon 22:00-01:30>`, or a `--` range whose end bracket is
+    /// the next day at an earlier time), so `timestamp_end_time` belongs to
+    /// the following day. `None` otherwise. The day agenda also lists such
+    /// an entry on the next day. Non-breaking optional field under ADR-0015.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp_overnight: Option<bool>,
+    /// Every `SCHEDULED:` and `DEADLINE:` timestamp of the task when it
+    /// has both, in the order written; `timestamp` keeps holding a single
+    /// one as before. The day agenda evaluates each entry on its own, so a task
+    /// shows up as scheduled and with its deadline warning. `None` for a
+    /// task with at most one. Non-breaking optional field under ADR-0015.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub planning: Option<Vec<String>>,
+    /// Every plain inactive `[...]` timestamp of the task, in the order
+    /// written, for reporting. They never feed the agenda (ADR-0014), and
+    /// one becomes `timestamp` only when the task has no other. `None`
+    /// when there are none. Non-breaking optional field under ADR-0015.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inactive_timestamps: Option<Vec<String>>,
+    /// Number of Org reschedule notes (`- Rescheduled from "[...]" on [...]`,
+    /// written by `org-log-reschedule`) found in the task's code blocks or
+    /// inline code. `None` when the task has none. Non-breaking optional
+    /// field under ADR-0015.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rescheduled_count: Option<u32>,
+    /// With `--roll-deadlines`: the next (or previous) workday when a
+    /// DEADLINE's `timestamp_date` falls on a weekend or holiday, as
+    /// `YYYY-MM-DD`. `None` when the date is already a workday. Non-breaking
+    /// optional field under ADR-0015.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effective_date: Option<String>,
+    /// Meeting attendees from the comma-separated `ATTENDEES` key of the
+    /// task's `org-properties` block (ADR-0020), in file order. `None` when
+    /// the key is absent or empty. Non-breaking optional field under
+    /// ADR-0015.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attendees: Option<Vec<String>>,
+    /// Event place from the `LOCATION` key of the `org-properties` block
+    /// (ADR-0020): an address, a room, or `lat,lon`. `None` when absent or
+    /// empty. Non-breaking optional field under ADR-0015.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    /// External reference: the `URL` key of the `org-properties` block, or
+    /// else the first `http(s)` link in the task's body. Other schemes are
+    /// ignored. Non-breaking optional field under ADR-0015.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Markdown `[text](target)` and Org `[[target][text]]` links in the
+    /// heading and body, in document order, each once. Non-breaking
+    /// optional field under ADR-0015.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<Vec<Link>>,
+    /// `- [ ]` / `- [x]` checklist items in the task's body, in document
+    /// order, nested items included. Non-breaking optional field under
+    /// ADR-0015.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subtasks: Option<Vec<Subtask>>,
+    /// Share of `subtasks` that are done, as a whole percentage rounded
+    /// down like Org's `[%]` cookie. Set whenever `subtasks` is.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subtasks_percent: Option<u8>,
+    /// Done and total `subtasks` as `done/total`, the text of Org's `[/]`
+    /// cookie (`2/5`). Set whenever `subtasks` is. Non-breaking optional
+    /// field under ADR-0015.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subtasks_progress: Option<String>,
+    /// Statistics cookie of the heading as written (`[2/7]`, `[%]`,
+    /// `[40%]`); it is removed from `heading`. Non-breaking optional field
+    /// under ADR-0015.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress_cookie: Option<String>,
+    /// Value the cookie should show, in its own form (`[3/7]`, `[42%]`):
+    /// counted over the body's checkboxes, or else over the direct child
+    /// task headings, where done and cancelled ones count as closed. Set
+    /// whenever `progress_cookie` is.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<String>,
+    /// Open tasks among the task headings nested anywhere below this one,
+    /// so a project heading shows its remaining work. `None` when no task
+    /// heading is nested below it. Non-breaking optional field under
+    /// ADR-0015.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub children_todo: Option<u32>,
+    /// Done and cancelled tasks among the nested task headings. Set
+    /// whenever `children_todo` is.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub children_done: Option<u32>,
+    /// Logical heading level: ATX `#` count or setext `=`/`-` (1/2), shifted
+    /// by `--heading-offset` and never below 1. `None` for a `[!todo]`
+    /// callout task. Non-breaking optional field under ADR-0015.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<u8>,
+    /// Date the task started waiting, as `YYYY-MM-DD`, from the
+    /// `WAITING_SINCE` key of the `org-properties` block (ADR-0020). `None`
+    /// when absent or not a date. Non-breaking optional field under
+    /// ADR-0015.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub waiting_since: Option<String>,
+    /// Person the task is assigned to: the `ASSIGNEE` key of the
+    /// `org-properties` block (ADR-0020), or else the first `@name`
+    /// mention in the heading, without the `@`. Non-breaking optional
+    /// field under ADR-0015.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    /// `true` when the `PAUSED` key of the `org-properties` block
+    /// (ADR-0020) suspends a recurring task: its repeater is kept but no
+    /// occurrence is projected. Non-breaking optional field under ADR-0015.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paused: Option<bool>,
+    /// Date, as `YYYY-MM-DD`, a recurring task resumes on, from the
+    /// `PAUSE_UNTIL` key of the `org-properties` block (ADR-0020); earlier
+    /// occurrences are not projected. Non-breaking optional field under
+    /// ADR-0015.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paused_until: Option<String>,
+    /// Priority after `--priority-aging` raised it for the task's age since
+    /// `CREATED`; sorting uses it in place of `priority`. `None` when no
+    /// aging applied. Non-breaking optional field under ADR-0015.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effective_priority: Option<Priority>,
+    /// Category of the task: its `CATEGORY` property, or else the
+    /// `category` key of the nearest `.mdextract.toml` under `--monorepo`.
+    /// Non-breaking optional field under ADR-0015.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// Tags of the task: those of the enclosing headings unless
+    /// `--no-tag-inheritance` is given, then the trailing `:tag:` list of
+    /// its own heading, then the `tags` key of the nearest `.mdextract.toml` under
+    /// `--monorepo`. Non-breaking optional field under ADR-0015.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    /// Titles of the headings enclosing the task in its file, outermost
+    /// first, without TODO keywords and priority cookies. `None` for a
+    /// top-level task. Non-breaking optional field under ADR-0015.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outline_path: Option<Vec<String>>,
+    /// Priority cookie of the nearest enclosing heading that has one,
+    /// whether or not that heading is a task. `--inherit-priority` moves it
+    /// into `priority` when the task has none of its own; never serialized.
+    #[serde(skip)]
+    pub inherited_priority: Option<Priority>,
+    /// Tags of the enclosing headings, outermost first. Merged into `tags`
+    /// unless `--no-tag-inheritance` is given; never serialized.
+    #[serde(skip)]
+    pub inherited_tags: Option<Vec<String>>,
+}
+
+/// A link found in a task's heading or body.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Link {
+    /// URL or note path as written, e.g. `https://…`, `notes/db.md` or
+    /// Org's `file:notes/db.org`.
+    pub target: String,
+    /// Link description; `None` for a bare `[[target]]` or an empty one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+/// One checklist item under a task heading.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Subtask {
+    pub text: String,
+    /// `[x]` / `[X]`; `[ ]` and Org's partial `[-]` are open.
+    pub done: bool,
+}
+
+/// How bad a [`TimestampDiagnostic`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The timestamp has no usable date; the task is missing from agendas.
+    Error,
+    /// The timestamp was repaired or one part of it was ignored.
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        })
+    }
+}
+
+/// One problem found in a task's planning timestamp during the scan.
+/// Collected for `--lint`; the first `MAX_DIAGNOSTIC_ITEMS` are also logged.
+#[derive(Debug, Clone, Serialize)]
+pub struct TimestampDiagnostic {
+    pub file: String,
+    pub line: u32,
+    pub severity: Severity,
+    /// The timestamp as written (before padding).
+    pub timestamp: String,
+    pub message: String,
 }
 
 /// Maximum file size to process (10 MB)
@@ -307,6 +760,9 @@ pub const MAX_DIAGNOSTIC_ITEMS: usize = 20;
 /// File processing statistics surfaced to stderr after a run.
 #[derive(Debug, Default)]
 pub struct ProcessingStats {
+    /// Files that matched `--glob`, before the keyword pre-filter. Only
+    /// used to explain an empty result.
+    pub files_matched: usize,
     pub files_processed: usize,
     pub files_skipped_size: usize,
     pub files_failed_search: usize,
@@ -321,16 +777,17 @@ pub struct ProcessingStats {
     pub max_tasks_limit: usize,
     /// Paths of files that could not be read or searched. Capped to avoid unbounded growth.
     pub failed_paths: Vec<String>,
-    /// Cumulative count of invalid-timestamp warnings encountered during the
-    /// scan, threaded through `extract_tasks_with_counter`. The first
-    /// `MAX_DIAGNOSTIC_ITEMS` are emitted verbatim; the next one collapses
-    /// into a single "suppressed (showed first N)" notice; further ones are
-    /// silent. Owned by `ProcessingStats` so the budget spans every file in
-    /// the run without resorting to process-global state.
-    pub ts_warnings_emitted: usize,
+    /// Timestamp problems encountered during the scan, threaded through
+    /// `extract_tasks_with_counter`. Every one is kept for `--lint`; only
+    /// the first `MAX_DIAGNOSTIC_ITEMS` are logged verbatim, the next one
+    /// collapses into a single "suppressed (showed first N)" notice and
+    /// further ones are silent. Owned by `ProcessingStats` so the budget
+    /// spans every file in the run without resorting to process-global
+    /// state.
+    pub ts_diagnostics: Vec<TimestampDiagnostic>,
     /// Cumulative count of malformed `org-properties` lines (a line with
     /// no `:`) encountered during the scan. Gated by `MAX_DIAGNOSTIC_ITEMS`
-    /// exactly like `ts_warnings_emitted`, and owned here so the budget
+    /// exactly like `ts_diagnostics`, and owned here so the budget
     /// spans every file in the run. See ADR-0020.
     pub prop_warnings_emitted: usize,
     /// Scan was aborted by SIGINT/SIGTERM before all entries were visited.
@@ -409,25 +866,393 @@ impl ProcessingStats {
     }
 }
 
+/// `--explain-empty` JSON document written instead of an empty result.
+#[derive(Debug, Serialize)]
+pub struct EmptyResult {
+    /// Always `true`, so a consumer can tell this object from a result array.
+    pub empty: bool,
+    pub files_matched: usize,
+    pub files_processed: usize,
+    /// Tasks extracted before agenda filtering.
+    pub tasks_found: usize,
+    /// Human-readable reasons the result is empty and what to try.
+    pub hints: Vec<String>,
+}
+
+/// `--bench-corpus` report.
+#[derive(Debug, Serialize)]
+pub struct BenchReport {
+    pub files: usize,
+    /// Total size of the parsed files.
+    pub bytes: usize,
+    /// Tasks found in one pass over the corpus.
+    pub tasks: usize,
+    /// Passes over the whole corpus that fit in the measured time.
+    pub passes: u32,
+    pub seconds: f64,
+    /// Parse throughput in megabytes (10^6 bytes) per second.
+    pub mb_per_sec: f64,
+}
+
+/// One CLOCK interval flattened out of its task, for the `clocks-csv` /
+/// `clocks-json` exports.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClockRow {
+    pub file: String,
+    pub line: u32,
+    pub heading: String,
+    pub start: String,
+    /// `None` for an open (still running) clock.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<String>,
+    /// Interval length in minutes, fractional for second-precision clocks
+    /// (rounded to two decimals). `None` when no duration can be determined.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minutes: Option<f64>,
+    /// Tags of the task, inherited ones included unless
+    /// `--no-tag-inheritance` is given.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
 /// Task paired with the number of days from the current date.
 /// Used for agenda rendering (overdue / upcoming).
-#[derive(Debug, Serialize, Deserialize)]
+///
+/// The task is shared by every day it appears on; what differs for this one
+/// occurrence lives in `occurrence` and replaces the task's fields on output.
+/// Read those fields through the accessors, not through `task`.
+#[derive(Debug, Deserialize)]
 pub struct TaskWithOffset {
     #[serde(flatten)]
-    pub task: Task,
+    pub task: Arc<Task>,
+    #[serde(skip)]
+    pub occurrence: Occurrence,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub days_offset: Option<i64>,
+    /// With `--collapse-recurring`: every date (`YYYY-MM-DD`) in the window
+    /// on which this recurring task occurs. Set on the first occurrence only;
+    /// the later ones are removed from their days.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub occurrence_days: Option<Vec<String>>,
+    /// With several `--tz` zones: the entry's start (and end) time converted
+    /// from the first zone into each additional one. Only timed entries.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local_times: Option<Vec<LocalTime>>,
+    /// For a date range `<d1>--<d2>`: which of its days this entry is.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range_day: Option<RangeDay>,
+    /// For an overdue entry: how long it has been due.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overdue_age: Option<OverdueAge>,
+    /// For an anniversary (a yearly task tagged `:anniversary:`): years
+    /// since the date in its timestamp.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anniversary_years: Option<u32>,
+    /// A timed entry whose time overlaps another timed entry of its day.
+    #[serde(default)]
+    pub conflict: bool,
+    /// For a repeating task: the next date it occurs on after this entry
+    /// (and after the agenda day), as `YYYY-MM-DD`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_occurrence: Option<String>,
+}
+
+/// Fields of one agenda occurrence that differ from its shared [`Task`]:
+/// the date of a repeat, a time of day dropped from an overdue entry, a
+/// rolled deadline. `None` / `false` keeps the task's own value.
+#[derive(Debug, Clone, Default)]
+pub struct Occurrence {
+    pub timestamp: Option<String>,
+    pub timestamp_date: Option<String>,
+    pub timestamp_time: Option<String>,
+    /// List the entry without a time of day (and without an end time).
+    pub untimed: bool,
+    pub effective_date: Option<String>,
+}
+
+impl TaskWithOffset {
+    pub fn new(task: Arc<Task>, occurrence: Occurrence, days_offset: Option<i64>) -> Self {
+        Self {
+            task,
+            occurrence,
+            days_offset,
+            occurrence_days: None,
+            local_times: None,
+            range_day: None,
+            overdue_age: None,
+            anniversary_years: None,
+            conflict: false,
+            next_occurrence: None,
+        }
+    }
+
+    pub fn timestamp(&self) -> Option<&str> {
+        self.occurrence
+            .timestamp
+            .as_deref()
+            .or(self.task.timestamp.as_deref())
+    }
+
+    pub fn timestamp_date(&self) -> Option<&str> {
+        self.occurrence
+            .timestamp_date
+            .as_deref()
+            .or(self.task.timestamp_date.as_deref())
+    }
+
+    pub fn timestamp_time(&self) -> Option<&str> {
+        if self.occurrence.untimed {
+            return None;
+        }
+        self.occurrence
+            .timestamp_time
+            .as_deref()
+            .or(self.task.timestamp_time.as_deref())
+    }
+
+    pub fn timestamp_end_time(&self) -> Option<&str> {
+        if self.occurrence.untimed {
+            return None;
+        }
+        self.task.timestamp_end_time.as_deref()
+    }
+
+    pub fn effective_date(&self) -> Option<&str> {
+        self.occurrence
+            .effective_date
+            .as_deref()
+            .or(self.task.effective_date.as_deref())
+    }
+
+    /// The task as this occurrence shows it. Borrowed unless the occurrence
+    /// changes a field, in which case a copy is made for the caller only.
+    pub fn resolved(&self) -> Cow<'_, Task> {
+        let o = &self.occurrence;
+        if o.timestamp.is_none()
+            && o.timestamp_date.is_none()
+            && o.timestamp_time.is_none()
+            && !o.untimed
+            && o.effective_date.is_none()
+        {
+            return Cow::Borrowed(&self.task);
+        }
+        let mut task = Task::clone(&self.task);
+        task.timestamp = self.timestamp().map(str::to_string);
+        task.timestamp_date = self.timestamp_date().map(str::to_string);
+        task.timestamp_time = self.timestamp_time().map(str::to_string);
+        task.timestamp_end_time = self.timestamp_end_time().map(str::to_string);
+        task.effective_date = self.effective_date().map(str::to_string);
+        Cow::Owned(task)
+    }
+}
+
+/// Same JSON as a flattened `Task` with the occurrence fields substituted in
+/// place, so the key order does not depend on whether a field was replaced.
+impl Serialize for TaskWithOffset {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        /// Borrowed wire form: the task as this occurrence shows it, then the
+        /// per-entry fields.
+        #[derive(Serialize)]
+        struct Wire<'a> {
+            #[serde(flatten)]
+            task: Cow<'a, Task>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            days_offset: Option<i64>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            overdue_age: Option<OverdueAge>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            range_day: Option<&'a RangeDay>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            anniversary_years: Option<u32>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            conflict: Option<bool>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            next_occurrence: Option<&'a str>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            occurrence_days: Option<&'a [String]>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            local_times: Option<&'a [LocalTime]>,
+        }
+
+        Wire {
+            task: self.resolved(),
+            days_offset: self.days_offset,
+            overdue_age: self.overdue_age,
+            range_day: self.range_day.as_ref(),
+            anniversary_years: self.anniversary_years,
+            conflict: self.conflict.then_some(true),
+            next_occurrence: self.next_occurrence.as_deref(),
+            occurrence_days: self.occurrence_days.as_deref(),
+            local_times: self.local_times.as_deref(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Age bucket of an overdue entry, so old items stand apart from recent
+/// slips. Ordered oldest first, as the overdue list is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum OverdueAge {
+    /// Due more than 30 days ago.
+    #[serde(rename = "over_month")]
+    OverMonth,
+    /// Due 8 to 30 days ago.
+    #[serde(rename = "over_week")]
+    OverWeek,
+    /// Due 4 to 7 days ago.
+    #[serde(rename = "days_4_7")]
+    Days4To7,
+    /// Due 1 to 3 days ago.
+    #[serde(rename = "days_1_3")]
+    Days1To3,
+}
+
+impl OverdueAge {
+    /// Bucket of an entry due `days` days ago.
+    pub fn from_days(days: i64) -> Self {
+        match days {
+            ..=3 => OverdueAge::Days1To3,
+            4..=7 => OverdueAge::Days4To7,
+            8..=30 => OverdueAge::OverWeek,
+            _ => OverdueAge::OverMonth,
+        }
+    }
+
+    /// Heading of the bucket in Markdown and HTML.
+    pub fn label(self) -> &'static str {
+        match self {
+            OverdueAge::OverMonth => "over a month",
+            OverdueAge::OverWeek => "over a week",
+            OverdueAge::Days4To7 => "4-7 days",
+            OverdueAge::Days1To3 => "1-3 days",
+        }
+    }
+}
+
+/// Position of one agenda day within a multi-day date range: `day` of
+/// `days`, counting from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RangeDay {
+    pub day: u32,
+    pub days: u32,
+}
+
+/// A timed agenda entry as seen from another `--tz` zone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalTime {
+    /// IANA zone name as given on the command line.
+    pub tz: String,
+    /// Local date as `YYYY-MM-DD`; may differ from the agenda day.
+    pub date: String,
+    /// Local start time as `HH:MM`.
+    pub time: String,
+    /// Local end time as `HH:MM` when the timestamp has a range.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<String>,
 }
 
 /// Tasks aggregated for a specific date, split into overdue / scheduled / upcoming buckets.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DayAgenda {
     pub date: String,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub overdue: Vec<TaskWithOffset>,
     pub scheduled_timed: Vec<TaskWithOffset>,
     pub scheduled_no_time: Vec<TaskWithOffset>,
     pub upcoming: Vec<TaskWithOffset>,
+    /// With `--log`: the CLOCK intervals started on this day, by start
+    /// time. Non-breaking optional field under ADR-0015.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub clocked: Vec<ClockRow>,
+    /// With `--show-gaps`: the open slots within the working hours, in
+    /// time order. Workdays only.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gaps: Vec<FreeSlot>,
+    /// With `--by-category`: the day's entries split by category, in
+    /// category order. The buckets above are then empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<CategoryAgenda>,
+    /// With `--max-entries-per-day`: entries left out of each bucket.
+    #[serde(default, skip_serializing_if = "Truncated::is_empty")]
+    pub truncated: Truncated,
+    /// With `--timeline`: the day's entries in one list, overdue first,
+    /// then the timed and untimed entries of the day, then upcoming. The
+    /// buckets above are then empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub timeline: Vec<TaskWithOffset>,
+    /// With `--timeline`, on today only: where the current time falls in
+    /// `timeline`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub now: Option<NowMarker>,
+    /// Totals of the day, so a consumer need not recount them. The agenda
+    /// views set it from [`DayAgenda::totals`] once the day is final.
+    #[serde(default)]
+    pub summary: DaySummary,
+}
+
+/// Entries of one category on a [`DayAgenda`], in the day's buckets.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CategoryAgenda {
+    pub category: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overdue: Vec<TaskWithOffset>,
+    pub scheduled_timed: Vec<TaskWithOffset>,
+    pub scheduled_no_time: Vec<TaskWithOffset>,
+    pub upcoming: Vec<TaskWithOffset>,
+    #[serde(default, skip_serializing_if = "Truncated::is_empty")]
+    pub truncated: Truncated,
+}
+
+impl CategoryAgenda {
+    pub fn new(category: String) -> Self {
+        Self {
+            category,
+            overdue: Vec::new(),
+            scheduled_timed: Vec::new(),
+            scheduled_no_time: Vec::new(),
+            upcoming: Vec::new(),
+            truncated: Truncated::default(),
+        }
+    }
+}
+
+/// Number of entries `--max-entries-per-day` left out of each section;
+/// `scheduled` covers the timed and untimed lists together.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Truncated {
+    pub overdue: usize,
+    pub scheduled: usize,
+    pub upcoming: usize,
+}
+
+impl Truncated {
+    /// True when nothing was left out.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// The current time on a `--timeline` day: the marker goes before
+/// `timeline[index]`, or after the last entry when `index` is its length.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NowMarker {
+    /// The time as `HH:MM`.
+    pub time: String,
+    pub index: usize,
+}
+
+/// Per-day counts and totals of a [`DayAgenda`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaySummary {
+    pub overdue: usize,
+    /// Timed and untimed scheduled entries together.
+    pub scheduled: usize,
+    pub upcoming: usize,
+    /// Summed length of the `clocked` intervals (`--log`), rounded.
+    pub clocked_minutes: u32,
+    /// Summed `Effort` of the scheduled entries still to do, each task
+    /// counted once even when several of its timestamps fall on the day.
+    pub effort_minutes: u32,
 }
 
 impl DayAgenda {
@@ -438,8 +1263,194 @@ impl DayAgenda {
             scheduled_timed: Vec::new(),
             scheduled_no_time: Vec::new(),
             upcoming: Vec::new(),
+            clocked: Vec::new(),
+            gaps: Vec::new(),
+            categories: Vec::new(),
+            truncated: Truncated::default(),
+            timeline: Vec::new(),
+            now: None,
+            summary: DaySummary::default(),
         }
     }
+
+    /// Entries of one bucket: the day's own, then each category group's.
+    fn bucket<'a>(
+        &'a self,
+        own: &'a [TaskWithOffset],
+        pick: fn(&CategoryAgenda) -> &[TaskWithOffset],
+    ) -> impl Iterator<Item = &'a TaskWithOffset> {
+        own.iter().chain(self.categories.iter().flat_map(pick))
+    }
+
+    /// Counts and totals of the day as it stands, category groups included.
+    pub fn totals(&self) -> DaySummary {
+        let clocked: f64 = self.clocked.iter().filter_map(|row| row.minutes).sum();
+        let overdue = self.bucket(&self.overdue, |c| &c.overdue);
+        let timed = self.bucket(&self.scheduled_timed, |c| &c.scheduled_timed);
+        let no_time = self.bucket(&self.scheduled_no_time, |c| &c.scheduled_no_time);
+        let upcoming = self.bucket(&self.upcoming, |c| &c.upcoming);
+        let scheduled: Vec<&TaskWithOffset> = timed.chain(no_time).collect();
+        let mut seen = std::collections::HashSet::new();
+        let effort_minutes = scheduled
+            .iter()
+            .map(|entry| &entry.task)
+            .filter(|task| {
+                !task
+                    .task_type
+                    .as_ref()
+                    .is_some_and(|t| t.is_done() || matches!(t, TaskType::Cancelled(_)))
+            })
+            .filter(|task| seen.insert((Arc::clone(&task.file), task.line)))
+            .filter_map(|task| task.effort_minutes)
+            .sum();
+        DaySummary {
+            overdue: overdue.count(),
+            scheduled: scheduled.len(),
+            upcoming: upcoming.count(),
+            clocked_minutes: clocked.round() as u32,
+            effort_minutes,
+        }
+    }
+
+    /// True when no bucket holds an entry: the day has nothing to show.
+    pub fn is_empty(&self) -> bool {
+        self.overdue.is_empty()
+            && self.scheduled_timed.is_empty()
+            && self.scheduled_no_time.is_empty()
+            && self.upcoming.is_empty()
+            && self.clocked.is_empty()
+            && self.categories.is_empty()
+            && self.timeline.is_empty()
+    }
+}
+
+/// One recurring task's contribution to a [`ForecastMonth`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ForecastEntry {
+    pub file: String,
+    pub line: u32,
+    pub heading: String,
+    /// Canonical org repeater string (`+1w`, `.+1m`), same form as
+    /// [`Task::timestamp_repeater`].
+    pub repeater: String,
+    /// Number of occurrences that fall inside the month's part of the window.
+    pub occurrences: u32,
+    /// Per-occurrence estimate from the task's `Effort` property (`H:MM`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effort: Option<String>,
+    /// `occurrences * effort`, formatted `H:MM`. `None` when `effort` is.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_effort: Option<String>,
+}
+
+/// Expected recurring workload for one calendar month of the
+/// `--agenda forecast` view.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ForecastMonth {
+    /// Calendar month as `YYYY-MM`.
+    pub month: String,
+    /// Sum of `occurrences` over `tasks`.
+    pub occurrences: u32,
+    /// Sum of every entry's `total_effort`, formatted `H:MM`. `None` when no
+    /// task in the month carries an `Effort` property.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_effort: Option<String>,
+    pub tasks: Vec<ForecastEntry>,
+}
+
+/// One DEADLINE occurrence inside a [`QuarterMonth`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QuarterDeadline {
+    pub file: String,
+    pub line: u32,
+    pub heading: String,
+    /// Occurrence date as `YYYY-MM-DD`; for a repeating deadline, one entry
+    /// per occurrence in the month.
+    pub date: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+}
+
+/// One month of the `--agenda quarter` view. With a `--fiscal-year-start`
+/// day other than the 1st, a "month" runs from that day to the day before
+/// it in the following month.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QuarterMonth {
+    /// First day of the month as `YYYY-MM-DD`.
+    pub start: String,
+    /// Last day of the month as `YYYY-MM-DD`.
+    pub end: String,
+    /// Open DEADLINE occurrences, ordered by date.
+    pub deadlines: Vec<QuarterDeadline>,
+    /// Recurring non-DEADLINE obligations with their occurrence counts.
+    pub recurring: Vec<ForecastEntry>,
+}
+
+/// One task occurrence inside a [`SimulatedDay`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SimulatedEntry {
+    pub file: String,
+    pub line: u32,
+    pub heading: String,
+    /// Value of the task's `Effort` property, normalised to `H:MM`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effort: Option<String>,
+}
+
+/// Projected workload of one day of the `--simulate` view.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SimulatedDay {
+    /// Day as `YYYY-MM-DD`.
+    pub date: String,
+    /// Number of task occurrences on this day.
+    pub occurrences: u32,
+    /// Sum of the occurrences' `effort`, formatted `H:MM`. `None` when no
+    /// task on the day carries an `Effort` property.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_effort: Option<String>,
+    pub tasks: Vec<SimulatedEntry>,
+}
+
+/// One open interval of the `--agenda free` view.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FreeSlot {
+    /// Start as `HH:MM`.
+    pub start: String,
+    /// End as `HH:MM`.
+    pub end: String,
+    pub minutes: u32,
+}
+
+/// Open slots of one workday of the `--agenda free` view, in time order.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FreeDay {
+    /// Day as `YYYY-MM-DD`.
+    pub date: String,
+    pub slots: Vec<FreeSlot>,
+}
+
+/// One habit of the `--agenda habits` view.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HabitRow {
+    pub file: String,
+    pub line: u32,
+    pub heading: String,
+    /// Repeater of the `SCHEDULED` timestamp (`.+2d`).
+    pub repeater: String,
+    /// Date of the `SCHEDULED` timestamp as `YYYY-MM-DD`.
+    pub scheduled: String,
+    /// Last completion on or before `end`, as `YYYY-MM-DD`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_done: Option<String>,
+    /// Days of the window with a completion.
+    pub done_days: u32,
+    /// First day of the graph as `YYYY-MM-DD`.
+    pub start: String,
+    /// Last day of the graph as `YYYY-MM-DD`.
+    pub end: String,
+    /// One mark per day from `start` to `end`: `*` done, `!` due and not
+    /// done, `-` not due yet.
+    pub graph: String,
 }
 
 #[cfg(test)]
@@ -511,8 +1522,55 @@ mod tests {
 
     #[test]
     fn task_type_deserialize_rejects_unknown_string() {
-        let r: Result<TaskType, _> = serde_json::from_str("\"MAYBE\"");
-        assert!(r.is_err(), "unknown task_type string must fail to parse");
+        // An upper-case word is a custom keyword (ADR-0025); anything else
+        // is not a keyword at all.
+        for bad in ["\"maybe\"", "\"NOT DONE\"", "\"\""] {
+            let r: Result<TaskType, _> = serde_json::from_str(bad);
+            assert!(r.is_err(), "{bad} must fail to parse");
+        }
+    }
+
+    #[test]
+    fn custom_keywords_classify_and_round_trip() {
+        let keywords = TodoKeywords {
+            todo: vec!["NEXT".to_string()],
+            done: vec!["SHIPPED".to_string()],
+        };
+        assert_eq!(keywords.task_type("TODO"), Some(TaskType::Todo));
+        assert_eq!(
+            keywords.task_type("NEXT"),
+            Some(TaskType::Other("NEXT".to_string()))
+        );
+        assert!(keywords.task_type("SHIPPED").unwrap().is_done());
+        assert_eq!(keywords.task_type("LATER"), None);
+
+        let json = serde_json::to_string(&TaskType::OtherDone("SHIPPED".to_string())).unwrap();
+        assert_eq!(json, "\"SHIPPED\"");
+        let mut back: TaskType = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, TaskType::Other("SHIPPED".to_string()));
+        keywords.reclassify(&mut back);
+        assert_eq!(back, TaskType::OtherDone("SHIPPED".to_string()));
+
+        assert!(TodoKeywords::validate("IN-REVIEW").is_ok());
+        assert!(TodoKeywords::validate("next").is_err());
+    }
+
+    #[test]
+    fn keyword_lists_drop_built_ins_and_reject_open_and_done_clashes() {
+        let words = |list: &[&str]| list.iter().map(|k| k.to_string()).collect::<Vec<_>>();
+        let keywords = TodoKeywords::new(
+            &words(&["NEXT", "TODO", "WAITING", "NEXT"]),
+            &words(&["CANCELLED", "SHIPPED"]),
+        )
+        .unwrap();
+        assert_eq!(keywords.todo, words(&["NEXT", "WAITING"]));
+        assert_eq!(keywords.done, words(&["SHIPPED"]));
+
+        assert!(TodoKeywords::new(&words(&["NEXT"]), &words(&["NEXT"]))
+            .unwrap_err()
+            .contains("'NEXT'"));
+        assert!(TodoKeywords::new(&[], &words(&["TODO"])).is_err());
+        assert!(TodoKeywords::validate("DONE").is_ok());
     }
 
     #[test]
@@ -623,6 +1681,46 @@ mod tests {
         assert!(Priority::C.order() < Priority::Other('D').order());
     }
 
+    #[test]
+    fn priority_raised_stays_on_its_scale() {
+        assert_eq!(Priority::C.raised(1), Priority::B);
+        assert_eq!(Priority::Other('E').raised(2), Priority::C);
+        assert_eq!(Priority::B.raised(5), Priority::A);
+        assert_eq!(Priority::A.raised(0), Priority::A);
+        assert_eq!(Priority::Numeric(10).raised(3), Priority::Numeric(7));
+        assert_eq!(Priority::Numeric(2).raised(u32::MAX), Priority::Numeric(0));
+    }
+
+    #[test]
+    fn priority_scheme_parses_ranges_and_defaults() {
+        let scheme = PriorityScheme::parse("A-E").unwrap();
+        assert_eq!(
+            (
+                scheme.highest.clone(),
+                scheme.lowest.clone(),
+                scheme.default
+            ),
+            (Priority::A, Priority::Other('E'), Priority::C)
+        );
+        let scheme = PriorityScheme::parse("1-9:7").unwrap();
+        assert_eq!(scheme.default, Priority::Numeric(7));
+        assert!(scheme.contains(&Priority::Numeric(9)));
+        assert!(!scheme.contains(&Priority::Numeric(0)));
+        assert!(!scheme.contains(&Priority::A));
+        assert_eq!(
+            scheme.raised(&Priority::Numeric(3), 5),
+            Priority::Numeric(1)
+        );
+        assert_eq!(
+            PriorityScheme::parse("1-10").unwrap().default,
+            Priority::Numeric(5)
+        );
+
+        for bad in ["A", "E-A", "A-9", "A-C:D", "a-c", "1-65"] {
+            assert!(PriorityScheme::parse(bad).is_err(), "{bad}");
+        }
+    }
+
     #[test]
     fn priority_serializes_as_string() {
         let json = serde_json::to_string(&Priority::A).unwrap();
@@ -650,6 +1748,7 @@ mod tests {
             task_type: None,
             priority: None,
             created: None,
+            closed: None,
             timestamp: None,
             timestamp_type: None,
             timestamp_active: None,
@@ -659,7 +1758,36 @@ mod tests {
             timestamp_repeater: None,
             clocks: None,
             total_clock_time: None,
+            log: None,
+            effort_minutes: None,
             properties: None,
+            timestamp_overnight: None,
+            planning: None,
+            inactive_timestamps: None,
+            rescheduled_count: None,
+            effective_date: None,
+            attendees: None,
+            location: None,
+            url: None,
+            links: None,
+            subtasks: None,
+            subtasks_percent: None,
+            subtasks_progress: None,
+            progress_cookie: None,
+            progress: None,
+            children_todo: None,
+            children_done: None,
+            level: None,
+            waiting_since: None,
+            assignee: None,
+            paused: None,
+            paused_until: None,
+            effective_priority: None,
+            category: None,
+            tags: None,
+            outline_path: None,
+            inherited_priority: None,
+            inherited_tags: None,
         }
     }
 